@@ -0,0 +1,428 @@
+//! Cache of verification results.
+//!
+//! The cache lives under `.pave/cache/` next to the config file. Each entry
+//! maps a verification item (its document, command, expectations, and the
+//! options it runs with) to a hash of the document and the input files
+//! matched by its `## Paths` section. An
+//! item whose inputs hash is unchanged since it last passed can be skipped.
+//! Entries also record when the item last passed, for `pave verify --since`.
+//! Items that failed on their last run are listed separately, for `pave
+//! verify --only-failed`.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::parser::ParsedDoc;
+use crate::paths::PathsMatcher;
+use crate::verification::VerificationItem;
+use crate::verify::RunOptions;
+
+/// Directory (relative to the project root) where pave stores caches.
+pub const CACHE_DIR: &str = ".pave/cache";
+
+/// Filename of the verification cache within [`CACHE_DIR`].
+const VERIFY_CACHE_FILE: &str = "verify.json";
+
+/// Passing verification items and the input hash they passed with, plus the
+/// items that failed when last run.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct VerifyCache {
+    entries: BTreeMap<String, CacheEntry>,
+    #[serde(default)]
+    failed: BTreeSet<String>,
+}
+
+/// The last pass recorded for a verification item.
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    /// Hash of the item's input files when it passed.
+    inputs_hash: String,
+    /// When the item passed, in seconds since the Unix epoch.
+    passed_at: u64,
+}
+
+impl VerifyCache {
+    /// Path to the verification cache file for a project root.
+    pub fn path(project_root: &Path) -> PathBuf {
+        project_root.join(CACHE_DIR).join(VERIFY_CACHE_FILE)
+    }
+
+    /// Returns true if a cache has been written for the project.
+    pub fn exists(project_root: &Path) -> bool {
+        Self::path(project_root).is_file()
+    }
+
+    /// Load the cache for a project, starting empty if it is missing or unreadable.
+    pub fn load(project_root: &Path) -> Self {
+        std::fs::read_to_string(Self::path(project_root))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the cache to disk, creating the cache directory if needed.
+    pub fn save(&self, project_root: &Path) -> Result<()> {
+        let path = Self::path(project_root);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let json = serde_json::to_string_pretty(self).context("Failed to serialize cache")?;
+        std::fs::write(&path, json)
+            .with_context(|| format!("Failed to write cache: {}", path.display()))
+    }
+
+    /// Returns true if `key` last passed with the same inputs hash.
+    pub fn is_fresh(&self, key: &str, inputs_hash: &str) -> bool {
+        self.entries
+            .get(key)
+            .is_some_and(|entry| entry.inputs_hash == inputs_hash)
+    }
+
+    /// Returns true if `key` passed no longer than `window` ago, whatever
+    /// its inputs were.
+    pub fn passed_within(&self, key: &str, window: Duration) -> bool {
+        let cutoff = SystemTime::now().checked_sub(window).map_or(0, unix_secs);
+        self.entries
+            .get(key)
+            .is_some_and(|entry| entry.passed_at >= cutoff)
+    }
+
+    /// Record that `key` passed just now with the given inputs hash.
+    pub fn record_pass(&mut self, key: String, inputs_hash: String) {
+        self.record_pass_at(key, inputs_hash, SystemTime::now());
+    }
+
+    /// Record that `key` passed at `passed_at` with the given inputs hash.
+    pub fn record_pass_at(&mut self, key: String, inputs_hash: String, passed_at: SystemTime) {
+        self.failed.remove(&key);
+        self.entries.insert(
+            key,
+            CacheEntry {
+                inputs_hash,
+                passed_at: unix_secs(passed_at),
+            },
+        );
+    }
+
+    /// Record that `key` failed, forgetting any previous pass.
+    pub fn record_failure(&mut self, key: String) {
+        self.entries.remove(&key);
+        self.failed.insert(key);
+    }
+
+    /// Returns true if `key` failed the last time it ran.
+    pub fn failed_last_run(&self, key: &str) -> bool {
+        self.failed.contains(key)
+    }
+
+    /// Forget any previous pass or failure for `key`.
+    pub fn invalidate(&mut self, key: &str) {
+        self.entries.remove(key);
+        self.failed.remove(key);
+    }
+}
+
+/// Build the cache key for a verification item.
+///
+/// `source_file` should be relative to the project root so the cache stays
+/// valid when the checkout moves. `options` are the options the item runs
+/// with; everything in them that can change the outcome (shell, timeouts,
+/// environment, directory, output strictness, isolation) is part of the key.
+pub fn item_key(source_file: &Path, item: &VerificationItem, options: &RunOptions) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(source_file.to_string_lossy().as_bytes());
+    hasher.update([0]);
+    hasher.update(item.command.as_bytes());
+    hasher.update([0]);
+    hasher.update(format!("{:?}", item.interpreter).as_bytes());
+    let working_dir = item.working_dir.as_ref().or(options.working_dir.as_ref());
+    hasher.update(format!("{:?}", working_dir).as_bytes());
+    hasher.update(format!("{:?}", item.env_vars).as_bytes());
+    hasher.update(format!("{:?}", item.expected_exit_code).as_bytes());
+    if let Some(codes) = &item.expected_exit_codes {
+        hasher.update(codes.to_string().as_bytes());
+    }
+    hasher.update(format!("{:?}", item.expected_output).as_bytes());
+    hasher.update(format!("{:?}", item.timeout_secs).as_bytes());
+    hasher.update(format!("{:?}", options.timeout).as_bytes());
+    hasher.update(format!("{:?}", options.default_timeout).as_bytes());
+    hasher.update(format!("{:?}", options.env).as_bytes());
+    hasher.update(format!("{:?}", options.shell).as_bytes());
+    hasher.update([options.strict_output as u8, options.isolated as u8]);
+    to_hex(&hasher.finalize())
+}
+
+/// Collect the input patterns declared by a document.
+///
+/// Combines the `## Paths` section entries with any `paths` in frontmatter.
+pub fn input_patterns(doc: &ParsedDoc) -> Vec<String> {
+    let mut patterns: Vec<String> = doc
+        .frontmatter
+        .as_ref()
+        .map(|fm| fm.paths.clone())
+        .unwrap_or_default();
+
+    patterns.extend(doc.paths_patterns());
+    patterns
+}
+
+/// Hash the document at `doc_file` together with the contents of every file
+/// matched by `patterns` under `root`.
+///
+/// Patterns may be globs or directory prefixes; `!` patterns exclude files
+/// matched earlier. Files are hashed in sorted order together with their
+/// relative paths, so renames also change the hash.
+///
+/// Returns `None` when the patterns match no files: with no declared inputs
+/// there is no way to tell that a cached pass has gone stale, so such
+/// documents aren't cached.
+pub fn hash_inputs(root: &Path, doc_file: &Path, patterns: &[String]) -> Option<String> {
+    let files = PathsMatcher::new(patterns).matched_files(root);
+    if files.is_empty() {
+        return None;
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(std::fs::read(doc_file).unwrap_or_default());
+    hasher.update([0]);
+    for relative in &files {
+        let Ok(content) = std::fs::read(root.join(relative)) else {
+            continue;
+        };
+        hasher.update(relative.to_string_lossy().as_bytes());
+        hasher.update([0]);
+        hasher.update(&content);
+        hasher.update([0]);
+    }
+    Some(to_hex(&hasher.finalize()))
+}
+
+/// Parse a duration such as `90s`, `10m`, `1h`, or `2d`.
+///
+/// A bare number is taken as seconds.
+pub fn parse_duration(s: &str) -> std::result::Result<Duration, String> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let number: u64 = number
+        .parse()
+        .map_err(|_| format!("invalid duration `{}` (expected e.g. 10m or 1h)", s))?;
+    let unit_secs = match unit {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => {
+            return Err(format!(
+                "unknown duration unit `{}` (use s, m, h, or d)",
+                unit
+            ));
+        }
+    };
+    let secs = number
+        .checked_mul(unit_secs)
+        .ok_or_else(|| format!("duration `{}` is too large", s))?;
+    Ok(Duration::from_secs(secs))
+}
+
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn item(command: &str) -> VerificationItem {
+        VerificationItem {
+            command: command.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn hash_inputs_changes_when_matched_file_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+        fs::write(temp_dir.path().join("src/lib.rs"), "fn a() {}").unwrap();
+        fs::write(temp_dir.path().join("README.md"), "readme").unwrap();
+        let doc = temp_dir.path().join("doc.md");
+        fs::write(&doc, "# Doc\n").unwrap();
+        let patterns = vec!["src/*.rs".to_string()];
+        let hash = || hash_inputs(temp_dir.path(), &doc, &patterns);
+
+        let before = hash();
+        assert!(before.is_some());
+        // Unmatched files don't affect the hash
+        fs::write(temp_dir.path().join("README.md"), "changed").unwrap();
+        assert_eq!(hash(), before);
+
+        fs::write(temp_dir.path().join("src/lib.rs"), "fn b() {}").unwrap();
+        let after_code = hash();
+        assert_ne!(after_code, before);
+
+        // Editing the document itself also changes the hash
+        fs::write(&doc, "# Doc\n\nEdited.\n").unwrap();
+        assert_ne!(hash(), after_code);
+    }
+
+    #[test]
+    fn hash_inputs_is_none_without_matched_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let doc = temp_dir.path().join("doc.md");
+        fs::write(&doc, "# Doc\n").unwrap();
+
+        assert_eq!(hash_inputs(temp_dir.path(), &doc, &[]), None);
+        let unmatched = vec!["src/*.rs".to_string()];
+        assert_eq!(hash_inputs(temp_dir.path(), &doc, &unmatched), None);
+    }
+
+    #[test]
+    fn item_key_depends_on_command() {
+        let path = Path::new("docs/a.md");
+        let options = RunOptions::default();
+        assert_eq!(
+            item_key(path, &item("true"), &options),
+            item_key(path, &item("true"), &options)
+        );
+        assert_ne!(
+            item_key(path, &item("true"), &options),
+            item_key(path, &item("false"), &options)
+        );
+    }
+
+    #[test]
+    fn item_key_depends_on_how_the_item_runs() {
+        let path = Path::new("docs/a.md");
+        let options = RunOptions::default();
+        let key = item_key(path, &item("true"), &options);
+
+        let script = VerificationItem {
+            interpreter: Some("python3".to_string()),
+            ..item("true")
+        };
+        let timed = VerificationItem {
+            timeout_secs: Some(5),
+            ..item("true")
+        };
+        assert_ne!(item_key(path, &script, &options), key);
+        assert_ne!(item_key(path, &timed, &options), key);
+
+        for changed in [
+            RunOptions {
+                shell: crate::verify::Shell::new("bash"),
+                ..RunOptions::default()
+            },
+            RunOptions {
+                default_timeout: Some(Duration::from_secs(5)),
+                ..RunOptions::default()
+            },
+            RunOptions {
+                working_dir: Some(PathBuf::from("docs")),
+                ..RunOptions::default()
+            },
+            RunOptions {
+                strict_output: true,
+                ..RunOptions::default()
+            },
+            RunOptions {
+                isolated: true,
+                ..RunOptions::default()
+            },
+        ] {
+            assert_ne!(item_key(path, &item("true"), &changed), key);
+        }
+    }
+
+    #[test]
+    fn cache_round_trips_through_disk() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut cache = VerifyCache::default();
+        cache.record_pass("key".to_string(), "hash".to_string());
+        cache.save(temp_dir.path()).unwrap();
+
+        let loaded = VerifyCache::load(temp_dir.path());
+        assert!(loaded.is_fresh("key", "hash"));
+        assert!(!loaded.is_fresh("key", "other"));
+        assert!(!loaded.is_fresh("missing", "hash"));
+    }
+
+    #[test]
+    fn failures_are_recorded_until_the_item_passes() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(!VerifyCache::exists(temp_dir.path()));
+
+        let mut cache = VerifyCache::default();
+        cache.record_pass("key".to_string(), "hash".to_string());
+        cache.record_failure("key".to_string());
+        assert!(!cache.is_fresh("key", "hash"));
+        cache.save(temp_dir.path()).unwrap();
+
+        assert!(VerifyCache::exists(temp_dir.path()));
+        let mut loaded = VerifyCache::load(temp_dir.path());
+        assert!(loaded.failed_last_run("key"));
+        loaded.record_pass("key".to_string(), "hash".to_string());
+        assert!(!loaded.failed_last_run("key"));
+    }
+
+    #[test]
+    fn passed_within_checks_the_last_pass_time() {
+        let mut cache = VerifyCache::default();
+        let window = Duration::from_secs(10 * 60);
+        cache.record_pass("recent".to_string(), "hash".to_string());
+        cache.record_pass_at(
+            "stale".to_string(),
+            "hash".to_string(),
+            SystemTime::now() - Duration::from_secs(20 * 60),
+        );
+
+        assert!(cache.passed_within("recent", window));
+        assert!(!cache.passed_within("stale", window));
+        assert!(!cache.passed_within("missing", window));
+    }
+
+    #[test]
+    fn parse_duration_accepts_units() {
+        assert_eq!(parse_duration("45"), Ok(Duration::from_secs(45)));
+        assert_eq!(parse_duration("10m"), Ok(Duration::from_secs(600)));
+        assert_eq!(parse_duration("1h"), Ok(Duration::from_secs(3600)));
+        assert_eq!(parse_duration("2d"), Ok(Duration::from_secs(172_800)));
+        assert!(parse_duration("10 minutes").is_err());
+        assert!(parse_duration("m").is_err());
+    }
+
+    #[test]
+    fn parse_duration_rejects_overflowing_values() {
+        assert_eq!(
+            parse_duration("999999999999999999d"),
+            Err("duration `999999999999999999d` is too large".to_string())
+        );
+        assert!(parse_duration("99999999999999999999").is_err());
+        assert_eq!(
+            parse_duration(&format!("{}s", u64::MAX)),
+            Ok(Duration::from_secs(u64::MAX))
+        );
+    }
+
+    #[test]
+    fn input_patterns_reads_paths_section() {
+        let doc = ParsedDoc::parse_content(
+            PathBuf::from("doc.md"),
+            "# Doc\n\n## Paths\n\n- `src/*.rs`\n* docs/\n",
+        )
+        .unwrap();
+
+        assert_eq!(input_patterns(&doc), vec!["src/*.rs", "docs/"]);
+    }
+}
@@ -1,5 +1,6 @@
 use clap::{Args, Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
+use std::time::Duration;
 
 use crate::templates::TemplateType;
 
@@ -8,10 +9,91 @@ use crate::templates::TemplateType;
 #[command(name = "pave")]
 #[command(version, about, long_about = None)]
 pub struct Cli {
+    /// Use this config file instead of discovering .pave.toml
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// When to use colored output: auto, always, never
+    #[arg(long, global = true, value_enum, default_value = "auto")]
+    pub color: ColorChoice,
+
+    /// Diagnostic log level written to stderr (overrides RUST_LOG; default warn)
+    #[arg(long, global = true, value_enum, value_name = "LEVEL")]
+    pub log_level: Option<LogLevel>,
+
     #[command(subcommand)]
     pub command: Command,
 }
 
+/// When to emit colored output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum ColorChoice {
+    /// Color when writing to a terminal and NO_COLOR is unset
+    #[default]
+    Auto,
+    /// Always color
+    Always,
+    /// Never color
+    Never,
+}
+
+/// How much diagnostic logging to write to stderr.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LogLevel {
+    /// Only errors
+    Error,
+    /// Errors and warnings
+    Warn,
+    /// High-level progress
+    Info,
+    /// Files scanned, globs compiled, commands extracted, cache hits
+    Debug,
+    /// Everything
+    Trace,
+}
+
+/// Which output streams `pave verify` captures from each command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum CaptureMode {
+    /// Stream stdout and stderr live without buffering them
+    None,
+    /// Capture stdout; stream stderr live
+    Stdout,
+    /// Capture stdout and stderr
+    #[default]
+    All,
+}
+
+/// What `pave verify` does when the shell isn't installed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum MissingShell {
+    /// Fail before running anything, naming the shell that was looked up
+    #[default]
+    Error,
+    /// Skip the commands that need the shell, with a warning
+    Skip,
+}
+
+/// Which items `pave verify --print-command` prints a reproducible command for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum PrintCommand {
+    /// Only items that failed or timed out
+    Failed,
+    /// Every item that ran
+    All,
+}
+
+/// Starting configuration written by `pave init --preset`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum InitPreset {
+    /// Structure checks only; no Verification or Examples sections required
+    Minimal,
+    /// Require verification and examples, validate paths, and enforce a coverage threshold
+    Strict,
+    /// Several documentation roots, with common build directories excluded from mapping
+    Monorepo,
+}
+
 /// Document type for PAVED documentation.
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum DocType {
@@ -33,6 +115,18 @@ pub enum PromptOutputFormat {
     Json,
 }
 
+/// How `pave prompt` frames documents and sections.
+#[derive(Debug, Clone, Copy, ValueEnum, Default)]
+pub enum PromptFramingFormat {
+    /// Markdown headings and fenced blocks.
+    #[default]
+    Markdown,
+    /// XML tags such as `<document path=...>` and `<section name=...>`.
+    Xml,
+    /// Structured JSON section objects.
+    Json,
+}
+
 /// Output format for adopt command.
 #[derive(Debug, Clone, Copy, ValueEnum, Default)]
 pub enum AdoptOutputFormat {
@@ -83,9 +177,9 @@ pub enum Command {
         #[arg()]
         paths: Vec<PathBuf>,
 
-        /// Output format: text, json, github
+        /// Output format: text, json, github, sarif
         #[arg(long, default_value = "text", value_enum)]
-        format: OutputFormat,
+        format: CheckOutputFormat,
 
         /// Treat warnings as errors (overrides gradual mode)
         #[arg(long)]
@@ -102,27 +196,93 @@ pub enum Command {
         /// Base ref for --changed comparison [default: origin/main]
         #[arg(long)]
         base: Option<String>,
+
+        /// Watch the docs and re-run validation on every change
+        #[arg(long)]
+        watch: bool,
+
+        /// Insert placeholder sections for any missing required sections, and strip
+        /// trailing whitespace when the trailing-whitespace rule is enabled
+        #[arg(long)]
+        fix: bool,
+
+        /// Report docs whose Paths match nothing and source files no doc covers
+        #[arg(long)]
+        orphans: bool,
+
+        /// Read the document from stdin instead of disk (requires --path)
+        #[arg(long, requires = "stdin_path", conflicts_with_all = ["paths", "changed", "watch", "fix", "orphans"])]
+        stdin: bool,
+
+        /// Logical path of the stdin document, used in messages
+        #[arg(long = "path", value_name = "PATH", requires = "stdin")]
+        stdin_path: Option<PathBuf>,
+
+        /// Also check files excluded by .gitignore and .ignore
+        #[arg(long)]
+        no_ignore: bool,
+
+        /// Only fail on issues not recorded in this baseline file
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["write_baseline", "watch", "orphans"])]
+        baseline: Option<PathBuf>,
+
+        /// Record the current issues to a baseline file and exit successfully
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["watch", "orphans"])]
+        write_baseline: Option<PathBuf>,
+
+        /// Print only failing issues and a one-line summary
+        #[arg(short, long)]
+        quiet: bool,
+
+        /// Only report this rule id, e.g. broken-link (repeatable)
+        #[arg(long = "rule", value_name = "ID")]
+        rules: Vec<String>,
+
+        /// Don't report this rule id, e.g. max-lines (repeatable)
+        #[arg(long = "ignore-rule", value_name = "ID")]
+        ignore_rules: Vec<String>,
+
+        /// Warn about any section longer than this many lines, overriding check.max_section_lines
+        #[arg(long, value_name = "N")]
+        max_section_lines: Option<usize>,
     },
 
     /// Create a new document from template
     New {
         /// Document type: component, runbook, adr
-        #[arg(value_enum)]
-        doc_type: DocType,
+        #[arg(value_enum, required_unless_present_any = ["interactive", "list_templates"])]
+        doc_type: Option<DocType>,
 
         /// Name for the document (used in filename and title)
-        name: String,
+        #[arg(required_unless_present_any = ["interactive", "list_templates"])]
+        name: Option<String>,
 
         /// Where to create the file [default: docs/{type}s/{name}.md]
         #[arg(long)]
         output: Option<PathBuf>,
+
+        /// Prompt for the title, type, owner, and Paths glob
+        #[arg(short, long)]
+        interactive: bool,
+
+        /// Print the rendered document to stdout instead of writing it
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Copy the section structure of an existing doc instead of the template
+        #[arg(long, value_name = "PATH", conflicts_with = "interactive")]
+        from: Option<PathBuf>,
+
+        /// List built-in templates and custom ones from docs.templates
+        #[arg(long, conflicts_with_all = ["interactive", "dry_run", "from", "output"])]
+        list_templates: bool,
     },
 
     /// Generate prompts for AI agents
     Prompt {
         /// Document type: component, runbook, adr
-        #[arg(value_enum)]
-        doc_type: DocType,
+        #[arg(value_enum, required_unless_present = "paths")]
+        doc_type: Option<DocType>,
 
         /// Name of the thing being documented
         #[arg(long = "for")]
@@ -136,9 +296,29 @@ pub enum Command {
         #[arg(long, value_name = "PATH")]
         context: Vec<PathBuf>,
 
+        /// Bundle the docs whose ## Paths cover files matching this glob
+        #[arg(long, value_name = "GLOB", conflicts_with_all = ["doc_type", "name", "update", "context"])]
+        paths: Option<String>,
+
         /// Output format: text, json
         #[arg(long, value_enum, default_value = "text")]
         output: PromptOutputFormat,
+
+        /// Prompt framing: markdown, xml, json
+        #[arg(long, value_enum, default_value = "markdown")]
+        format: PromptFramingFormat,
+
+        /// With --paths, inline the source files each doc's ## Paths match
+        #[arg(long, requires = "paths")]
+        include_source: bool,
+
+        /// Cap on inlined source across the prompt, in bytes
+        #[arg(long, value_name = "BYTES", requires = "include_source", default_value_t = crate::commands::prompt::DEFAULT_MAX_SOURCE_BYTES)]
+        max_source_bytes: usize,
+
+        /// Prepend a system preamble explaining PAVED docs (text from prompt.system_file if set)
+        #[arg(long)]
+        system: bool,
     },
 
     /// Manage git hooks for documentation validation
@@ -151,13 +331,17 @@ pub enum Command {
 
     /// Generate an index document mapping all PAVED documentation
     Index {
-        /// Where to write the index document
-        #[arg(short, long, default_value = "docs/index.md")]
-        output: PathBuf,
+        /// Where to write the index document [default: index.md in the docs root]
+        #[arg(short, long)]
+        output: Option<PathBuf>,
 
         /// Update existing index (preserve custom content)
         #[arg(short, long)]
         update: bool,
+
+        /// Don't write; exit non-zero if the index is out of date
+        #[arg(long)]
+        check: bool,
     },
 
     /// Show docs impacted by code changes
@@ -170,6 +354,10 @@ pub enum Command {
         #[arg(long, default_value = "text", value_enum)]
         format: ChangedOutputFormat,
 
+        /// Shorthand for --format json
+        #[arg(long, conflicts_with = "format")]
+        json: bool,
+
         /// Fail if impacted docs weren't updated
         #[arg(long)]
         strict: bool,
@@ -185,17 +373,144 @@ pub enum Command {
         #[arg(long, default_value = "text", value_enum)]
         format: OutputFormat,
 
-        /// Write JSON report to file
-        #[arg(long)]
+        /// Write a JSON run summary (with timestamp and git SHA) to file
+        #[arg(long, visible_alias = "report-file", value_name = "PATH")]
         report: Option<PathBuf>,
 
-        /// Timeout per command in seconds
-        #[arg(long, default_value = "30")]
-        timeout: u32,
+        /// Append the summary to --report as one JSON line instead of overwriting
+        #[arg(long, requires = "report")]
+        append: bool,
+
+        /// Write just the aggregate counts and duration as JSON to file
+        #[arg(long, value_name = "PATH")]
+        summary_json: Option<PathBuf>,
+
+        /// Save each command's full stdout/stderr, plus an index.json, in this directory
+        #[arg(long, value_name = "DIR")]
+        out_dir: Option<PathBuf>,
+
+        /// Timeout per command in seconds, overriding doc and config timeouts (0 disables)
+        #[arg(long)]
+        timeout: Option<u32>,
 
         /// Continue running after first failure
         #[arg(long)]
         keep_going: bool,
+
+        /// Stop once this many commands have failed, instead of at the first
+        #[arg(long, value_name = "N", conflicts_with = "keep_going", value_parser = clap::value_parser!(u64).range(1..))]
+        bail_after: Option<u64>,
+
+        /// Run up to N commands at once; commands sharing a directory, or in a serial doc, still run in order
+        #[arg(long, value_name = "N", default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+        jobs: u64,
+
+        /// Re-run every command, ignoring cached passes
+        #[arg(long)]
+        no_cache: bool,
+
+        /// Skip commands that passed within this long ago, e.g. 10m or 1h
+        #[arg(long, value_name = "DURATION", value_parser = crate::cache::parse_duration, conflicts_with = "no_cache")]
+        since: Option<Duration>,
+
+        /// Only verify docs impacted by changes since the base ref
+        #[arg(long)]
+        changed: bool,
+
+        /// Base ref for --changed comparison [default: origin/main]
+        #[arg(long)]
+        base: Option<String>,
+
+        /// With --changed, use the changes staged for commit instead of the base ref
+        #[arg(long, requires = "changed", conflicts_with = "base")]
+        staged: bool,
+
+        /// Show output of passing commands too (included in JSON output)
+        #[arg(short, long)]
+        verbose: bool,
+
+        /// Run identical commands in a document only once
+        #[arg(long)]
+        dedupe: bool,
+
+        /// List verification items with their numbers instead of running them
+        #[arg(long, conflicts_with = "only")]
+        list: bool,
+
+        /// Run only the item with this number from --list
+        #[arg(long, value_name = "INDEX")]
+        only: Option<usize>,
+
+        /// Run only the items that failed in the previous run
+        #[arg(long, conflicts_with = "only")]
+        only_failed: bool,
+
+        /// Print each doc with verification items, one path per line, instead of running
+        #[arg(long, conflicts_with_all = ["list", "only"])]
+        list_docs: bool,
+
+        /// Print each doc with an item that failed in the previous run, one path per line
+        #[arg(long, conflicts_with_all = ["list", "list_docs", "only"])]
+        list_failing_docs: bool,
+
+        /// Interpreter to run commands with, e.g. bash or pwsh [default: sh]
+        #[arg(long, value_name = "PATH")]
+        shell: Option<String>,
+
+        /// Output to capture; commands with expected output always capture stdout
+        #[arg(long, value_enum, default_value_t = CaptureMode::All)]
+        capture: CaptureMode,
+
+        /// If the shell isn't installed: error, or skip the commands that need it
+        #[arg(long, value_enum, value_name = "POLICY", default_value_t = MissingShell::Error)]
+        on_missing_shell: MissingShell,
+
+        /// Run each command in a fresh temporary directory unless it sets its own working_dir
+        #[arg(long)]
+        isolated: bool,
+
+        /// Print a copy-pasteable `cd <dir> && ENV=... <shell> -c '<command>'` line for failed items (or every item with `=all`)
+        #[arg(
+            long,
+            value_enum,
+            value_name = "WHEN",
+            num_args = 0..=1,
+            require_equals = true,
+            default_missing_value = "failed"
+        )]
+        print_command: Option<PrintCommand>,
+
+        /// Show environment values in --print-command lines instead of masking secret-looking ones
+        #[arg(long)]
+        show_env: bool,
+
+        /// Fail on output mismatches, requiring exact output and full regex matches
+        #[arg(long, overrides_with = "no_strict_output")]
+        strict_output: bool,
+
+        /// Warn on output mismatches and match substrings, overriding the config
+        #[arg(long, overrides_with = "strict_output")]
+        no_strict_output: bool,
+
+        /// Report timed-out commands as inconclusive instead of failing the run
+        #[arg(long)]
+        allow_timeout: bool,
+
+        /// Run only items tagged with this tag (repeatable; any tag matches)
+        #[arg(long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+
+        /// Skip items tagged with this tag (repeatable)
+        #[arg(long = "exclude-tag", value_name = "TAG")]
+        exclude_tags: Vec<String>,
+
+        /// Run this command once before any doc is verified (repeatable)
+        #[arg(long, value_name = "CMD")]
+        before: Vec<String>,
+
+        /// Run this command once after all docs, even if some failed (repeatable)
+        #[arg(long, value_name = "CMD")]
+        after: Vec<String>,
     },
 
     /// Build static documentation site
@@ -226,6 +541,26 @@ pub enum Command {
         /// Exclude these code patterns (can be specified multiple times)
         #[arg(long = "exclude", value_name = "PATTERN")]
         exclude: Vec<String>,
+
+        /// Also write a self-contained HTML report to DIR/index.html
+        #[arg(long, value_name = "DIR")]
+        html: Option<PathBuf>,
+
+        /// Also count files excluded by .gitignore and .ignore
+        #[arg(long)]
+        no_ignore: bool,
+
+        /// Break coverage down by file extension
+        #[arg(long)]
+        by_extension: bool,
+
+        /// Fail if coverage of one extension is below a percentage, e.g. rs=90 (repeatable)
+        #[arg(long, value_name = "EXT=PERCENT", value_parser = crate::commands::coverage::parse_extension_threshold)]
+        threshold_ext: Vec<(String, u32)>,
+
+        /// Fail if any code file is covered by fewer than N docs
+        #[arg(long, value_name = "N")]
+        min_docs_per_file: Option<usize>,
     },
 
     /// Check if newly added code files are covered by documentation
@@ -281,6 +616,24 @@ pub enum Command {
         format: OutputFormat,
     },
 
+    /// Output a graph of which docs cover which source files
+    Graph {
+        /// Output format: dot, mermaid, json
+        #[arg(long, default_value = "dot", value_enum)]
+        format: GraphOutputFormat,
+
+        /// Collapse source files into their parent directories
+        #[arg(long)]
+        dirs: bool,
+    },
+
+    /// Summarize the docs corpus: counts, lengths, verification, coverage
+    Stats {
+        /// Output format: text, json
+        #[arg(long, default_value = "text", value_enum)]
+        format: StatsOutputFormat,
+    },
+
     /// Show documentation status and health overview
     Status {
         /// Specific files or directories to check [default: docs root from config]
@@ -300,7 +653,7 @@ pub enum Command {
         base: Option<String>,
     },
 
-    /// Bulk-insert missing PAVED sections into existing documentation
+    /// Upgrade existing docs: rename legacy headings and insert missing PAVED sections
     Migrate {
         /// Path to migrate (file or directory) [default: docs root from config]
         #[arg()]
@@ -326,6 +679,16 @@ pub enum Command {
         #[arg(long, default_value = "true", action = clap::ArgAction::Set)]
         backup: bool,
     },
+
+    /// Run a language server on stdio that publishes check diagnostics
+    Lsp,
+
+    /// Generate shell completions
+    Completions {
+        /// Shell to generate completions for: bash, zsh, fish, powershell, elvish
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
 }
 
 /// Output format for the `pave changed` command.
@@ -348,6 +711,28 @@ pub enum StatusOutputFormat {
     Json,
 }
 
+/// Output format for the `pave stats` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum StatsOutputFormat {
+    /// Human-readable text output
+    #[default]
+    Text,
+    /// JSON output for programmatic use
+    Json,
+}
+
+/// Output format for the `pave graph` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
+pub enum GraphOutputFormat {
+    /// Graphviz DOT, for `dot -Tsvg`
+    #[default]
+    Dot,
+    /// Mermaid flowchart
+    Mermaid,
+    /// JSON nodes and edges for programmatic use
+    Json,
+}
+
 /// Output format for the `pave coverage` command.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Default)]
 pub enum CoverageOutputFormat {
@@ -362,8 +747,12 @@ pub enum CoverageOutputFormat {
 pub enum ConfigCommand {
     /// Get a config value
     Get {
-        /// The key to get (e.g., docs.root, rules.max_lines)
+        /// The key to get (e.g., docs.root, rules.max_lines, verification)
         key: String,
+
+        /// Print the value (or whole table) as JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Set a config value
@@ -374,15 +763,54 @@ pub enum ConfigCommand {
         value: String,
     },
 
+    /// Remove a config value from the file, reverting it to its default
+    Unset {
+        /// The key to remove (e.g., rules.max_lines)
+        key: String,
+    },
+
     /// List all config values
     List,
 
     /// Print path to config file
     Path,
+
+    /// Open the config file in $EDITOR and validate it afterwards
+    Edit,
+
+    /// Check every key in the config file for unknown names and wrong types
+    Validate,
+
+    /// Print the effective config (defaults, file, and PAVE_* overrides) as TOML
+    Export,
+
+    /// Validate a config file and install it as the project config
+    Import {
+        /// Config file to import
+        file: PathBuf,
+
+        /// Overwrite an existing config file
+        #[arg(long)]
+        force: bool,
+    },
 }
 
 /// Output format for the `pave check` command.
 #[derive(Debug, Clone, Copy, ValueEnum, Default)]
+pub enum CheckOutputFormat {
+    /// Human-readable text output
+    #[default]
+    Text,
+    /// JSON output for programmatic use
+    Json,
+    /// GitHub Actions annotation format
+    Github,
+    /// SARIF 2.1.0 for code-scanning tools
+    Sarif,
+}
+
+/// Output format shared by the `pave verify`, `pave lint`, and `pave doctor` commands.
+#[derive(Debug, Clone, Copy, ValueEnum, Default)]
 pub enum OutputFormat {
     /// Human-readable text output
     #[default]
@@ -462,4 +890,8 @@ pub struct InitArgs {
     /// Overwrite existing files
     #[arg(long)]
     pub force: bool,
+
+    /// Write tailored .pave.toml defaults
+    #[arg(long, value_enum, value_name = "PRESET")]
+    pub preset: Option<InitPreset>,
 }
@@ -0,0 +1,115 @@
+//! Terminal color handling.
+//!
+//! All colored output goes through [`Palette`], so `--color` and `NO_COLOR`
+//! are honored in one place.
+
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::cli::ColorChoice;
+
+/// Whether colored output is enabled for this process.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+const GREEN: &str = "32";
+const RED: &str = "31";
+const YELLOW: &str = "33";
+
+/// Configure colored output from the `--color` flag and the environment.
+pub fn init(choice: ColorChoice) {
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    let enabled = should_color(choice, no_color, std::io::stdout().is_terminal());
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Decide whether to color output.
+///
+/// An explicit `always`/`never` wins; `auto` colors only on a terminal and
+/// when `NO_COLOR` is not set.
+pub fn should_color(choice: ColorChoice, no_color: bool, is_terminal: bool) -> bool {
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => is_terminal && !no_color,
+    }
+}
+
+/// The palette for the current process, as configured by [`init`].
+pub fn palette() -> Palette {
+    Palette::new(ENABLED.load(Ordering::Relaxed))
+}
+
+/// Applies ANSI colors to text when enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    enabled: bool,
+}
+
+impl Palette {
+    /// Create a palette that colors text only if `enabled` is true.
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+
+    /// Color text green (success).
+    pub fn green(&self, text: &str) -> String {
+        self.paint(GREEN, text)
+    }
+
+    /// Color text red (failure).
+    pub fn red(&self, text: &str) -> String {
+        self.paint(RED, text)
+    }
+
+    /// Color text yellow (warning).
+    pub fn yellow(&self, text: &str) -> String {
+        self.paint(YELLOW, text)
+    }
+
+    /// Escape sequence that clears the screen and homes the cursor, or
+    /// nothing when colors are off (piped output, `--color never`).
+    pub fn clear_screen(&self) -> &'static str {
+        if self.enabled { "\x1b[2J\x1b[H" } else { "" }
+    }
+
+    fn paint(&self, code: &str, text: &str) -> String {
+        if self.enabled {
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn never_produces_no_escape_codes() {
+        let palette = Palette::new(should_color(ColorChoice::Never, false, true));
+        let text = palette.green("PASS") + &palette.red("FAIL");
+        assert_eq!(text, "PASSFAIL");
+        assert!(!text.contains('\x1b'));
+    }
+
+    #[test]
+    fn always_includes_escape_codes() {
+        let palette = Palette::new(should_color(ColorChoice::Always, true, false));
+        assert_eq!(palette.green("PASS"), "\x1b[32mPASS\x1b[0m");
+        assert_eq!(palette.red("FAIL"), "\x1b[31mFAIL\x1b[0m");
+    }
+
+    #[test]
+    fn clear_screen_only_when_enabled() {
+        assert_eq!(Palette::new(false).clear_screen(), "");
+        assert_eq!(Palette::new(true).clear_screen(), "\x1b[2J\x1b[H");
+    }
+
+    #[test]
+    fn auto_respects_terminal_and_no_color() {
+        assert!(should_color(ColorChoice::Auto, false, true));
+        assert!(!should_color(ColorChoice::Auto, true, true));
+        assert!(!should_color(ColorChoice::Auto, false, false));
+    }
+}
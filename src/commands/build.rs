@@ -8,26 +8,20 @@ use pulldown_cmark::{Options, Parser, html};
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::config::{CONFIG_FILENAME, PaveConfig};
+use crate::config::DocsSection;
 
 /// Arguments for the `pave build` command.
 pub struct BuildArgs {
     /// Output directory for the built site.
     pub output: PathBuf,
+    /// Explicit config file path (from `--config`).
+    pub config: Option<PathBuf>,
 }
 
 /// Execute the `pave build` command.
 pub fn execute(args: BuildArgs) -> Result<()> {
-    let config = load_config()?;
-    let docs_root = &config.docs.root;
-
-    // Check if docs directory exists
-    if !docs_root.exists() {
-        anyhow::bail!(
-            "documentation directory '{}' does not exist",
-            docs_root.display()
-        );
-    }
+    let (config, config_dir) = crate::config::load_project(args.config.as_deref())?;
+    let docs_root = &DocsSection::resolve_root_dir(&config.docs.root, &config_dir)?;
 
     // Find the site source directory
     let cwd = std::env::current_dir().context("failed to get current directory")?;
@@ -74,28 +68,6 @@ pub fn execute(args: BuildArgs) -> Result<()> {
     Ok(())
 }
 
-/// Load pave configuration from current directory or parents.
-fn load_config() -> Result<PaveConfig> {
-    let cwd = std::env::current_dir().context("failed to get current directory")?;
-
-    // Search for config file in current directory and parents
-    let mut search_path = cwd.as_path();
-    loop {
-        let config_path = search_path.join(CONFIG_FILENAME);
-        if config_path.exists() {
-            return PaveConfig::load(&config_path);
-        }
-
-        match search_path.parent() {
-            Some(parent) => search_path = parent,
-            None => break,
-        }
-    }
-
-    // No config found, use defaults
-    Ok(PaveConfig::default())
-}
-
 /// Find the site source directory.
 fn find_site_source(start: &Path) -> Result<PathBuf> {
     let mut search_path = start;
@@ -4,15 +4,15 @@
 //! or updated based on code-to-doc mappings defined in the docs.
 
 use anyhow::{Context, Result};
-use glob::Pattern;
 use serde::Serialize;
 use std::collections::HashSet;
-use std::env;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use crate::cli::ChangedOutputFormat;
-use crate::config::{CONFIG_FILENAME, PaveConfig};
+use crate::config::PaveConfig;
+use crate::parser::ParsedDoc;
+use crate::paths::PathsMatcher;
 
 /// Arguments for the `pave changed` command.
 pub struct ChangedArgs {
@@ -22,6 +22,8 @@ pub struct ChangedArgs {
     pub format: ChangedOutputFormat,
     /// Fail if impacted docs weren't updated.
     pub strict: bool,
+    /// Explicit config file path (from `--config`).
+    pub config: Option<PathBuf>,
 }
 
 /// A documentation file with its path mappings.
@@ -39,13 +41,16 @@ pub struct DocMapping {
 #[derive(Debug, Clone, Serialize)]
 pub struct ImpactedDoc {
     /// Path to the documentation file.
+    #[serde(rename = "doc")]
     pub doc_path: PathBuf,
     /// Document title, if available.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
     /// Code files that matched this doc's patterns.
     pub matched_files: Vec<PathBuf>,
-    /// Whether the doc was also modified in the diff.
+    /// Whether the doc was also modified in the diff. `--strict` fails for
+    /// impacted docs where this is false.
+    #[serde(rename = "modified_in_diff")]
     pub was_updated: bool,
 }
 
@@ -56,6 +61,8 @@ pub struct ChangedResults {
     pub base_ref: String,
     /// Number of changed files in the diff.
     pub changed_files_count: usize,
+    /// Files changed in the diff, sorted.
+    pub changed_files: Vec<PathBuf>,
     /// Docs that were impacted by code changes.
     pub impacted_docs: Vec<ImpactedDoc>,
     /// Docs that were impacted but not updated.
@@ -65,51 +72,31 @@ pub struct ChangedResults {
 /// Execute the `pave changed` command.
 pub fn execute(args: ChangedArgs) -> Result<()> {
     // Find and load config
-    let config_path = find_config()?;
+    let config_path = crate::config::find_config(args.config.as_deref())?;
     let config = PaveConfig::load(&config_path)?;
-    let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
-    let docs_root = config_dir.join(&config.docs.root);
+    let config_dir = &crate::config::config_base_dir(&config_path);
+    let docs_roots = config.docs.resolve_root_dirs(config_dir)?;
 
     // Determine base ref
-    let base_ref = determine_base_ref(args.base.as_deref())?;
+    let base_ref = determine_base_ref(args.base.as_deref(), config_dir)?;
 
     // Get changed files from git
-    let changed_files = get_changed_files(&base_ref)?;
+    let changed_files = get_changed_files(&base_ref, config_dir)?;
 
     if changed_files.is_empty() {
         if args.format == ChangedOutputFormat::Text {
             println!("No changed files found compared to {}", base_ref);
         } else {
-            let results = ChangedResults {
-                base_ref,
-                changed_files_count: 0,
-                impacted_docs: vec![],
-                missing_updates: vec![],
-            };
-            output_json(&results)?;
+            output_json(&build_results(base_ref, &changed_files, &[], config_dir))?;
         }
         return Ok(());
     }
 
     // Load all docs with path mappings
-    let doc_mappings = load_doc_mappings(&docs_root, config_dir)?;
+    let doc_mappings = load_doc_mappings(&docs_roots, config_dir)?;
 
-    // Find impacted docs
-    let impacted_docs = find_impacted_docs(&doc_mappings, &changed_files, config_dir);
-
-    // Collect missing updates
-    let missing_updates: Vec<PathBuf> = impacted_docs
-        .iter()
-        .filter(|d| !d.was_updated)
-        .map(|d| d.doc_path.clone())
-        .collect();
-
-    let results = ChangedResults {
-        base_ref: base_ref.clone(),
-        changed_files_count: changed_files.len(),
-        impacted_docs,
-        missing_updates: missing_updates.clone(),
-    };
+    let results = build_results(base_ref, &changed_files, &doc_mappings, config_dir);
+    let missing_updates = &results.missing_updates;
 
     // Output results
     match args.format {
@@ -129,40 +116,20 @@ pub fn execute(args: ChangedArgs) -> Result<()> {
     Ok(())
 }
 
-/// Find the .pave.toml config file by walking up from the current directory.
-fn find_config() -> Result<PathBuf> {
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let mut dir = current_dir.as_path();
-
-    loop {
-        let config_path = dir.join(CONFIG_FILENAME);
-        if config_path.exists() {
-            return Ok(config_path);
-        }
-
-        match dir.parent() {
-            Some(parent) => dir = parent,
-            None => anyhow::bail!(
-                "No {} found in current directory or any parent directory",
-                CONFIG_FILENAME
-            ),
-        }
-    }
-}
-
-/// Determine the base ref to compare against.
-fn determine_base_ref(explicit_base: Option<&str>) -> Result<String> {
+/// Determine the base ref to compare against, looking up refs in the
+/// repository containing `dir`.
+pub fn determine_base_ref(explicit_base: Option<&str>, dir: &Path) -> Result<String> {
     if let Some(base) = explicit_base {
         return Ok(base.to_string());
     }
 
     // Try origin/main first
-    if ref_exists("origin/main") {
+    if ref_exists("origin/main", dir) {
         return Ok("origin/main".to_string());
     }
 
     // Try origin/master
-    if ref_exists("origin/master") {
+    if ref_exists("origin/master", dir) {
         return Ok("origin/master".to_string());
     }
 
@@ -170,19 +137,21 @@ fn determine_base_ref(explicit_base: Option<&str>) -> Result<String> {
     Ok("HEAD~1".to_string())
 }
 
-/// Check if a git ref exists.
-fn ref_exists(ref_name: &str) -> bool {
+/// Check if a git ref exists in the repository containing `dir`.
+fn ref_exists(ref_name: &str, dir: &Path) -> bool {
     Command::new("git")
         .args(["rev-parse", "--verify", "--quiet", ref_name])
+        .current_dir(dir)
         .output()
         .map(|output| output.status.success())
         .unwrap_or(false)
 }
 
-/// Get the list of changed files from git diff.
-fn get_changed_files(base_ref: &str) -> Result<HashSet<PathBuf>> {
+/// Get the list of changed files from git diff, run from `dir`.
+fn get_changed_files(base_ref: &str, dir: &Path) -> Result<HashSet<PathBuf>> {
     let output = Command::new("git")
         .args(["diff", "--name-only", &format!("{}..HEAD", base_ref)])
+        .current_dir(dir)
         .output()
         .context("Failed to run git diff")?;
 
@@ -190,6 +159,7 @@ fn get_changed_files(base_ref: &str) -> Result<HashSet<PathBuf>> {
         // Try without ..HEAD for cases like HEAD~1
         let output = Command::new("git")
             .args(["diff", "--name-only", base_ref])
+            .current_dir(dir)
             .output()
             .context("Failed to run git diff")?;
 
@@ -204,6 +174,68 @@ fn get_changed_files(base_ref: &str) -> Result<HashSet<PathBuf>> {
     parse_git_diff_output(&output.stdout)
 }
 
+/// Get the list of files staged in the index, run from `dir`.
+fn get_staged_files(dir: &Path) -> Result<HashSet<PathBuf>> {
+    let output = Command::new("git")
+        .args(["diff", "--cached", "--name-only"])
+        .current_dir(dir)
+        .output()
+        .context("Failed to run git diff")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("git diff failed: {}", stderr);
+    }
+
+    parse_git_diff_output(&output.stdout)
+}
+
+/// Find the docs impacted by the diff against `base_ref`.
+///
+/// A doc is impacted when its `## Paths` match a changed file or when the doc
+/// itself changed. Returned paths are relative to `config_dir`.
+pub fn impacted_doc_paths(
+    config_dir: &Path,
+    docs_roots: &[PathBuf],
+    base_ref: &str,
+) -> Result<HashSet<PathBuf>> {
+    let changed_files = get_changed_files(base_ref, config_dir)?;
+    docs_impacted_by(config_dir, docs_roots, changed_files)
+}
+
+/// Find the docs impacted by the changes staged in the index.
+///
+/// Like [`impacted_doc_paths`], a staged doc edit impacts that doc even
+/// without a staged code change.
+pub fn staged_impacted_doc_paths(
+    config_dir: &Path,
+    docs_roots: &[PathBuf],
+) -> Result<HashSet<PathBuf>> {
+    let staged_files = get_staged_files(config_dir)?;
+    docs_impacted_by(config_dir, docs_roots, staged_files)
+}
+
+/// Docs whose `## Paths` match one of `changed_files`, plus changed docs.
+fn docs_impacted_by(
+    config_dir: &Path,
+    docs_roots: &[PathBuf],
+    changed_files: HashSet<PathBuf>,
+) -> Result<HashSet<PathBuf>> {
+    let doc_mappings = load_doc_mappings(docs_roots, config_dir)?;
+
+    let mut impacted: HashSet<PathBuf> =
+        find_impacted_docs(&doc_mappings, &changed_files, config_dir)
+            .into_iter()
+            .map(|doc| doc.doc_path)
+            .collect();
+    impacted.extend(
+        changed_files
+            .into_iter()
+            .filter(|f| f.extension().is_some_and(|ext| ext == "md")),
+    );
+    Ok(impacted)
+}
+
 /// Parse git diff --name-only output into a set of paths.
 fn parse_git_diff_output(output: &[u8]) -> Result<HashSet<PathBuf>> {
     let stdout = String::from_utf8_lossy(output);
@@ -215,10 +247,17 @@ fn parse_git_diff_output(output: &[u8]) -> Result<HashSet<PathBuf>> {
     Ok(files)
 }
 
-/// Load all documentation files with their path mappings.
-fn load_doc_mappings(docs_root: &Path, config_dir: &Path) -> Result<Vec<DocMapping>> {
+/// Load all documentation files under `docs_roots` with their path mappings.
+///
+/// Docs without a `## Paths` section are skipped, and a doc under several
+/// roots is loaded once.
+pub fn load_doc_mappings(docs_roots: &[PathBuf], config_dir: &Path) -> Result<Vec<DocMapping>> {
     let mut mappings = Vec::new();
-    load_doc_mappings_recursive(docs_root, config_dir, &mut mappings)?;
+    for docs_root in docs_roots {
+        load_doc_mappings_recursive(docs_root, config_dir, &mut mappings)?;
+    }
+    mappings.sort_by(|a, b| a.doc_path.cmp(&b.doc_path));
+    mappings.dedup_by(|a, b| a.doc_path == b.doc_path);
     Ok(mappings)
 }
 
@@ -266,7 +305,7 @@ fn parse_doc_mapping(path: &Path, config_dir: &Path) -> Result<Option<DocMapping
     let relative_path = path.strip_prefix(config_dir).unwrap_or(path).to_path_buf();
 
     let title = extract_title(&content);
-    let patterns = extract_paths_patterns(&content);
+    let patterns = ParsedDoc::parse_content(path.to_path_buf(), &content)?.paths_patterns();
 
     // Only include docs that have path mappings
     if patterns.is_empty() {
@@ -293,41 +332,29 @@ fn extract_title(content: &str) -> Option<String> {
     None
 }
 
-/// Extract path patterns from the ## Paths section.
-fn extract_paths_patterns(content: &str) -> Vec<String> {
-    let mut patterns = Vec::new();
-    let mut in_paths_section = false;
-
-    for line in content.lines() {
-        let trimmed = line.trim();
-
-        // Check if entering Paths section
-        if trimmed.starts_with("## Paths") {
-            in_paths_section = true;
-            continue;
-        }
-
-        // Check if leaving Paths section (another ## heading)
-        if in_paths_section && trimmed.starts_with("## ") {
-            break;
-        }
+/// Map the changed files onto the docs that cover them.
+fn build_results(
+    base_ref: String,
+    changed_files: &HashSet<PathBuf>,
+    doc_mappings: &[DocMapping],
+    config_dir: &Path,
+) -> ChangedResults {
+    let impacted_docs = find_impacted_docs(doc_mappings, changed_files, config_dir);
+    let missing_updates = impacted_docs
+        .iter()
+        .filter(|d| !d.was_updated)
+        .map(|d| d.doc_path.clone())
+        .collect();
+    let mut sorted_files: Vec<PathBuf> = changed_files.iter().cloned().collect();
+    sorted_files.sort();
 
-        // Collect patterns (lines starting with - or *)
-        if in_paths_section
-            && let Some(pattern) = trimmed
-                .strip_prefix("- ")
-                .or_else(|| trimmed.strip_prefix("* "))
-        {
-            let pattern = pattern.trim();
-            // Remove backticks if present
-            let pattern = pattern.trim_matches('`');
-            if !pattern.is_empty() {
-                patterns.push(pattern.to_string());
-            }
-        }
+    ChangedResults {
+        base_ref,
+        changed_files_count: changed_files.len(),
+        changed_files: sorted_files,
+        impacted_docs,
+        missing_updates,
     }
-
-    patterns
 }
 
 /// Find docs impacted by the changed files.
@@ -339,10 +366,11 @@ fn find_impacted_docs(
     let mut impacted = Vec::new();
 
     for doc in doc_mappings {
+        let matcher = PathsMatcher::new(&doc.patterns);
         let mut matched_files = Vec::new();
 
         for changed_file in changed_files {
-            if matches_any_pattern(changed_file, &doc.patterns) {
+            if matcher.matches(changed_file) {
                 matched_files.push(changed_file.clone());
             }
         }
@@ -375,30 +403,6 @@ fn find_impacted_docs(
     impacted
 }
 
-/// Check if a path matches any of the glob patterns.
-fn matches_any_pattern(path: &Path, patterns: &[String]) -> bool {
-    let path_str = path.to_string_lossy();
-
-    for pattern_str in patterns {
-        // Try to compile as glob pattern
-        if let Ok(pattern) = Pattern::new(pattern_str)
-            && pattern.matches(&path_str)
-        {
-            return true;
-        }
-
-        // Also do simple prefix matching for patterns like "src/foo/"
-        if pattern_str.ends_with('/') || pattern_str.ends_with('*') {
-            let prefix = pattern_str.trim_end_matches('*').trim_end_matches('/');
-            if path_str.starts_with(prefix) {
-                return true;
-            }
-        }
-    }
-
-    false
-}
-
 /// Output results in text format.
 fn output_text(results: &ChangedResults) {
     println!(
@@ -469,17 +473,52 @@ fn output_text(results: &ChangedResults) {
 
 /// Output results in JSON format.
 fn output_json(results: &ChangedResults) -> Result<()> {
-    let json = serde_json::to_string_pretty(results).context("Failed to serialize results")?;
-    println!("{}", json);
+    println!("{}", render_json(results)?);
     Ok(())
 }
 
+/// Render results as pretty-printed JSON.
+fn render_json(results: &ChangedResults) -> Result<String> {
+    serde_json::to_string_pretty(results).context("Failed to serialize results")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::fs;
     use tempfile::TempDir;
 
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args([
+                "-c",
+                "user.name=pave",
+                "-c",
+                "user.email=pave@example.com",
+                "-c",
+                "commit.gpgsign=false",
+            ])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    fn determine_base_ref_looks_up_refs_in_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path();
+        git(repo, &["init", "-q"]);
+        git(repo, &["commit", "-q", "--allow-empty", "-m", "init"]);
+        assert_eq!(determine_base_ref(None, repo).unwrap(), "HEAD~1");
+
+        git(repo, &["update-ref", "refs/remotes/origin/main", "HEAD"]);
+        assert_eq!(determine_base_ref(None, repo).unwrap(), "origin/main");
+        assert_eq!(determine_base_ref(Some("v1"), repo).unwrap(), "v1");
+    }
+
     #[test]
     fn test_extract_title() {
         let content = "# My Document\n\n## Purpose\nSome content.";
@@ -492,89 +531,39 @@ mod tests {
         assert_eq!(extract_title(content), Some("Actual Title".to_string()));
     }
 
-    #[test]
-    fn test_extract_paths_patterns() {
-        let content = r#"# Doc
-
-## Purpose
-Does something.
-
-## Paths
-- `src/commands/*.rs`
-- `src/cli.rs`
-
-## Examples
-Some examples.
-"#;
-
-        let patterns = extract_paths_patterns(content);
-        assert_eq!(patterns.len(), 2);
-        assert_eq!(patterns[0], "src/commands/*.rs");
-        assert_eq!(patterns[1], "src/cli.rs");
-    }
-
-    #[test]
-    fn test_extract_paths_patterns_with_asterisks() {
-        let content = r#"# Doc
-
-## Paths
-* src/foo.rs
-* src/bar/*.rs
-"#;
-
-        let patterns = extract_paths_patterns(content);
-        assert_eq!(patterns.len(), 2);
-        assert_eq!(patterns[0], "src/foo.rs");
-        assert_eq!(patterns[1], "src/bar/*.rs");
-    }
-
-    #[test]
-    fn test_extract_paths_patterns_empty() {
-        let content = r#"# Doc
-
-## Purpose
-No paths section.
-
-## Examples
-"#;
-
-        let patterns = extract_paths_patterns(content);
-        assert!(patterns.is_empty());
-    }
-
     #[test]
     fn test_matches_any_pattern_exact() {
         let path = PathBuf::from("src/cli.rs");
         let patterns = vec!["src/cli.rs".to_string()];
-        assert!(matches_any_pattern(&path, &patterns));
+        assert!(PathsMatcher::new(&patterns).matches(&path));
     }
 
     #[test]
     fn test_matches_any_pattern_glob() {
         let path = PathBuf::from("src/commands/check.rs");
         let patterns = vec!["src/commands/*.rs".to_string()];
-        assert!(matches_any_pattern(&path, &patterns));
+        assert!(PathsMatcher::new(&patterns).matches(&path));
     }
 
     #[test]
     fn test_matches_any_pattern_glob_recursive() {
         let path = PathBuf::from("src/commands/sub/deep.rs");
         let patterns = vec!["src/**/*.rs".to_string()];
-        assert!(matches_any_pattern(&path, &patterns));
+        assert!(PathsMatcher::new(&patterns).matches(&path));
     }
 
     #[test]
     fn test_matches_any_pattern_no_match() {
         let path = PathBuf::from("tests/test.rs");
         let patterns = vec!["src/*.rs".to_string()];
-        assert!(!matches_any_pattern(&path, &patterns));
+        assert!(!PathsMatcher::new(&patterns).matches(&path));
     }
 
     #[test]
     fn test_matches_any_pattern_prefix() {
         let path = PathBuf::from("src/commands/check.rs");
         let patterns = vec!["src/commands/".to_string()];
-        assert!(matches_any_pattern(&path, &patterns));
+        assert!(PathsMatcher::new(&patterns).matches(&path));
     }
 
     #[test]
@@ -666,6 +655,60 @@ No paths section.
         );
     }
 
+    #[test]
+    fn test_json_maps_changed_files_to_docs() {
+        let doc_mappings = vec![
+            DocMapping {
+                doc_path: PathBuf::from("docs/auth.md"),
+                title: Some("Auth".to_string()),
+                patterns: vec!["src/auth/**".to_string()],
+            },
+            DocMapping {
+                doc_path: PathBuf::from("docs/session.md"),
+                title: None,
+                patterns: vec!["src/auth/session.rs".to_string()],
+            },
+        ];
+        let changed_files: HashSet<PathBuf> = ["src/auth/session.rs", "docs/auth.md", "README.md"]
+            .into_iter()
+            .map(PathBuf::from)
+            .collect();
+
+        let results = build_results(
+            "main".to_string(),
+            &changed_files,
+            &doc_mappings,
+            Path::new("."),
+        );
+        let json: serde_json::Value =
+            serde_json::from_str(&render_json(&results).unwrap()).unwrap();
+
+        assert_eq!(
+            json["changed_files"],
+            serde_json::json!(["README.md", "docs/auth.md", "src/auth/session.rs"])
+        );
+        assert_eq!(
+            json["impacted_docs"],
+            serde_json::json!([
+                {
+                    "doc": "docs/auth.md",
+                    "title": "Auth",
+                    "matched_files": ["src/auth/session.rs"],
+                    "modified_in_diff": true
+                },
+                {
+                    "doc": "docs/session.md",
+                    "matched_files": ["src/auth/session.rs"],
+                    "modified_in_diff": false
+                }
+            ])
+        );
+        assert_eq!(
+            json["missing_updates"],
+            serde_json::json!(["docs/session.md"])
+        );
+    }
+
     #[test]
     fn test_find_impacted_docs_with_update() {
         let doc_mappings = vec![DocMapping {
@@ -734,7 +777,7 @@ No paths section.
         // Create an index.md (should be skipped)
         fs::write(docs_dir.join("index.md"), "# Index").unwrap();
 
-        let mappings = load_doc_mappings(&docs_dir, temp_dir.path()).unwrap();
+        let mappings = load_doc_mappings(&[docs_dir], temp_dir.path()).unwrap();
 
         // Should only include the doc with paths, not the one without or index.md
         assert_eq!(mappings.len(), 1);
@@ -1,23 +1,32 @@
 //! Implementation of the `pave check` command for validating PAVED documents.
 
 use anyhow::{Context, Result};
-use serde::Serialize;
-use std::collections::HashSet;
-use std::env;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::process::Command;
-
-use crate::cli::OutputFormat;
-use crate::config::{CONFIG_FILENAME, PaveConfig};
-use crate::parser::ParsedDoc;
-use crate::rules::{RulesEngine, detect_doc_type, get_type_specific_rules};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+use crate::cli::CheckOutputFormat;
+use crate::color;
+use crate::commands::coverage::{OrphanReport, find_orphans};
+use crate::config::PaveConfig;
+use crate::parser::{
+    CodeBlockTracker, FenceIssue, FenceIssueKind, ParsedDoc, section_name_matches,
+};
+use crate::paths::walk_files;
+use crate::rules::{DocType, Rule, RulesEngine, detect_doc_type, get_type_specific_rules};
+use crate::templates::{TemplateType, get_template};
 
 /// Arguments for the `pave check` command.
 pub struct CheckArgs {
     /// Specific files or directories to check.
     pub paths: Vec<PathBuf>,
     /// Output format.
-    pub format: OutputFormat,
+    pub format: CheckOutputFormat,
     /// Treat warnings as errors (overrides gradual mode).
     pub strict: bool,
     /// Force gradual mode (treat errors as warnings, exit 0).
@@ -26,6 +35,32 @@ pub struct CheckArgs {
     pub changed: bool,
     /// Base ref for --changed comparison.
     pub base: Option<String>,
+    /// Re-run validation whenever the docs change.
+    pub watch: bool,
+    /// Insert placeholder sections for missing required sections.
+    pub fix: bool,
+    /// Report orphaned docs and uncovered source files instead of validating.
+    pub orphans: bool,
+    /// Read the document to check from stdin.
+    pub stdin: bool,
+    /// Logical path of the stdin document, used for messages and type detection.
+    pub stdin_path: Option<PathBuf>,
+    /// Walk directories without honoring `.gitignore` and `.ignore`.
+    pub no_ignore: bool,
+    /// Only fail on issues not recorded in this baseline file.
+    pub baseline: Option<PathBuf>,
+    /// Record the current issues to this baseline file instead of failing.
+    pub write_baseline: Option<PathBuf>,
+    /// Print only failing issues and a one-line summary.
+    pub quiet: bool,
+    /// Only report issues from these rule ids.
+    pub rules: Vec<String>,
+    /// Never report issues from these rule ids.
+    pub ignore_rules: Vec<String>,
+    /// Section length limit, overriding `check.max_section_lines`.
+    pub max_section_lines: Option<usize>,
+    /// Explicit config file path (from `--config`).
+    pub config: Option<PathBuf>,
 }
 
 /// Severity of a validation issue.
@@ -43,8 +78,13 @@ pub struct Issue {
     pub file: PathBuf,
     /// Line number where the issue was found (1-indexed).
     pub line: usize,
+    /// Column where the issue starts (1-indexed, in characters).
+    pub column: usize,
     /// Severity of the issue.
     pub severity: Severity,
+    /// Identifier of the rule that produced the issue (e.g. `max-lines`).
+    #[serde(rename = "code")]
+    pub rule: String,
     /// Description of the issue.
     pub message: String,
     /// Hint for fixing the issue.
@@ -69,6 +109,33 @@ pub struct CheckResults {
     /// Only populated when gradual mode is active.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub would_fail_count: Option<usize>,
+    /// Number of issues suppressed because they're in the `--baseline`.
+    #[serde(skip_serializing_if = "is_zero")]
+    pub baselined: usize,
+    /// Number of `status: draft` docs left out under `--strict`.
+    #[serde(skip_serializing_if = "is_zero")]
+    pub drafts_skipped: usize,
+    /// Issues kept out by docs' `pave: ignore-rule` directives.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub suppressed: Vec<Suppression>,
+    /// Docs whose frontmatter marks them `status: draft`.
+    #[serde(skip)]
+    drafts: Vec<PathBuf>,
+}
+
+/// Issues of one rule a doc suppressed with a `pave: ignore-rule` directive.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Suppression {
+    /// The doc with the directive.
+    pub file: PathBuf,
+    /// The suppressed rule id.
+    pub rule: String,
+    /// How many issues were suppressed.
+    pub count: usize,
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
 }
 
 impl CheckResults {
@@ -78,9 +145,58 @@ impl CheckResults {
             errors: Vec::new(),
             warnings: Vec::new(),
             would_fail_count: None,
+            baselined: 0,
+            drafts_skipped: 0,
+            suppressed: Vec::new(),
+            drafts: Vec::new(),
         }
     }
 
+    /// Drop draft docs and their issues from the results, as `--strict`
+    /// does, counting them in `drafts_skipped`.
+    fn skip_drafts(&mut self) {
+        let drafts = std::mem::take(&mut self.drafts);
+        self.errors.retain(|issue| !drafts.contains(&issue.file));
+        self.warnings.retain(|issue| !drafts.contains(&issue.file));
+        self.suppressed.retain(|s| !drafts.contains(&s.file));
+        self.files_checked -= drafts.len();
+        self.drafts_skipped += drafts.len();
+    }
+
+    /// Move the issues added since `first_error`/`first_warning` whose rule
+    /// is in `rules` into `suppressed`.
+    fn suppress(&mut self, first_error: usize, first_warning: usize, rules: &[String]) {
+        let added: Vec<Issue> = self
+            .errors
+            .drain(first_error..)
+            .chain(self.warnings.drain(first_warning..))
+            .collect();
+        for issue in added {
+            if !rules.contains(&issue.rule) {
+                self.add_issue(issue);
+                continue;
+            }
+            match self
+                .suppressed
+                .iter_mut()
+                .find(|s| s.file == issue.file && s.rule == issue.rule)
+            {
+                Some(suppression) => suppression.count += 1,
+                None => self.suppressed.push(Suppression {
+                    file: issue.file,
+                    rule: issue.rule,
+                    count: 1,
+                }),
+            }
+        }
+    }
+
+    /// Keep only the issues whose rule `selection` allows.
+    fn retain_rules(&mut self, selection: &RuleSelection) {
+        self.errors.retain(|issue| selection.allows(&issue.rule));
+        self.warnings.retain(|issue| selection.allows(&issue.rule));
+    }
+
     fn add_issue(&mut self, issue: Issue) {
         match issue.severity {
             Severity::Error => self.errors.push(issue),
@@ -98,6 +214,144 @@ impl CheckResults {
     }
 }
 
+/// Rules that only run when listed in `check.enabled_rules` or named with
+/// `--rule`.
+pub const OPT_IN_RULES: &[&str] = &["trailing-whitespace", "mixed-indentation"];
+
+/// Which rule ids `pave check` reports.
+///
+/// `--ignore-rule` always wins. Otherwise `--rule` limits the report to the
+/// named rules, even ones in `check.disabled_rules`; without it every rule
+/// but the disabled ones is reported.
+struct RuleSelection<'a> {
+    only: &'a [String],
+    ignored: &'a [String],
+    disabled: &'a [String],
+}
+
+impl<'a> RuleSelection<'a> {
+    fn new(args: &'a CheckArgs, config: &'a PaveConfig) -> Self {
+        Self {
+            only: &args.rules,
+            ignored: &args.ignore_rules,
+            disabled: &config.check.disabled_rules,
+        }
+    }
+
+    /// The selection `check.disabled_rules` alone makes, for editor
+    /// diagnostics.
+    fn from_config(config: &'a PaveConfig) -> Self {
+        Self {
+            only: &[],
+            ignored: &[],
+            disabled: &config.check.disabled_rules,
+        }
+    }
+
+    fn allows(&self, rule: &str) -> bool {
+        let named = |ids: &[String]| ids.iter().any(|id| id == rule);
+        if named(self.ignored) {
+            false
+        } else if !self.only.is_empty() {
+            named(self.only)
+        } else {
+            !named(self.disabled)
+        }
+    }
+}
+
+/// Known issues recorded by `pave check --write-baseline`.
+///
+/// Issues are fingerprinted by file, rule, and message with numbers masked,
+/// so they still match after lines shift or a count changes. `count` lets a
+/// baseline absorb several identical issues in one file.
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+struct Baseline {
+    issues: Vec<BaselineEntry>,
+}
+
+/// One fingerprinted issue in a [`Baseline`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+struct BaselineEntry {
+    /// Document path relative to the project root, with `/` separators.
+    file: String,
+    /// Rule that produced the issue.
+    rule: String,
+    /// Issue message with every run of digits replaced by `#`.
+    message: String,
+    /// How many identical issues this entry covers.
+    count: usize,
+}
+
+impl Baseline {
+    /// Fingerprint every issue in `results`.
+    fn from_results(results: &CheckResults, config_dir: &Path) -> Self {
+        let mut counts: BTreeMap<(String, String, String), usize> = BTreeMap::new();
+        for issue in results.errors.iter().chain(&results.warnings) {
+            *counts.entry(fingerprint(issue, config_dir)).or_default() += 1;
+        }
+        Self {
+            issues: counts
+                .into_iter()
+                .map(|((file, rule, message), count)| BaselineEntry {
+                    file,
+                    rule,
+                    message,
+                    count,
+                })
+                .collect(),
+        }
+    }
+
+    fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read baseline: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse baseline: {}", path.display()))
+    }
+
+    fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).context("Failed to serialize baseline")?;
+        std::fs::write(path, json + "\n")
+            .with_context(|| format!("Failed to write baseline: {}", path.display()))
+    }
+
+    /// Drop issues covered by the baseline from `results`, returning how
+    /// many were dropped.
+    fn suppress(&self, results: &mut CheckResults, config_dir: &Path) -> usize {
+        let mut remaining: BTreeMap<(String, String, String), usize> = self
+            .issues
+            .iter()
+            .map(|e| ((e.file.clone(), e.rule.clone(), e.message.clone()), e.count))
+            .collect();
+        let mut known = |issue: &Issue| match remaining.get_mut(&fingerprint(issue, config_dir)) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                true
+            }
+            _ => false,
+        };
+
+        let before = results.errors.len() + results.warnings.len();
+        results.errors.retain(|issue| !known(issue));
+        results.warnings.retain(|issue| !known(issue));
+        before - results.errors.len() - results.warnings.len()
+    }
+}
+
+/// The `(file, rule, message)` fingerprint of an issue.
+fn fingerprint(issue: &Issue, config_dir: &Path) -> (String, String, String) {
+    let file = issue.file.strip_prefix(config_dir).unwrap_or(&issue.file);
+    let file = file
+        .to_string_lossy()
+        .replace('\\', "/")
+        .trim_start_matches("./")
+        .to_string();
+    let digits = Regex::new(r"\d+").expect("valid regex");
+    let message = digits.replace_all(&issue.message, "#").into_owned();
+    (file, issue.rule.clone(), message)
+}
+
 /// Check if the gradual deadline has passed.
 /// Returns true if the deadline has passed (gradual mode should be disabled).
 fn is_gradual_deadline_passed(deadline: &str) -> bool {
@@ -210,56 +464,83 @@ fn is_gradual_mode_active(config: &PaveConfig, args: &CheckArgs) -> bool {
     false
 }
 
+/// How long to wait for follow-up file events before re-running in watch mode.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
+
 /// Execute the `pave check` command.
 pub fn execute(args: CheckArgs) -> Result<()> {
+    if args.watch {
+        return watch(&args);
+    }
+    run_check(&args)
+}
+
+/// Run a single validation pass.
+fn run_check(args: &CheckArgs) -> Result<()> {
     // Find and load config
-    let config_path = find_config()?;
-    let config = PaveConfig::load(&config_path)?;
-    let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+    let config_path = crate::config::find_config(args.config.as_deref())?;
+    let mut config = PaveConfig::load(&config_path)?;
+    let config_dir = &crate::config::config_base_dir(&config_path);
 
-    // Determine paths to check
-    let paths = if args.paths.is_empty() {
-        // Use docs root from config, relative to config file location
-        vec![config_dir.join(&config.docs.root)]
-    } else {
-        args.paths.clone()
-    };
+    // --strict always requires a Verification section spelled canonically
+    if args.strict {
+        config.rules.require_verification = true;
+        config.rules.section_casing = true;
+    }
 
-    // Find all markdown files
-    let mut files = find_markdown_files(&paths)?;
+    // Naming an opt-in rule with --rule turns it on
+    for rule in &args.rules {
+        if OPT_IN_RULES.contains(&rule.as_str()) && !config.check.enabled_rules.contains(rule) {
+            config.check.enabled_rules.push(rule.clone());
+        }
+    }
 
-    // Filter to only changed files if --changed flag is set
-    if args.changed {
-        let base_ref = determine_base_ref(args.base.as_deref())?;
-        let changed_files = get_changed_md_files(&base_ref, config_dir)?;
+    if args.max_section_lines.is_some() {
+        config.check.max_section_lines = args.max_section_lines;
+    }
 
-        if changed_files.is_empty() {
-            eprintln!("No changed markdown files found compared to {}", base_ref);
-            return Ok(());
+    if args.orphans {
+        return run_orphans(args, &config, config_dir);
+    }
+
+    let mut results = if args.stdin {
+        let path = args
+            .stdin_path
+            .as_deref()
+            .context("--stdin requires --path to name the document")?;
+        check_reader(path, std::io::stdin().lock(), &config)?
+    } else {
+        match check_files(args, &config, config_dir)? {
+            Some(results) => results,
+            None => return Ok(()),
         }
+    };
 
-        // Filter files to only include those that changed
-        files.retain(|f| {
-            // Normalize path for comparison
-            let relative = f.strip_prefix(config_dir).unwrap_or(f).to_path_buf();
-            changed_files.contains(&relative) || changed_files.contains(f)
-        });
+    results.retain_rules(&RuleSelection::new(args, &config));
+
+    // Drafts are checked leniently, and not at all under --strict
+    if args.strict {
+        results.skip_drafts();
     }
 
-    if files.is_empty() {
-        eprintln!("No markdown files found to check");
+    if let Some(path) = &args.write_baseline {
+        let baseline = Baseline::from_results(&results, config_dir);
+        baseline.save(path)?;
+        let count = results.errors.len() + results.warnings.len();
+        eprintln!(
+            "Wrote {} issue{} to baseline {}",
+            count,
+            if count == 1 { "" } else { "s" },
+            path.display()
+        );
         return Ok(());
     }
-
-    // Check each file
-    let mut results = CheckResults::new();
-    for file in &files {
-        check_file(file, &config, &mut results)?;
+    if let Some(path) = &args.baseline {
+        results.baselined = Baseline::load(path)?.suppress(&mut results, config_dir);
     }
-    results.files_checked = files.len();
 
     // Determine if gradual mode is active
-    let gradual_mode = is_gradual_mode_active(&config, &args);
+    let gradual_mode = is_gradual_mode_active(&config, args);
 
     // In gradual mode, convert errors to warnings
     if gradual_mode && !results.errors.is_empty() {
@@ -276,9 +557,10 @@ pub fn execute(args: CheckArgs) -> Result<()> {
 
     // Output results in the requested format
     match args.format {
-        OutputFormat::Text => output_text(&results, gradual_mode),
-        OutputFormat::Json => output_json(&results)?,
-        OutputFormat::Github => output_github(&results, gradual_mode),
+        CheckOutputFormat::Text => output_text(&results, gradual_mode, args.quiet, args.strict),
+        CheckOutputFormat::Json => output_json(&results)?,
+        CheckOutputFormat::Github => output_github(&results, args.quiet, args.strict),
+        CheckOutputFormat::Sarif => output_sarif(&results, config_dir)?,
     }
 
     // Return error if checks failed
@@ -304,25 +586,253 @@ pub fn execute(args: CheckArgs) -> Result<()> {
     }
 }
 
-/// Find the .pave.toml config file by walking up from the current directory.
-fn find_config() -> Result<PathBuf> {
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let mut dir = current_dir.as_path();
+/// Check the docs on disk selected by the command-line paths.
+///
+/// Returns `None` when there is nothing to check.
+fn check_files(
+    args: &CheckArgs,
+    config: &PaveConfig,
+    config_dir: &Path,
+) -> Result<Option<CheckResults>> {
+    // Determine paths to check
+    let paths = if args.paths.is_empty() {
+        // Use docs roots from config, relative to config file location
+        config.docs.resolve_root_dirs(config_dir)?
+    } else {
+        args.paths.clone()
+    };
+
+    // Find all markdown files
+    let mut files = find_markdown_files(&paths, !args.no_ignore)?;
 
-    loop {
-        let config_path = dir.join(CONFIG_FILENAME);
-        if config_path.exists() {
-            return Ok(config_path);
+    // Filter to only changed files if --changed flag is set
+    if args.changed {
+        let base_ref = determine_base_ref(args.base.as_deref())?;
+        let changed_files = get_changed_md_files(&base_ref, config_dir)?;
+
+        if changed_files.is_empty() {
+            eprintln!("No changed markdown files found compared to {}", base_ref);
+            return Ok(None);
         }
 
-        match dir.parent() {
-            Some(parent) => dir = parent,
-            None => anyhow::bail!(
-                "No {} found in current directory or any parent directory",
-                CONFIG_FILENAME
-            ),
+        // Filter files to only include those that changed
+        files.retain(|f| {
+            // Normalize path for comparison
+            let relative = f.strip_prefix(config_dir).unwrap_or(f).to_path_buf();
+            changed_files.contains(&relative) || changed_files.contains(f)
+        });
+    }
+
+    if files.is_empty() {
+        eprintln!("No markdown files found to check");
+        return Ok(None);
+    }
+
+    // Stub out missing required sections before checking
+    if args.fix {
+        let strip_whitespace = rule_enabled(config, "trailing-whitespace");
+        for file in &files {
+            let added = fix_file(file, config)?;
+            if !added.is_empty() && !args.quiet {
+                eprintln!("Fixed {}: added {}", file.display(), added.join(", "));
+            }
+            if strip_whitespace {
+                let stripped = fix_trailing_whitespace(file)?;
+                if stripped > 0 && !args.quiet {
+                    eprintln!(
+                        "Fixed {}: stripped trailing whitespace from {} line{}",
+                        file.display(),
+                        stripped,
+                        if stripped == 1 { "" } else { "s" }
+                    );
+                }
+            }
         }
     }
+
+    // Check each file
+    let mut results = CheckResults::new();
+    for file in &files {
+        check_file(file, config, &mut results)?;
+    }
+    results.files_checked = files.len();
+    Ok(Some(results))
+}
+
+/// Check a single document read from `reader`, reported under `path`.
+fn check_reader(path: &Path, mut reader: impl Read, config: &PaveConfig) -> Result<CheckResults> {
+    let mut content = String::new();
+    reader
+        .read_to_string(&mut content)
+        .context("Failed to read document from stdin")?;
+
+    let mut results = CheckResults::new();
+    check_content(path, &content, config, &mut results)?;
+    results.files_checked = 1;
+    Ok(results)
+}
+
+/// Report orphaned docs and uncovered source files.
+///
+/// Orphans are warnings: the command only fails under `--strict`.
+fn run_orphans(args: &CheckArgs, config: &PaveConfig, config_dir: &Path) -> Result<()> {
+    let report = find_orphans(config, config_dir, !args.no_ignore)?;
+
+    match args.format {
+        CheckOutputFormat::Text => output_orphans_text(&report),
+        CheckOutputFormat::Json => {
+            let json =
+                serde_json::to_string_pretty(&report).context("Failed to serialize results")?;
+            println!("{}", json);
+        }
+        CheckOutputFormat::Github => {
+            for doc in &report.orphan_docs {
+                println!(
+                    "::warning file={}::Paths section matches no files",
+                    doc.display()
+                );
+            }
+            for file in &report.uncovered_files {
+                println!(
+                    "::warning file={}::Source file is not covered by any doc",
+                    file.display()
+                );
+            }
+        }
+        CheckOutputFormat::Sarif => anyhow::bail!("--orphans does not support SARIF output"),
+    }
+
+    if args.strict && !report.is_empty() {
+        anyhow::bail!(
+            "Check failed: {} orphaned doc{}, {} uncovered file{} (strict mode)",
+            report.orphan_docs.len(),
+            if report.orphan_docs.len() == 1 {
+                ""
+            } else {
+                "s"
+            },
+            report.uncovered_files.len(),
+            if report.uncovered_files.len() == 1 {
+                ""
+            } else {
+                "s"
+            }
+        );
+    }
+    Ok(())
+}
+
+/// Output an orphan report in text format.
+fn output_orphans_text(report: &OrphanReport) {
+    let palette = color::palette();
+
+    if report.is_empty() {
+        println!(
+            "{}",
+            palette.green("No orphaned docs or uncovered source files")
+        );
+        return;
+    }
+
+    if !report.orphan_docs.is_empty() {
+        println!("Orphaned docs (Paths match no files):");
+        for doc in &report.orphan_docs {
+            println!("  {}", palette.yellow(&doc.display().to_string()));
+        }
+        println!();
+    }
+
+    if !report.uncovered_files.is_empty() {
+        println!("Source files not covered by any doc:");
+        for file in &report.uncovered_files {
+            println!("  {}", palette.yellow(&file.display().to_string()));
+        }
+        println!();
+    }
+
+    println!(
+        "Found {} orphaned doc{} and {} uncovered file{}",
+        report.orphan_docs.len(),
+        if report.orphan_docs.len() == 1 {
+            ""
+        } else {
+            "s"
+        },
+        report.uncovered_files.len(),
+        if report.uncovered_files.len() == 1 {
+            ""
+        } else {
+            "s"
+        }
+    );
+}
+
+/// Watch the docs and re-run validation on every change.
+fn watch(args: &CheckArgs) -> Result<()> {
+    let config_path = crate::config::find_config(args.config.as_deref())?;
+    let config = PaveConfig::load(&config_path)?;
+    let config_dir = &crate::config::config_base_dir(&config_path);
+
+    let watch_paths = if args.paths.is_empty() {
+        config.docs.resolve_root_dirs(config_dir)?
+    } else {
+        args.paths.clone()
+    };
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if let Ok(event) = res
+            && !event.kind.is_access()
+        {
+            let _ = tx.send(event.paths);
+        }
+    })
+    .context("Failed to start file watcher")?;
+
+    for path in &watch_paths {
+        notify::Watcher::watch(&mut watcher, path, notify::RecursiveMode::Recursive)
+            .with_context(|| format!("Failed to watch {}", path.display()))?;
+    }
+
+    run_watch_iteration(args);
+    watch_loop(&rx, WATCH_DEBOUNCE, || run_watch_iteration(args));
+    Ok(())
+}
+
+/// Clear the screen, run validation, and print a timestamped summary.
+fn run_watch_iteration(args: &CheckArgs) {
+    let palette = color::palette();
+    print!("{}", palette.clear_screen());
+    let outcome = run_check(args);
+    let timestamp = chrono::Local::now().format("%H:%M:%S");
+    match outcome {
+        Ok(()) => println!("\n[{}] {}", timestamp, palette.green("PASS")),
+        Err(err) => println!("\n[{}] {}: {}", timestamp, palette.red("FAIL"), err),
+    }
+    println!("Watching for changes (Ctrl+C to stop)...");
+}
+
+/// Invoke `on_change` once per burst of events until the source disconnects.
+///
+/// Events arriving within `debounce` of each other are coalesced, so a single
+/// editor save (which often emits several events) triggers one run.
+fn watch_loop<T, F>(events: &Receiver<T>, debounce: Duration, mut on_change: F)
+where
+    F: FnMut(),
+{
+    while events.recv().is_ok() {
+        loop {
+            match events.recv_timeout(debounce) {
+                Ok(_) => continue,
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => {
+                    on_change();
+                    return;
+                }
+            }
+        }
+        on_change();
+    }
 }
 
 /// Determine the base ref to compare against.
@@ -394,7 +904,10 @@ fn parse_changed_md_files(output: &[u8]) -> Result<HashSet<PathBuf>> {
 }
 
 /// Find all markdown files in the given paths.
-fn find_markdown_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+///
+/// Directories are walked honoring `.gitignore` and `.ignore` when
+/// `respect_ignore` is set; explicitly named files are always included.
+fn find_markdown_files(paths: &[PathBuf], respect_ignore: bool) -> Result<Vec<PathBuf>> {
     let mut files = Vec::new();
 
     for path in paths {
@@ -403,7 +916,11 @@ fn find_markdown_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
                 files.push(path.clone());
             }
         } else if path.is_dir() {
-            collect_markdown_files_recursive(path, &mut files)?;
+            files.extend(
+                walk_files(path, respect_ignore, |_| false)
+                    .into_iter()
+                    .filter(|f| f.extension().is_some_and(|ext| ext == "md")),
+            );
         } else {
             anyhow::bail!("Path does not exist: {}", path.display());
         }
@@ -411,53 +928,88 @@ fn find_markdown_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
 
     // Sort for consistent output
     files.sort();
+    files.dedup();
     Ok(files)
 }
 
-/// Recursively collect markdown files from a directory.
-fn collect_markdown_files_recursive(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
-    let entries = std::fs::read_dir(dir)
-        .with_context(|| format!("Failed to read directory: {}", dir.display()))?;
-
-    for entry in entries {
-        let entry = entry?;
-        let path = entry.path();
-
-        if path.is_dir() {
-            collect_markdown_files_recursive(&path, files)?;
-        } else if path.extension().is_some_and(|ext| ext == "md") {
-            files.push(path);
-        }
-    }
-
-    Ok(())
-}
-
 /// Check a single file against the validation rules.
 fn check_file(path: &Path, config: &PaveConfig, results: &mut CheckResults) -> Result<()> {
-    // Skip validation of index.md files - they are navigation documents
-    // that don't need Verification and Examples sections
-    if path.file_name().is_some_and(|f| f == "index.md") {
-        return Ok(());
-    }
-
-    // Skip template files - they are scaffolds, not actual documentation
-    let path_str = path.to_string_lossy();
-    if path_str.contains("/templates/") || path_str.contains("\\templates\\") {
+    if is_exempt(path) {
         return Ok(());
     }
 
     // Read file content once for parsing and type detection
     let content = std::fs::read_to_string(path)
         .with_context(|| format!("Failed to read file: {}", path.display()))?;
-    let doc = ParsedDoc::parse_content(path.to_path_buf(), &content)?;
+    check_content(path, &content, config, results)
+}
 
+/// Check `content` as if it were the document at `path`, returning every issue.
+///
+/// This is the model behind `pave check --format json` and `pave lsp`.
+pub fn diagnose(path: &Path, content: &str, config: &PaveConfig) -> Result<Vec<Issue>> {
+    let mut results = CheckResults::new();
+    check_content(path, content, config, &mut results)?;
+    results.retain_rules(&RuleSelection::from_config(config));
+    let mut issues = results.errors;
+    issues.append(&mut results.warnings);
+    issues.sort_by_key(|issue| (issue.line, issue.column));
+    Ok(issues)
+}
+
+/// Validate document content, reporting issues under `path`.
+fn check_content(
+    path: &Path,
+    content: &str,
+    config: &PaveConfig,
+    results: &mut CheckResults,
+) -> Result<()> {
+    if is_exempt(path) {
+        tracing::debug!(file = %path.display(), "skipping exempt file");
+        return Ok(());
+    }
+
+    tracing::debug!(file = %path.display(), "checking document");
+    let doc = ParsedDoc::parse_content_with(
+        path.to_path_buf(),
+        content,
+        &config.verification.parse_options(),
+    )?;
+    let first_error = results.errors.len();
+    let first_warning = results.warnings.len();
+    check_doc(path, content, &doc, config, results);
+    if !doc.ignored_rules.is_empty() {
+        results.suppress(first_error, first_warning, &doc.ignored_rules);
+    }
+
+    // Drafts still get every check, but nothing in them fails the run
+    if doc.is_draft() {
+        results.drafts.push(path.to_path_buf());
+        let demoted: Vec<Issue> = results.errors.drain(first_error..).collect();
+        for mut issue in demoted {
+            issue.severity = Severity::Warning;
+            results.warnings.push(issue);
+        }
+    }
+    Ok(())
+}
+
+/// Run every content check on the parsed `doc`.
+fn check_doc(
+    path: &Path,
+    content: &str,
+    doc: &ParsedDoc,
+    config: &PaveConfig,
+    results: &mut CheckResults,
+) {
     // Check max lines
     if doc.line_count > config.rules.max_lines as usize {
         results.add_issue(Issue {
             file: path.to_path_buf(),
             line: doc.line_count,
+            column: 1,
             severity: Severity::Warning,
+            rule: "max-lines".to_string(),
             message: format!(
                 "Document exceeds {} line limit ({} lines)",
                 config.rules.max_lines, doc.line_count
@@ -467,43 +1019,86 @@ fn check_file(path: &Path, config: &PaveConfig, results: &mut CheckResults) -> R
         });
     }
 
-    // Check for required Verification section
-    if config.rules.require_verification && !doc.has_section("Verification") {
-        results.add_issue(Issue {
-            file: path.to_path_buf(),
-            line: 1,
-            severity: Severity::Error,
-            message: "Missing required section 'Verification'".to_string(),
-            hint: Some("Add a '## Verification' section with test commands".to_string()),
-            converted_from_error: false,
-        });
+    // Check each section's length, reported at its heading
+    if let Some(max) = config.check.max_section_lines {
+        for section in doc.sections.iter().filter(|s| s.line_count() > max) {
+            results.add_issue(Issue {
+                file: path.to_path_buf(),
+                line: section.start_line,
+                column: 1,
+                severity: Severity::Warning,
+                rule: "max-section-lines".to_string(),
+                message: format!(
+                    "Section '{}' exceeds {} line limit ({} lines, {}-{})",
+                    section.name,
+                    max,
+                    section.line_count(),
+                    section.start_line,
+                    section.end_line
+                ),
+                hint: Some(
+                    "Consider splitting it into ### subsections or a separate document".to_string(),
+                ),
+                converted_from_error: false,
+            });
+        }
+    }
+
+    // Check for required sections, reported at the document title
+    let doc_type = detect_doc_type(path, content);
+    let title_line = doc.title_line.unwrap_or(1);
+    for name in base_required_sections(doc_type, config) {
+        if !doc.has_section(&name) {
+            results.add_issue(missing_section_issue(path, title_line, &name));
+        }
+    }
+
+    for issue in broken_link_issues(path, content) {
+        results.add_issue(issue);
     }
 
-    // Check for required Examples section
-    if config.rules.require_examples && !doc.has_section("Examples") {
+    if let Some(frontmatter) = &doc.frontmatter_issue {
         results.add_issue(Issue {
             file: path.to_path_buf(),
-            line: 1,
+            line: frontmatter.line,
+            column: 1,
             severity: Severity::Error,
-            message: "Missing required section 'Examples'".to_string(),
-            hint: Some("Add an '## Examples' section with concrete usage examples".to_string()),
+            rule: "invalid-frontmatter".to_string(),
+            message: format!("Frontmatter is not valid YAML: {}", frontmatter.message),
+            hint: Some("Fix the YAML between the `---` lines; until then the doc is read as if it had no frontmatter".to_string()),
             converted_from_error: false,
         });
     }
 
+    for fence in &doc.fence_issues {
+        results.add_issue(fence_issue(path, fence));
+    }
+
+    if config.rules.section_casing {
+        let canonical = canonical_section_names(config);
+        for issue in section_casing_issues(path, doc, &canonical) {
+            results.add_issue(issue);
+        }
+    }
+
+    for issue in whitespace_issues(path, content, config) {
+        results.add_issue(issue);
+    }
+
     // Apply document-type-specific validation rules
-    let doc_type = detect_doc_type(path, &content);
     let type_rules = get_type_specific_rules(doc_type, &config.rules);
 
     if !type_rules.is_empty() {
         let engine = RulesEngine::new(type_rules);
-        let validation_result = engine.validate(&doc);
+        let validation_result = engine.validate(doc);
 
         for error in validation_result.errors {
             results.add_issue(Issue {
                 file: path.to_path_buf(),
                 line: error.line.unwrap_or(1),
+                column: 1,
                 severity: Severity::Error,
+                rule: error.rule,
                 message: error.message,
                 hint: error.suggestion,
                 converted_from_error: false,
@@ -514,80 +1109,614 @@ fn check_file(path: &Path, config: &PaveConfig, results: &mut CheckResults) -> R
             results.add_issue(Issue {
                 file: path.to_path_buf(),
                 line: warning.line.unwrap_or(1),
+                column: 1,
                 severity: Severity::Warning,
+                rule: warning.rule,
                 message: warning.message,
                 hint: None,
                 converted_from_error: false,
             });
         }
     }
+}
 
-    Ok(())
+/// Turn a malformed fence into an issue: an unclosed fence is an error, the
+/// rest are warnings.
+fn fence_issue(path: &Path, fence: &FenceIssue) -> Issue {
+    let (severity, rule, message, hint) = match &fence.kind {
+        FenceIssueKind::Unclosed => (
+            Severity::Error,
+            "unclosed-fence",
+            "Code block is never closed".to_string(),
+            "Add a closing ``` fence; everything after this line is read as code",
+        ),
+        FenceIssueKind::HeadingInside { heading } => (
+            Severity::Warning,
+            "suspicious-fence",
+            format!("Heading '## {}' is inside a code block", heading),
+            "Close the code block before the heading, or tag the block `markdown` if the heading is an example",
+        ),
+        FenceIssueKind::BacktickInInfo => (
+            Severity::Warning,
+            "suspicious-fence",
+            "Code fence info string contains a backtick, so this line opens a block".to_string(),
+            "Put the opening and closing fences on their own lines",
+        ),
+    };
+    Issue {
+        file: path.to_path_buf(),
+        line: fence.line,
+        column: 1,
+        severity,
+        rule: rule.to_string(),
+        message,
+        hint: Some(hint.to_string()),
+        converted_from_error: false,
+    }
+}
+
+/// Sections every document of `doc_type` must have, before type-specific rules.
+///
+/// `check.required_sections` applies to component docs; runbooks and ADRs
+/// get their required sections from `rules.type_specific` instead.
+/// Verification and Examples follow `rules.require_verification` and
+/// `rules.require_examples` for every type.
+fn base_required_sections(doc_type: DocType, config: &PaveConfig) -> Vec<String> {
+    let mut required = match doc_type {
+        DocType::Runbook | DocType::Adr => Vec::new(),
+        DocType::Component | DocType::Other => config.check.required_sections.clone(),
+    };
+    if config.rules.require_verification {
+        required.push("Verification".to_string());
+    }
+    if config.rules.require_examples {
+        required.push("Examples".to_string());
+    }
+
+    let mut seen = HashSet::new();
+    required.retain(|name| seen.insert(name.to_ascii_lowercase()));
+    required
+}
+
+/// Build the error for a document missing a required section.
+/// Warn about headings that name a canonical section with different casing
+/// or trailing punctuation, such as `## verification:`.
+fn section_casing_issues(path: &Path, doc: &ParsedDoc, canonical: &[String]) -> Vec<Issue> {
+    doc.sections
+        .iter()
+        .filter_map(|section| {
+            let name = canonical
+                .iter()
+                .find(|name| section_name_matches(&section.name, name))?;
+            (section.name != *name).then(|| Issue {
+                file: path.to_path_buf(),
+                line: section.start_line,
+                column: 1,
+                severity: Severity::Warning,
+                rule: "section-casing".to_string(),
+                message: format!(
+                    "Section '## {}' should be spelled '## {}'",
+                    section.name, name
+                ),
+                hint: Some(format!("Rename the heading to '## {}'", name)),
+                converted_from_error: false,
+            })
+        })
+        .collect()
+}
+
+fn missing_section_issue(path: &Path, line: usize, name: &str) -> Issue {
+    let (rule, hint) = match name {
+        "Verification" => (
+            "missing-verification".to_string(),
+            "Add a '## Verification' section with test commands".to_string(),
+        ),
+        "Examples" => (
+            "missing-examples".to_string(),
+            "Add an '## Examples' section with concrete usage examples".to_string(),
+        ),
+        _ => (
+            "missing-section".to_string(),
+            format!(
+                "Add a '## {}' section (required by check.required_sections)",
+                name
+            ),
+        ),
+    };
+    Issue {
+        file: path.to_path_buf(),
+        line,
+        column: 1,
+        severity: Severity::Error,
+        rule,
+        message: format!("Missing required section '{}'", name),
+        hint: Some(hint),
+        converted_from_error: false,
+    }
+}
+
+/// Returns true if the opt-in `rule` is listed in `check.enabled_rules`.
+fn rule_enabled(config: &PaveConfig, rule: &str) -> bool {
+    config.check.enabled_rules.iter().any(|id| id == rule)
+}
+
+/// Whether each line of `content` is prose: past the frontmatter and
+/// outside fenced code blocks (fences included).
+fn prose_lines(content: &str) -> Vec<bool> {
+    let lines: Vec<&str> = content.lines().collect();
+    let body_start = ParsedDoc::frontmatter_end(&lines);
+    let mut tracker = CodeBlockTracker::new();
+    lines
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| {
+            let fence = tracker.process_line(line);
+            idx >= body_start && !fence && !tracker.in_code_block()
+        })
+        .collect()
+}
+
+/// Report trailing whitespace and tab/space indentation issues in prose
+/// lines, for whichever of the two opt-in rules are enabled.
+///
+/// A line is mis-indented when its indentation mixes tabs and spaces, or
+/// uses a different character than the first indented line of the doc.
+fn whitespace_issues(path: &Path, content: &str, config: &PaveConfig) -> Vec<Issue> {
+    let trailing = rule_enabled(config, "trailing-whitespace");
+    let indentation = rule_enabled(config, "mixed-indentation");
+    if !trailing && !indentation {
+        return Vec::new();
+    }
+
+    let issue = |line: usize, column: usize, rule: &str, message: String, hint: &str| Issue {
+        file: path.to_path_buf(),
+        line,
+        column,
+        severity: Severity::Warning,
+        rule: rule.to_string(),
+        message,
+        hint: Some(hint.to_string()),
+        converted_from_error: false,
+    };
+    let prose = prose_lines(content);
+    let mut doc_indent: Option<char> = None;
+    let mut issues = Vec::new();
+
+    for (idx, line) in content.lines().enumerate() {
+        if !prose[idx] {
+            continue;
+        }
+
+        let text = line.trim_end();
+        if trailing && text.len() < line.len() {
+            issues.push(issue(
+                idx + 1,
+                text.chars().count() + 1,
+                "trailing-whitespace",
+                "Trailing whitespace".to_string(),
+                "Remove it, or run `pave check --fix --rule trailing-whitespace`",
+            ));
+        }
+
+        let indent = &text[..text.len() - text.trim_start().len()];
+        if !indentation || indent.is_empty() {
+            continue;
+        }
+        if indent.contains(' ') && indent.contains('\t') {
+            issues.push(issue(
+                idx + 1,
+                1,
+                "mixed-indentation",
+                "Indentation mixes tabs and spaces".to_string(),
+                "Indent with spaces only",
+            ));
+            continue;
+        }
+        let used = if indent.starts_with('\t') { '\t' } else { ' ' };
+        match doc_indent {
+            None => doc_indent = Some(used),
+            Some(expected) if expected != used => {
+                let name = |c: char| if c == '\t' { "tabs" } else { "spaces" };
+                issues.push(issue(
+                    idx + 1,
+                    1,
+                    "mixed-indentation",
+                    format!(
+                        "Line is indented with {}, but earlier lines use {}",
+                        name(used),
+                        name(expected)
+                    ),
+                    "Indent every line the same way",
+                ));
+            }
+            Some(_) => {}
+        }
+    }
+
+    issues
+}
+
+/// Strip trailing whitespace from the prose lines of `content`.
+///
+/// Returns the new content and how many lines changed; line endings, code
+/// blocks, and frontmatter are left as they are.
+fn strip_trailing_whitespace(content: &str) -> (String, usize) {
+    let prose = prose_lines(content);
+    let mut fixed = String::with_capacity(content.len());
+    let mut stripped = 0;
+    for (idx, line) in content.split_inclusive('\n').enumerate() {
+        let text = line.trim_end_matches(['\n', '\r']);
+        let ending = &line[text.len()..];
+        if prose.get(idx).copied().unwrap_or(false) && text.trim_end().len() < text.len() {
+            fixed.push_str(text.trim_end());
+            stripped += 1;
+        } else {
+            fixed.push_str(text);
+        }
+        fixed.push_str(ending);
+    }
+    (fixed, stripped)
+}
+
+/// Strip trailing whitespace from a file's prose lines, returning how many
+/// lines changed. The file is only rewritten when something changed.
+fn fix_trailing_whitespace(path: &Path) -> Result<usize> {
+    if is_exempt(path) {
+        return Ok(0);
+    }
+
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read file: {}", path.display()))?;
+    let (fixed, stripped) = strip_trailing_whitespace(&content);
+    if stripped > 0 {
+        std::fs::write(path, fixed)
+            .with_context(|| format!("Failed to write file: {}", path.display()))?;
+    }
+    Ok(stripped)
+}
+
+/// Report relative links whose target file doesn't exist.
+///
+/// Targets resolve against the document's directory. External links,
+/// anchors, and root-relative (`/...`) links aren't checked, nor are links
+/// inside code blocks.
+fn broken_link_issues(path: &Path, content: &str) -> Vec<Issue> {
+    let link_re = Regex::new(r"\[[^\]]*\]\(([^)\s]+)[^)]*\)").unwrap();
+    let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let mut tracker = CodeBlockTracker::new();
+    let mut issues = Vec::new();
+
+    for (idx, line) in content.lines().enumerate() {
+        tracker.process_line(line);
+        if tracker.in_code_block() {
+            continue;
+        }
+
+        for cap in link_re.captures_iter(line) {
+            let target = cap[1].trim_start_matches('<').trim_end_matches('>');
+            let file_path = target.split('#').next().unwrap_or(target);
+            if file_path.is_empty() || file_path.starts_with('/') || file_path.contains("://") {
+                continue;
+            }
+            if file_path.starts_with("mailto:") || base_dir.join(file_path).exists() {
+                continue;
+            }
+
+            let start = cap.get(0).map_or(0, |m| m.start());
+            issues.push(Issue {
+                file: path.to_path_buf(),
+                line: idx + 1,
+                column: line[..start].chars().count() + 1,
+                severity: Severity::Warning,
+                rule: "broken-link".to_string(),
+                message: format!("Broken link to '{}' (file not found)", file_path),
+                hint: Some("Fix the path or remove the link".to_string()),
+                converted_from_error: false,
+            });
+        }
+    }
+
+    issues
+}
+
+/// Returns true for files that are never validated.
+fn is_exempt(path: &Path) -> bool {
+    // Skip validation of index.md files - they are navigation documents
+    // that don't need Verification and Examples sections
+    if path.file_name().is_some_and(|f| f == "index.md") {
+        return true;
+    }
+
+    // Skip template files - they are scaffolds, not actual documentation
+    let path_str = path.to_string_lossy();
+    path_str.contains("/templates/") || path_str.contains("\\templates\\")
+}
+
+/// Insert placeholder sections for any missing required sections in a file.
+///
+/// Returns the names of the sections that were added. The file is only
+/// rewritten when something changed.
+fn fix_file(path: &Path, config: &PaveConfig) -> Result<Vec<String>> {
+    if is_exempt(path) {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read file: {}", path.display()))?;
+    let doc_type = detect_doc_type(path, &content);
+    let (fixed, added) = insert_missing_sections(path, &content, doc_type, config)?;
+
+    if !added.is_empty() {
+        std::fs::write(path, fixed)
+            .with_context(|| format!("Failed to write file: {}", path.display()))?;
+    }
+    Ok(added)
+}
+
+/// Add placeholder sections for missing required sections to `content`.
+///
+/// Sections are inserted in the canonical order of the document type's
+/// template: each goes right before the first existing section that follows
+/// it, or at the end of the document.
+fn insert_missing_sections(
+    path: &Path,
+    content: &str,
+    doc_type: DocType,
+    config: &PaveConfig,
+) -> Result<(String, Vec<String>)> {
+    let order = canonical_section_order(doc_type);
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let mut added = Vec::new();
+    let original = ParsedDoc::parse_content(path.to_path_buf(), content)?;
+
+    for name in required_sections(doc_type, config, &order, &original) {
+        let doc = ParsedDoc::parse_content(path.to_path_buf(), &lines.join("\n"))?;
+        if doc.has_section(&name) {
+            continue;
+        }
+
+        let rank = |section: &str| order.iter().position(|s| section_name_matches(section, s));
+        let missing_rank = rank(&name).unwrap_or(order.len());
+        let next_section = doc
+            .sections
+            .iter()
+            .find(|s| rank(&s.name).is_some_and(|r| r > missing_rank));
+
+        let placeholder = [
+            format!("## {}", name),
+            String::new(),
+            format!("<!-- TODO: fill in the {} section -->", name),
+        ];
+        match next_section {
+            Some(section) => {
+                let idx = section.start_line - 1;
+                let mut block: Vec<String> = Vec::new();
+                if idx > 0 && !lines[idx - 1].trim().is_empty() {
+                    block.push(String::new());
+                }
+                block.extend(placeholder);
+                block.push(String::new());
+                lines.splice(idx..idx, block);
+            }
+            None => {
+                while lines.last().is_some_and(|l| l.trim().is_empty()) {
+                    lines.pop();
+                }
+                lines.push(String::new());
+                lines.extend(placeholder);
+            }
+        }
+        added.push(name);
+    }
+
+    let mut fixed = lines.join("\n");
+    if content.ends_with('\n') || !added.is_empty() {
+        fixed.push('\n');
+    }
+    Ok((fixed, added))
+}
+
+/// Section order for a document type, taken from its built-in template.
+fn canonical_section_order(doc_type: DocType) -> Vec<String> {
+    let template_type = match doc_type {
+        DocType::Runbook => TemplateType::Runbook,
+        DocType::Adr => TemplateType::Adr,
+        DocType::Component | DocType::Other => TemplateType::Component,
+    };
+    let template = ParsedDoc::parse_content(PathBuf::new(), get_template(template_type))
+        .map(|doc| doc.sections.into_iter().map(|s| s.name).collect())
+        .unwrap_or_else(|_| Vec::new());
+
+    let mut order: Vec<String> = template;
+    order.push("Paths".to_string());
+    order
+}
+
+/// Section names whose spelling `rules.section_casing` enforces:
+/// `check.canonical_sections` first, then the sections of every built-in
+/// template and the verify hook sections.
+fn canonical_section_names(config: &PaveConfig) -> Vec<String> {
+    let mut names = config.check.canonical_sections.clone();
+    for doc_type in [DocType::Component, DocType::Runbook, DocType::Adr] {
+        names.extend(canonical_section_order(doc_type));
+    }
+    names.extend(["Setup", "Teardown"].map(String::from));
+    names
+}
+
+/// Sections `pave check` requires for a document type, in canonical order.
+///
+/// For "one of" requirements the first option is used unless `doc` already
+/// has another.
+fn required_sections(
+    doc_type: DocType,
+    config: &PaveConfig,
+    order: &[String],
+    doc: &ParsedDoc,
+) -> Vec<String> {
+    let mut required = base_required_sections(doc_type, config);
+    for rule in get_type_specific_rules(doc_type, &config.rules) {
+        match rule {
+            Rule::RequireSection { name } => required.push(name),
+            Rule::RequireOneOf { sections } if !sections.iter().any(|s| doc.has_section(s)) => {
+                required.extend(sections.into_iter().next());
+            }
+            _ => {}
+        }
+    }
+
+    let rank = |name: &String| order.iter().position(|s| s == name).unwrap_or(order.len());
+    required.sort_by_key(rank);
+    required
 }
 
 /// Output results in text format.
-fn output_text(results: &CheckResults, gradual_mode: bool) {
-    // Print all issues
-    for issue in results.errors.iter().chain(results.warnings.iter()) {
+fn output_text(results: &CheckResults, gradual_mode: bool, quiet: bool, strict: bool) {
+    print!("{}", render_text(results, gradual_mode, quiet, strict));
+}
+
+/// Render results as text.
+///
+/// With `quiet`, only issues that fail the run (errors, plus warnings under
+/// `strict`) are shown, followed by the one-line [`quiet_summary`].
+fn render_text(results: &CheckResults, gradual_mode: bool, quiet: bool, strict: bool) -> String {
+    let palette = color::palette();
+    let mut out = String::new();
+
+    for issue in shown_issues(results, quiet, strict) {
         let severity = match issue.severity {
-            Severity::Error => "error",
-            Severity::Warning => "warning",
+            Severity::Error => palette.red("error"),
+            Severity::Warning => palette.yellow("warning"),
         };
-        println!(
-            "{}:{}: {}: {}",
+        out.push_str(&format!(
+            "{}:{}: {}: {}\n",
             issue.file.display(),
             issue.line,
             severity,
             issue.message
-        );
+        ));
         if let Some(hint) = &issue.hint {
-            println!("  hint: {}", hint);
+            out.push_str(&format!("  hint: {}\n", hint));
         }
         // Show note only for issues converted from errors in gradual mode
         if issue.converted_from_error {
-            println!("  note: This would be an error outside gradual mode");
+            out.push_str("  note: This would be an error outside gradual mode\n");
         }
-        println!();
+        out.push('\n');
+    }
+
+    if quiet {
+        out.push_str(&quiet_summary(results));
+        out.push('\n');
+        return out;
     }
 
     // Print summary
     let error_count = results.errors.len();
     let warning_count = results.warnings.len();
 
-    print!(
+    out.push_str(&format!(
         "Checked {} document{}: ",
         results.files_checked,
         if results.files_checked == 1 { "" } else { "s" }
-    );
+    ));
+
+    let mut note = if results.baselined > 0 {
+        format!(" ({} baselined)", results.baselined)
+    } else {
+        String::new()
+    };
+    if results.drafts_skipped > 0 {
+        note.push_str(&format!(
+            " ({} draft{} skipped)",
+            results.drafts_skipped,
+            if results.drafts_skipped == 1 { "" } else { "s" }
+        ));
+    }
 
     if error_count == 0 && warning_count == 0 {
-        println!("all checks passed");
+        out.push_str(&format!("{}{}\n", palette.green("all checks passed"), note));
     } else if gradual_mode {
-        println!(
-            "{} error{}, {} warning{} (gradual mode active)",
+        out.push_str(&format!(
+            "{} error{}, {} warning{}{} (gradual mode active)\n",
             error_count,
             if error_count == 1 { "" } else { "s" },
             warning_count,
-            if warning_count == 1 { "" } else { "s" }
-        );
+            if warning_count == 1 { "" } else { "s" },
+            note
+        ));
     } else {
-        println!(
-            "{} error{}, {} warning{}",
+        out.push_str(&format!(
+            "{} error{}, {} warning{}{}\n",
             error_count,
             if error_count == 1 { "" } else { "s" },
             warning_count,
-            if warning_count == 1 { "" } else { "s" }
-        );
+            if warning_count == 1 { "" } else { "s" },
+            note
+        ));
+    }
+
+    // Suppressions are listed so a directive can't hide issues silently
+    for suppression in &results.suppressed {
+        out.push_str(&format!(
+            "Suppressed {} {} issue{} in {} (pave: ignore-rule)\n",
+            suppression.count,
+            suppression.rule,
+            if suppression.count == 1 { "" } else { "s" },
+            suppression.file.display()
+        ));
     }
 
     // In gradual mode, show how many issues would fail in strict mode
     if let Some(would_fail) = results.would_fail_count {
-        println!(
-            "Note: {} issue{} would fail in strict mode. Run 'pave check --strict' to see.",
+        out.push_str(&format!(
+            "Note: {} issue{} would fail in strict mode. Run 'pave check --strict' to see.\n",
             would_fail,
             if would_fail == 1 { "" } else { "s" }
-        );
+        ));
+    }
+    out
+}
+
+/// The issues to print: all of them, or with `quiet` only those that fail
+/// the run.
+fn shown_issues(results: &CheckResults, quiet: bool, strict: bool) -> Vec<&Issue> {
+    let warnings = if quiet && !strict {
+        &[][..]
+    } else {
+        &results.warnings[..]
+    };
+    results.errors.iter().chain(warnings).collect()
+}
+
+/// The `--quiet` summary: `N docs, M errors`, plus warnings and baselined
+/// issues when there are any.
+fn quiet_summary(results: &CheckResults) -> String {
+    let plural = |count: usize, noun: &str| {
+        format!("{} {}{}", count, noun, if count == 1 { "" } else { "s" })
+    };
+    let mut summary = format!(
+        "{}, {}",
+        plural(results.files_checked, "doc"),
+        plural(results.errors.len(), "error")
+    );
+    if !results.warnings.is_empty() {
+        summary.push_str(&format!(", {}", plural(results.warnings.len(), "warning")));
+    }
+    if results.baselined > 0 {
+        summary.push_str(&format!(", {} baselined", results.baselined));
+    }
+    if results.drafts_skipped > 0 {
+        summary.push_str(&format!(
+            ", {} skipped",
+            plural(results.drafts_skipped, "draft")
+        ));
+    }
+    let suppressed: usize = results.suppressed.iter().map(|s| s.count).sum();
+    if suppressed > 0 {
+        summary.push_str(&format!(", {} suppressed", suppressed));
     }
+    summary
 }
 
 /// Output results in JSON format.
@@ -598,8 +1727,10 @@ fn output_json(results: &CheckResults) -> Result<()> {
 }
 
 /// Output results in GitHub Actions annotation format.
-fn output_github(results: &CheckResults, _gradual_mode: bool) {
-    for issue in results.errors.iter().chain(results.warnings.iter()) {
+///
+/// With `quiet`, warnings that don't fail the run are left out.
+fn output_github(results: &CheckResults, quiet: bool, strict: bool) {
+    for issue in shown_issues(results, quiet, strict) {
         let level = match issue.severity {
             Severity::Error => "error",
             Severity::Warning => "warning",
@@ -610,10 +1741,11 @@ fn output_github(results: &CheckResults, _gradual_mode: bool) {
             issue.message.clone()
         };
         println!(
-            "::{} file={},line={}::{}",
+            "::{} file={},line={},col={}::{}",
             level,
             issue.file.display(),
             issue.line,
+            issue.column,
             message
         );
     }
@@ -627,6 +1759,85 @@ fn output_github(results: &CheckResults, _gradual_mode: bool) {
     }
 }
 
+/// Output results as a SARIF 2.1.0 log.
+fn output_sarif(results: &CheckResults, base_dir: &Path) -> Result<()> {
+    let report = sarif_report(results, base_dir);
+    let json = serde_json::to_string_pretty(&report).context("Failed to serialize SARIF")?;
+    println!("{}", json);
+    Ok(())
+}
+
+/// Build a SARIF 2.1.0 log with one result per issue.
+///
+/// Artifact URIs are made relative to `base_dir` so code-scanning tools can
+/// map them onto repository files.
+fn sarif_report(results: &CheckResults, base_dir: &Path) -> serde_json::Value {
+    let issues: Vec<&Issue> = results
+        .errors
+        .iter()
+        .chain(results.warnings.iter())
+        .collect();
+
+    let mut rule_ids: Vec<&str> = issues.iter().map(|issue| issue.rule.as_str()).collect();
+    rule_ids.sort_unstable();
+    rule_ids.dedup();
+
+    let rules: Vec<serde_json::Value> = rule_ids
+        .iter()
+        .map(|id| serde_json::json!({ "id": id }))
+        .collect();
+
+    let sarif_results: Vec<serde_json::Value> = issues
+        .iter()
+        .map(|issue| {
+            let uri = issue
+                .file
+                .strip_prefix(base_dir)
+                .unwrap_or(&issue.file)
+                .to_string_lossy()
+                .replace('\\', "/");
+            let level = match issue.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            };
+            let mut text = issue.message.clone();
+            if let Some(hint) = &issue.hint {
+                text.push_str(&format!(" (hint: {})", hint));
+            }
+
+            serde_json::json!({
+                "ruleId": issue.rule,
+                "level": level,
+                "message": { "text": text },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": uri },
+                        "region": {
+                            "startLine": issue.line.max(1),
+                            "startColumn": issue.column.max(1)
+                        }
+                    }
+                }]
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "pave",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules
+                }
+            },
+            "results": sarif_results
+        }]
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -692,8 +1903,9 @@ This document is missing required sections.
         let docs_dir = temp_dir.path().join("docs");
         fs::create_dir_all(&docs_dir).unwrap();
 
-        let mut content =
-            String::from("# Long Document\n\n## Verification\nTest\n\n## Examples\nExample\n");
+        let mut content = String::from(
+            "# Long Document\n\n## Purpose\nLong.\n\n## Verification\nTest\n\n## Examples\nExample\n",
+        );
         for i in 0..lines {
             content.push_str(&format!("Line {}\n", i));
         }
@@ -703,6 +1915,79 @@ This document is missing required sections.
         path
     }
 
+    #[test]
+    fn custom_required_sections_are_enforced_by_name() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = create_test_config(&temp_dir);
+        let mut config = PaveConfig::load(&config_path).unwrap();
+        config.check.required_sections = vec!["Purpose".to_string(), "Interface".to_string()];
+
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(&docs_dir).unwrap();
+        let body = "## Purpose\nText.\n\n## Verification\nTest\n\n## Examples\nExample\n";
+        let missing = docs_dir.join("missing.md");
+        fs::write(
+            &missing,
+            format!("---\nstatus: active\n---\n# Missing\n\n{body}"),
+        )
+        .unwrap();
+        let complete = docs_dir.join("complete.md");
+        fs::write(
+            &complete,
+            format!("# Complete\n\n## Interface\nAPI.\n\n{body}"),
+        )
+        .unwrap();
+
+        let mut results = CheckResults::new();
+        check_file(&missing, &config, &mut results).unwrap();
+        assert_eq!(results.errors.len(), 1);
+        let error = &results.errors[0];
+        assert_eq!(error.rule, "missing-section");
+        assert_eq!(error.message, "Missing required section 'Interface'");
+        assert_eq!(error.line, 4);
+
+        let mut results = CheckResults::new();
+        check_file(&complete, &config, &mut results).unwrap();
+        assert!(results.errors.is_empty());
+
+        // Dropping the requirement lets the first doc pass
+        config.check.required_sections.clear();
+        let mut results = CheckResults::new();
+        check_file(&missing, &config, &mut results).unwrap();
+        assert!(results.errors.is_empty());
+    }
+
+    #[test]
+    fn required_sections_default_to_purpose_for_components_only() {
+        let config = PaveConfig::default();
+        assert_eq!(
+            base_required_sections(DocType::Component, &config),
+            vec!["Purpose", "Verification", "Examples"]
+        );
+        assert_eq!(
+            base_required_sections(DocType::Runbook, &config),
+            vec!["Verification", "Examples"]
+        );
+    }
+
+    #[test]
+    fn check_reader_reports_logical_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = create_test_config(&temp_dir);
+        let config = PaveConfig::load(&config_path).unwrap();
+
+        let content = "# Unsaved\n\n## Purpose\nNot on disk yet.\n";
+        let results = check_reader(Path::new("docs/foo.md"), content.as_bytes(), &config).unwrap();
+
+        assert_eq!(results.files_checked, 1);
+        assert!(!results.errors.is_empty());
+        let error = &results.errors[0];
+        assert_eq!(error.file, PathBuf::from("docs/foo.md"));
+        assert_eq!(error.line, 1);
+        assert_eq!(error.message, "Missing required section 'Verification'");
+        assert!(!temp_dir.path().join("docs/foo.md").exists());
+    }
+
     #[test]
     fn check_valid_document_passes() {
         let temp_dir = TempDir::new().unwrap();
@@ -711,7 +1996,7 @@ This document is missing required sections.
 
         let config = PaveConfig::load(&config_path).unwrap();
         let docs_dir = temp_dir.path().join("docs");
-        let files = find_markdown_files(&[docs_dir]).unwrap();
+        let files = find_markdown_files(&[docs_dir], true).unwrap();
 
         let mut results = CheckResults::new();
         for file in &files {
@@ -747,6 +2032,60 @@ This document is missing required sections.
         );
     }
 
+    #[test]
+    fn fix_inserts_missing_purpose_after_title() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = create_test_config(&temp_dir);
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(&docs_dir).unwrap();
+        let doc_path = docs_dir.join("no-purpose.md");
+        fs::write(
+            &doc_path,
+            "# No Purpose\n\n## Verification\n```bash\n$ cargo test\n```\n\n## Examples\nExample.\n",
+        )
+        .unwrap();
+
+        let config = PaveConfig::load(&config_path).unwrap();
+        let added = fix_file(&doc_path, &config).unwrap();
+        assert_eq!(added, vec!["Purpose"]);
+
+        let fixed = fs::read_to_string(&doc_path).unwrap();
+        assert!(
+            fixed.starts_with(
+                "# No Purpose\n\n## Purpose\n\n<!-- TODO: fill in the Purpose section -->\n\n## Verification\n"
+            ),
+            "unexpected content:\n{}",
+            fixed
+        );
+
+        let mut results = CheckResults::new();
+        check_file(&doc_path, &config, &mut results).unwrap();
+        assert!(results.is_success(false));
+
+        // A second pass has nothing left to fix
+        assert!(fix_file(&doc_path, &config).unwrap().is_empty());
+    }
+
+    #[test]
+    fn fix_inserts_sections_in_canonical_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = create_test_config(&temp_dir);
+        let doc_path = create_invalid_doc(&temp_dir, "invalid.md");
+        fs::write(
+            &doc_path,
+            "# Doc\n\n## Purpose\nText.\n\n## Paths\n\n- `src/`\n",
+        )
+        .unwrap();
+
+        let config = PaveConfig::load(&config_path).unwrap();
+        let added = fix_file(&doc_path, &config).unwrap();
+        assert_eq!(added, vec!["Verification", "Examples"]);
+
+        let doc = ParsedDoc::parse(&doc_path).unwrap();
+        let names: Vec<&str> = doc.sections.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Purpose", "Verification", "Examples", "Paths"]);
+    }
+
     #[test]
     fn check_long_document_reports_warning() {
         let temp_dir = TempDir::new().unwrap();
@@ -777,20 +2116,38 @@ This document is missing required sections.
         fs::write(nested_dir.join("doc2.md"), "# Doc 2").unwrap();
         fs::write(docs_dir.join("readme.txt"), "Not markdown").unwrap();
 
-        let files = find_markdown_files(&[docs_dir]).unwrap();
+        let files = find_markdown_files(&[docs_dir], true).unwrap();
 
         assert_eq!(files.len(), 2);
         assert!(files.iter().any(|f| f.file_name().unwrap() == "doc1.md"));
         assert!(files.iter().any(|f| f.file_name().unwrap() == "doc2.md"));
     }
 
+    #[test]
+    fn find_markdown_files_skips_gitignored_docs() {
+        let temp_dir = TempDir::new().unwrap();
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(docs_dir.join("generated")).unwrap();
+        fs::write(temp_dir.path().join(".gitignore"), "docs/generated/\n").unwrap();
+        fs::write(docs_dir.join("doc1.md"), "# Doc 1").unwrap();
+        fs::write(docs_dir.join("generated/api.md"), "# API").unwrap();
+
+        let files = find_markdown_files(std::slice::from_ref(&docs_dir), true).unwrap();
+        assert_eq!(files, vec![docs_dir.join("doc1.md")]);
+
+        let files = find_markdown_files(&[docs_dir], false).unwrap();
+        assert_eq!(files.len(), 2);
+    }
+
     #[test]
     fn check_results_success_without_strict() {
         let mut results = CheckResults::new();
         results.add_issue(Issue {
             file: PathBuf::from("test.md"),
             line: 1,
+            column: 1,
             severity: Severity::Warning,
+            rule: "test".to_string(),
             message: "A warning".to_string(),
             hint: None,
             converted_from_error: false,
@@ -806,7 +2163,9 @@ This document is missing required sections.
         results.add_issue(Issue {
             file: PathBuf::from("test.md"),
             line: 1,
+            column: 1,
             severity: Severity::Error,
+            rule: "test".to_string(),
             message: "An error".to_string(),
             hint: None,
             converted_from_error: false,
@@ -823,7 +2182,9 @@ This document is missing required sections.
         results.add_issue(Issue {
             file: PathBuf::from("test.md"),
             line: 5,
+            column: 1,
             severity: Severity::Error,
+            rule: "test".to_string(),
             message: "Test error".to_string(),
             hint: Some("Fix it".to_string()),
             converted_from_error: false,
@@ -838,6 +2199,42 @@ This document is missing required sections.
         assert_eq!(parsed["errors"][0]["message"], "Test error");
     }
 
+    #[test]
+    fn json_reports_broken_links_with_position() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = create_test_config(&temp_dir);
+        let config = PaveConfig::load(&config_path).unwrap();
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(&docs_dir).unwrap();
+        fs::write(docs_dir.join("other.md"), "# Other\n").unwrap();
+        let doc_path = docs_dir.join("links.md");
+        fs::write(
+            &doc_path,
+            "# Links\n\n## Purpose\n\nSee [other](other.md#top) and [the web](https://example.com).\n\n```markdown\n[ignored](nowhere.md)\n```\n\nAlso see [gone](missing.md \"Title\").\n",
+        )
+        .unwrap();
+
+        let mut results = CheckResults::new();
+        check_file(&doc_path, &config, &mut results).unwrap();
+
+        let json = serde_json::to_value(&results).unwrap();
+        let broken: Vec<&serde_json::Value> = json["warnings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|w| w["code"] == "broken-link")
+            .collect();
+        assert_eq!(broken.len(), 1);
+        assert_eq!(broken[0]["file"], doc_path.to_string_lossy().as_ref());
+        assert_eq!(broken[0]["line"], 11);
+        assert_eq!(broken[0]["column"], 10);
+        assert_eq!(broken[0]["severity"], "warning");
+        assert_eq!(
+            broken[0]["message"],
+            "Broken link to 'missing.md' (file not found)"
+        );
+    }
+
     #[test]
     fn check_skips_index_md_files() {
         let temp_dir = TempDir::new().unwrap();
@@ -1368,11 +2765,24 @@ gradual_until = "{}"
         // Simulate gradual mode conversion
         let args = CheckArgs {
             paths: vec![],
-            format: OutputFormat::Text,
+            format: CheckOutputFormat::Text,
             strict: false,
             gradual: false,
             changed: false,
             base: None,
+            watch: false,
+            fix: false,
+            orphans: false,
+            stdin: false,
+            stdin_path: None,
+            no_ignore: false,
+            baseline: None,
+            write_baseline: None,
+            quiet: false,
+            rules: vec![],
+            ignore_rules: vec![],
+            max_section_lines: None,
+            config: None,
         };
 
         assert!(is_gradual_mode_active(&config, &args));
@@ -1397,11 +2807,24 @@ gradual_until = "{}"
         let config = PaveConfig::load(&config_path).unwrap();
         let args = CheckArgs {
             paths: vec![],
-            format: OutputFormat::Text,
+            format: CheckOutputFormat::Text,
             strict: true, // This should override gradual
             gradual: false,
             changed: false,
             base: None,
+            watch: false,
+            fix: false,
+            orphans: false,
+            stdin: false,
+            stdin_path: None,
+            no_ignore: false,
+            baseline: None,
+            write_baseline: None,
+            quiet: false,
+            rules: vec![],
+            ignore_rules: vec![],
+            max_section_lines: None,
+            config: None,
         };
 
         assert!(!is_gradual_mode_active(&config, &args));
@@ -1415,11 +2838,24 @@ gradual_until = "{}"
         let config = PaveConfig::load(&config_path).unwrap();
         let args = CheckArgs {
             paths: vec![],
-            format: OutputFormat::Text,
+            format: CheckOutputFormat::Text,
             strict: false,
             gradual: true, // CLI flag should enable gradual mode
             changed: false,
             base: None,
+            watch: false,
+            fix: false,
+            orphans: false,
+            stdin: false,
+            stdin_path: None,
+            no_ignore: false,
+            baseline: None,
+            write_baseline: None,
+            quiet: false,
+            rules: vec![],
+            ignore_rules: vec![],
+            max_section_lines: None,
+            config: None,
         };
 
         assert!(is_gradual_mode_active(&config, &args));
@@ -1434,11 +2870,24 @@ gradual_until = "{}"
         let config = PaveConfig::load(&config_path).unwrap();
         let args = CheckArgs {
             paths: vec![],
-            format: OutputFormat::Text,
+            format: CheckOutputFormat::Text,
             strict: false,
             gradual: false,
             changed: false,
             base: None,
+            watch: false,
+            fix: false,
+            orphans: false,
+            stdin: false,
+            stdin_path: None,
+            no_ignore: false,
+            baseline: None,
+            write_baseline: None,
+            quiet: false,
+            rules: vec![],
+            ignore_rules: vec![],
+            max_section_lines: None,
+            config: None,
         };
 
         assert!(is_gradual_mode_active(&config, &args));
@@ -1453,11 +2902,24 @@ gradual_until = "{}"
         let config = PaveConfig::load(&config_path).unwrap();
         let args = CheckArgs {
             paths: vec![],
-            format: OutputFormat::Text,
+            format: CheckOutputFormat::Text,
             strict: false,
             gradual: false,
             changed: false,
             base: None,
+            watch: false,
+            fix: false,
+            orphans: false,
+            stdin: false,
+            stdin_path: None,
+            no_ignore: false,
+            baseline: None,
+            write_baseline: None,
+            quiet: false,
+            rules: vec![],
+            ignore_rules: vec![],
+            max_section_lines: None,
+            config: None,
         };
 
         // Should be disabled due to past deadline
@@ -1480,4 +2942,494 @@ gradual_until = "{}"
         assert!(!is_gradual_deadline_passed("2024-13-01")); // Invalid month
         assert!(!is_gradual_deadline_passed("2024-01-32")); // Invalid day
     }
+
+    #[test]
+    fn sarif_report_contains_results_for_failures() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = create_test_config(&temp_dir);
+        let doc_path = create_invalid_doc(&temp_dir, "invalid.md");
+        let long_path = create_long_doc(&temp_dir, "long.md", 60);
+
+        let config = PaveConfig::load(&config_path).unwrap();
+        let mut results = CheckResults::new();
+        check_file(&doc_path, &config, &mut results).unwrap();
+        check_file(&long_path, &config, &mut results).unwrap();
+
+        let report = sarif_report(&results, temp_dir.path());
+
+        assert_eq!(report["version"], "2.1.0");
+        let sarif_results = report["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(sarif_results.len(), 3);
+
+        let missing = sarif_results
+            .iter()
+            .find(|r| r["ruleId"] == "missing-verification")
+            .unwrap();
+        assert_eq!(missing["level"], "error");
+        let location = &missing["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "docs/invalid.md");
+        assert_eq!(location["region"]["startLine"], 1);
+
+        let max_lines = sarif_results
+            .iter()
+            .find(|r| r["ruleId"] == "max-lines")
+            .unwrap();
+        assert_eq!(max_lines["level"], "warning");
+        assert_eq!(
+            max_lines["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "docs/long.md"
+        );
+
+        let rules = report["runs"][0]["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap();
+        assert_eq!(rules.len(), 3);
+    }
+
+    #[test]
+    fn quiet_text_shows_only_failures_and_a_summary() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = create_test_config(&temp_dir);
+        let config = PaveConfig::load(&config_path).unwrap();
+        let valid = create_valid_doc(&temp_dir, "valid.md");
+        let invalid = create_invalid_doc(&temp_dir, "invalid.md");
+
+        let mut results = CheckResults::new();
+        check_file(&valid, &config, &mut results).unwrap();
+        check_file(&invalid, &config, &mut results).unwrap();
+        results.files_checked = 2;
+        results.warnings.push(Issue {
+            file: valid.clone(),
+            line: 1,
+            column: 1,
+            severity: Severity::Warning,
+            rule: "stale".to_string(),
+            message: "Doc may be stale".to_string(),
+            hint: None,
+            converted_from_error: false,
+        });
+        let error_count = results.errors.len();
+        assert!(error_count > 0);
+
+        let quiet = render_text(&results, false, true, false);
+        assert!(quiet.contains(&results.errors[0].message));
+        assert!(!quiet.contains("Doc may be stale"));
+        assert!(!quiet.contains("/valid.md:"));
+        assert!(!quiet.contains("Checked"));
+        assert!(quiet.ends_with(&format!("\n2 docs, {} errors, 1 warning\n", error_count)));
+
+        // Under --strict the warning fails the run, so it's shown too
+        assert!(render_text(&results, false, true, true).contains("Doc may be stale"));
+        assert!(render_text(&results, false, false, false).contains("Checked 2 documents"));
+    }
+
+    #[test]
+    fn draft_docs_are_lenient_and_skipped_under_strict() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = create_test_config(&temp_dir);
+        create_valid_doc(&temp_dir, "valid.md");
+        fs::write(
+            temp_dir.path().join("docs/wip.md"),
+            "---\nstatus: draft\n---\n# Work in Progress\n\n## Purpose\nNot done yet.\n",
+        )
+        .unwrap();
+        let config = PaveConfig::load(&config_path).unwrap();
+
+        // Without --strict the draft is still checked, but only warns
+        let mut results = CheckResults::new();
+        check_file(&temp_dir.path().join("docs/wip.md"), &config, &mut results).unwrap();
+        assert!(results.errors.is_empty());
+        assert!(!results.warnings.is_empty());
+        assert!(execute(baseline_args(&config_path)).is_ok());
+
+        // --strict leaves it out entirely and counts it
+        let args = CheckArgs {
+            strict: true,
+            ..baseline_args(&config_path)
+        };
+        let mut strict_config = config.clone();
+        strict_config.rules.require_verification = true;
+        let mut results = check_files(&args, &strict_config, temp_dir.path())
+            .unwrap()
+            .unwrap();
+        results.skip_drafts();
+        assert_eq!(results.files_checked, 1);
+        assert_eq!(results.drafts_skipped, 1);
+        assert!(results.errors.is_empty() && results.warnings.is_empty());
+        assert!(execute(args).is_ok());
+        assert!(quiet_summary(&results).ends_with(", 1 draft skipped"));
+    }
+
+    #[test]
+    fn malformed_frontmatter_is_an_error_for_that_doc_only() {
+        let content = "---\npave:\n  paths: [src/\n---\n# Doc\n\n## Purpose\nText.\n";
+        let issues = diagnose(Path::new("docs/doc.md"), content, &PaveConfig::default()).unwrap();
+
+        let frontmatter = issues
+            .iter()
+            .find(|issue| issue.rule == "invalid-frontmatter")
+            .unwrap();
+        assert_eq!(frontmatter.severity, Severity::Error);
+        assert_eq!(frontmatter.line, 4);
+    }
+
+    #[test]
+    fn unclosed_fence_is_an_error_at_its_opening_line() {
+        let content = "# Doc\n\n## Purpose\nText.\n\n## Verification\n```bash\ncargo test\n\n## Examples\nMore.\n";
+        let issues = diagnose(Path::new("docs/doc.md"), content, &PaveConfig::default()).unwrap();
+
+        let unclosed = issues
+            .iter()
+            .find(|issue| issue.rule == "unclosed-fence")
+            .unwrap();
+        assert_eq!(unclosed.severity, Severity::Error);
+        assert_eq!(unclosed.line, 7);
+        let heading = issues
+            .iter()
+            .find(|issue| issue.rule == "suspicious-fence")
+            .unwrap();
+        assert_eq!(heading.severity, Severity::Warning);
+        assert_eq!(heading.line, 10);
+        assert!(heading.message.contains("## Examples"));
+    }
+
+    #[test]
+    fn strict_mode_flags_non_canonical_section_casing() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = create_test_config(&temp_dir);
+        fs::create_dir_all(temp_dir.path().join("docs")).unwrap();
+        fs::write(
+            temp_dir.path().join("docs/doc.md"),
+            "# Doc\n\n## purpose\nText.\n\n## VERIFICATION:\n```bash\ncargo test\n```\n\n## Examples\nMore.\n",
+        )
+        .unwrap();
+
+        // Sections are still found, so the lenient check passes
+        assert!(execute(baseline_args(&config_path)).is_ok());
+
+        let mut config = PaveConfig::default();
+        config.rules.section_casing = true;
+        let content = fs::read_to_string(temp_dir.path().join("docs/doc.md")).unwrap();
+        let issues = diagnose(Path::new("docs/doc.md"), &content, &config).unwrap();
+        let casing: Vec<(usize, &str)> = issues
+            .iter()
+            .filter(|issue| issue.rule == "section-casing")
+            .map(|issue| (issue.line, issue.message.as_str()))
+            .collect();
+        assert_eq!(
+            casing,
+            vec![
+                (3, "Section '## purpose' should be spelled '## Purpose'"),
+                (
+                    6,
+                    "Section '## VERIFICATION:' should be spelled '## Verification'"
+                ),
+            ]
+        );
+
+        let err = execute(CheckArgs {
+            strict: true,
+            ..baseline_args(&config_path)
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("strict mode"));
+    }
+
+    #[test]
+    fn rule_selection_ignores_max_lines_but_keeps_other_rules() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = create_test_config(&temp_dir);
+        let doc = create_valid_doc(&temp_dir, "long.md");
+        let mut content = fs::read_to_string(&doc).unwrap();
+        content.push_str(&"filler\n".repeat(400));
+        fs::write(&doc, &content).unwrap();
+
+        let strict = |rules: &[&str], ignore_rules: &[&str]| CheckArgs {
+            strict: true,
+            rules: rules.iter().map(|r| r.to_string()).collect(),
+            ignore_rules: ignore_rules.iter().map(|r| r.to_string()).collect(),
+            max_section_lines: None,
+            ..baseline_args(&config_path)
+        };
+        assert!(run_check(&strict(&[], &[])).is_err());
+        assert!(run_check(&strict(&[], &["max-lines"])).is_ok());
+        assert!(run_check(&strict(&["broken-link"], &[])).is_ok());
+
+        // Other rules still run with max-lines ignored
+        content.push_str("See [missing](missing.md).\n");
+        fs::write(&doc, &content).unwrap();
+        assert!(run_check(&strict(&[], &["max-lines"])).is_err());
+        assert!(run_check(&strict(&["broken-link"], &[])).is_err());
+        assert!(run_check(&strict(&["section-casing"], &[])).is_ok());
+
+        // check.disabled_rules works like --ignore-rule, but --rule overrides it
+        let mut config = fs::read_to_string(&config_path).unwrap();
+        config.push_str("\n[check]\ndisabled_rules = [\"max-lines\", \"broken-link\"]\n");
+        fs::write(&config_path, config).unwrap();
+        assert!(run_check(&strict(&[], &[])).is_ok());
+        assert!(run_check(&strict(&["max-lines"], &[])).is_err());
+    }
+
+    const WHITESPACE_DOC: &str = "---\nowner: docs   \n---\n# Spacing\n\n## Purpose\nKeeps lines tidy.  \n\n- item\n  - nested\n\t- tabbed\n \t- mixed\n\n```bash\necho kept   \n\techo tab\n```\n";
+
+    #[test]
+    fn whitespace_rules_flag_prose_lines_only_when_enabled() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = create_test_config(&temp_dir);
+        let doc = temp_dir.path().join("docs/spacing.md");
+        fs::create_dir_all(doc.parent().unwrap()).unwrap();
+        fs::write(&doc, WHITESPACE_DOC).unwrap();
+        let mut config = PaveConfig::load(&config_path).unwrap();
+        let flagged = |config: &PaveConfig| {
+            let mut results = CheckResults::new();
+            check_file(&doc, config, &mut results).unwrap();
+            results
+                .warnings
+                .iter()
+                .filter(|i| OPT_IN_RULES.contains(&i.rule.as_str()))
+                .map(|i| (i.line, i.column, i.rule.clone()))
+                .collect::<Vec<_>>()
+        };
+
+        // Off by default
+        assert!(flagged(&config).is_empty());
+
+        // Frontmatter and code blocks are left alone
+        config.check.enabled_rules = vec![
+            "trailing-whitespace".to_string(),
+            "mixed-indentation".to_string(),
+        ];
+        assert_eq!(
+            flagged(&config),
+            vec![
+                (7, 18, "trailing-whitespace".to_string()),
+                (11, 1, "mixed-indentation".to_string()),
+                (12, 1, "mixed-indentation".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn max_section_lines_flags_only_sections_over_the_limit() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = create_test_config(&temp_dir);
+        let doc = temp_dir.path().join("docs/long.md");
+        fs::create_dir_all(doc.parent().unwrap()).unwrap();
+        // Purpose spans lines 3-7 (5 lines), Interface 8-13 (6), Gotchas 14-15 (2)
+        fs::write(
+            &doc,
+            "# Long\n\n## Purpose\na\nb\nc\n\n## Interface\na\nb\nc\nd\n\n## Gotchas\nNone.\n",
+        )
+        .unwrap();
+        let mut config = PaveConfig::load(&config_path).unwrap();
+        let flagged = |config: &PaveConfig| {
+            let mut results = CheckResults::new();
+            check_file(&doc, config, &mut results).unwrap();
+            results
+                .warnings
+                .into_iter()
+                .filter(|i| i.rule == "max-section-lines")
+                .map(|i| (i.line, i.message))
+                .collect::<Vec<_>>()
+        };
+
+        // Off by default
+        assert!(flagged(&config).is_empty());
+
+        // Interface is one line over; Purpose sits exactly at the limit
+        config.check.max_section_lines = Some(5);
+        assert_eq!(
+            flagged(&config),
+            vec![(
+                8,
+                "Section 'Interface' exceeds 5 line limit (6 lines, 8-13)".to_string()
+            )]
+        );
+
+        config.check.max_section_lines = Some(6);
+        assert!(flagged(&config).is_empty());
+
+        // --max-section-lines applies without any config
+        let args = |max_section_lines| CheckArgs {
+            strict: true,
+            rules: vec!["max-section-lines".to_string()],
+            max_section_lines,
+            ..baseline_args(&config_path)
+        };
+        assert!(run_check(&args(None)).is_ok());
+        assert!(run_check(&args(Some(5))).is_err());
+    }
+
+    #[test]
+    fn fix_strips_trailing_whitespace_when_the_rule_is_named() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = create_test_config(&temp_dir);
+        let doc = create_valid_doc(&temp_dir, "spacing.md");
+        fs::write(&doc, WHITESPACE_DOC).unwrap();
+        let args = |fix| CheckArgs {
+            fix,
+            rules: vec!["trailing-whitespace".to_string()],
+            ..baseline_args(&config_path)
+        };
+
+        // Without the rule, --fix only stubs out the missing sections
+        run_check(&args(false)).unwrap();
+        run_check(&CheckArgs {
+            fix: true,
+            ..baseline_args(&config_path)
+        })
+        .unwrap();
+        let stubbed = fs::read_to_string(&doc).unwrap();
+        assert!(stubbed.starts_with(WHITESPACE_DOC));
+
+        // Only prose loses its trailing whitespace
+        run_check(&args(true)).unwrap();
+        assert_eq!(
+            fs::read_to_string(&doc).unwrap(),
+            stubbed.replace("tidy.  \n", "tidy.\n")
+        );
+    }
+
+    #[test]
+    fn ignore_rule_directive_suppresses_only_its_own_doc() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = create_test_config(&temp_dir);
+        let reference = create_valid_doc(&temp_dir, "reference.md");
+        let sibling = create_valid_doc(&temp_dir, "sibling.md");
+        let filler = "row\n".repeat(400);
+        let content = fs::read_to_string(&reference).unwrap();
+        fs::write(
+            &reference,
+            format!("<!-- pave: ignore-rule max-lines -->\n{content}{filler}"),
+        )
+        .unwrap();
+        fs::write(&sibling, format!("{content}{filler}")).unwrap();
+        let config = PaveConfig::load(&config_path).unwrap();
+
+        let mut results = CheckResults::new();
+        check_file(&reference, &config, &mut results).unwrap();
+        check_file(&sibling, &config, &mut results).unwrap();
+
+        let flagged: Vec<&Path> = results
+            .warnings
+            .iter()
+            .filter(|issue| issue.rule == "max-lines")
+            .map(|issue| issue.file.as_path())
+            .collect();
+        assert_eq!(flagged, vec![sibling.as_path()]);
+        assert_eq!(
+            results.suppressed,
+            vec![Suppression {
+                file: reference.clone(),
+                rule: "max-lines".to_string(),
+                count: 1,
+            }]
+        );
+        let text = render_text(&results, false, false, true);
+        assert!(text.contains("Suppressed 1 max-lines issue in"));
+        assert!(quiet_summary(&results).ends_with(", 1 suppressed"));
+    }
+
+    fn baseline_args(config_path: &Path) -> CheckArgs {
+        CheckArgs {
+            paths: vec![],
+            format: CheckOutputFormat::Json,
+            strict: false,
+            gradual: false,
+            changed: false,
+            base: None,
+            watch: false,
+            fix: false,
+            orphans: false,
+            stdin: false,
+            stdin_path: None,
+            no_ignore: false,
+            baseline: None,
+            write_baseline: None,
+            quiet: false,
+            rules: vec![],
+            ignore_rules: vec![],
+            max_section_lines: None,
+            config: Some(config_path.to_path_buf()),
+        }
+    }
+
+    #[test]
+    fn baseline_suppresses_known_issues_but_not_new_ones() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = create_test_config(&temp_dir);
+        create_invalid_doc(&temp_dir, "old.md");
+        create_long_doc(&temp_dir, "long.md", 60);
+        let baseline = temp_dir.path().join("pave-baseline.json");
+
+        assert!(run_check(&baseline_args(&config_path)).is_err());
+        run_check(&CheckArgs {
+            write_baseline: Some(baseline.clone()),
+            ..baseline_args(&config_path)
+        })
+        .unwrap();
+        let written: Baseline =
+            serde_json::from_str(&fs::read_to_string(&baseline).unwrap()).unwrap();
+        assert!(written.issues.iter().all(|e| e.file.starts_with("docs/")));
+        assert!(
+            written
+                .issues
+                .iter()
+                .any(|e| e.rule == "max-lines" && !e.message.chars().any(|c| c.is_ascii_digit()))
+        );
+
+        let with_baseline = || CheckArgs {
+            baseline: Some(baseline.clone()),
+            ..baseline_args(&config_path)
+        };
+        run_check(&with_baseline()).unwrap();
+
+        // Growing the long doc changes its line count but not its fingerprint
+        create_long_doc(&temp_dir, "long.md", 80);
+        run_check(&with_baseline()).unwrap();
+
+        // A newly broken doc still fails
+        let new_doc = create_invalid_doc(&temp_dir, "new.md");
+        let config = PaveConfig::load(&config_path).unwrap();
+        let mut expected = CheckResults::new();
+        check_file(&new_doc, &config, &mut expected).unwrap();
+        let err = run_check(&with_baseline()).unwrap_err().to_string();
+        assert_eq!(
+            err,
+            format!(
+                "Check failed: {} error{}",
+                expected.errors.len(),
+                if expected.errors.len() == 1 { "" } else { "s" }
+            )
+        );
+    }
+
+    #[test]
+    fn watch_loop_debounces_burst_into_single_run() {
+        let (tx, rx) = mpsc::channel();
+        // A single save often produces several events in quick succession
+        for _ in 0..3 {
+            tx.send(PathBuf::from("docs/a.md")).unwrap();
+        }
+        drop(tx);
+
+        let mut runs = 0;
+        watch_loop(&rx, Duration::from_millis(50), || runs += 1);
+        assert_eq!(runs, 1);
+    }
+
+    #[test]
+    fn watch_loop_reruns_for_each_separate_change() {
+        let (tx, rx) = mpsc::channel();
+        let sender = std::thread::spawn(move || {
+            tx.send(PathBuf::from("docs/a.md")).unwrap();
+            std::thread::sleep(Duration::from_millis(200));
+            tx.send(PathBuf::from("docs/b.md")).unwrap();
+        });
+
+        let mut runs = 0;
+        watch_loop(&rx, Duration::from_millis(20), || runs += 1);
+        sender.join().unwrap();
+        assert_eq!(runs, 2);
+    }
 }
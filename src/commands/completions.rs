@@ -0,0 +1,37 @@
+//! Implementation of the `pave completions` command.
+
+use clap::CommandFactory;
+use clap_complete::Shell;
+use std::io::Write;
+
+use crate::cli::Cli;
+
+/// Write the completion script for `shell` to `out`.
+///
+/// The script is generated from the `Cli` definition, so new subcommands and
+/// flags are picked up automatically.
+pub fn generate(shell: Shell, out: &mut dyn Write) {
+    let mut command = Cli::command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, out);
+}
+
+/// Execute the `pave completions` command, printing the script to stdout.
+pub fn execute(shell: Shell) {
+    generate(shell, &mut std::io::stdout());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bash_completions_include_subcommands() {
+        let mut out = Vec::new();
+        generate(Shell::Bash, &mut out);
+
+        let script = String::from_utf8(out).unwrap();
+        assert!(script.contains("verify"));
+        assert!(script.contains("check"));
+    }
+}
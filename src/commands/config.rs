@@ -1,159 +1,473 @@
-use anyhow::{Context, Result, anyhow};
+use anyhow::{Context, Result, anyhow, bail};
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::Path;
+use std::process::Command;
 use toml::Value;
+use toml_edit::{DocumentMut, Item, Table};
 
-const CONFIG_FILENAME: &str = ".pave.toml";
+use crate::config::{
+    CONFIG_FILENAME, PaveConfig, apply_env_overrides, check_config, find_config, is_known_key,
+    merge_values, parse_value,
+};
 
-/// Find the config file by walking up from current directory.
-pub fn find_config_path() -> Result<PathBuf> {
-    let cwd = std::env::current_dir().context("Failed to get current directory")?;
-    find_config_path_from(&cwd)
+/// Common alternate spellings of config keys and the keys they stand for.
+const KEY_ALIASES: &[(&str, &str)] = &[
+    ("root", "docs.root"),
+    ("docs_root", "docs.root"),
+    ("docs_dir", "docs.root"),
+    ("docs.dir", "docs.root"),
+    ("docs.path", "docs.root"),
+    ("templates_dir", "docs.templates"),
+    ("version", "pave.version"),
+    ("max_lines", "rules.max_lines"),
+    ("rules.max-lines", "rules.max_lines"),
+    ("gradual", "rules.gradual"),
+    ("strict_output", "rules.strict_output_matching"),
+    ("shell", "verification.shell"),
+    ("timeout", "verification.default_timeout_secs"),
+    ("verification.timeout", "verification.default_timeout_secs"),
+    ("required_sections", "check.required_sections"),
+];
+
+/// The canonical key `key` is an alias for, if it is one.
+fn canonical_key(key: &str) -> Option<&'static str> {
+    KEY_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == key)
+        .map(|&(_, canonical)| canonical)
 }
 
-fn find_config_path_from(start: &Path) -> Result<PathBuf> {
-    let mut current = start.to_path_buf();
-    loop {
-        let config_path = current.join(CONFIG_FILENAME);
-        if config_path.exists() {
-            return Ok(config_path);
-        }
-        if !current.pop() {
-            return Err(anyhow!(
-                "No {} found in current directory or any parent directory",
-                CONFIG_FILENAME
-            ));
+/// Resolve an alias to its canonical key, noting the substitution on stderr.
+fn resolve_key(key: &str) -> &str {
+    match canonical_key(key) {
+        Some(canonical) => {
+            eprintln!("Note: `{}` is an alias for `{}`", key, canonical);
+            canonical
         }
+        None => key,
     }
 }
 
-/// Load the config file as a TOML Value.
+/// Load the config file as a TOML Value, merged over the configs it
+/// `extends`.
 fn load_config(path: &Path) -> Result<Value> {
+    Ok(crate::config::load_value(path, &mut Vec::new())?)
+}
+
+/// Load the config file with `PAVE_*` environment overrides applied.
+fn load_effective_config<I>(path: &Path, vars: I) -> Result<Value>
+where
+    I: IntoIterator<Item = (String, String)>,
+{
+    let mut value = load_config(path)?;
+    apply_env_overrides(&mut value, vars);
+    Ok(value)
+}
+
+/// Load the config file as an editable document that keeps its formatting.
+fn load_document(path: &Path) -> Result<DocumentMut> {
     let content = fs::read_to_string(path)
         .with_context(|| format!("Failed to read config file: {}", path.display()))?;
-    let value: Value = toml::from_str(&content)
-        .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
-    Ok(value)
+    content
+        .parse()
+        .with_context(|| format!("Failed to parse config file: {}", path.display()))
 }
 
-/// Save a TOML Value to the config file.
-fn save_config(path: &Path, value: &Value) -> Result<()> {
-    let content = toml::to_string_pretty(value).context("Failed to serialize config")?;
-    fs::write(path, content)
+/// Save an edited document to the config file.
+fn save_document(path: &Path, doc: &DocumentMut) -> Result<()> {
+    fs::write(path, doc.to_string())
         .with_context(|| format!("Failed to write config file: {}", path.display()))?;
     Ok(())
 }
 
 /// Get a value from the config using dot notation (e.g., "docs.root").
-pub fn get(key: &str) -> Result<()> {
-    let config_path = find_config_path()?;
-    let config = load_config(&config_path)?;
-
-    let value = get_nested_value(&config, key)?;
-    println!("{}", format_value(value));
+///
+/// Tables print one `key = value` line per nested value, or the whole
+/// subtree as JSON with `json`.
+pub fn get(key: &str, json: bool, config: Option<&Path>) -> Result<()> {
+    println!("{}", get_value(key, json, config, std::env::vars())?);
     Ok(())
 }
 
+/// Render the effective value at `key`, with `vars` layered over the file
+/// and the file over the defaults.
+fn get_value<I>(key: &str, json: bool, config: Option<&Path>, vars: I) -> Result<String>
+where
+    I: IntoIterator<Item = (String, String)>,
+{
+    let config_path = find_config(config)?;
+    let mut values =
+        Value::try_from(PaveConfig::default()).context("Failed to serialize the default config")?;
+    merge_values(&mut values, load_effective_config(&config_path, vars)?);
+    render_value(&values, resolve_key(key), json)
+}
+
+/// Render the value at `key` for `config get`.
+///
+/// Keys outside the schema are an error. A known key that is unset and has
+/// no default, like `docs.templates`, renders as empty (`null` in JSON).
+fn render_value(values: &Value, key: &str, json: bool) -> Result<String> {
+    if !is_known_key(key) {
+        bail!(
+            "Unknown config key '{}'\n\nHint: run `pave config export` to see every key",
+            key
+        );
+    }
+    let Ok(value) = get_nested_value(values, key) else {
+        eprintln!("Note: `{}` is not set", key);
+        return Ok(if json { "null" } else { "" }.to_string());
+    };
+    if json {
+        return serde_json::to_string_pretty(value).context("Failed to serialize value as JSON");
+    }
+    Ok(match value {
+        Value::Table(_) => {
+            let mut lines = Vec::new();
+            collect_config_values(value, key, &mut lines);
+            lines.join("\n")
+        }
+        _ => format_value(value),
+    })
+}
+
 /// Set a value in the config using dot notation.
-pub fn set(key: &str, value: &str) -> Result<()> {
-    let config_path = find_config_path()?;
-    let mut config = load_config(&config_path)?;
+///
+/// Only the targeted value changes: comments, key order, and whitespace
+/// elsewhere in the file are preserved.
+pub fn set(key: &str, value: &str, config: Option<&Path>) -> Result<()> {
+    let config_path = find_config(config)?;
+    set_key(&config_path, resolve_key(key), value)
+}
+
+/// Set `key` to `value` in the config file at `path`, refusing keys the
+/// config doesn't know.
+fn set_key(path: &Path, key: &str, value: &str) -> Result<()> {
+    let mut doc = load_document(path)?;
 
     let parsed_value = parse_value(value);
-    set_nested_value(&mut config, key, parsed_value)?;
+    set_nested_value(&mut doc, key, &parsed_value)?;
 
-    save_config(&config_path, &config)?;
+    let unknown = check_config(&doc.to_string()).into_iter().any(|problem| {
+        problem.key.as_deref() == Some(key) && problem.message.starts_with("unknown key")
+    });
+    if unknown {
+        bail!(
+            "Unknown config key '{}'\n\nHint: run `pave config export` to see every key",
+            key
+        );
+    }
+
+    save_document(path, &doc)?;
+    Ok(())
+}
+
+/// Remove a value from the config using dot notation.
+///
+/// Like `set`, only the targeted key changes; the rest of the file keeps its
+/// comments and formatting.
+pub fn unset(key: &str, config: Option<&Path>) -> Result<()> {
+    let config_path = find_config(config)?;
+    unset_key(&config_path, resolve_key(key))
+}
+
+/// Remove `key` from the config file at `path`, refusing to leave the file
+/// with a problem it didn't already have (such as a missing required key).
+fn unset_key(path: &Path, key: &str) -> Result<()> {
+    let mut doc = load_document(path)?;
+    let before = check_config(&doc.to_string());
+
+    if !remove_nested_value(&mut doc, key)? {
+        bail!("Config key '{}' is not set in {}", key, path.display());
+    }
+
+    if let Some(problem) = check_config(&doc.to_string())
+        .into_iter()
+        .find(|problem| !before.contains(problem))
+    {
+        bail!("Cannot unset '{}': {}", key, problem.message);
+    }
+
+    save_document(path, &doc)?;
     Ok(())
 }
 
 /// List all config values.
-pub fn list() -> Result<()> {
-    let config_path = find_config_path()?;
-    let config = load_config(&config_path)?;
+pub fn list(config: Option<&Path>) -> Result<()> {
+    for line in list_values(config, std::env::vars())? {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+/// Collect all effective config values as `key = value` lines.
+fn list_values<I>(config: Option<&Path>, vars: I) -> Result<Vec<String>>
+where
+    I: IntoIterator<Item = (String, String)>,
+{
+    let config_path = find_config(config)?;
+    let values = load_effective_config(&config_path, vars)?;
+
+    let mut lines = Vec::new();
+    collect_config_values(&values, "", &mut lines);
+    Ok(lines)
+}
+
+/// Print the effective config as TOML: defaults, the file, and `PAVE_*`
+/// environment overrides merged.
+pub fn export(config: Option<&Path>) -> Result<()> {
+    let config_path = find_config(config)?;
+    print!("{}", export_config(&config_path, std::env::vars())?);
+    Ok(())
+}
+
+/// Render the effective config for `config export`.
+fn export_config<I>(path: &Path, vars: I) -> Result<String>
+where
+    I: IntoIterator<Item = (String, String)>,
+{
+    let config: PaveConfig = load_effective_config(path, vars)?
+        .try_into()
+        .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+    config.validate()?;
+    toml::to_string_pretty(&config).context("Failed to serialize config")
+}
+
+/// Write `source` as the project config after validating it.
+///
+/// The target is `--config` when given, otherwise `.pave.toml` in the
+/// current directory. An existing file is only replaced with `force`.
+pub fn import(source: &Path, force: bool, config: Option<&Path>) -> Result<()> {
+    let target = match config {
+        Some(path) => path.to_path_buf(),
+        None => std::env::current_dir()
+            .context("Failed to get current directory")?
+            .join(CONFIG_FILENAME),
+    };
+    import_config(source, &target, force)?;
+    println!("Imported {} to {}", source.display(), target.display());
+    Ok(())
+}
+
+/// Validate `source` and copy it to `target` as written, comments included.
+fn import_config(source: &Path, target: &Path, force: bool) -> Result<()> {
+    if target.exists() && !force {
+        bail!(
+            "{} already exists\n\nHint: pass --force to overwrite it",
+            target.display()
+        );
+    }
 
-    print_config_values(&config, "");
+    let problems = validation_problems(source)?;
+    if !problems.is_empty() {
+        bail!(
+            "Refusing to import an invalid config:\n{}",
+            problems.join("\n")
+        );
+    }
+
+    fs::copy(source, target)
+        .with_context(|| format!("Failed to write config file: {}", target.display()))?;
     Ok(())
 }
 
 /// Print the path to the config file.
-pub fn path() -> Result<()> {
-    let config_path = find_config_path()?;
+pub fn path(config: Option<&Path>) -> Result<()> {
+    let config_path = find_config(config)?;
     println!("{}", config_path.display());
     Ok(())
 }
 
+/// Open the config file in the user's editor, then validate the result.
+///
+/// Creates a default config in the current directory if none exists.
+pub fn edit(config: Option<&Path>) -> Result<()> {
+    let config_path = match find_config(config) {
+        Ok(path) => path,
+        Err(err) if config.is_some() => return Err(err.into()),
+        Err(_) => {
+            let cwd = std::env::current_dir().context("Failed to get current directory")?;
+            let path = cwd.join(CONFIG_FILENAME);
+            PaveConfig::default().save(&path)?;
+            println!("Created {}", path.display());
+            path
+        }
+    };
+
+    println!("{}", opening_message(&config_path));
+    edit_with(&config_path, &resolve_editor())
+}
+
+/// Say which config `config edit` opens, flagging the global config so it
+/// isn't mistaken for the project's.
+fn opening_message(config_path: &Path) -> String {
+    if crate::config::is_global_config(config_path) {
+        format!(
+            "Opening global config {} (no {} found here; run `pave init` to create one)",
+            config_path.display(),
+            CONFIG_FILENAME
+        )
+    } else {
+        format!("Opening {}", config_path.display())
+    }
+}
+
+/// Check the whole config file against the schema.
+///
+/// Prints each problem with its line number and fails if there are any.
+pub fn validate(config: Option<&Path>) -> Result<()> {
+    let config_path = find_config(config)?;
+    let problems = validation_problems(&config_path)?;
+
+    if problems.is_empty() {
+        println!("Config is valid: {}", config_path.display());
+        return Ok(());
+    }
+    for problem in &problems {
+        println!("{}", problem);
+    }
+    bail!(
+        "Config has {} problem{}",
+        problems.len(),
+        if problems.len() == 1 { "" } else { "s" }
+    )
+}
+
+/// Describe every problem in a config file as `path:line: message`.
+fn validation_problems(path: &Path) -> Result<Vec<String>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config file: {}", path.display()))?;
+    Ok(check_config(&content)
+        .into_iter()
+        .map(|problem| match problem.line {
+            Some(line) => format!("{}:{}: {}", path.display(), line, problem.message),
+            None => format!("{}: {}", path.display(), problem.message),
+        })
+        .collect())
+}
+
+/// Determine which editor to launch: `$EDITOR`, then `$VISUAL`, then a platform default.
+fn resolve_editor() -> String {
+    ["EDITOR", "VISUAL"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| default_editor().to_string())
+}
+
+/// Platform default editor used when neither `$EDITOR` nor `$VISUAL` is set.
+fn default_editor() -> &'static str {
+    if cfg!(windows) { "notepad" } else { "vi" }
+}
+
+/// Run the editor on the config file and validate the edited result.
+fn edit_with(path: &Path, editor: &str) -> Result<()> {
+    let status = editor_command(editor, path)
+        .status()
+        .with_context(|| format!("Failed to launch editor: {}", editor))?;
+
+    if !status.success() {
+        bail!("Editor '{}' exited with {}", editor, status);
+    }
+
+    PaveConfig::load(path)
+        .with_context(|| format!("Config is invalid after editing: {}", path.display()))?;
+    println!("Config is valid: {}", path.display());
+    Ok(())
+}
+
+/// Build the editor invocation through the shell so editors with arguments
+/// (e.g. `code --wait`) work as expected.
+fn editor_command(editor: &str, path: &Path) -> Command {
+    if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C")
+            .arg(format!("{} \"{}\"", editor, path.display()));
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c")
+            .arg(format!("{} \"$1\"", editor))
+            .arg("sh")
+            .arg(path);
+        cmd
+    }
+}
+
 /// Get a nested value using dot notation.
 fn get_nested_value<'a>(value: &'a Value, key: &str) -> Result<&'a Value> {
     let parts: Vec<&str> = key.split('.').collect();
     let mut current = value;
 
+    let not_found = || {
+        anyhow!(
+            "Key '{}' not found in config\n\nHint: run `pave config list` to see the keys that are set",
+            key
+        )
+    };
     for part in &parts {
         current = match current {
-            Value::Table(table) => table
-                .get(*part)
-                .ok_or_else(|| anyhow!("Key '{}' not found in config", key))?,
-            _ => return Err(anyhow!("Key '{}' not found in config", key)),
+            Value::Table(table) => table.get(*part).ok_or_else(not_found)?,
+            _ => return Err(not_found()),
         };
     }
 
     Ok(current)
 }
 
-/// Set a nested value using dot notation.
-fn set_nested_value(value: &mut Value, key: &str, new_value: Value) -> Result<()> {
+/// Set a nested value in a document using dot notation.
+///
+/// Missing tables are created as `[section]` headers. Replacing an existing
+/// value keeps its surrounding whitespace and trailing comment.
+fn set_nested_value(doc: &mut DocumentMut, key: &str, new_value: &Value) -> Result<()> {
     let parts: Vec<&str> = key.split('.').collect();
 
-    if parts.is_empty() {
+    if parts.iter().any(|part| part.is_empty()) {
         return Err(anyhow!("Empty key is not allowed"));
     }
 
-    let mut current = value;
+    let mut current = doc.as_table_mut() as &mut dyn toml_edit::TableLike;
 
     // Navigate to the parent, creating tables as needed
     for part in &parts[..parts.len() - 1] {
-        current = match current {
-            Value::Table(table) => {
-                if !table.contains_key(*part) {
-                    table.insert(part.to_string(), Value::Table(toml::map::Map::new()));
-                }
-                table.get_mut(*part).unwrap()
-            }
-            _ => return Err(anyhow!("Cannot set nested key: parent is not a table")),
-        };
+        current = current
+            .entry(part)
+            .or_insert_with(|| Item::Table(Table::new()))
+            .as_table_like_mut()
+            .ok_or_else(|| anyhow!("Cannot set nested key: parent is not a table"))?;
     }
 
-    // Set the final value
+    // Set the final value, carrying over the old value's decoration
+    let mut new_value: toml_edit::Value = new_value
+        .to_string()
+        .parse()
+        .with_context(|| format!("Cannot write value for '{}'", key))?;
     let last_key = parts.last().unwrap();
-    match current {
-        Value::Table(table) => {
-            table.insert(last_key.to_string(), new_value);
-            Ok(())
-        }
-        _ => Err(anyhow!("Cannot set key: parent is not a table")),
+    if let Some(old) = current.get(last_key).and_then(Item::as_value) {
+        *new_value.decor_mut() = old.decor().clone();
     }
+    current.insert(last_key, Item::Value(new_value));
+    Ok(())
 }
 
-/// Parse a string value into an appropriate TOML Value.
-fn parse_value(s: &str) -> Value {
-    // Try to parse as integer
-    if let Ok(i) = s.parse::<i64>() {
-        return Value::Integer(i);
-    }
+/// Remove a nested value from a TOML document using dot notation.
+///
+/// Returns whether the key was present. Only the key's own line (and any
+/// comment directly above it) is removed.
+fn remove_nested_value(doc: &mut DocumentMut, key: &str) -> Result<bool> {
+    let parts: Vec<&str> = key.split('.').collect();
 
-    // Try to parse as float
-    if let Ok(f) = s.parse::<f64>() {
-        return Value::Float(f);
+    if parts.iter().any(|part| part.is_empty()) {
+        return Err(anyhow!("Empty key is not allowed"));
     }
 
-    // Try to parse as boolean
-    match s.to_lowercase().as_str() {
-        "true" => return Value::Boolean(true),
-        "false" => return Value::Boolean(false),
-        _ => {}
+    let mut current = doc.as_table_mut() as &mut dyn toml_edit::TableLike;
+    for part in &parts[..parts.len() - 1] {
+        match current.get_mut(part).and_then(Item::as_table_like_mut) {
+            Some(table) => current = table,
+            None => return Ok(false),
+        }
     }
-
-    // Default to string
-    Value::String(s.to_string())
+    Ok(current.remove(parts.last().unwrap()).is_some())
 }
 
 /// Format a TOML Value for display.
@@ -186,8 +500,8 @@ fn format_value_quoted(value: &Value) -> String {
     }
 }
 
-/// Recursively print all config values with their full key paths.
-fn print_config_values(value: &Value, prefix: &str) {
+/// Recursively collect all config values with their full key paths.
+fn collect_config_values(value: &Value, prefix: &str, lines: &mut Vec<String>) {
     if let Value::Table(table) = value {
         for (key, val) in table {
             let full_key = if prefix.is_empty() {
@@ -198,10 +512,10 @@ fn print_config_values(value: &Value, prefix: &str) {
 
             match val {
                 Value::Table(_) => {
-                    print_config_values(val, &full_key);
+                    collect_config_values(val, &full_key, lines);
                 }
                 _ => {
-                    println!("{} = {}", full_key, format_value_quoted(val));
+                    lines.push(format!("{} = {}", full_key, format_value_quoted(val)));
                 }
             }
         }
@@ -211,6 +525,7 @@ fn print_config_values(value: &Value, prefix: &str) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::path::PathBuf;
 
     #[test]
     fn test_get_nested_value() {
@@ -257,23 +572,22 @@ mod tests {
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
 
+    /// Apply `set_nested_value` to `content` and parse the result back.
+    fn set_in(content: &str, key: &str, value: Value) -> (String, Value) {
+        let mut doc: DocumentMut = content.parse().unwrap();
+        set_nested_value(&mut doc, key, &value).unwrap();
+        let written = doc.to_string();
+        let parsed = toml::from_str(&written).unwrap();
+        (written, parsed)
+    }
+
     #[test]
     fn test_set_nested_value() {
-        let mut config: Value = toml::from_str(
-            r#"
-            [docs]
-            root = "docs"
-        "#
-            .trim(),
-        )
-        .unwrap();
-
-        set_nested_value(
-            &mut config,
+        let (_, config) = set_in(
+            "[docs]\nroot = \"docs\"\n",
             "docs.root",
             Value::String("documentation".to_string()),
-        )
-        .unwrap();
+        );
         assert_eq!(
             get_nested_value(&config, "docs.root").unwrap(),
             &Value::String("documentation".to_string())
@@ -282,20 +596,93 @@ mod tests {
 
     #[test]
     fn test_set_creates_intermediate_tables() {
-        let mut config: Value = toml::from_str(
-            r#"
-            [docs]
-            root = "docs"
-        "#
-            .trim(),
-        )
-        .unwrap();
-
-        set_nested_value(&mut config, "rules.max_lines", Value::Integer(500)).unwrap();
+        let (written, config) = set_in(
+            "[docs]\nroot = \"docs\"\n",
+            "rules.max_lines",
+            Value::Integer(500),
+        );
         assert_eq!(
             get_nested_value(&config, "rules.max_lines").unwrap(),
             &Value::Integer(500)
         );
+        assert!(written.ends_with("[rules]\nmax_lines = 500\n"));
+    }
+
+    #[test]
+    fn test_set_preserves_comments_and_order() {
+        let content = r#"# Team config: ask #docs before changing rules
+[pave]
+version = "0.1"
+
+[docs]
+root = "docs"  # moved from documentation/ in 2024
+
+[rules]
+# Keep docs short enough to read in one sitting
+max_lines = 300 # hard limit
+require_examples = true
+"#;
+        let (written, _) = set_in(content, "rules.require_examples", Value::Boolean(false));
+        assert_eq!(
+            written,
+            content.replace("require_examples = true", "require_examples = false")
+        );
+
+        // The replaced value keeps its own trailing comment too
+        let (written, _) = set_in(content, "rules.max_lines", Value::Integer(200));
+        assert!(written.contains("max_lines = 200 # hard limit\n"));
+        assert!(written.starts_with("# Team config: ask #docs before changing rules\n"));
+    }
+
+    #[test]
+    fn test_unset_removes_key_and_keeps_comments() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        let content = r#"# Team config: ask #docs before changing rules
+[pave]
+version = "0.1"
+
+[docs]
+root = "docs"  # moved from documentation/ in 2024
+
+[rules]
+# Keep docs short enough to read in one sitting
+max_lines = 200 # hard limit
+require_examples = true
+"#;
+        fs::write(&config_path, content).unwrap();
+
+        unset_key(&config_path, "rules.require_examples").unwrap();
+        assert_eq!(
+            fs::read_to_string(&config_path).unwrap(),
+            content.replace("require_examples = true\n", "")
+        );
+
+        let err = unset_key(&config_path, "rules.require_examples").unwrap_err();
+        assert!(err.to_string().contains("is not set"), "{err}");
+        assert!(unset_key(&config_path, "mapping.exclude").is_err());
+    }
+
+    #[test]
+    fn test_unset_refuses_required_keys() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        PaveConfig::default().save(&config_path).unwrap();
+        let before = fs::read_to_string(&config_path).unwrap();
+
+        let err = unset_key(&config_path, "docs.root").unwrap_err();
+        assert!(
+            err.to_string().starts_with("Cannot unset 'docs.root'"),
+            "{err}"
+        );
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), before);
+    }
+
+    #[test]
+    fn test_set_rejects_non_table_parent() {
+        let mut doc: DocumentMut = "[docs]\nroot = \"docs\"\n".parse().unwrap();
+        assert!(set_nested_value(&mut doc, "docs.root.x", &Value::Integer(1)).is_err());
+        assert!(set_nested_value(&mut doc, "docs..root", &Value::Integer(1)).is_err());
     }
 
     #[test]
@@ -323,10 +710,358 @@ mod tests {
         assert_eq!(parse_value("docs"), Value::String("docs".to_string()));
     }
 
+    #[cfg(unix)]
+    fn write_editor_script(dir: &Path, body: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let script = dir.join("editor.sh");
+        fs::write(&script, format!("#!/bin/sh\n{}", body)).unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        script
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_edit_with_stub_editor_updates_and_validates() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        PaveConfig::default().save(&config_path).unwrap();
+
+        let script = write_editor_script(
+            temp_dir.path(),
+            "cat > \"$1\" <<'EOF'\n[pave]\nversion = \"0.1\"\n\n[docs]\nroot = \"documentation\"\nEOF\n",
+        );
+
+        edit_with(&config_path, &script.to_string_lossy()).unwrap();
+
+        let config = PaveConfig::load(&config_path).unwrap();
+        assert_eq!(config.docs.root, PathBuf::from("documentation"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_edit_with_stub_editor_reports_invalid_config() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        PaveConfig::default().save(&config_path).unwrap();
+
+        let script = write_editor_script(temp_dir.path(), "echo 'not = [valid' > \"$1\"\n");
+
+        let err = edit_with(&config_path, &script.to_string_lossy()).unwrap_err();
+        assert!(err.to_string().contains("invalid after editing"));
+    }
+
+    #[test]
+    fn test_env_override_changes_effective_value_but_not_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        PaveConfig::default().save(&config_path).unwrap();
+        let before = fs::read_to_string(&config_path).unwrap();
+
+        let vars = vec![("PAVE_DOCS_ROOT".to_string(), "documentation".to_string())];
+        let config = load_effective_config(&config_path, vars).unwrap();
+
+        assert_eq!(
+            get_nested_value(&config, "docs.root").unwrap(),
+            &Value::String("documentation".to_string())
+        );
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), before);
+    }
+
+    #[test]
+    fn test_edit_names_the_file_it_opens() {
+        let project = PathBuf::from("/repo").join(CONFIG_FILENAME);
+        assert_eq!(
+            opening_message(&project),
+            format!("Opening {}", project.display())
+        );
+        if let Some(global) = crate::config::global_config_path() {
+            let message = opening_message(&global);
+            assert!(message.starts_with("Opening global config "), "{message}");
+            assert!(message.contains("pave init"), "{message}");
+        }
+    }
+
+    #[test]
+    fn test_get_returns_env_override() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        PaveConfig::default().save(&config_path).unwrap();
+        let vars = || {
+            vec![
+                ("PAVE_DOCS_ROOT".to_string(), "handbook".to_string()),
+                ("PAVE_RULES_MAX_LINES".to_string(), "120".to_string()),
+            ]
+        };
+
+        let root = get_value("docs.root", false, Some(&config_path), vars()).unwrap();
+        assert_eq!(root, "handbook");
+        let max_lines = get_value("rules.max_lines", true, Some(&config_path), vars()).unwrap();
+        assert_eq!(max_lines, "120");
+        let root = get_value("docs.root", false, Some(&config_path), Vec::new()).unwrap();
+        assert_eq!(root, "docs");
+    }
+
+    #[test]
+    fn test_get_list_and_export_read_the_extends_chain() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("base.toml"),
+            "[pave]\nversion = \"0.1\"\n\n[docs]\nroot = \"docs\"\n\n[rules]\nmax_lines = 120\n",
+        )
+        .unwrap();
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        fs::write(
+            &config_path,
+            "extends = \"base.toml\"\n\n[rules]\nrequire_examples = false\n",
+        )
+        .unwrap();
+
+        let max_lines = get_value("rules.max_lines", false, Some(&config_path), Vec::new());
+        assert_eq!(max_lines.unwrap(), "120");
+        let lines = list_values(Some(&config_path), Vec::new()).unwrap();
+        assert!(lines.contains(&"rules.max_lines = 120".to_string()));
+        assert!(lines.contains(&"rules.require_examples = false".to_string()));
+        assert!(!lines.iter().any(|line| line.starts_with("extends")));
+        let exported = export_config(&config_path, Vec::new()).unwrap();
+        assert!(exported.contains("max_lines = 120"), "{exported}");
+    }
+
+    #[test]
+    fn test_export_round_trips_through_import() {
+        let source_dir = tempfile::TempDir::new().unwrap();
+        let source = source_dir.path().join(CONFIG_FILENAME);
+        fs::write(
+            &source,
+            "[pave]\nversion = \"0.1\"\n\n[rules]\nmax_lines = 120\n",
+        )
+        .unwrap();
+        let vars = vec![("PAVE_DOCS_ROOT".to_string(), "handbook".to_string())];
+
+        // Defaults, file values, and env overrides all land in the export
+        let exported = export_config(&source, vars).unwrap();
+        let effective = PaveConfig::parse(&exported).unwrap();
+        assert_eq!(effective.rules.max_lines, 120);
+        assert_eq!(effective.docs.root, PathBuf::from("handbook"));
+        assert_eq!(
+            effective.verification.default_timeout_secs,
+            PaveConfig::default().verification.default_timeout_secs
+        );
+
+        let shared = source_dir.path().join("shared.toml");
+        fs::write(&shared, &exported).unwrap();
+        let target_dir = tempfile::TempDir::new().unwrap();
+        let target = target_dir.path().join(CONFIG_FILENAME);
+        import_config(&shared, &target, false).unwrap();
+
+        assert_eq!(export_config(&target, Vec::new()).unwrap(), exported);
+        assert_eq!(PaveConfig::load(&target).unwrap(), effective);
+    }
+
+    #[test]
+    fn test_import_refuses_to_overwrite_or_import_invalid_config() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let target = temp_dir.path().join(CONFIG_FILENAME);
+        PaveConfig::default().save(&target).unwrap();
+        let source = temp_dir.path().join("shared.toml");
+        fs::write(
+            &source,
+            "[pave]\nversion = \"0.1\"\n\n[docs]\nroot = \"docs\"\n\n[rules]\nmax_lines = 80\n",
+        )
+        .unwrap();
+
+        let err = import_config(&source, &target, false).unwrap_err();
+        assert!(err.to_string().contains("--force"));
+        assert_eq!(PaveConfig::load(&target).unwrap().rules.max_lines, 300);
+
+        import_config(&source, &target, true).unwrap();
+        assert_eq!(PaveConfig::load(&target).unwrap().rules.max_lines, 80);
+
+        fs::write(&source, "[rules]\nmax_lines = \"many\"\nunknown = 1\n").unwrap();
+        let err = import_config(&source, &target, true).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Refusing to import an invalid config")
+        );
+        assert_eq!(PaveConfig::load(&target).unwrap().rules.max_lines, 80);
+    }
+
+    #[test]
+    fn test_list_values_uses_explicit_config() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("custom.toml");
+        let mut config = PaveConfig::default();
+        config.docs.root = PathBuf::from("handbook");
+        config.rules.max_lines = 120;
+        config.save(&config_path).unwrap();
+
+        let lines = list_values(Some(&config_path), Vec::new()).unwrap();
+
+        assert!(lines.contains(&"docs.root = \"handbook\"".to_string()));
+        assert!(lines.contains(&"rules.max_lines = 120".to_string()));
+    }
+
+    #[test]
+    fn test_set_resolves_aliases_and_rejects_unknown_keys() {
+        assert_eq!(canonical_key("max_lines"), Some("rules.max_lines"));
+        assert_eq!(canonical_key("docs.dir"), Some("docs.root"));
+        assert_eq!(canonical_key("rules.max_lines"), None);
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        PaveConfig::default().save(&config_path).unwrap();
+
+        set("max_lines", "250", Some(&config_path)).unwrap();
+        let config = PaveConfig::load(&config_path).unwrap();
+        assert_eq!(config.rules.max_lines, 250);
+
+        let before = fs::read_to_string(&config_path).unwrap();
+        let err = set("rules.max_line", "10", Some(&config_path)).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Unknown config key 'rules.max_line'")
+        );
+        let err = set("docs_rooot", "x", Some(&config_path)).unwrap_err();
+        assert!(err.to_string().contains("Unknown config key 'docs_rooot'"));
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), before);
+    }
+
+    #[test]
+    fn test_explicit_config_missing_errors() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let missing = temp_dir.path().join("missing.toml");
+
+        let err = list_values(Some(&missing), Vec::new()).unwrap_err();
+        assert!(err.to_string().contains("Config file not found"));
+    }
+
+    #[test]
+    fn test_validation_problems_for_saved_and_broken_configs() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        PaveConfig::default().save(&config_path).unwrap();
+        assert!(validation_problems(&config_path).unwrap().is_empty());
+
+        fs::write(
+            &config_path,
+            "[pave]\nversion = \"0.1\"\n[docs]\nroot = \"docs\"\n[rules]\nmax_line = 10\n",
+        )
+        .unwrap();
+        let problems = validation_problems(&config_path).unwrap();
+        assert_eq!(
+            problems,
+            vec![format!(
+                "{}:6: unknown key `rules.max_line`",
+                config_path.display()
+            )]
+        );
+        assert!(validate(Some(&config_path)).is_err());
+
+        fs::write(
+            &config_path,
+            "[pave]\nversion = \"0.1\"\n[docs]\nroot = [\"docs\", \"crates/api/docs\"]\n",
+        )
+        .unwrap();
+        assert!(validation_problems(&config_path).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_get_docs_root_list() {
+        let config: Value =
+            toml::from_str("[docs]\nroot = [\"docs\", \"crates/api/docs\"]\n").unwrap();
+        let root = get_nested_value(&config, "docs.root").unwrap();
+        assert_eq!(format_value(root), "[docs, crates/api/docs]");
+    }
+
     #[test]
     fn test_format_value() {
         assert_eq!(format_value(&Value::String("test".to_string())), "test");
         assert_eq!(format_value(&Value::Integer(42)), "42");
         assert_eq!(format_value(&Value::Boolean(true)), "true");
     }
+
+    fn get_fixture() -> Value {
+        toml::from_str(
+            "[pave]\nversion = \"0.1\"\n\n[verification]\ndefault_timeout_secs = 30\nshell = \"bash\"\n\n[rules.type_specific]\nrunbooks = true\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_render_scalar_prints_bare_value() {
+        let config = get_fixture();
+        assert_eq!(
+            render_value(&config, "verification.shell", false).unwrap(),
+            "bash"
+        );
+        assert_eq!(
+            render_value(&config, "verification.default_timeout_secs", true).unwrap(),
+            "30"
+        );
+    }
+
+    #[test]
+    fn test_render_table_as_json_and_lines() {
+        let config = get_fixture();
+
+        let json = render_value(&config, "verification", true).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            parsed,
+            serde_json::json!({ "default_timeout_secs": 30, "shell": "bash" })
+        );
+        let json = render_value(&config, "rules", true).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            parsed,
+            serde_json::json!({ "type_specific": { "runbooks": true } })
+        );
+
+        assert_eq!(
+            render_value(&config, "verification", false).unwrap(),
+            "verification.default_timeout_secs = 30\nverification.shell = \"bash\""
+        );
+    }
+
+    #[test]
+    fn test_render_unknown_key_errors() {
+        let config = get_fixture();
+        for key in ["verification.missing", "pave.version.major", "nope"] {
+            let err = render_value(&config, key, true).unwrap_err().to_string();
+            assert!(err.contains(&format!("Unknown config key '{}'", key)));
+            assert!(err.contains("pave config export"));
+        }
+
+        // Known keys without a value aren't unknown
+        assert_eq!(render_value(&config, "docs.templates", false).unwrap(), "");
+        assert_eq!(
+            render_value(&config, "docs.templates", true).unwrap(),
+            "null"
+        );
+    }
+
+    #[test]
+    fn test_get_falls_back_to_defaults_for_unset_keys() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(CONFIG_FILENAME);
+        fs::write(
+            &config_path,
+            "[pave]\nversion = \"0.1\"\n\n[verification]\nshell = \"bash\"\n",
+        )
+        .unwrap();
+        let get = |key: &str, json: bool| get_value(key, json, Some(&config_path), Vec::new());
+
+        assert_eq!(get("verification.timeout", false).unwrap(), "30");
+        assert_eq!(get("docs.root", false).unwrap(), "docs");
+        let json: serde_json::Value =
+            serde_json::from_str(&get("verification", true).unwrap()).unwrap();
+        assert_eq!(json["shell"], "bash");
+        assert_eq!(json["default_timeout_secs"], 30);
+        assert!(
+            get("verification.timeout_secs", false)
+                .unwrap_err()
+                .to_string()
+                .contains("Unknown config key")
+        );
+    }
 }
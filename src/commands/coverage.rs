@@ -1,18 +1,21 @@
 //! Implementation of the `pave coverage` command for analyzing code-to-doc coverage.
 //!
-//! This module analyzes which code files are covered by documentation patterns
-//! defined in the `## Paths` sections of PAVED documents.
+//! The analysis itself lives in [`crate::coverage`]; this module adds the
+//! thresholds, per-directory and per-extension breakdowns, suggestions, and
+//! text, JSON, and HTML output.
 
 use anyhow::{Context, Result};
-use glob::Pattern;
 use serde::Serialize;
-use std::collections::HashMap;
-use std::env;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 
 use crate::cli::CoverageOutputFormat;
-use crate::config::{CONFIG_FILENAME, PaveConfig};
-use crate::parser::CodeBlockTracker;
+use crate::config::PaveConfig;
+use crate::coverage::{
+    self, CoverageOptions, CoveredFile, PartialFile, analyze_coverage, collect_code_files,
+    load_doc_mappings, without_drafts,
+};
+use crate::paths::PathsMatcher;
 
 /// Arguments for the `pave coverage` command.
 pub struct CoverageArgs {
@@ -26,6 +29,18 @@ pub struct CoverageArgs {
     pub include: Vec<String>,
     /// Patterns to exclude (skip these code files).
     pub exclude: Vec<String>,
+    /// Directory to write an HTML report (`index.html`) into.
+    pub html: Option<PathBuf>,
+    /// Count files excluded by `.gitignore` and `.ignore` too.
+    pub no_ignore: bool,
+    /// Break coverage down by file extension.
+    pub by_extension: bool,
+    /// Minimum coverage percentage per extension, such as `("rs", 90)`.
+    pub threshold_ext: Vec<(String, u32)>,
+    /// Minimum number of docs that must cover every code file.
+    pub min_docs_per_file: Option<usize>,
+    /// Explicit config file path (from `--config`).
+    pub config: Option<PathBuf>,
 }
 
 /// Coverage statistics for a directory.
@@ -41,6 +56,24 @@ pub struct DirectoryCoverage {
     pub percentage: f64,
 }
 
+/// Coverage statistics for one file extension.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExtensionCoverage {
+    /// File extension, without the leading dot.
+    pub extension: String,
+    /// Number of covered files.
+    pub covered: usize,
+    /// Total number of files.
+    pub total: usize,
+    /// Coverage percentage.
+    pub percentage: f64,
+    /// The `--threshold-ext` checked for this extension, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<u32>,
+    /// Whether the extension's threshold was met (always true without one).
+    pub passed: bool,
+}
+
 /// Information about an uncovered file.
 #[derive(Debug, Clone, Serialize)]
 pub struct UncoveredFile {
@@ -63,391 +96,268 @@ pub struct CoverageSuggestion {
 /// Results of the coverage analysis.
 #[derive(Debug, Serialize)]
 pub struct CoverageResults {
+    /// Total number of code files.
+    pub total: usize,
     /// Number of covered files.
-    pub covered_files: usize,
+    pub covered: usize,
     /// Number of uncovered files.
-    pub uncovered_files: usize,
-    /// Total number of code files.
-    pub total_files: usize,
+    pub uncovered: usize,
     /// Overall coverage percentage.
-    pub coverage_percentage: f64,
+    pub percentage: f64,
+    /// The threshold that was checked, if any.
+    pub threshold: Option<u32>,
+    /// Whether the threshold was met (always true without a threshold).
+    pub passed: bool,
+    /// List of covered files with the docs that cover them.
+    pub covered_files: Vec<CoveredFile>,
+    /// Files only partly spanned by `## Paths` line ranges.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub partially_covered: Vec<PartialFile>,
+    /// List of uncovered files.
+    pub uncovered_files: Vec<UncoveredFile>,
     /// Coverage by directory.
     pub by_directory: Vec<DirectoryCoverage>,
-    /// List of uncovered files.
-    pub uncovered: Vec<UncoveredFile>,
+    /// Coverage by file extension (with `--by-extension` or `--threshold-ext`).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub by_extension: Vec<ExtensionCoverage>,
     /// Suggestions for improving coverage.
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub suggestions: Vec<CoverageSuggestion>,
-    /// Whether the threshold was met (if specified).
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub threshold_met: Option<bool>,
-    /// The threshold that was checked.
+    /// Number of `status: draft` docs left out of the analysis.
+    #[serde(skip_serializing_if = "is_zero")]
+    pub drafts_skipped: usize,
+    /// The `--min-docs-per-file` that was checked, if any.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub threshold: Option<u32>,
+    pub min_docs_per_file: Option<usize>,
+    /// Files covered by fewer than `min_docs_per_file` docs, with the docs
+    /// that do cover them.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub under_documented: Vec<CoveredFile>,
 }
 
-/// A documentation file with its path mappings.
-#[derive(Debug, Clone)]
-struct DocMapping {
-    /// Glob patterns for code paths this doc covers.
-    patterns: Vec<String>,
+fn is_zero(n: &usize) -> bool {
+    *n == 0
 }
 
-/// Execute the `pave coverage` command.
-pub fn execute(args: CoverageArgs) -> Result<()> {
-    // Find and load config
-    let config_path = find_config()?;
-    let config = PaveConfig::load(&config_path)?;
-    let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
-    let docs_root = config_dir.join(&config.docs.root);
-
-    // Determine the path to analyze
-    let analyze_path = args.path.unwrap_or_else(|| config_dir.to_path_buf());
-
-    // Build exclude patterns (merge config + CLI)
-    let mut exclude_patterns = config.mapping.exclude.clone();
-    exclude_patterns.extend(args.exclude.clone());
-
-    // Collect code files
-    let code_files = collect_code_files(&analyze_path, &args.include, &exclude_patterns)?;
+/// Docs and code files that have drifted apart.
+#[derive(Debug, Default, Serialize)]
+pub struct OrphanReport {
+    /// Docs whose `## Paths` patterns match no files.
+    pub orphan_docs: Vec<PathBuf>,
+    /// Code files not covered by any doc's `## Paths`.
+    pub uncovered_files: Vec<PathBuf>,
+}
 
-    if code_files.is_empty() {
-        if args.format == CoverageOutputFormat::Text {
-            println!("No code files found to analyze.");
-            if !args.include.is_empty() {
-                println!("Include patterns: {:?}", args.include);
-            }
-        } else {
-            let results = CoverageResults {
-                covered_files: 0,
-                uncovered_files: 0,
-                total_files: 0,
-                coverage_percentage: 100.0,
-                by_directory: vec![],
-                uncovered: vec![],
-                suggestions: vec![],
-                threshold_met: args.threshold.map(|_| true),
-                threshold: args.threshold,
-            };
-            output_json(&results)?;
-        }
-        return Ok(());
+impl OrphanReport {
+    /// Returns true if nothing is orphaned.
+    pub fn is_empty(&self) -> bool {
+        self.orphan_docs.is_empty() && self.uncovered_files.is_empty()
     }
+}
+
+/// Find docs whose paths match nothing and code files no doc covers.
+///
+/// Paths in the report are relative to `config_dir`. With `respect_ignore`,
+/// files excluded by `.gitignore` and `.ignore` are left out.
+pub fn find_orphans(
+    config: &PaveConfig,
+    config_dir: &Path,
+    respect_ignore: bool,
+) -> Result<OrphanReport> {
+    let docs_roots = config.docs.resolve_root_dirs(config_dir)?;
+    let mut code_files =
+        collect_code_files(config_dir, &[], &config.mapping.exclude, respect_ignore)?;
+    let (doc_mappings, _) = without_drafts(
+        &mut code_files,
+        load_doc_mappings(&docs_roots, config_dir, respect_ignore)?,
+    );
 
-    // Load all doc mappings
-    let doc_mappings = load_doc_mappings(&docs_root, config_dir)?;
+    let mut orphan_docs: Vec<PathBuf> = doc_mappings
+        .iter()
+        .filter(|doc| {
+            PathsMatcher::new(&doc.patterns)
+                .matched_files(config_dir)
+                .is_empty()
+        })
+        .map(|doc| {
+            doc.path
+                .strip_prefix(config_dir)
+                .unwrap_or(&doc.path)
+                .to_path_buf()
+        })
+        .collect();
+    orphan_docs.sort();
 
-    // Determine coverage for each file
-    let (covered, uncovered) = analyze_coverage(&code_files, &doc_mappings, config_dir);
+    let (_, _, uncovered_files) = analyze_coverage(&code_files, &doc_mappings, config_dir);
 
-    // Calculate directory-level coverage
-    let by_directory = calculate_directory_coverage(&covered, &uncovered);
+    Ok(OrphanReport {
+        orphan_docs,
+        uncovered_files,
+    })
+}
 
-    // Generate suggestions
-    let suggestions = generate_suggestions(&uncovered, config_dir);
-
-    // Calculate percentages
-    let total_files = code_files.len();
-    let covered_count = covered.len();
-    let uncovered_count = uncovered.len();
-    let coverage_percentage = if total_files > 0 {
-        (covered_count as f64 / total_files as f64) * 100.0
-    } else {
-        100.0
-    };
+/// Percentage of the project's code files covered by some doc's `## Paths`.
+///
+/// Uses `mapping.exclude` and honors `.gitignore`, like a bare
+/// `pave coverage`; a project without code files is fully covered.
+pub fn overall_percentage(config: &PaveConfig, config_dir: &Path) -> Result<f64> {
+    Ok(coverage::compute(config, config_dir)?.percentage)
+}
 
-    // Check threshold
-    let threshold_met = args.threshold.map(|t| coverage_percentage >= t as f64);
+/// Execute the `pave coverage` command.
+pub fn execute(args: CoverageArgs) -> Result<()> {
+    // Find and load config
+    let config_path = crate::config::find_config(args.config.as_deref())?;
+    let config = PaveConfig::load(&config_path)?;
+    let config_dir = &crate::config::config_base_dir(&config_path);
 
-    let results = CoverageResults {
-        covered_files: covered_count,
-        uncovered_files: uncovered_count,
-        total_files,
-        coverage_percentage,
-        by_directory,
-        uncovered: uncovered
-            .iter()
-            .map(|p| UncoveredFile {
-                path: p.clone(),
-                suggested_doc: suggest_doc_name(p),
-            })
-            .collect(),
-        suggestions,
-        threshold_met,
-        threshold: args.threshold,
-    };
+    let results = compute_results(&args, &config, config_dir)?;
 
     // Output results
     match args.format {
+        CoverageOutputFormat::Text if results.total == 0 => {
+            println!("No code files found to analyze.");
+            if !args.include.is_empty() {
+                println!("Include patterns: {:?}", args.include);
+            }
+        }
         CoverageOutputFormat::Text => output_text(&results),
         CoverageOutputFormat::Json => output_json(&results)?,
     }
 
-    // Return error if threshold not met
-    if let Some(false) = threshold_met {
+    if let Some(dir) = &args.html {
+        let path = write_html_report(&results, dir)?;
+        eprintln!("Wrote HTML report to {}", path.display());
+    }
+
+    // The exit code reflects the threshold checks regardless of format
+    if let Some(ext) = results.by_extension.iter().find(|ext| !ext.passed) {
         anyhow::bail!(
-            "Coverage {:.1}% is below threshold {}%",
-            coverage_percentage,
-            args.threshold.unwrap()
+            "Coverage of .{} files {:.1}% is below threshold {}%",
+            ext.extension,
+            ext.percentage,
+            ext.threshold.unwrap_or_default()
         );
     }
-
-    Ok(())
-}
-
-/// Find the .pave.toml config file by walking up from the current directory.
-fn find_config() -> Result<PathBuf> {
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let mut dir = current_dir.as_path();
-
-    loop {
-        let config_path = dir.join(CONFIG_FILENAME);
-        if config_path.exists() {
-            return Ok(config_path);
-        }
-
-        match dir.parent() {
-            Some(parent) => dir = parent,
-            None => anyhow::bail!(
-                "No {} found in current directory or any parent directory",
-                CONFIG_FILENAME
-            ),
-        }
+    if !results.passed {
+        anyhow::bail!(
+            "Coverage {:.1}% is below threshold {}%",
+            results.percentage,
+            results.threshold.unwrap_or_default()
+        );
     }
-}
-
-/// Collect code files from the given path, applying include/exclude patterns.
-fn collect_code_files(root: &Path, include: &[String], exclude: &[String]) -> Result<Vec<PathBuf>> {
-    let mut files = Vec::new();
-    collect_code_files_recursive(root, root, include, exclude, &mut files)?;
-    files.sort();
-    Ok(files)
-}
-
-/// Recursively collect code files.
-fn collect_code_files_recursive(
-    root: &Path,
-    current: &Path,
-    include: &[String],
-    exclude: &[String],
-    files: &mut Vec<PathBuf>,
-) -> Result<()> {
-    let entries = match std::fs::read_dir(current) {
-        Ok(entries) => entries,
-        Err(_) => return Ok(()),
-    };
-
-    for entry in entries {
-        let entry = entry?;
-        let path = entry.path();
-        let relative = path.strip_prefix(root).unwrap_or(&path);
-
-        // Check exclusions first
-        if matches_any_pattern(relative, exclude) {
-            continue;
-        }
-
-        // Skip hidden directories and common non-code directories
-        if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-            if name.starts_with('.') {
-                continue;
-            }
-            if path.is_dir() {
-                // Skip common non-code directories
-                if matches!(
-                    name,
-                    "target" | "node_modules" | "dist" | "build" | "__pycache__" | ".git"
-                ) {
-                    continue;
-                }
-            }
-        }
-
-        if path.is_dir() {
-            collect_code_files_recursive(root, &path, include, exclude, files)?;
-        } else if is_code_file(&path) {
-            // If include patterns specified, file must match at least one
-            if !include.is_empty() && !matches_any_pattern(relative, include) {
-                continue;
+    if !results.under_documented.is_empty() {
+        anyhow::bail!(
+            "{} file{} covered by fewer than {} doc{}",
+            results.under_documented.len(),
+            if results.under_documented.len() == 1 {
+                " is"
+            } else {
+                "s are"
+            },
+            results.min_docs_per_file.unwrap_or_default(),
+            if results.min_docs_per_file == Some(1) {
+                ""
+            } else {
+                "s"
             }
-            files.push(relative.to_path_buf());
-        }
+        );
     }
 
     Ok(())
 }
 
-/// Check if a file is a code file based on extension.
-fn is_code_file(path: &Path) -> bool {
-    let code_extensions = [
-        "rs", "py", "js", "ts", "jsx", "tsx", "go", "java", "c", "cpp", "h", "hpp", "rb", "php",
-        "swift", "kt", "scala", "sh", "bash", "zsh", "pl", "pm", "lua", "ex", "exs", "erl", "hrl",
-        "hs", "ml", "mli", "fs", "fsi", "clj", "cljs", "lisp", "el", "vim", "sql",
-    ];
-
-    path.extension()
-        .and_then(|ext| ext.to_str())
-        .map(|ext| code_extensions.contains(&ext))
-        .unwrap_or(false)
-}
-
-/// Load all documentation files with their path mappings.
-fn load_doc_mappings(docs_root: &Path, config_dir: &Path) -> Result<Vec<DocMapping>> {
-    let mut mappings = Vec::new();
-    load_doc_mappings_recursive(docs_root, config_dir, &mut mappings)?;
-    Ok(mappings)
-}
-
-/// Recursively load documentation files.
-fn load_doc_mappings_recursive(
-    current: &Path,
+/// Analyze coverage for the project rooted at `config_dir`.
+fn compute_results(
+    args: &CoverageArgs,
+    config: &PaveConfig,
     config_dir: &Path,
-    mappings: &mut Vec<DocMapping>,
-) -> Result<()> {
-    let entries = match std::fs::read_dir(current) {
-        Ok(entries) => entries,
-        Err(_) => return Ok(()),
+) -> Result<CoverageResults> {
+    let options = CoverageOptions {
+        path: args.path.clone(),
+        include: args.include.clone(),
+        exclude: args.exclude.clone(),
+        respect_ignore: !args.no_ignore,
     };
+    let report = coverage::compute_with(config, config_dir, &options)?;
 
-    for entry in entries {
-        let entry = entry?;
-        let path = entry.path();
-
-        if path.is_dir() {
-            // Skip templates directory
-            if path.file_name().is_some_and(|n| n == "templates") {
-                continue;
-            }
-            load_doc_mappings_recursive(&path, config_dir, mappings)?;
-        } else if path.extension().is_some_and(|ext| ext == "md") {
-            // Skip index.md
-            if path.file_name().is_some_and(|n| n == "index.md") {
-                continue;
-            }
-
-            if let Some(doc_mapping) = parse_doc_mapping(&path, config_dir)? {
-                mappings.push(doc_mapping);
-            }
-        }
-    }
-
-    Ok(())
-}
-
-/// Parse a documentation file to extract path mappings.
-fn parse_doc_mapping(path: &Path, _config_dir: &Path) -> Result<Option<DocMapping>> {
-    let content = std::fs::read_to_string(path)
-        .with_context(|| format!("Failed to read file: {}", path.display()))?;
-
-    let patterns = extract_paths_patterns(&content);
-
-    // Only include docs that have path mappings
-    if patterns.is_empty() {
-        return Ok(None);
-    }
-
-    Ok(Some(DocMapping { patterns }))
-}
-
-/// Extract path patterns from the ## Paths section.
-fn extract_paths_patterns(content: &str) -> Vec<String> {
-    let mut patterns = Vec::new();
-    let mut in_paths_section = false;
-    let mut tracker = CodeBlockTracker::new();
-
-    for line in content.lines() {
-        let trimmed = line.trim();
-
-        // Track code blocks (handles language tags and nested fences)
-        tracker.process_line(trimmed);
-
-        // Skip processing if inside a code block
-        if tracker.in_code_block() {
-            continue;
-        }
-
-        // Check if entering Paths section
-        if trimmed.starts_with("## Paths") {
-            in_paths_section = true;
-            continue;
-        }
-
-        // Check if leaving Paths section (another ## heading)
-        if in_paths_section && trimmed.starts_with("## ") {
-            break;
-        }
-
-        // Collect patterns (lines starting with - or *)
-        if in_paths_section
-            && let Some(pattern) = trimmed
-                .strip_prefix("- ")
-                .or_else(|| trimmed.strip_prefix("* "))
-        {
-            let pattern = pattern.trim();
-            // Remove backticks if present
-            let pattern = pattern.trim_matches('`');
-            if !pattern.is_empty() {
-                patterns.push(pattern.to_string());
-            }
-        }
-    }
-
-    patterns
-}
-
-/// Analyze coverage of code files against doc patterns.
-fn analyze_coverage(
-    code_files: &[PathBuf],
-    doc_mappings: &[DocMapping],
-    _config_dir: &Path,
-) -> (Vec<PathBuf>, Vec<PathBuf>) {
-    let mut covered = Vec::new();
-    let mut uncovered = Vec::new();
-
-    // Collect all patterns from all docs
-    let all_patterns: Vec<&String> = doc_mappings
+    let covered: Vec<PathBuf> = report
+        .covered_files
         .iter()
-        .flat_map(|d| d.patterns.iter())
+        .map(|file| file.path.clone())
+        .collect();
+    let uncovered = &report.uncovered_files;
+    // Partially covered files count against the breakdowns
+    let not_covered: Vec<PathBuf> = uncovered
+        .iter()
+        .cloned()
+        .chain(
+            report
+                .partially_covered
+                .iter()
+                .map(|file| file.path.clone()),
+        )
         .collect();
 
-    for file in code_files {
-        if matches_any_pattern(
-            file,
-            &all_patterns.iter().map(|s| s.as_str()).collect::<Vec<_>>(),
-        ) {
-            covered.push(file.clone());
-        } else {
-            uncovered.push(file.clone());
-        }
-    }
-
-    (covered, uncovered)
-}
-
-/// Check if a path matches any of the glob patterns.
-fn matches_any_pattern<S: AsRef<str>>(path: &Path, patterns: &[S]) -> bool {
-    let path_str = path.to_string_lossy();
+    // Calculate directory-level coverage
+    let by_directory = calculate_directory_coverage(&covered, &not_covered);
 
-    for pattern_str in patterns {
-        let pattern_str = pattern_str.as_ref();
+    // Generate suggestions
+    let suggestions = generate_suggestions(uncovered, config_dir);
 
-        // Try to compile as glob pattern
-        if let Ok(pattern) = Pattern::new(pattern_str)
-            && pattern.matches(&path_str)
-        {
-            return true;
-        }
+    let by_extension = if report.total > 0 && (args.by_extension || !args.threshold_ext.is_empty())
+    {
+        calculate_extension_coverage(&covered, &not_covered, &args.threshold_ext)
+    } else {
+        vec![]
+    };
 
-        // Also do simple prefix matching for patterns like "src/foo/"
-        if pattern_str.ends_with('/') || pattern_str.ends_with('*') {
-            let prefix = pattern_str.trim_end_matches('*').trim_end_matches('/');
-            if path_str.starts_with(prefix) {
-                return true;
-            }
+    // Check threshold; per-extension failures are reported separately
+    let threshold = args.threshold.or(config.mapping.coverage_threshold);
+    let passed = report.total == 0 || threshold.is_none_or(|t| report.percentage >= t as f64);
+
+    // Uncovered files count as covered by zero docs
+    let under_documented = match args.min_docs_per_file {
+        Some(min) => {
+            let mut files: Vec<CoveredFile> = report
+                .covered_files
+                .iter()
+                .filter(|file| file.docs.len() < min)
+                .cloned()
+                .chain(uncovered.iter().map(|path| CoveredFile {
+                    path: path.clone(),
+                    docs: vec![],
+                }))
+                .collect();
+            files.sort_by(|a, b| a.path.cmp(&b.path));
+            files
         }
-    }
+        None => vec![],
+    };
 
-    false
+    Ok(CoverageResults {
+        total: report.total,
+        covered: report.covered,
+        uncovered: uncovered.len(),
+        percentage: report.percentage,
+        threshold,
+        passed,
+        uncovered_files: uncovered
+            .iter()
+            .map(|p| UncoveredFile {
+                path: p.clone(),
+                suggested_doc: suggest_doc_name(p),
+            })
+            .collect(),
+        covered_files: report.covered_files,
+        partially_covered: report.partially_covered,
+        by_directory,
+        by_extension,
+        suggestions,
+        drafts_skipped: report.drafts_skipped,
+        min_docs_per_file: args.min_docs_per_file,
+        under_documented,
+    })
 }
 
 /// Calculate coverage statistics by directory.
@@ -501,6 +411,71 @@ fn calculate_directory_coverage(
     result
 }
 
+/// Calculate coverage statistics by file extension, checking `thresholds`.
+///
+/// An extension with a threshold but no files passes: there is nothing to
+/// cover.
+fn calculate_extension_coverage(
+    covered: &[PathBuf],
+    uncovered: &[PathBuf],
+    thresholds: &[(String, u32)],
+) -> Vec<ExtensionCoverage> {
+    let extension = |file: &PathBuf| {
+        file.extension()
+            .map(|ext| ext.to_string_lossy().to_string())
+            .unwrap_or_default()
+    };
+
+    let mut ext_stats: BTreeMap<String, (usize, usize)> = BTreeMap::new();
+    for file in covered {
+        let entry = ext_stats.entry(extension(file)).or_default();
+        entry.0 += 1;
+        entry.1 += 1;
+    }
+    for file in uncovered {
+        ext_stats.entry(extension(file)).or_default().1 += 1;
+    }
+
+    ext_stats
+        .into_iter()
+        .map(|(extension, (covered, total))| {
+            let percentage = (covered as f64 / total as f64) * 100.0;
+            let threshold = thresholds
+                .iter()
+                .rev()
+                .find(|(ext, _)| *ext == extension)
+                .map(|(_, t)| *t);
+            ExtensionCoverage {
+                passed: threshold.is_none_or(|t| percentage >= t as f64),
+                extension,
+                covered,
+                total,
+                percentage,
+                threshold,
+            }
+        })
+        .collect()
+}
+
+/// Parse a `--threshold-ext` value such as `rs=90`.
+pub fn parse_extension_threshold(s: &str) -> std::result::Result<(String, u32), String> {
+    let (ext, threshold) = s
+        .split_once('=')
+        .ok_or_else(|| format!("invalid extension threshold `{}` (expected e.g. rs=90)", s))?;
+    let ext = ext.trim().trim_start_matches('.');
+    if ext.is_empty() {
+        return Err(format!(
+            "missing extension in `{}` (expected e.g. rs=90)",
+            s
+        ));
+    }
+    let threshold = threshold
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid percentage in `{}` (expected e.g. rs=90)", s))?;
+    Ok((ext.to_string(), threshold))
+}
+
 /// Generate suggestions for improving coverage.
 fn generate_suggestions(uncovered: &[PathBuf], _config_dir: &Path) -> Vec<CoverageSuggestion> {
     // Group uncovered files by directory
@@ -528,7 +503,7 @@ fn generate_suggestions(uncovered: &[PathBuf], _config_dir: &Path) -> Vec<Covera
         .collect();
 
     // Sort by number of files (most impactful first)
-    suggestions.sort_by(|a, b| b.files.len().cmp(&a.files.len()));
+    suggestions.sort_by_key(|s| std::cmp::Reverse(s.files.len()));
 
     // Limit to top 5 suggestions
     suggestions.truncate(5);
@@ -557,20 +532,35 @@ fn output_text(results: &CoverageResults) {
     println!();
     println!(
         "Covered: {} file{} ({:.1}%)",
-        results.covered_files,
-        if results.covered_files == 1 { "" } else { "s" },
-        results.coverage_percentage
+        results.covered,
+        if results.covered == 1 { "" } else { "s" },
+        results.percentage
     );
+    if !results.partially_covered.is_empty() {
+        println!(
+            "Partial: {} file{} ({:.1}%)",
+            results.partially_covered.len(),
+            if results.partially_covered.len() == 1 {
+                ""
+            } else {
+                "s"
+            },
+            results.partially_covered.len() as f64 / results.total as f64 * 100.0
+        );
+    }
     println!(
         "Uncovered: {} file{} ({:.1}%)",
-        results.uncovered_files,
-        if results.uncovered_files == 1 {
-            ""
-        } else {
-            "s"
-        },
-        100.0 - results.coverage_percentage
+        results.uncovered,
+        if results.uncovered == 1 { "" } else { "s" },
+        results.uncovered as f64 / results.total as f64 * 100.0
     );
+    if results.drafts_skipped > 0 {
+        println!(
+            "Skipped: {} draft doc{}",
+            results.drafts_skipped,
+            if results.drafts_skipped == 1 { "" } else { "s" }
+        );
+    }
     println!();
 
     if !results.by_directory.is_empty() {
@@ -587,15 +577,59 @@ fn output_text(results: &CoverageResults) {
         println!();
     }
 
-    if !results.uncovered.is_empty() {
-        println!("Uncovered Files ({}):", results.uncovered.len());
+    if !results.by_extension.is_empty() {
+        println!("By Extension:");
+        for ext in &results.by_extension {
+            let threshold = match ext.threshold {
+                Some(t) if ext.passed => format!("  (threshold {}%: ✓ PASS)", t),
+                Some(t) => format!("  (threshold {}%: ✗ FAIL)", t),
+                None => String::new(),
+            };
+            println!(
+                "  {:<10} {}/{} files ({:.1}%){}",
+                format!(".{}", ext.extension),
+                ext.covered,
+                ext.total,
+                ext.percentage,
+                threshold
+            );
+        }
+        println!(
+            "  {:<10} {}/{} files ({:.1}%)",
+            "Total", results.covered, results.total, results.percentage
+        );
+        println!();
+    }
+
+    if !results.partially_covered.is_empty() {
+        println!(
+            "Partially Covered Files ({}):",
+            results.partially_covered.len()
+        );
+        for file in &results.partially_covered {
+            println!(
+                "  {} ({}/{} lines; missing {})",
+                file.path.display(),
+                file.covered_lines,
+                file.total_lines,
+                file.uncovered_ranges.join(", ")
+            );
+        }
+        println!();
+    }
+
+    if !results.uncovered_files.is_empty() {
+        println!("Uncovered Files ({}):", results.uncovered_files.len());
         // Limit display to first 20 files
         let display_limit = 20;
-        for file in results.uncovered.iter().take(display_limit) {
+        for file in results.uncovered_files.iter().take(display_limit) {
             println!("  {}", file.path.display());
         }
-        if results.uncovered.len() > display_limit {
-            println!("  ... and {} more", results.uncovered.len() - display_limit);
+        if results.uncovered_files.len() > display_limit {
+            println!(
+                "  ... and {} more",
+                results.uncovered_files.len() - display_limit
+            );
         }
         println!();
     }
@@ -613,15 +647,36 @@ fn output_text(results: &CoverageResults) {
         println!();
     }
 
+    if let Some(min) = results.min_docs_per_file {
+        if results.under_documented.is_empty() {
+            println!("Min docs per file: {} ✓ PASS", min);
+        } else {
+            println!(
+                "Min docs per file: {} ✗ FAIL ({} file{} short):",
+                min,
+                results.under_documented.len(),
+                if results.under_documented.len() == 1 {
+                    ""
+                } else {
+                    "s"
+                }
+            );
+            for file in &results.under_documented {
+                println!("  {} ({} of {})", file.path.display(), file.docs.len(), min);
+            }
+        }
+        println!();
+    }
+
     if let Some(threshold) = results.threshold {
-        let status = if results.threshold_met.unwrap_or(true) {
+        let status = if results.passed {
             "✓ PASS"
         } else {
             "✗ FAIL"
         };
         println!(
             "Threshold: {}% (actual: {:.1}%) {}",
-            threshold, results.coverage_percentage, status
+            threshold, results.percentage, status
         );
     }
 }
@@ -633,125 +688,164 @@ fn output_json(results: &CoverageResults) -> Result<()> {
     Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::fs;
-    use tempfile::TempDir;
-
-    #[test]
-    fn test_extract_paths_patterns() {
-        let content = r#"# Doc
-
-## Purpose
-Does something.
-
-## Paths
-- `src/commands/*.rs`
-- `src/cli.rs`
-
-## Examples
-Some examples.
-"#;
+/// Write a self-contained HTML report to `dir/index.html`.
+///
+/// Returns the path of the written file.
+fn write_html_report(results: &CoverageResults, dir: &Path) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create directory: {}", dir.display()))?;
+    let path = dir.join("index.html");
+    std::fs::write(&path, render_html(results))
+        .with_context(|| format!("Failed to write HTML report: {}", path.display()))?;
+    Ok(path)
+}
 
-        let patterns = extract_paths_patterns(content);
-        assert_eq!(patterns.len(), 2);
-        assert_eq!(patterns[0], "src/commands/*.rs");
-        assert_eq!(patterns[1], "src/cli.rs");
+/// Styles for the HTML report, inlined so the page has no external assets.
+const HTML_STYLE: &str = "\
+body { font-family: system-ui, sans-serif; margin: 2rem auto; max-width: 60rem; color: #1f2328; }
+.bar { background: #f3d5d5; border-radius: 4px; height: 1.25rem; overflow: hidden; }
+.bar > div { background: #3fb950; height: 100%; }
+table { border-collapse: collapse; width: 100%; margin-top: 1.5rem; }
+th, td { text-align: left; padding: 0.35rem 0.6rem; border-bottom: 1px solid #d0d7de; }
+td.covered { color: #1a7f37; }
+td.uncovered { color: #cf222e; font-weight: 600; }
+td.partial { color: #9a6700; }
+code { font-size: 0.9em; }";
+
+/// Render the coverage report as a standalone HTML page.
+///
+/// Lists every file, uncovered first, with its status and covering docs.
+fn render_html(results: &CoverageResults) -> String {
+    let mut rows = String::new();
+    for file in &results.uncovered_files {
+        let suggestion = file
+            .suggested_doc
+            .as_deref()
+            .map(|doc| format!("suggested: <code>{}</code>", escape_html(doc)))
+            .unwrap_or_default();
+        rows.push_str(&format!(
+            "<tr><td><code>{}</code></td><td class=\"uncovered\">uncovered</td><td>{}</td></tr>\n",
+            escape_html(&file.path.display().to_string()),
+            suggestion
+        ));
     }
-
-    #[test]
-    fn test_extract_paths_patterns_with_asterisks() {
-        let content = r#"# Doc
-
-## Paths
-* src/foo.rs
-* src/bar/*.rs
-"#;
-
-        let patterns = extract_paths_patterns(content);
-        assert_eq!(patterns.len(), 2);
-        assert_eq!(patterns[0], "src/foo.rs");
-        assert_eq!(patterns[1], "src/bar/*.rs");
+    for file in &results.partially_covered {
+        let docs: Vec<String> = file
+            .docs
+            .iter()
+            .map(|doc| format!("<code>{}</code>", escape_html(&doc.display().to_string())))
+            .collect();
+        rows.push_str(&format!(
+            "<tr><td><code>{}</code></td><td class=\"partial\">partial: missing {}</td><td>{}</td></tr>\n",
+            escape_html(&file.path.display().to_string()),
+            file.uncovered_ranges.join(", "),
+            docs.join(", ")
+        ));
     }
-
-    #[test]
-    fn test_extract_paths_patterns_empty() {
-        let content = r#"# Doc
-
-## Purpose
-No paths section.
-
-## Examples
-"#;
-
-        let patterns = extract_paths_patterns(content);
-        assert!(patterns.is_empty());
+    for file in &results.covered_files {
+        let docs: Vec<String> = file
+            .docs
+            .iter()
+            .map(|doc| format!("<code>{}</code>", escape_html(&doc.display().to_string())))
+            .collect();
+        rows.push_str(&format!(
+            "<tr><td><code>{}</code></td><td class=\"covered\">covered</td><td>{}</td></tr>\n",
+            escape_html(&file.path.display().to_string()),
+            docs.join(", ")
+        ));
     }
 
-    #[test]
-    fn test_extract_paths_patterns_skips_code_blocks() {
-        let content = r#"# Doc
-
-## Setup
-
-```markdown
-## Paths
-- `should/not/match`
-```
+    let threshold = results
+        .threshold
+        .map(|t| {
+            let status = if results.passed { "pass" } else { "fail" };
+            format!("<p>Threshold: {}% ({})</p>\n", t, status)
+        })
+        .unwrap_or_default();
+
+    format!(
+        "<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<title>Documentation coverage</title>
+<style>
+{style}
+</style>
+</head>
+<body>
+<h1>Documentation coverage: {percentage:.1}%</h1>
+<div class=\"bar\"><div style=\"width: {percentage:.1}%\"></div></div>
+<p>{covered} of {total} files covered, {partial}{uncovered} uncovered.</p>
+{threshold}<table>
+<thead><tr><th>File</th><th>Status</th><th>Docs</th></tr></thead>
+<tbody>
+{rows}</tbody>
+</table>
+</body>
+</html>
+",
+        style = HTML_STYLE,
+        percentage = results.percentage,
+        covered = results.covered,
+        total = results.total,
+        partial = match results.partially_covered.len() {
+            0 => String::new(),
+            n => format!("{} partial, ", n),
+        },
+        uncovered = results.uncovered,
+        threshold = threshold,
+        rows = rows,
+    )
+}
 
-## Paths
-- `src/real/*.rs`
-"#;
+/// Escape text for inclusion in HTML.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
 
-        let patterns = extract_paths_patterns(content);
-        assert_eq!(patterns.len(), 1);
-        assert_eq!(patterns[0], "src/real/*.rs");
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
 
     #[test]
     fn test_matches_any_pattern_exact() {
         let path = PathBuf::from("src/cli.rs");
         let patterns = vec!["src/cli.rs"];
-        assert!(matches_any_pattern(&path, &patterns));
+        assert!(PathsMatcher::new(&patterns).matches(&path));
     }
 
     #[test]
     fn test_matches_any_pattern_glob() {
         let path = PathBuf::from("src/commands/check.rs");
         let patterns = vec!["src/commands/*.rs"];
-        assert!(matches_any_pattern(&path, &patterns));
+        assert!(PathsMatcher::new(&patterns).matches(&path));
     }
 
     #[test]
     fn test_matches_any_pattern_glob_recursive() {
         let path = PathBuf::from("src/commands/sub/deep.rs");
         let patterns = vec!["src/**/*.rs"];
-        assert!(matches_any_pattern(&path, &patterns));
+        assert!(PathsMatcher::new(&patterns).matches(&path));
     }
 
     #[test]
     fn test_matches_any_pattern_no_match() {
         let path = PathBuf::from("tests/test.rs");
         let patterns = vec!["src/*.rs"];
-        assert!(!matches_any_pattern(&path, &patterns));
+        assert!(!PathsMatcher::new(&patterns).matches(&path));
     }
 
     #[test]
     fn test_matches_any_pattern_prefix() {
         let path = PathBuf::from("src/commands/check.rs");
         let patterns = vec!["src/commands/"];
-        assert!(matches_any_pattern(&path, &patterns));
-    }
-
-    #[test]
-    fn test_is_code_file() {
-        assert!(is_code_file(Path::new("src/main.rs")));
-        assert!(is_code_file(Path::new("lib/utils.py")));
-        assert!(is_code_file(Path::new("app/index.ts")));
-        assert!(!is_code_file(Path::new("docs/readme.md")));
-        assert!(!is_code_file(Path::new("config.toml")));
-        assert!(!is_code_file(Path::new("data.json")));
+        assert!(PathsMatcher::new(&patterns).matches(&path));
     }
 
     #[test]
@@ -782,71 +876,6 @@ No paths section.
         assert_eq!(src_stats.total, 2);
     }
 
-    #[test]
-    fn test_analyze_coverage() {
-        let code_files = vec![
-            PathBuf::from("src/cli.rs"),
-            PathBuf::from("src/main.rs"),
-            PathBuf::from("src/utils.rs"),
-        ];
-
-        let doc_mappings = vec![DocMapping {
-            patterns: vec!["src/cli.rs".to_string(), "src/main.rs".to_string()],
-        }];
-
-        let (covered, uncovered) = analyze_coverage(&code_files, &doc_mappings, Path::new("."));
-
-        assert_eq!(covered.len(), 2);
-        assert!(covered.contains(&PathBuf::from("src/cli.rs")));
-        assert!(covered.contains(&PathBuf::from("src/main.rs")));
-        assert_eq!(uncovered.len(), 1);
-        assert!(uncovered.contains(&PathBuf::from("src/utils.rs")));
-    }
-
-    #[test]
-    fn test_parse_doc_mapping() {
-        let temp_dir = TempDir::new().unwrap();
-        let doc_path = temp_dir.path().join("test.md");
-
-        let content = r#"# Test Component
-
-## Purpose
-Test document.
-
-## Paths
-- `src/test.rs`
-- `src/lib/*.rs`
-
-## Examples
-Example content.
-"#;
-        fs::write(&doc_path, content).unwrap();
-
-        let mapping = parse_doc_mapping(&doc_path, temp_dir.path())
-            .unwrap()
-            .unwrap();
-
-        assert_eq!(mapping.patterns.len(), 2);
-        assert_eq!(mapping.patterns[0], "src/test.rs");
-        assert_eq!(mapping.patterns[1], "src/lib/*.rs");
-    }
-
-    #[test]
-    fn test_parse_doc_mapping_no_paths() {
-        let temp_dir = TempDir::new().unwrap();
-        let doc_path = temp_dir.path().join("test.md");
-
-        let content = r#"# Test
-
-## Purpose
-No paths section.
-"#;
-        fs::write(&doc_path, content).unwrap();
-
-        let mapping = parse_doc_mapping(&doc_path, temp_dir.path()).unwrap();
-        assert!(mapping.is_none());
-    }
-
     #[test]
     fn test_generate_suggestions() {
         let uncovered = vec![
@@ -872,37 +901,354 @@ No paths section.
     }
 
     #[test]
-    fn test_load_doc_mappings() {
+    fn test_find_orphans_reports_orphan_doc_and_uncovered_file() {
         let temp_dir = TempDir::new().unwrap();
-        let docs_dir = temp_dir.path().join("docs");
-        fs::create_dir_all(&docs_dir).unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/covered.rs"), "").unwrap();
+        fs::write(root.join("src/uncovered.rs"), "").unwrap();
+
+        fs::write(
+            root.join("docs/live.md"),
+            "# Live\n\n## Paths\n- `src/covered.rs`\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("docs/stale.md"),
+            "# Stale\n\n## Paths\n- `src/removed/`\n",
+        )
+        .unwrap();
+
+        let config = PaveConfig::default();
+        let report = find_orphans(&config, root, true).unwrap();
+
+        assert_eq!(report.orphan_docs, vec![PathBuf::from("docs/stale.md")]);
+        assert_eq!(
+            report.uncovered_files,
+            vec![PathBuf::from("src/uncovered.rs")]
+        );
+        assert!(!report.is_empty());
+    }
 
-        // Create a doc with paths
-        let doc_with_paths = r#"# Component
+    fn coverage_args(threshold: Option<u32>) -> CoverageArgs {
+        CoverageArgs {
+            path: None,
+            format: CoverageOutputFormat::Json,
+            threshold,
+            include: vec![],
+            exclude: vec![],
+            html: None,
+            no_ignore: false,
+            by_extension: false,
+            threshold_ext: vec![],
+            min_docs_per_file: None,
+            config: None,
+        }
+    }
 
-## Paths
-- `src/*.rs`
+    #[test]
+    fn test_compute_results_json_reports_percentage_and_threshold() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        for name in ["a.rs", "b.rs", "c.rs", "d.rs"] {
+            fs::write(root.join("src").join(name), "").unwrap();
+        }
+        fs::write(
+            root.join("docs/core.md"),
+            "# Core\n\n## Paths\n- `src/a.rs`\n- `src/b.rs`\n- `src/c.rs`\n",
+        )
+        .unwrap();
+        let config = PaveConfig::default();
+
+        let results = compute_results(&coverage_args(Some(70)), &config, root).unwrap();
+        let json = serde_json::to_value(&results).unwrap();
+        assert_eq!(json["total"], 4);
+        assert_eq!(json["covered"], 3);
+        assert_eq!(json["uncovered"], 1);
+        assert_eq!(json["percentage"], 75.0);
+        assert_eq!(json["threshold"], 70);
+        assert_eq!(json["passed"], true);
+        assert_eq!(json["uncovered_files"][0]["path"], "src/d.rs");
+
+        let results = compute_results(&coverage_args(Some(80)), &config, root).unwrap();
+        assert!(!results.passed);
+
+        let results = compute_results(&coverage_args(None), &config, root).unwrap();
+        let json = serde_json::to_value(&results).unwrap();
+        assert!(json["threshold"].is_null());
+        assert_eq!(json["passed"], true);
+
+        // mapping.coverage_threshold applies unless --threshold overrides it
+        let mut config = PaveConfig::default();
+        config.mapping.coverage_threshold = Some(80);
+        let results = compute_results(&coverage_args(None), &config, root).unwrap();
+        assert_eq!(results.threshold, Some(80));
+        assert!(!results.passed);
+        let results = compute_results(&coverage_args(Some(70)), &config, root).unwrap();
+        assert!(results.passed);
+    }
 
-## Purpose
-Has paths.
-"#;
-        fs::write(docs_dir.join("component.md"), doc_with_paths).unwrap();
+    #[test]
+    fn test_compute_results_excludes_draft_docs() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        for name in ["a.rs", "b.rs", "c.rs"] {
+            fs::write(root.join("src").join(name), "").unwrap();
+        }
+        fs::write(
+            root.join("docs/core.md"),
+            "# Core\n\n## Paths\n- `src/a.rs`\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("docs/wip.md"),
+            "---\nstatus: draft\n---\n# WIP\n\n## Paths\n- `src/a.rs`\n- `src/b.rs`\n",
+        )
+        .unwrap();
+        let config = PaveConfig::default();
+
+        // src/b.rs is only covered by the draft, so it drops out of the total
+        let results = compute_results(&coverage_args(None), &config, root).unwrap();
+        assert_eq!(results.total, 2);
+        assert_eq!(results.covered, 1);
+        assert_eq!(results.drafts_skipped, 1);
+        assert_eq!(
+            results.covered_files[0].docs,
+            vec![PathBuf::from("docs/core.md")]
+        );
+        assert_eq!(overall_percentage(&config, root).unwrap(), 50.0);
+    }
 
-        // Create a doc without paths
-        let doc_without_paths = r#"# Other
+    #[test]
+    fn test_compute_results_spans_docs_roots() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("docs/components")).unwrap();
+        fs::create_dir_all(root.join("crates/api/docs")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        for name in ["a.rs", "b.rs", "c.rs", "d.rs"] {
+            fs::write(root.join("src").join(name), "").unwrap();
+        }
+        fs::write(
+            root.join("docs/components/a.md"),
+            "# A\n\n## Paths\n- `src/a.rs`\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("crates/api/docs/b.md"),
+            "# B\n\n## Paths\n- `src/b.rs`\n",
+        )
+        .unwrap();
+        let config = PaveConfig::parse(
+            "[pave]\nversion = \"0.1\"\n\n[docs]\nroot = [\"docs\", \"crates/api/docs\", \"docs/components\"]\n",
+        )
+        .unwrap();
+
+        let results = compute_results(&coverage_args(None), &config, root).unwrap();
+        assert_eq!(results.total, 4);
+        assert_eq!(results.covered, 2);
+        assert_eq!(results.percentage, 50.0);
+
+        // docs/components/a.md sits under two roots but is loaded once
+        let mappings = load_doc_mappings(&config.docs.root_dirs(root), root, true).unwrap();
+        assert_eq!(mappings.len(), 2);
+    }
 
-## Purpose
-No paths section.
-"#;
-        fs::write(docs_dir.join("other.md"), doc_without_paths).unwrap();
+    #[test]
+    fn test_compute_results_skips_gitignored_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("docs/vendor")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("gen")).unwrap();
+        fs::write(root.join(".gitignore"), "gen/\ndocs/vendor/\n").unwrap();
+        fs::write(root.join("src/a.rs"), "").unwrap();
+        fs::write(root.join("gen/out.rs"), "").unwrap();
+        fs::write(root.join("docs/a.md"), "# A\n\n## Paths\n- `src/a.rs`\n").unwrap();
+        fs::write(
+            root.join("docs/vendor/gen.md"),
+            "# Gen\n\n## Paths\n- `gen/**`\n",
+        )
+        .unwrap();
+        let config = PaveConfig::default();
+
+        let results = compute_results(&coverage_args(None), &config, root).unwrap();
+        assert_eq!(results.total, 1);
+        assert_eq!(results.covered, 1);
+
+        let mappings = load_doc_mappings(&[root.join("docs")], root, true).unwrap();
+        assert_eq!(mappings.len(), 1);
 
-        // Create an index.md (should be skipped)
-        fs::write(docs_dir.join("index.md"), "# Index").unwrap();
+        // --no-ignore counts the generated file, and the ignored doc covers it
+        let args = CoverageArgs {
+            no_ignore: true,
+            ..coverage_args(None)
+        };
+        let results = compute_results(&args, &config, root).unwrap();
+        assert_eq!(results.total, 2);
+        assert_eq!(results.covered, 2);
+    }
 
-        let mappings = load_doc_mappings(&docs_dir, temp_dir.path()).unwrap();
+    #[test]
+    fn test_by_extension_reports_percentages_and_thresholds() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        for name in ["a.rs", "b.rs", "c.rs", "d.rs", "e.py", "f.py", "g.sql"] {
+            fs::write(root.join("src").join(name), "").unwrap();
+        }
+        fs::write(
+            root.join("docs/core.md"),
+            "# Core\n\n## Paths\n- `src/a.rs`\n- `src/b.rs`\n- `src/c.rs`\n- `src/*.py`\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join(".pave.toml"),
+            "[pave]\nversion = \"0.1\"\n\n[docs]\nroot = \"docs\"\n",
+        )
+        .unwrap();
+
+        let args = CoverageArgs {
+            by_extension: true,
+            ..coverage_args(None)
+        };
+        let results = compute_results(&args, &PaveConfig::default(), root).unwrap();
+        let by_ext: Vec<_> = results
+            .by_extension
+            .iter()
+            .map(|e| (e.extension.as_str(), e.covered, e.total, e.percentage))
+            .collect();
+        assert_eq!(
+            by_ext,
+            vec![("py", 2, 2, 100.0), ("rs", 3, 4, 75.0), ("sql", 0, 1, 0.0)]
+        );
+        assert!(results.by_extension.iter().all(|e| e.passed));
 
-        // Should only include the doc with paths, not the one without or index.md
-        assert_eq!(mappings.len(), 1);
-        assert!(mappings[0].patterns.contains(&"src/*.rs".to_string()));
+        let args = |threshold_ext: &str| CoverageArgs {
+            path: Some(root.to_path_buf()),
+            threshold_ext: vec![parse_extension_threshold(threshold_ext).unwrap()],
+            config: Some(root.join(".pave.toml")),
+            ..coverage_args(Some(50))
+        };
+        let results = compute_results(&args("rs=90"), &PaveConfig::default(), root).unwrap();
+        let rs = &results.by_extension[1];
+        assert_eq!(rs.threshold, Some(90));
+        assert!(!rs.passed);
+        // The overall 71% still meets --threshold 50, but the .rs check fails the run
+        assert!(results.passed);
+        let err = execute(args("rs=90")).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains(".rs files 75.0% is below threshold 90%")
+        );
+        assert!(execute(args(".rs=75")).is_ok());
+    }
+
+    #[test]
+    fn test_min_docs_per_file_reports_files_covered_by_too_few_docs() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/auth.rs"), "").unwrap();
+        fs::write(root.join("src/util.rs"), "").unwrap();
+        fs::write(
+            root.join("docs/overview.md"),
+            "# Overview\n\n## Paths\n- `src/**`\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("docs/auth.md"),
+            "# Auth\n\n## Paths\n- `src/auth.rs`\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join(".pave.toml"),
+            "[pave]\nversion = \"0.1\"\n\n[docs]\nroot = \"docs\"\n",
+        )
+        .unwrap();
+
+        let args = |min| CoverageArgs {
+            path: Some(root.to_path_buf()),
+            min_docs_per_file: Some(min),
+            config: Some(root.join(".pave.toml")),
+            ..coverage_args(None)
+        };
+        let results = compute_results(&args(2), &PaveConfig::default(), root).unwrap();
+        assert_eq!(results.covered, 2);
+        // src/auth.rs has a dedicated doc; src/util.rs only the broad pattern
+        assert_eq!(results.under_documented.len(), 1);
+        assert_eq!(
+            results.under_documented[0].path,
+            PathBuf::from("src/util.rs")
+        );
+        assert_eq!(
+            results.under_documented[0].docs,
+            vec![PathBuf::from("docs/overview.md")]
+        );
+        let err = execute(args(2)).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("1 file is covered by fewer than 2 docs")
+        );
+        assert!(execute(args(1)).is_ok());
+    }
+
+    #[test]
+    fn test_parse_extension_threshold() {
+        assert_eq!(
+            parse_extension_threshold("rs=90"),
+            Ok(("rs".to_string(), 90))
+        );
+        assert_eq!(
+            parse_extension_threshold(".py = 80"),
+            Ok(("py".to_string(), 80))
+        );
+        assert!(parse_extension_threshold("rs").is_err());
+        assert!(parse_extension_threshold("=90").is_err());
+        assert!(parse_extension_threshold("rs=high").is_err());
+    }
+
+    #[test]
+    fn test_html_report_lists_files_and_percentage() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        for name in ["a.rs", "b.rs", "c.rs", "d.rs"] {
+            fs::write(root.join("src").join(name), "").unwrap();
+        }
+        fs::write(
+            root.join("docs/core.md"),
+            "# Core\n\n## Paths\n- `src/a.rs`\n- `src/b.rs`\n- `src/c.rs`\n",
+        )
+        .unwrap();
+        let results = compute_results(&coverage_args(None), &PaveConfig::default(), root).unwrap();
+
+        let path = write_html_report(&results, &root.join("report")).unwrap();
+        assert_eq!(path, root.join("report/index.html"));
+        let html = fs::read_to_string(path).unwrap();
+        assert!(html.contains("Documentation coverage: 75.0%"));
+        assert!(
+            html.contains(
+                "<tr><td><code>src/d.rs</code></td><td class=\"uncovered\">uncovered</td>"
+            )
+        );
+        assert!(html.contains("<code>src/a.rs</code></td><td class=\"covered\">covered</td><td><code>docs/core.md</code>"));
+        assert!(!html.contains("<link") && !html.contains("<script"));
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            escape_html("<a href=\"x\">&</a>"),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;"
+        );
     }
 }
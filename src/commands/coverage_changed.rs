@@ -4,15 +4,14 @@
 //! are covered by documentation patterns defined in the `## Paths` sections of PAVED documents.
 
 use anyhow::{Context, Result};
-use glob::Pattern;
 use serde::Serialize;
-use std::env;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use crate::cli::CoverageOutputFormat;
-use crate::config::{CONFIG_FILENAME, PaveConfig};
-use crate::parser::CodeBlockTracker;
+use crate::config::PaveConfig;
+use crate::parser::ParsedDoc;
+use crate::paths::PathsMatcher;
 
 /// Arguments for the `pave coverage-changed` command.
 pub struct CoverageChangedArgs {
@@ -24,11 +23,15 @@ pub struct CoverageChangedArgs {
     pub include: Vec<String>,
     /// Patterns to exclude (skip these code files).
     pub exclude: Vec<String>,
+    /// Explicit config file path (from `--config`).
+    pub config: Option<PathBuf>,
 }
 
 /// A documentation file with its path mappings.
 #[derive(Debug, Clone)]
 struct DocMapping {
+    /// Path to the doc.
+    doc_path: PathBuf,
     /// Glob patterns for code paths this doc covers.
     patterns: Vec<String>,
 }
@@ -65,10 +68,10 @@ pub struct CoverageChangedResults {
 /// Execute the `pave coverage-changed` command.
 pub fn execute(args: CoverageChangedArgs) -> Result<()> {
     // Find and load config
-    let config_path = find_config()?;
+    let config_path = crate::config::find_config(args.config.as_deref())?;
     let config = PaveConfig::load(&config_path)?;
-    let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
-    let docs_root = config_dir.join(&config.docs.root);
+    let config_dir = &crate::config::config_base_dir(&config_path);
+    let docs_roots = config.docs.resolve_root_dirs(config_dir)?;
 
     // Build exclude patterns (merge config + CLI)
     let mut exclude_patterns = config.mapping.exclude.clone();
@@ -100,20 +103,18 @@ pub fn execute(args: CoverageChangedArgs) -> Result<()> {
     }
 
     // Filter to code files only, applying include/exclude patterns
+    let include = PathsMatcher::new(&args.include);
+    let exclude = PathsMatcher::new(&exclude_patterns);
     let new_code_files: Vec<PathBuf> = added_files
         .into_iter()
         .filter(|p| is_code_file(p))
         .filter(|p| {
             // Check exclusions
-            !matches_any_pattern(p, &exclude_patterns)
+            !exclude.matches(p)
         })
         .filter(|p| {
             // If include patterns specified, file must match at least one
-            if args.include.is_empty() {
-                true
-            } else {
-                matches_any_pattern(p, &args.include)
-            }
+            include.is_empty() || include.matches(p)
         })
         .collect();
 
@@ -139,7 +140,7 @@ pub fn execute(args: CoverageChangedArgs) -> Result<()> {
     }
 
     // Load all doc mappings
-    let doc_mappings = load_doc_mappings(&docs_root)?;
+    let doc_mappings = load_doc_mappings(&docs_roots)?;
 
     // Determine coverage for each new file
     let (covered, uncovered) = analyze_coverage(&new_code_files, &doc_mappings);
@@ -178,27 +179,6 @@ pub fn execute(args: CoverageChangedArgs) -> Result<()> {
     Ok(())
 }
 
-/// Find the .pave.toml config file by walking up from the current directory.
-fn find_config() -> Result<PathBuf> {
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let mut dir = current_dir.as_path();
-
-    loop {
-        let config_path = dir.join(CONFIG_FILENAME);
-        if config_path.exists() {
-            return Ok(config_path);
-        }
-
-        match dir.parent() {
-            Some(parent) => dir = parent,
-            None => anyhow::bail!(
-                "No {} found in current directory or any parent directory",
-                CONFIG_FILENAME
-            ),
-        }
-    }
-}
-
 /// Determine the base ref to compare against.
 fn determine_base_ref(explicit_base: Option<&str>) -> Result<String> {
     if let Some(base) = explicit_base {
@@ -284,10 +264,16 @@ fn is_code_file(path: &Path) -> bool {
         .unwrap_or(false)
 }
 
-/// Load all documentation files with their path mappings.
-fn load_doc_mappings(docs_root: &Path) -> Result<Vec<DocMapping>> {
+/// Load all documentation files under `docs_roots` with their path mappings.
+///
+/// A doc under several roots is loaded once.
+fn load_doc_mappings(docs_roots: &[PathBuf]) -> Result<Vec<DocMapping>> {
     let mut mappings = Vec::new();
-    load_doc_mappings_recursive(docs_root, &mut mappings)?;
+    for docs_root in docs_roots {
+        load_doc_mappings_recursive(docs_root, &mut mappings)?;
+    }
+    mappings.sort_by(|a, b| a.doc_path.cmp(&b.doc_path));
+    mappings.dedup_by(|a, b| a.doc_path == b.doc_path);
     Ok(mappings)
 }
 
@@ -331,78 +317,32 @@ fn parse_doc_mapping(path: &Path) -> Result<Option<DocMapping>> {
     let content = std::fs::read_to_string(path)
         .with_context(|| format!("Failed to read file: {}", path.display()))?;
 
-    let patterns = extract_paths_patterns(&content);
+    let patterns = ParsedDoc::parse_content(path.to_path_buf(), &content)?.paths_patterns();
 
     // Only include docs that have path mappings
     if patterns.is_empty() {
         return Ok(None);
     }
 
-    Ok(Some(DocMapping { patterns }))
-}
-
-/// Extract path patterns from the ## Paths section.
-fn extract_paths_patterns(content: &str) -> Vec<String> {
-    let mut patterns = Vec::new();
-    let mut in_paths_section = false;
-    let mut tracker = CodeBlockTracker::new();
-
-    for line in content.lines() {
-        let trimmed = line.trim();
-
-        // Track code blocks (handles language tags and nested fences)
-        tracker.process_line(trimmed);
-
-        // Skip processing if inside a code block
-        if tracker.in_code_block() {
-            continue;
-        }
-
-        // Check if entering Paths section
-        if trimmed.starts_with("## Paths") {
-            in_paths_section = true;
-            continue;
-        }
-
-        // Check if leaving Paths section (another ## heading)
-        if in_paths_section && trimmed.starts_with("## ") {
-            break;
-        }
-
-        // Collect patterns (lines starting with - or *)
-        if in_paths_section
-            && let Some(pattern) = trimmed
-                .strip_prefix("- ")
-                .or_else(|| trimmed.strip_prefix("* "))
-        {
-            let pattern = pattern.trim();
-            // Remove backticks if present
-            let pattern = pattern.trim_matches('`');
-            if !pattern.is_empty() {
-                patterns.push(pattern.to_string());
-            }
-        }
-    }
-
-    patterns
+    Ok(Some(DocMapping {
+        doc_path: path.to_path_buf(),
+        patterns,
+    }))
 }
 
 /// Analyze coverage of code files against doc patterns.
 fn analyze_coverage(code_files: &[PathBuf], doc_mappings: &[DocMapping]) -> (Vec<PathBuf>, Vec<PathBuf>) {
-    let mut covered = Vec::new();
-    let mut uncovered = Vec::new();
-
-    // Collect all patterns from all docs
-    let all_patterns: Vec<&String> = doc_mappings
+    let matchers: Vec<PathsMatcher> = doc_mappings
         .iter()
-        .flat_map(|d| d.patterns.iter())
+        .map(|doc| PathsMatcher::new(&doc.patterns))
         .collect();
+    let mut covered = Vec::new();
+    let mut uncovered = Vec::new();
 
+    // Evaluate each doc separately so one doc's `!` patterns can't
+    // exclude files another doc covers
     for file in code_files {
-        if matches_any_pattern(
-            file,
-            &all_patterns.iter().map(|s| s.as_str()).collect::<Vec<_>>(),
-        ) {
+        if matchers.iter().any(|m| m.matches(file)) {
             covered.push(file.clone());
         } else {
             uncovered.push(file.clone());
@@ -412,32 +352,6 @@ fn analyze_coverage(code_files: &[PathBuf], doc_mappings: &[DocMapping]) -> (Vec
     (covered, uncovered)
 }
 
-/// Check if a path matches any of the glob patterns.
-fn matches_any_pattern<S: AsRef<str>>(path: &Path, patterns: &[S]) -> bool {
-    let path_str = path.to_string_lossy();
-
-    for pattern_str in patterns {
-        let pattern_str = pattern_str.as_ref();
-
-        // Try to compile as glob pattern
-        if let Ok(pattern) = Pattern::new(pattern_str)
-            && pattern.matches(&path_str)
-        {
-            return true;
-        }
-
-        // Also do simple prefix matching for patterns like "src/foo/"
-        if pattern_str.ends_with('/') || pattern_str.ends_with('*') {
-            let prefix = pattern_str.trim_end_matches('*').trim_end_matches('/');
-            if path_str.starts_with(prefix) {
-                return true;
-            }
-        }
-    }
-
-    false
-}
-
 /// Suggest a documentation file name for a code file.
 fn suggest_doc_name(path: &Path) -> Option<String> {
     path.parent().and_then(|parent| {
@@ -523,109 +437,39 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
-    #[test]
-    fn test_extract_paths_patterns() {
-        let content = r#"# Doc
-
-## Purpose
-Does something.
-
-## Paths
-- `src/commands/*.rs`
-- `src/cli.rs`
-
-## Examples
-Some examples.
-"#;
-
-        let patterns = extract_paths_patterns(content);
-        assert_eq!(patterns.len(), 2);
-        assert_eq!(patterns[0], "src/commands/*.rs");
-        assert_eq!(patterns[1], "src/cli.rs");
-    }
-
-    #[test]
-    fn test_extract_paths_patterns_with_asterisks() {
-        let content = r#"# Doc
-
-## Paths
-* src/foo.rs
-* src/bar/*.rs
-"#;
-
-        let patterns = extract_paths_patterns(content);
-        assert_eq!(patterns.len(), 2);
-        assert_eq!(patterns[0], "src/foo.rs");
-        assert_eq!(patterns[1], "src/bar/*.rs");
-    }
-
-    #[test]
-    fn test_extract_paths_patterns_empty() {
-        let content = r#"# Doc
-
-## Purpose
-No paths section.
-
-## Examples
-"#;
-
-        let patterns = extract_paths_patterns(content);
-        assert!(patterns.is_empty());
-    }
-
-    #[test]
-    fn test_extract_paths_patterns_skips_code_blocks() {
-        let content = r#"# Doc
-
-## Setup
-
-```markdown
-## Paths
-- `should/not/match`
-```
-
-## Paths
-- `src/real/*.rs`
-"#;
-
-        let patterns = extract_paths_patterns(content);
-        assert_eq!(patterns.len(), 1);
-        assert_eq!(patterns[0], "src/real/*.rs");
-    }
-
     #[test]
     fn test_matches_any_pattern_exact() {
         let path = PathBuf::from("src/cli.rs");
         let patterns = vec!["src/cli.rs"];
-        assert!(matches_any_pattern(&path, &patterns));
+        assert!(PathsMatcher::new(&patterns).matches(&path));
     }
 
     #[test]
     fn test_matches_any_pattern_glob() {
         let path = PathBuf::from("src/commands/check.rs");
         let patterns = vec!["src/commands/*.rs"];
-        assert!(matches_any_pattern(&path, &patterns));
+        assert!(PathsMatcher::new(&patterns).matches(&path));
     }
 
     #[test]
     fn test_matches_any_pattern_glob_recursive() {
         let path = PathBuf::from("src/commands/sub/deep.rs");
         let patterns = vec!["src/**/*.rs"];
-        assert!(matches_any_pattern(&path, &patterns));
+        assert!(PathsMatcher::new(&patterns).matches(&path));
     }
 
     #[test]
     fn test_matches_any_pattern_no_match() {
         let path = PathBuf::from("tests/test.rs");
         let patterns = vec!["src/*.rs"];
-        assert!(!matches_any_pattern(&path, &patterns));
+        assert!(!PathsMatcher::new(&patterns).matches(&path));
     }
 
     #[test]
     fn test_matches_any_pattern_prefix() {
         let path = PathBuf::from("src/commands/check.rs");
         let patterns = vec!["src/commands/"];
-        assert!(matches_any_pattern(&path, &patterns));
+        assert!(PathsMatcher::new(&patterns).matches(&path));
     }
 
     #[test]
@@ -647,6 +491,7 @@ No paths section.
         ];
 
         let doc_mappings = vec![DocMapping {
+            doc_path: PathBuf::from("docs/cli.md"),
             patterns: vec!["src/cli.rs".to_string(), "src/main.rs".to_string()],
         }];
 
@@ -736,13 +581,30 @@ No paths section.
         // Create an index.md (should be skipped)
         fs::write(docs_dir.join("index.md"), "# Index").unwrap();
 
-        let mappings = load_doc_mappings(&docs_dir).unwrap();
+        let mappings = load_doc_mappings(&[docs_dir]).unwrap();
 
         // Should only include the doc with paths, not the one without or index.md
         assert_eq!(mappings.len(), 1);
         assert!(mappings[0].patterns.contains(&"src/*.rs".to_string()));
     }
 
+    #[test]
+    fn test_load_doc_mappings_dedupes_overlapping_roots() {
+        let temp_dir = TempDir::new().unwrap();
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(docs_dir.join("api")).unwrap();
+        let doc = "# Component\n\n## Paths\n- `src/*.rs`\n";
+        fs::write(docs_dir.join("api/component.md"), doc).unwrap();
+        fs::write(docs_dir.join("other.md"), doc).unwrap();
+
+        let mappings = load_doc_mappings(&[docs_dir.join("api"), docs_dir.clone()]).unwrap();
+        let paths: Vec<&Path> = mappings.iter().map(|m| m.doc_path.as_path()).collect();
+        assert_eq!(
+            paths,
+            vec![docs_dir.join("api/component.md"), docs_dir.join("other.md")]
+        );
+    }
+
     #[test]
     fn test_parse_git_diff_output() {
         let output = b"src/cli.rs\nsrc/main.rs\ndocs/readme.md\n";
@@ -9,7 +9,6 @@
 use anyhow::{Context, Result};
 use serde::Serialize;
 use std::collections::HashSet;
-use std::env;
 use std::path::{Path, PathBuf};
 
 use crate::cli::OutputFormat;
@@ -23,6 +22,8 @@ pub struct DoctorArgs {
     pub paths: Vec<PathBuf>,
     /// Output format.
     pub format: OutputFormat,
+    /// Explicit config file path (from `--config`).
+    pub config: Option<PathBuf>,
 }
 
 /// Status of a diagnostic check.
@@ -101,38 +102,7 @@ impl DoctorResults {
 
 /// Execute the `pave doctor` command.
 pub fn execute(args: DoctorArgs) -> Result<()> {
-    // Find and load config
-    let config_result = find_config();
-    let mut results = DoctorResults::new();
-
-    // Run configuration checks
-    let config_category = run_config_checks(&config_result);
-    results.add_category(config_category);
-
-    // If config exists and is valid, run further checks
-    if let Ok(ref config_path) = config_result {
-        let config = PaveConfig::load(config_path)?;
-        let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
-
-        // Determine paths to check
-        let paths = if args.paths.is_empty() {
-            vec![config_dir.join(&config.docs.root)]
-        } else {
-            args.paths.clone()
-        };
-
-        // Run documentation structure checks
-        let docs_category = run_docs_checks(&paths, &config, config_dir)?;
-        results.add_category(docs_category);
-
-        // Run verification checks
-        let verify_category = run_verification_checks(&paths, config_dir)?;
-        results.add_category(verify_category);
-
-        // Run code coverage checks
-        let coverage_category = run_coverage_checks(&paths, &config, config_dir)?;
-        results.add_category(coverage_category);
-    }
+    let results = diagnose(&args)?;
 
     // Output results
     match args.format {
@@ -141,6 +111,7 @@ pub fn execute(args: DoctorArgs) -> Result<()> {
         OutputFormat::Github => output_github(&results),
     }
 
+    // Only hard failures (errors) fail the command; warnings are advisory
     if results.is_healthy() {
         Ok(())
     } else {
@@ -154,29 +125,89 @@ pub fn execute(args: DoctorArgs) -> Result<()> {
     }
 }
 
-/// Find the .pave.toml config file by walking up from the current directory.
-fn find_config() -> Result<PathBuf> {
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let mut dir = current_dir.as_path();
+/// Run every diagnostic and collect the results.
+fn diagnose(args: &DoctorArgs) -> Result<DoctorResults> {
+    let config_result = crate::config::find_config(args.config.as_deref());
+    let mut results = DoctorResults::new();
 
-    loop {
-        let config_path = dir.join(CONFIG_FILENAME);
-        if config_path.exists() {
-            return Ok(config_path);
-        }
+    // Run configuration checks
+    let config_category = run_config_checks(&config_result);
+    results.add_category(config_category);
+
+    // Run environment checks
+    results.add_category(run_environment_checks(git_available()));
+
+    // If config exists and is valid, run further checks; an invalid config
+    // has already been reported above
+    let Ok(config_path) = config_result else {
+        return Ok(results);
+    };
+    let Ok(config) = PaveConfig::load(&config_path) else {
+        return Ok(results);
+    };
+    let config_dir = &crate::config::config_base_dir(&config_path);
+
+    // Determine paths to check
+    let paths = if args.paths.is_empty() {
+        config.docs.root_dirs(config_dir)
+    } else {
+        args.paths.clone()
+    };
+
+    // Run documentation structure checks
+    let docs_category = run_docs_checks(&paths, &config, config_dir)?;
+    results.add_category(docs_category);
+
+    // Run verification checks
+    let verify_category = run_verification_checks(&paths, config_dir)?;
+    results.add_category(verify_category);
 
-        match dir.parent() {
-            Some(parent) => dir = parent,
-            None => anyhow::bail!(
-                "No {} found in current directory or any parent directory",
-                CONFIG_FILENAME
+    // Run code coverage checks
+    let coverage_category = run_coverage_checks(&paths, &config, config_dir)?;
+    results.add_category(coverage_category);
+
+    Ok(results)
+}
+
+/// Returns true if a `git` executable can be run.
+fn git_available() -> bool {
+    std::process::Command::new("git")
+        .arg("--version")
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+/// Run checks on the tools pave depends on.
+fn run_environment_checks(git_available: bool) -> DiagnosticCategory {
+    let check = if git_available {
+        DiagnosticCheck {
+            name: "Git available".to_string(),
+            status: CheckStatus::Pass,
+            message: "git is installed".to_string(),
+            suggestion: None,
+            affected_files: vec![],
+        }
+    } else {
+        // Only --changed and hooks need git, so this is not a hard failure
+        DiagnosticCheck {
+            name: "Git available".to_string(),
+            status: CheckStatus::Warning,
+            message: "git was not found on PATH".to_string(),
+            suggestion: Some(
+                "Install git to use --changed, `pave changed`, and `pave hooks`".to_string(),
             ),
+            affected_files: vec![],
         }
+    };
+
+    DiagnosticCategory {
+        name: "Environment".to_string(),
+        checks: vec![check],
     }
 }
 
 /// Run configuration health checks.
-fn run_config_checks(config_result: &Result<PathBuf>) -> DiagnosticCategory {
+fn run_config_checks(config_result: &crate::Result<PathBuf>) -> DiagnosticCategory {
     let mut checks = Vec::new();
 
     // Check if config file exists
@@ -228,31 +259,31 @@ fn run_config_checks(config_result: &Result<PathBuf>) -> DiagnosticCategory {
                         });
                     }
 
-                    // Check docs root exists
-                    let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
-                    let docs_root = config_dir.join(&config.docs.root);
-                    if !docs_root.exists() {
-                        checks.push(DiagnosticCheck {
-                            name: "Docs root exists".to_string(),
-                            status: CheckStatus::Error,
-                            message: format!(
-                                "Docs root '{}' does not exist",
-                                config.docs.root.display()
-                            ),
-                            suggestion: Some(format!(
-                                "Create the docs directory or update docs.root in {}",
-                                CONFIG_FILENAME
-                            )),
-                            affected_files: vec![],
-                        });
-                    } else {
-                        checks.push(DiagnosticCheck {
-                            name: "Docs root exists".to_string(),
-                            status: CheckStatus::Pass,
-                            message: format!("Docs root '{}' exists", config.docs.root.display()),
-                            suggestion: None,
-                            affected_files: vec![],
-                        });
+                    // Check every docs root exists
+                    let config_dir = &crate::config::config_base_dir(config_path);
+                    for root in config.docs.roots() {
+                        if let Err(e) =
+                            crate::config::DocsSection::resolve_root_dir(root, config_dir)
+                        {
+                            checks.push(DiagnosticCheck {
+                                name: "Docs root exists".to_string(),
+                                status: CheckStatus::Error,
+                                message: e.to_string(),
+                                suggestion: Some(format!(
+                                    "Create the docs directory or update docs.root in {}",
+                                    CONFIG_FILENAME
+                                )),
+                                affected_files: vec![],
+                            });
+                        } else {
+                            checks.push(DiagnosticCheck {
+                                name: "Docs root exists".to_string(),
+                                status: CheckStatus::Pass,
+                                message: format!("Docs root '{}' exists", root.display()),
+                                suggestion: None,
+                                affected_files: vec![],
+                            });
+                        }
                     }
 
                     // Check templates directory if configured
@@ -319,6 +350,7 @@ fn find_markdown_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
     }
 
     files.sort();
+    files.dedup();
     Ok(files)
 }
 
@@ -390,23 +422,66 @@ fn run_docs_checks(
     let mut missing_verification = Vec::new();
     let mut missing_examples = Vec::new();
     let mut exceeds_line_limit = Vec::new();
+    let mut parse_errors = Vec::new();
 
     for file in &validatable_files {
-        if let Ok(doc) = ParsedDoc::parse(file) {
-            if config.rules.require_verification && !doc.has_section("Verification") {
-                missing_verification.push((*file).clone());
+        let doc = match ParsedDoc::parse(file) {
+            Ok(doc) => {
+                if let Some(issue) = &doc.frontmatter_issue {
+                    parse_errors.push((
+                        (*file).clone(),
+                        format!(
+                            "{}:{}: invalid frontmatter: {}",
+                            file.display(),
+                            issue.line,
+                            issue.message
+                        ),
+                    ));
+                }
+                doc
             }
-
-            if config.rules.require_examples && !doc.has_section("Examples") {
-                missing_examples.push((*file).clone());
+            Err(err) => {
+                parse_errors.push(((*file).clone(), err.to_string()));
+                continue;
             }
+        };
+        if config.rules.require_verification && !doc.has_section("Verification") {
+            missing_verification.push((*file).clone());
+        }
 
-            if doc.line_count > config.rules.max_lines as usize {
-                exceeds_line_limit.push(((*file).clone(), doc.line_count));
-            }
+        if config.rules.require_examples && !doc.has_section("Examples") {
+            missing_examples.push((*file).clone());
+        }
+
+        if doc.line_count > config.rules.max_lines as usize {
+            exceeds_line_limit.push(((*file).clone(), doc.line_count));
         }
     }
 
+    // Report documents that failed to parse
+    if parse_errors.is_empty() {
+        checks.push(DiagnosticCheck {
+            name: "Document parsing".to_string(),
+            status: CheckStatus::Pass,
+            message: "All documents parse".to_string(),
+            suggestion: None,
+            affected_files: vec![],
+        });
+    } else {
+        let details: Vec<&str> = parse_errors.iter().map(|(_, e)| e.as_str()).collect();
+        checks.push(DiagnosticCheck {
+            name: "Document parsing".to_string(),
+            status: CheckStatus::Error,
+            message: format!(
+                "{} document(s) failed to parse: {}",
+                parse_errors.len(),
+                details.join("; ")
+            ),
+            suggestion: Some("Fix the frontmatter or markdown at the reported line".to_string()),
+            affected_files: parse_errors.into_iter().map(|(p, _)| p).collect(),
+        });
+    }
+
     // Report missing Verification sections
     if missing_verification.is_empty() {
         if config.rules.require_verification {
@@ -935,7 +1010,7 @@ Missing required sections.
 
     #[test]
     fn config_check_reports_missing_config() {
-        let category = run_config_checks(&Err(anyhow::anyhow!("No config")));
+        let category = run_config_checks(&Err(crate::Error::ConfigNotFound { path: None }));
 
         assert!(!category.checks.is_empty());
         assert!(
@@ -1161,4 +1236,82 @@ Example here.
                 .any(|c| c.name == "Code-to-doc mapping" && c.status == CheckStatus::Warning)
         );
     }
+
+    fn find_check<'a>(results: &'a DoctorResults, name: &str) -> Option<&'a DiagnosticCheck> {
+        results
+            .categories
+            .iter()
+            .flat_map(|c| &c.checks)
+            .find(|c| c.name == name)
+    }
+
+    fn doctor_args(config_path: PathBuf) -> DoctorArgs {
+        DoctorArgs {
+            paths: vec![],
+            format: OutputFormat::Text,
+            config: Some(config_path),
+        }
+    }
+
+    #[test]
+    fn diagnose_flags_missing_docs_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = create_test_config(&temp_dir);
+
+        let results = diagnose(&doctor_args(config_path)).unwrap();
+
+        let check = find_check(&results, "Docs root exists").unwrap();
+        assert_eq!(check.status, CheckStatus::Error);
+        assert!(check.suggestion.as_ref().unwrap().contains("docs.root"));
+        assert!(!results.is_healthy());
+    }
+
+    #[test]
+    fn diagnose_flags_malformed_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join(".pave.toml");
+        fs::write(&config_path, "[pave\nversion = ").unwrap();
+
+        let results = diagnose(&doctor_args(config_path)).unwrap();
+
+        let check = find_check(&results, "Config file valid").unwrap();
+        assert_eq!(check.status, CheckStatus::Error);
+        assert!(check.message.contains("Failed to parse"));
+        // Nothing past the config can be checked without a valid config
+        assert!(find_check(&results, "Docs root exists").is_none());
+        assert!(!results.is_healthy());
+    }
+
+    #[test]
+    fn docs_check_reports_parse_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = create_test_config(&temp_dir);
+        let config = PaveConfig::load(&config_path).unwrap();
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(&docs_dir).unwrap();
+        fs::write(
+            docs_dir.join("broken.md"),
+            "---\ntitle: [unclosed\n---\n# Broken\n",
+        )
+        .unwrap();
+
+        let category = run_docs_checks(&[docs_dir], &config, temp_dir.path()).unwrap();
+
+        let check = category
+            .checks
+            .iter()
+            .find(|c| c.name == "Document parsing")
+            .unwrap();
+        assert_eq!(check.status, CheckStatus::Error);
+        assert!(check.affected_files[0].ends_with("broken.md"));
+    }
+
+    #[test]
+    fn environment_check_warns_without_git() {
+        let category = run_environment_checks(false);
+        assert_eq!(category.checks[0].status, CheckStatus::Warning);
+
+        let category = run_environment_checks(true);
+        assert_eq!(category.checks[0].status, CheckStatus::Pass);
+    }
 }
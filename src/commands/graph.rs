@@ -0,0 +1,262 @@
+//! Implementation of the `pave graph` command for drawing which docs cover
+//! which source files.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use crate::cli::GraphOutputFormat;
+use crate::commands::changed;
+use crate::config::PaveConfig;
+use crate::paths::PathsMatcher;
+
+/// Arguments for the `pave graph` command.
+pub struct GraphArgs {
+    /// Output format.
+    pub format: GraphOutputFormat,
+    /// Collapse source files into their parent directories.
+    pub dirs: bool,
+    /// Explicit config file path (from `--config`).
+    pub config: Option<PathBuf>,
+}
+
+/// What a graph node stands for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeKind {
+    /// A document with a `## Paths` section.
+    Doc,
+    /// A source file matched by some doc.
+    File,
+    /// A directory holding matched source files (with `--dirs`).
+    Dir,
+}
+
+/// A doc or source node, identified by its path relative to the project root.
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphNode {
+    /// Path relative to the project root, with `/` separators.
+    pub id: String,
+    /// What the node stands for.
+    pub kind: NodeKind,
+    /// Text to draw: a doc's title, otherwise the path.
+    pub label: String,
+}
+
+/// A doc covering a source node through its `## Paths`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct GraphEdge {
+    /// Id of the doc node.
+    pub from: String,
+    /// Id of the source node.
+    pub to: String,
+}
+
+/// Docs, the source they cover, and the coverage edges between them.
+#[derive(Debug, Serialize)]
+pub struct DocGraph {
+    /// Doc nodes sorted by path, then source nodes sorted by path.
+    pub nodes: Vec<GraphNode>,
+    /// Edges sorted by doc, then source.
+    pub edges: Vec<GraphEdge>,
+}
+
+/// Execute the `pave graph` command.
+pub fn execute(args: GraphArgs) -> Result<()> {
+    let config_path = crate::config::find_config(args.config.as_deref())?;
+    let config = PaveConfig::load(&config_path)?;
+    let config_dir = &crate::config::config_base_dir(&config_path);
+
+    let graph = build_graph(&config, config_dir, args.dirs)?;
+
+    match args.format {
+        GraphOutputFormat::Dot => print!("{}", render_dot(&graph)),
+        GraphOutputFormat::Mermaid => print!("{}", render_mermaid(&graph)),
+        GraphOutputFormat::Json => {
+            let json = serde_json::to_string_pretty(&graph).context("Failed to serialize graph")?;
+            println!("{}", json);
+        }
+    }
+    Ok(())
+}
+
+/// Build the graph for the project rooted at `config_dir`.
+///
+/// Docs without `## Paths` are left out. With `dirs`, each matched file is
+/// replaced by its parent directory (`.` for files at the root).
+fn build_graph(config: &PaveConfig, config_dir: &Path, dirs: bool) -> Result<DocGraph> {
+    let docs_roots = config.docs.resolve_root_dirs(config_dir)?;
+    let mappings = changed::load_doc_mappings(&docs_roots, config_dir)?;
+
+    let mut nodes = Vec::new();
+    let mut sources = BTreeSet::new();
+    let mut edges = BTreeSet::new();
+    for doc in &mappings {
+        let id = slash_path(&doc.doc_path);
+        for file in PathsMatcher::new(&doc.patterns).matched_files(config_dir) {
+            let target = if dirs {
+                match file.parent().map(slash_path) {
+                    Some(parent) if !parent.is_empty() => parent,
+                    _ => ".".to_string(),
+                }
+            } else {
+                slash_path(&file)
+            };
+            edges.insert(GraphEdge {
+                from: id.clone(),
+                to: target.clone(),
+            });
+            sources.insert(target);
+        }
+        nodes.push(GraphNode {
+            label: doc.title.clone().unwrap_or_else(|| id.clone()),
+            id,
+            kind: NodeKind::Doc,
+        });
+    }
+
+    let kind = if dirs { NodeKind::Dir } else { NodeKind::File };
+    nodes.extend(sources.into_iter().map(|id| GraphNode {
+        label: id.clone(),
+        id,
+        kind,
+    }));
+
+    Ok(DocGraph {
+        nodes,
+        edges: edges.into_iter().collect(),
+    })
+}
+
+/// A path as a string with `/` separators.
+fn slash_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// Render the graph in Graphviz DOT, with docs as notes and source as boxes.
+fn render_dot(graph: &DocGraph) -> String {
+    let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+
+    let mut out = String::from("digraph pave {\n    rankdir=LR;\n");
+    for node in &graph.nodes {
+        let shape = match node.kind {
+            NodeKind::Doc => "note",
+            NodeKind::File => "box",
+            NodeKind::Dir => "folder",
+        };
+        out.push_str(&format!(
+            "    {} [label={}, shape={}];\n",
+            quote(&node.id),
+            quote(&node.label),
+            shape
+        ));
+    }
+    for edge in &graph.edges {
+        out.push_str(&format!(
+            "    {} -> {};\n",
+            quote(&edge.from),
+            quote(&edge.to)
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Render the graph as a Mermaid flowchart.
+///
+/// Mermaid ids can't hold paths, so nodes are numbered in order and the
+/// path goes in the label.
+fn render_mermaid(graph: &DocGraph) -> String {
+    let index = |id: &str| graph.nodes.iter().position(|n| n.id == id).unwrap_or(0);
+
+    let mut out = String::from("flowchart LR\n");
+    for (i, node) in graph.nodes.iter().enumerate() {
+        let label = node.label.replace('"', "#quot;");
+        let shape = match node.kind {
+            NodeKind::Doc => format!("n{}[\"{}\"]", i, label),
+            NodeKind::File | NodeKind::Dir => format!("n{}([\"{}\"])", i, label),
+        };
+        out.push_str(&format!("    {}\n", shape));
+    }
+    for edge in &graph.edges {
+        out.push_str(&format!(
+            "    n{} --> n{}\n",
+            index(&edge.from),
+            index(&edge.to)
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn fixture() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("docs/components")).unwrap();
+        fs::create_dir_all(root.join("src/auth")).unwrap();
+        fs::write(root.join("src/auth/login.rs"), "").unwrap();
+        fs::write(root.join("src/auth/session.rs"), "").unwrap();
+        fs::write(root.join("src/db.rs"), "").unwrap();
+        fs::write(
+            root.join("docs/components/auth.md"),
+            "# Auth Service\n\n## Paths\n- `src/auth/`\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("docs/components/db.md"),
+            "# Database\n\n## Paths\n- `src/db.rs`\n",
+        )
+        .unwrap();
+        fs::write(root.join("docs/components/notes.md"), "# Notes\n").unwrap();
+        temp_dir
+    }
+
+    #[test]
+    fn dot_output_links_docs_to_matched_files() {
+        let temp_dir = fixture();
+        let graph = build_graph(&PaveConfig::default(), temp_dir.path(), false).unwrap();
+        let dot = render_dot(&graph);
+
+        assert!(dot.starts_with("digraph pave {\n"));
+        assert!(
+            dot.contains("    \"docs/components/auth.md\" [label=\"Auth Service\", shape=note];\n")
+        );
+        assert!(dot.contains("    \"docs/components/auth.md\" -> \"src/auth/login.rs\";\n"));
+        assert!(dot.contains("    \"docs/components/auth.md\" -> \"src/auth/session.rs\";\n"));
+        assert!(dot.contains("    \"docs/components/db.md\" -> \"src/db.rs\";\n"));
+        assert!(!dot.contains("\"docs/components/auth.md\" -> \"src/db.rs\""));
+        assert!(!dot.contains("notes.md"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn dirs_collapse_files_and_mermaid_numbers_nodes() {
+        let temp_dir = fixture();
+        let graph = build_graph(&PaveConfig::default(), temp_dir.path(), true).unwrap();
+
+        let ids: Vec<&str> = graph.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "docs/components/auth.md",
+                "docs/components/db.md",
+                "src",
+                "src/auth"
+            ]
+        );
+        assert_eq!(graph.edges.len(), 2);
+
+        let mermaid = render_mermaid(&graph);
+        assert!(mermaid.starts_with("flowchart LR\n"));
+        assert!(mermaid.contains("    n0[\"Auth Service\"]\n"));
+        assert!(mermaid.contains("    n3([\"src/auth\"])\n"));
+        assert!(mermaid.contains("    n0 --> n3\n"));
+        assert!(mermaid.contains("    n1 --> n2\n"));
+    }
+}
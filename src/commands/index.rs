@@ -8,8 +8,6 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::config::{CONFIG_FILENAME, PaveConfig};
-
 /// Document type detected from content or path.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DocType {
@@ -48,26 +46,66 @@ pub struct DocInfo {
 const CUSTOM_SECTION_START: &str = "<!-- CUSTOM CONTENT START -->";
 const CUSTOM_SECTION_END: &str = "<!-- CUSTOM CONTENT END -->";
 
+/// What [`refresh_index`] did with the index file.
+#[derive(Debug, PartialEq, Eq)]
+enum IndexStatus {
+    /// The docs root has no documents, so nothing was generated.
+    NoDocs,
+    /// The file already had the generated content and was left alone.
+    UpToDate,
+    /// The file was (re)written with this many documents indexed.
+    Written(usize),
+}
+
 /// Run the index command.
-pub fn run(output: &Path, update: bool) -> Result<()> {
+///
+/// The index is only written when its content changes. With `check`, nothing
+/// is written and a stale index is an error. `output` defaults to
+/// `index.md` in the primary docs root.
+pub fn run(output: Option<&Path>, update: bool, check: bool, config: Option<&Path>) -> Result<()> {
     // Find and load config
-    let config = load_config()?;
-    let docs_root = &config.docs.root;
+    let (config, config_dir) = crate::config::load_project(config)?;
+    let docs_roots = config.docs.resolve_root_dirs(&config_dir)?;
+    let output = match output {
+        Some(output) => output.to_path_buf(),
+        None => docs_roots[0].join("index.md"),
+    };
+    let output = output.as_path();
 
-    // Check if docs directory exists
-    if !docs_root.exists() {
-        anyhow::bail!(
-            "documentation directory '{}' does not exist",
-            docs_root.display()
-        );
+    match refresh_index(&docs_roots, output, update, check)? {
+        IndexStatus::NoDocs => {
+            let roots: Vec<String> = docs_roots
+                .iter()
+                .map(|root| format!("'{}'", root.display()))
+                .collect();
+            println!("No documentation files found in {}", roots.join(", "));
+        }
+        IndexStatus::UpToDate => {
+            println!("Index at {} is up to date", output.display());
+        }
+        IndexStatus::Written(count) => {
+            println!("Generated index at: {}", output.display());
+            println!("  - {} documents indexed", count);
+        }
     }
 
+    Ok(())
+}
+
+/// Regenerate the index for `docs_roots` at `output` if its content changed.
+///
+/// Links are relative to the first root, where the index normally lives.
+fn refresh_index(
+    docs_roots: &[PathBuf],
+    output: &Path,
+    update: bool,
+    check: bool,
+) -> Result<IndexStatus> {
     // Scan for markdown files
-    let docs = scan_docs(docs_root)?;
+    let docs = scan_roots(docs_roots)?;
 
     if docs.is_empty() {
-        println!("No documentation files found in '{}'", docs_root.display());
-        return Ok(());
+        return Ok(IndexStatus::NoDocs);
     }
 
     // Load existing custom content if updating
@@ -80,6 +118,18 @@ pub fn run(output: &Path, update: bool) -> Result<()> {
     // Generate the index document
     let index_content = generate_index(&docs, custom_content.as_deref())?;
 
+    // Leave an unchanged index alone to avoid noisy rewrites
+    if fs::read_to_string(output).is_ok_and(|existing| existing == index_content) {
+        return Ok(IndexStatus::UpToDate);
+    }
+
+    if check {
+        anyhow::bail!(
+            "index at {} is out of date; run `pave index` to regenerate it",
+            output.display()
+        );
+    }
+
     // Ensure parent directory exists
     if let Some(parent) = output.parent() {
         fs::create_dir_all(parent)
@@ -90,38 +140,48 @@ pub fn run(output: &Path, update: bool) -> Result<()> {
     fs::write(output, &index_content)
         .with_context(|| format!("failed to write index file: {}", output.display()))?;
 
-    println!("Generated index at: {}", output.display());
-    println!("  - {} documents indexed", docs.len());
-
-    Ok(())
+    Ok(IndexStatus::Written(docs.len()))
 }
 
-/// Load pave configuration from current directory or parents.
-fn load_config() -> Result<PaveConfig> {
-    let cwd = std::env::current_dir().context("failed to get current directory")?;
-
-    // Search for config file in current directory and parents
-    let mut search_path = cwd.as_path();
-    loop {
-        let config_path = search_path.join(CONFIG_FILENAME);
-        if config_path.exists() {
-            return PaveConfig::load(&config_path);
-        }
-
-        match search_path.parent() {
-            Some(parent) => search_path = parent,
-            None => break,
+/// Scan every docs root for markdown files, with paths relative to the
+/// first root.
+///
+/// A doc under several roots is listed once.
+fn scan_roots(docs_roots: &[PathBuf]) -> Result<Vec<DocInfo>> {
+    let Some(primary) = docs_roots.first() else {
+        return Ok(Vec::new());
+    };
+    let mut docs = Vec::new();
+    for docs_root in docs_roots {
+        for mut doc in scan_docs(docs_root)? {
+            doc.path = relative_to(&docs_root.join(&doc.path), primary);
+            docs.push(doc);
         }
     }
+    docs.sort_by(|a, b| a.path.cmp(&b.path));
+    docs.dedup_by(|a, b| a.path == b.path);
+    Ok(docs)
+}
 
-    // No config found, use defaults
-    Ok(PaveConfig::default())
+/// Lexical path from `base` to `path`, such as `../crates/api/docs/a.md`.
+fn relative_to(path: &Path, base: &Path) -> PathBuf {
+    let path: Vec<_> = path.components().collect();
+    let base: Vec<_> = base.components().collect();
+    let common = path.iter().zip(&base).take_while(|(a, b)| a == b).count();
+    let mut relative = PathBuf::new();
+    for _ in common..base.len() {
+        relative.push("..");
+    }
+    relative.extend(&path[common..]);
+    relative
 }
 
 /// Scan the docs directory for markdown files.
 fn scan_docs(docs_root: &Path) -> Result<Vec<DocInfo>> {
     let mut docs = Vec::new();
     scan_docs_recursive(docs_root, docs_root, &mut docs)?;
+    // Directory order varies between runs; keep the output stable
+    docs.sort_by(|a, b| a.path.cmp(&b.path));
     Ok(docs)
 }
 
@@ -320,7 +380,7 @@ fn generate_index(docs: &[DocInfo], custom_content: Option<&str>) -> Result<Stri
 
     // Sort documents within each group by title
     for docs_in_group in grouped.values_mut() {
-        docs_in_group.sort_by(|a, b| a.title.to_lowercase().cmp(&b.title.to_lowercase()));
+        docs_in_group.sort_by_key(|d| d.title.to_lowercase());
     }
 
     // Identify top-level docs for Quick Links
@@ -337,7 +397,7 @@ fn generate_index(docs: &[DocInfo], custom_content: Option<&str>) -> Result<Stri
             .iter()
             .filter(|d| top_level_paths.contains(&d.path))
             .collect();
-        top_level.sort_by(|a, b| a.title.to_lowercase().cmp(&b.title.to_lowercase()));
+        top_level.sort_by_key(|d| d.title.to_lowercase());
         for doc in top_level {
             output.push_str(&format!("- [{}](./{})\n", doc.title, doc.path.display()));
         }
@@ -582,6 +642,99 @@ mod tests {
         assert_eq!(deploy_doc.doc_type, DocType::Runbook);
     }
 
+    #[test]
+    fn test_index_spans_every_root_once() {
+        let dir = TempDir::new().unwrap();
+        let docs = dir.path().join("docs");
+        let api_docs = dir.path().join("crates/api/docs");
+        create_test_doc(&docs, "components/auth.md", "# Auth\n");
+        create_test_doc(&api_docs, "components/api.md", "# API\n");
+
+        // `docs/components` overlaps `docs`, so auth.md is found twice
+        let roots = [docs.clone(), api_docs, docs.join("components")];
+        let paths: Vec<PathBuf> = scan_roots(&roots)
+            .unwrap()
+            .into_iter()
+            .map(|doc| doc.path)
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("../crates/api/docs/components/api.md"),
+                PathBuf::from("components/auth.md"),
+            ]
+        );
+
+        let output = docs.join("index.md");
+        assert_eq!(
+            refresh_index(&roots, &output, false, false).unwrap(),
+            IndexStatus::Written(2)
+        );
+    }
+
+    #[test]
+    fn test_index_resolves_roots_against_the_config_from_a_subdirectory() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        fs::create_dir(root.join(".git")).unwrap();
+        fs::write(
+            root.join(".pave.toml"),
+            "[pave]\nversion = \"0.1\"\n\n[docs]\nroot = \"docs\"\n",
+        )
+        .unwrap();
+        create_test_doc(&root.join("docs"), "components/auth.md", "# Auth\n");
+        fs::create_dir_all(root.join("src/nested")).unwrap();
+
+        // The config found from a subdirectory, with the tests' cwd elsewhere
+        let config = crate::config::find_config_from(&root.join("src/nested")).unwrap();
+        run(None, false, false, Some(&config)).unwrap();
+
+        let index = fs::read_to_string(root.join("docs/index.md")).unwrap();
+        assert!(index.contains("[Auth](./components/auth.md)"));
+        assert!(!root.join("src/nested/docs").exists());
+    }
+
+    #[test]
+    fn test_refresh_index_writes_only_on_change() {
+        let dir = TempDir::new().unwrap();
+        let docs_root = dir.path();
+        let output = docs_root.join("index.md");
+        create_test_doc(
+            docs_root,
+            "components/auth.md",
+            "# Auth Service\n\n## Purpose\n\nHandles authentication.\n",
+        );
+
+        assert_eq!(
+            refresh_index(&[docs_root.to_path_buf()], &output, false, false).unwrap(),
+            IndexStatus::Written(1)
+        );
+        assert_eq!(
+            refresh_index(&[docs_root.to_path_buf()], &output, false, false).unwrap(),
+            IndexStatus::UpToDate
+        );
+        assert_eq!(
+            refresh_index(&[docs_root.to_path_buf()], &output, false, true).unwrap(),
+            IndexStatus::UpToDate
+        );
+
+        // A new doc makes the index stale; --check reports it without writing
+        create_test_doc(
+            docs_root,
+            "runbooks/deploy.md",
+            "# Deploy Guide\n\n## When to Use\n\nWhen deploying.\n",
+        );
+        let before = fs::read_to_string(&output).unwrap();
+        let err = refresh_index(&[docs_root.to_path_buf()], &output, false, true).unwrap_err();
+        assert!(err.to_string().contains("out of date"));
+        assert_eq!(fs::read_to_string(&output).unwrap(), before);
+
+        assert_eq!(
+            refresh_index(&[docs_root.to_path_buf()], &output, false, false).unwrap(),
+            IndexStatus::Written(2)
+        );
+    }
+
     #[test]
     fn test_links_are_valid_relative_paths() {
         let docs = vec![
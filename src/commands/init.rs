@@ -4,28 +4,75 @@ use anyhow::{Context, Result, bail};
 use std::fs;
 use std::path::Path;
 
-use crate::cli::HookType;
+use crate::cli::{HookType, InitPreset};
 use crate::commands::hooks;
 use crate::templates::{TemplateType, get_template};
 
-/// Default content for the .pave.toml configuration file.
-fn default_config(docs_root: &str) -> String {
-    format!(
+/// Extra documentation root created by the `monorepo` preset.
+const MONOREPO_EXTRA_ROOT: &str = "packages/docs";
+
+/// Content for the .pave.toml configuration file, tailored to `preset`.
+fn default_config(docs_root: &str, preset: Option<InitPreset>) -> String {
+    let root = match preset {
+        Some(InitPreset::Monorepo) => format!(
+            r#"# Documentation roots; the first holds new docs, the index, and templates.
+# Add one entry per package that keeps its own docs.
+root = ["{docs_root}", "{MONOREPO_EXTRA_ROOT}"]"#
+        ),
+        _ => format!(
+            r#"# Root directory for documentation
+root = "{docs_root}""#
+        ),
+    };
+    let mut config = format!(
         r#"# Paver configuration file
 # See https://github.com/tessro/pave for documentation
 
+[pave]
+version = "0.1"
+
 [docs]
-# Root directory for documentation
-root = "{docs_root}"
+{root}
 
 # Directory containing document templates
 templates = "{docs_root}/templates"
-
-[validation]
-# Enable strict validation mode
-strict = false
 "#
-    )
+    );
+    let extra = match preset {
+        None => "",
+        Some(InitPreset::Minimal) => {
+            r#"
+[rules]
+# Structure checks only: no Verification or Examples sections required
+require_verification = false
+require_examples = false
+require_verification_commands = false
+"#
+        }
+        Some(InitPreset::Strict) => {
+            r#"
+[rules]
+require_verification = true
+require_examples = true
+require_verification_commands = true
+validate_paths = true
+warn_empty_paths = true
+section_casing = true
+
+[mapping]
+# Minimum percentage of code files covered by docs (pave coverage)
+coverage_threshold = 80
+"#
+        }
+        Some(InitPreset::Monorepo) => {
+            r#"
+[mapping]
+exclude = ["node_modules/", "target/", "vendor/", "dist/"]
+"#
+        }
+    };
+    config.push_str(extra);
+    config
 }
 
 /// Returns the content for the index.md file.
@@ -41,6 +88,8 @@ pub struct InitArgs {
     pub skip_hooks: bool,
     /// Overwrite existing files
     pub force: bool,
+    /// Tailored config defaults to write (plain defaults if None)
+    pub preset: Option<InitPreset>,
     /// Working directory (for testing; uses current dir if None)
     pub working_dir: Option<std::path::PathBuf>,
 }
@@ -51,6 +100,7 @@ impl Default for InitArgs {
             docs_root: "docs".to_string(),
             skip_hooks: false,
             force: false,
+            preset: None,
             working_dir: None,
         }
     }
@@ -80,8 +130,15 @@ pub fn run(args: InitArgs) -> Result<()> {
         )
     })?;
 
+    // The monorepo preset lists a second root, which must exist to be scanned
+    if args.preset == Some(InitPreset::Monorepo) {
+        let extra_root = base.join(MONOREPO_EXTRA_ROOT);
+        fs::create_dir_all(&extra_root)
+            .with_context(|| format!("Failed to create {}", extra_root.display()))?;
+    }
+
     // Write .pave.toml
-    fs::write(&config_path, default_config(&args.docs_root))
+    fs::write(&config_path, default_config(&args.docs_root, args.preset))
         .context("Failed to write .pave.toml")?;
 
     // Write index.md
@@ -118,6 +175,12 @@ pub fn run(args: InitArgs) -> Result<()> {
         "  {}/templates/        - Document templates",
         args.docs_root
     );
+    if args.preset == Some(InitPreset::Monorepo) {
+        println!(
+            "  {}/           - Additional docs root",
+            MONOREPO_EXTRA_ROOT
+        );
+    }
     println!();
     println!("Next steps:");
     println!("  pave new component <name>  - Create a component doc");
@@ -139,7 +202,9 @@ fn install_git_hooks(base: &Path) -> Result<()> {
 mod tests {
     use super::*;
     use crate::commands::hooks::PAVE_HOOK_MARKER;
+    use crate::config::PaveConfig;
     use std::fs;
+    use std::path::PathBuf;
     use tempfile::TempDir;
 
     /// Helper to create a fake git repo structure for testing.
@@ -250,11 +315,77 @@ mod tests {
 
     #[test]
     fn config_is_valid_toml() {
-        let config = default_config("docs");
+        let config = default_config("docs", None);
         let parsed: Result<toml::Value, _> = toml::from_str(&config);
         assert!(parsed.is_ok(), "Generated config should be valid TOML");
     }
 
+    /// Run `pave init --preset` in a fresh directory and load the result.
+    fn init_with_preset(preset: Option<InitPreset>) -> (TempDir, PaveConfig) {
+        let temp_dir = TempDir::new().unwrap();
+        run(InitArgs {
+            skip_hooks: true,
+            preset,
+            working_dir: Some(temp_dir.path().to_path_buf()),
+            ..Default::default()
+        })
+        .unwrap();
+        let config = PaveConfig::load(temp_dir.path().join(".pave.toml")).unwrap();
+        (temp_dir, config)
+    }
+
+    #[test]
+    fn default_config_loads_and_validates() {
+        let (temp_dir, config) = init_with_preset(None);
+        config.validate().unwrap();
+        assert_eq!(config.docs.root, PathBuf::from("docs"));
+        assert_eq!(config.docs.templates, Some(PathBuf::from("docs/templates")));
+        assert_eq!(config.rules, PaveConfig::default().rules);
+        assert!(config.docs.resolve_root_dirs(temp_dir.path()).is_ok());
+    }
+
+    #[test]
+    fn minimal_preset_drops_verification_requirements() {
+        let (_temp_dir, config) = init_with_preset(Some(InitPreset::Minimal));
+        config.validate().unwrap();
+        assert!(!config.rules.require_verification);
+        assert!(!config.rules.require_examples);
+        assert!(!config.rules.require_verification_commands);
+        assert_eq!(config.mapping.coverage_threshold, None);
+    }
+
+    #[test]
+    fn strict_preset_requires_verification_and_coverage() {
+        let (_temp_dir, config) = init_with_preset(Some(InitPreset::Strict));
+        config.validate().unwrap();
+        assert!(config.rules.require_verification);
+        assert!(config.rules.require_examples);
+        assert!(config.rules.require_verification_commands);
+        assert!(config.rules.validate_paths);
+        assert!(config.rules.warn_empty_paths);
+        assert!(config.rules.section_casing);
+        assert_eq!(config.mapping.coverage_threshold, Some(80));
+    }
+
+    #[test]
+    fn monorepo_preset_lists_and_creates_every_root() {
+        let (temp_dir, config) = init_with_preset(Some(InitPreset::Monorepo));
+        config.validate().unwrap();
+        assert_eq!(config.docs.root, PathBuf::from("docs"));
+        assert_eq!(
+            config.docs.extra_roots,
+            vec![PathBuf::from(MONOREPO_EXTRA_ROOT)]
+        );
+        assert!(
+            config
+                .mapping
+                .exclude
+                .contains(&"node_modules/".to_string())
+        );
+        let roots = config.docs.resolve_root_dirs(temp_dir.path()).unwrap();
+        assert_eq!(roots.len(), 2);
+    }
+
     #[test]
     fn init_installs_hook_by_default_in_git_repo() {
         let temp_dir = TempDir::new().unwrap();
@@ -4,11 +4,10 @@ use anyhow::{Context, Result};
 use regex::Regex;
 use serde::Serialize;
 use std::collections::{HashMap, HashSet};
-use std::env;
 use std::path::{Path, PathBuf};
 
 use crate::cli::OutputFormat;
-use crate::config::{CONFIG_FILENAME, LintSection, PaveConfig};
+use crate::config::{LintSection, PaveConfig};
 use crate::parser::{CodeBlockTracker, ParsedDoc};
 
 /// Arguments for the `pave lint` command.
@@ -23,6 +22,8 @@ pub struct LintArgs {
     pub rules: Option<String>,
     /// Check external link validity (slow).
     pub external_links: bool,
+    /// Explicit config file path (from `--config`).
+    pub config: Option<PathBuf>,
 }
 
 /// All available lint rules.
@@ -154,13 +155,13 @@ impl LintResults {
 /// Execute the `pave lint` command.
 pub fn execute(args: LintArgs) -> Result<()> {
     // Find and load config
-    let config_path = find_config()?;
+    let config_path = crate::config::find_config(args.config.as_deref())?;
     let config = PaveConfig::load(&config_path)?;
-    let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+    let config_dir = &crate::config::config_base_dir(&config_path);
 
     // Determine paths to lint
     let paths = if args.paths.is_empty() {
-        vec![config_dir.join(&config.docs.root)]
+        config.docs.resolve_root_dirs(config_dir)?
     } else {
         args.paths.clone()
     };
@@ -214,27 +215,6 @@ pub fn execute(args: LintArgs) -> Result<()> {
     Ok(())
 }
 
-/// Find the .pave.toml config file by walking up from the current directory.
-fn find_config() -> Result<PathBuf> {
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let mut dir = current_dir.as_path();
-
-    loop {
-        let config_path = dir.join(CONFIG_FILENAME);
-        if config_path.exists() {
-            return Ok(config_path);
-        }
-
-        match dir.parent() {
-            Some(parent) => dir = parent,
-            None => anyhow::bail!(
-                "No {} found in current directory or any parent directory",
-                CONFIG_FILENAME
-            ),
-        }
-    }
-}
-
 /// Determine which rules to run based on CLI args and config.
 fn determine_rules(args: &LintArgs, config: &LintSection) -> Result<HashSet<LintRule>> {
     let mut rules: HashSet<LintRule> = if let Some(ref rules_str) = args.rules {
@@ -291,6 +271,7 @@ fn find_markdown_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
 
     // Sort for consistent output
     files.sort();
+    files.dedup();
     Ok(files)
 }
 
@@ -1273,6 +1254,7 @@ Second section.
             fix: false,
             rules: None,
             external_links: false,
+            config: None,
         };
 
         let rules = determine_rules(&args, &config).unwrap();
@@ -1288,6 +1270,7 @@ Second section.
             fix: false,
             rules: Some("broken-internal-links,trailing-whitespace".to_string()),
             external_links: false,
+            config: None,
         };
 
         let rules = determine_rules(&args, &config).unwrap();
@@ -1309,6 +1292,7 @@ Second section.
             fix: false,
             rules: None,
             external_links: false,
+            config: None,
         };
 
         let rules = determine_rules(&args, &config).unwrap();
@@ -0,0 +1,265 @@
+//! Implementation of the `pave lsp` command.
+//!
+//! A minimal language server speaking LSP over stdio. It keeps no state of
+//! its own: every `didOpen` and `didChange` re-runs the `pave check` rules on
+//! the buffer and publishes the resulting issues as diagnostics.
+
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tower_lsp::jsonrpc;
+use tower_lsp::lsp_types::{
+    Diagnostic, DiagnosticSeverity, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, InitializeParams, InitializeResult, NumberOrString, Position, Range,
+    ServerCapabilities, ServerInfo, TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+};
+use tower_lsp::{Client, LanguageServer, LspService, Server};
+
+use crate::commands::check::{self, Issue, Severity};
+use crate::config::PaveConfig;
+
+/// Arguments for the `pave lsp` command.
+pub struct LspArgs {
+    /// Explicit config file path (from `--config`).
+    pub config: Option<PathBuf>,
+}
+
+/// Execute the lsp command, serving until the client disconnects.
+pub fn execute(args: LspArgs) -> Result<()> {
+    let config = crate::config::load_or_default(args.config.as_deref())?;
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .context("Failed to start the language server runtime")?;
+    runtime.block_on(serve(tokio::io::stdin(), tokio::io::stdout(), config));
+    Ok(())
+}
+
+/// Serve LSP requests read from `input`, writing responses to `output`.
+async fn serve(input: impl AsyncRead + Unpin, output: impl AsyncWrite, config: PaveConfig) {
+    let (service, socket) = LspService::new(|client| Backend { client, config });
+    Server::new(input, output, socket).serve(service).await;
+}
+
+struct Backend {
+    client: Client,
+    config: PaveConfig,
+}
+
+impl Backend {
+    /// Check `text` and publish its diagnostics for `uri`.
+    async fn publish(&self, uri: Url, text: &str, version: i32) {
+        let path = uri
+            .to_file_path()
+            .unwrap_or_else(|_| PathBuf::from(uri.path()));
+        let diagnostics = match check::diagnose(&path, text, &self.config) {
+            Ok(issues) => issues
+                .iter()
+                .map(|issue| to_diagnostic(issue, text))
+                .collect(),
+            Err(err) => vec![Diagnostic {
+                range: Range::default(),
+                severity: Some(DiagnosticSeverity::ERROR),
+                source: Some("pave".to_string()),
+                message: format!("{:#}", err),
+                ..Diagnostic::default()
+            }],
+        };
+        self.client
+            .publish_diagnostics(uri, diagnostics, Some(version))
+            .await;
+    }
+}
+
+#[tower_lsp::async_trait]
+impl LanguageServer for Backend {
+    async fn initialize(&self, _: InitializeParams) -> jsonrpc::Result<InitializeResult> {
+        Ok(InitializeResult {
+            capabilities: ServerCapabilities {
+                text_document_sync: Some(TextDocumentSyncCapability::Kind(
+                    TextDocumentSyncKind::FULL,
+                )),
+                ..ServerCapabilities::default()
+            },
+            server_info: Some(ServerInfo {
+                name: "pave".to_string(),
+                version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            }),
+        })
+    }
+
+    async fn shutdown(&self) -> jsonrpc::Result<()> {
+        Ok(())
+    }
+
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        let doc = params.text_document;
+        self.publish(doc.uri, &doc.text, doc.version).await;
+    }
+
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        // Full sync: the last change holds the whole document.
+        if let Some(change) = params.content_changes.into_iter().last() {
+            let doc = params.text_document;
+            self.publish(doc.uri, &change.text, doc.version).await;
+        }
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        self.client
+            .publish_diagnostics(params.text_document.uri, Vec::new(), None)
+            .await;
+    }
+}
+
+/// Convert a check issue into a diagnostic spanning the rest of its line.
+fn to_diagnostic(issue: &Issue, text: &str) -> Diagnostic {
+    let line = issue.line.saturating_sub(1);
+    let line_text = text.lines().nth(line).unwrap_or("");
+    let start = utf16_len(line_text.chars().take(issue.column.saturating_sub(1)));
+    let end = utf16_len(line_text.chars()).max(start);
+    Diagnostic {
+        range: Range::new(
+            Position::new(line as u32, start),
+            Position::new(line as u32, end),
+        ),
+        severity: Some(match issue.severity {
+            Severity::Error => DiagnosticSeverity::ERROR,
+            Severity::Warning => DiagnosticSeverity::WARNING,
+        }),
+        code: Some(NumberOrString::String(issue.rule.clone())),
+        source: Some("pave".to_string()),
+        message: match &issue.hint {
+            Some(hint) => format!("{}\n{}", issue.message, hint),
+            None => issue.message.clone(),
+        },
+        ..Diagnostic::default()
+    }
+}
+
+/// Length of `chars` in UTF-16 code units, the LSP default position encoding.
+fn utf16_len(chars: impl Iterator<Item = char>) -> u32 {
+    chars.map(|c| c.len_utf16() as u32).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{Value, json};
+    use std::time::Duration;
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+    async fn send(writer: &mut (impl AsyncWrite + Unpin), message: Value) {
+        let body = message.to_string();
+        let frame = format!("Content-Length: {}\r\n\r\n{}", body.len(), body);
+        writer.write_all(frame.as_bytes()).await.unwrap();
+    }
+
+    async fn receive(reader: &mut (impl tokio::io::AsyncBufRead + Unpin)) -> Value {
+        let mut length = 0;
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).await.unwrap();
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some(value) = header.strip_prefix("Content-Length: ") {
+                length = value.parse().unwrap();
+            }
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn publishes_diagnostics_for_opened_doc() {
+        let (client, server) = tokio::io::duplex(64 * 1024);
+        let (server_read, server_write) = tokio::io::split(server);
+        tokio::spawn(serve(server_read, server_write, PaveConfig::default()));
+
+        let (client_read, mut client_write) = tokio::io::split(client);
+        let mut client_read = BufReader::new(client_read);
+
+        send(
+            &mut client_write,
+            json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {"capabilities": {}}}),
+        )
+        .await;
+        let response = receive(&mut client_read).await;
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["result"]["capabilities"]["textDocumentSync"], 1);
+
+        send(
+            &mut client_write,
+            json!({"jsonrpc": "2.0", "method": "initialized", "params": {}}),
+        )
+        .await;
+        send(
+            &mut client_write,
+            json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/didOpen",
+                "params": {"textDocument": {
+                    "uri": "file:///project/docs/components/auth.md",
+                    "languageId": "markdown",
+                    "version": 3,
+                    "text": "# Auth\n\nNo sections here.\n"
+                }}
+            }),
+        )
+        .await;
+
+        let notification = tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                let message = receive(&mut client_read).await;
+                if message["method"] == "textDocument/publishDiagnostics" {
+                    return message;
+                }
+            }
+        })
+        .await
+        .expect("no diagnostics published");
+
+        let params = &notification["params"];
+        assert_eq!(params["uri"], "file:///project/docs/components/auth.md");
+        assert_eq!(params["version"], 3);
+        let diagnostics = params["diagnostics"].as_array().unwrap();
+        let purpose = diagnostics
+            .iter()
+            .find(|d| d["code"] == "missing-section")
+            .expect("missing-section diagnostic");
+        assert!(
+            purpose["message"]
+                .as_str()
+                .unwrap()
+                .starts_with("Missing required section 'Purpose'")
+        );
+        assert_eq!(purpose["severity"], 1);
+        assert_eq!(purpose["source"], "pave");
+        assert_eq!(
+            purpose["range"]["start"],
+            json!({"line": 0, "character": 0})
+        );
+        assert_eq!(purpose["range"]["end"], json!({"line": 0, "character": 6}));
+    }
+
+    #[test]
+    fn to_diagnostic_uses_utf16_columns() {
+        let issue = Issue {
+            file: PathBuf::from("doc.md"),
+            line: 2,
+            column: 3,
+            severity: Severity::Warning,
+            rule: "broken-link".to_string(),
+            message: "Broken link".to_string(),
+            hint: None,
+            converted_from_error: false,
+        };
+        let diagnostic = to_diagnostic(&issue, "# T\n😀 [x](y.md)\n");
+        assert_eq!(diagnostic.range.start, Position::new(1, 3));
+        assert_eq!(diagnostic.range.end, Position::new(1, 12));
+        assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::WARNING));
+    }
+}
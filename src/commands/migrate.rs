@@ -1,16 +1,17 @@
-//! Migrate command for bulk insertion of missing PAVED sections.
+//! Migrate command for upgrading docs to the current PAVED structure.
 //!
 //! This module implements the `pave migrate` command which helps bulk-update
-//! existing documentation by inserting missing PAVED sections with placeholder content.
+//! existing documentation by renaming legacy section headings and inserting
+//! missing PAVED sections with placeholder content. Both transforms are
+//! idempotent, so re-running migrate on its own output changes nothing.
 
 use anyhow::{Context, Result};
 use serde::Serialize;
 use std::collections::HashSet;
-use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::config::{CONFIG_FILENAME, PaveConfig};
+use crate::config::PaveConfig;
 use crate::parser::{CodeBlockTracker, ParsedDoc};
 use crate::rules::{DocType, detect_doc_type};
 
@@ -28,6 +29,8 @@ pub struct MigrateArgs {
     pub interactive: bool,
     /// Create .bak files before modifying (default: true).
     pub backup: bool,
+    /// Explicit config file path (from `--config`).
+    pub config: Option<PathBuf>,
 }
 
 /// Output format for the migrate command.
@@ -47,6 +50,15 @@ pub struct MissingSection {
     pub placeholder: String,
 }
 
+/// A legacy section heading to rename.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SectionRename {
+    /// Heading text as written in the document.
+    pub from: String,
+    /// Current PAVED section name.
+    pub to: String,
+}
+
 /// Analysis result for a single file.
 #[derive(Debug, Clone, Serialize)]
 pub struct FileAnalysis {
@@ -54,6 +66,8 @@ pub struct FileAnalysis {
     pub path: PathBuf,
     /// Detected document type.
     pub doc_type: String,
+    /// Legacy headings that need renaming.
+    pub renamed_sections: Vec<SectionRename>,
     /// Sections that need to be added.
     pub missing_sections: Vec<MissingSection>,
 }
@@ -79,6 +93,9 @@ pub struct FileResult {
     pub status: MigrationStatus,
     /// Message describing what happened.
     pub message: String,
+    /// Legacy headings that were renamed.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sections_renamed: Vec<SectionRename>,
     /// Sections that were added.
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub sections_added: Vec<String>,
@@ -104,6 +121,16 @@ pub struct MigrationReport {
     pub files: Vec<FileResult>,
 }
 
+/// Legacy H2 headings and the PAVED sections they became.
+const LEGACY_HEADINGS: &[(&str, &str)] = &[
+    ("Tests", "Verification"),
+    ("Testing", "Verification"),
+    ("Verify", "Verification"),
+    ("Example", "Examples"),
+    ("Usage", "Examples"),
+    ("Overview", "Purpose"),
+];
+
 /// Standard sections for different document types.
 fn get_required_sections(doc_type: DocType) -> Vec<(&'static str, &'static str)> {
     match doc_type {
@@ -197,27 +224,6 @@ fn section_order(name: &str) -> usize {
     }
 }
 
-/// Find the config file by walking up the directory tree.
-fn find_config() -> Result<PathBuf> {
-    let current_dir = env::current_dir()?;
-    let mut dir = current_dir.as_path();
-
-    loop {
-        let config_path = dir.join(CONFIG_FILENAME);
-        if config_path.exists() {
-            return Ok(config_path);
-        }
-
-        match dir.parent() {
-            Some(parent) => dir = parent,
-            None => anyhow::bail!(
-                "No {} found in current directory or any parent directory",
-                CONFIG_FILENAME
-            ),
-        }
-    }
-}
-
 /// Recursively find markdown files in a directory.
 fn find_markdown_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
     let mut files = Vec::new();
@@ -293,16 +299,36 @@ fn analyze_file(
     }
     .to_string();
 
-    // Get required sections for this document type
-    let required_sections = get_required_sections(doc_type);
+    let in_filter = |name: &str| {
+        filter_sections
+            .as_ref()
+            .is_none_or(|filter| filter.contains(&name.to_lowercase()))
+    };
 
-    // Find missing sections
-    let mut missing_sections = Vec::new();
-    for (name, placeholder) in required_sections {
-        // Skip if not in filter set
-        if let Some(filter) = filter_sections
-            && !filter.contains(&name.to_lowercase())
+    // Rename legacy headings, unless the document already has the new one
+    let mut renamed_sections: Vec<SectionRename> = Vec::new();
+    for section in &doc.sections {
+        let Some(&(_, to)) = LEGACY_HEADINGS
+            .iter()
+            .find(|(from, _)| section.name.eq_ignore_ascii_case(from))
+        else {
+            continue;
+        };
+        if in_filter(to)
+            && !doc.has_section(to)
+            && !renamed_sections.iter().any(|rename| rename.to == to)
         {
+            renamed_sections.push(SectionRename {
+                from: section.name.clone(),
+                to: to.to_string(),
+            });
+        }
+    }
+
+    // Find missing sections, counting the renamed ones as present
+    let mut missing_sections = Vec::new();
+    for (name, placeholder) in get_required_sections(doc_type) {
+        if !in_filter(name) || renamed_sections.iter().any(|rename| rename.to == name) {
             continue;
         }
 
@@ -314,18 +340,73 @@ fn analyze_file(
         }
     }
 
-    // If no sections are missing, skip this file
-    if missing_sections.is_empty() {
+    // If nothing needs to change, skip this file
+    if renamed_sections.is_empty() && missing_sections.is_empty() {
         return Ok(None);
     }
 
     Ok(Some(FileAnalysis {
         path: relative_path,
         doc_type: doc_type_str,
+        renamed_sections,
         missing_sections,
     }))
 }
 
+/// Rename legacy `##` headings outside code blocks.
+fn rename_sections(content: &str, renames: &[SectionRename]) -> String {
+    let mut tracker = CodeBlockTracker::new();
+    let mut out = String::with_capacity(content.len());
+    for line in content.split_inclusive('\n') {
+        let rename = if tracker.process_line(line) || tracker.in_code_block() {
+            None
+        } else {
+            line.trim_end()
+                .strip_prefix("## ")
+                .and_then(|name| renames.iter().find(|rename| rename.from == name.trim()))
+        };
+        match rename {
+            Some(rename) => {
+                out.push_str("## ");
+                out.push_str(&rename.to);
+                if line.ends_with('\n') {
+                    out.push('\n');
+                }
+            }
+            None => out.push_str(line),
+        }
+    }
+    out
+}
+
+/// Apply every transform in `analysis` to `content`.
+fn migrate_content(content: &str, analysis: &FileAnalysis) -> String {
+    let renamed = rename_sections(content, &analysis.renamed_sections);
+    if analysis.missing_sections.is_empty() {
+        renamed
+    } else {
+        insert_sections(&renamed, &analysis.missing_sections)
+    }
+}
+
+/// Describe a file's changes, e.g. `renamed 1 section(s), added 2 section(s)`.
+fn change_summary(analysis: &FileAnalysis) -> String {
+    let mut parts = Vec::new();
+    if !analysis.renamed_sections.is_empty() {
+        parts.push(format!(
+            "renamed {} section(s)",
+            analysis.renamed_sections.len()
+        ));
+    }
+    if !analysis.missing_sections.is_empty() {
+        parts.push(format!(
+            "added {} section(s)",
+            analysis.missing_sections.len()
+        ));
+    }
+    parts.join(", ")
+}
+
 /// Insert missing sections into a document.
 fn insert_sections(content: &str, missing_sections: &[MissingSection]) -> String {
     let mut lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
@@ -436,20 +517,26 @@ fn prompt_user(file: &FileAnalysis) -> bool {
 /// Execute the migrate command.
 pub fn execute(args: MigrateArgs) -> Result<()> {
     // Find and load config
-    let config_path = find_config()?;
+    let config_path = crate::config::find_config(args.config.as_deref())?;
     let config = PaveConfig::load(&config_path)?;
-    let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+    let config_dir = &crate::config::config_base_dir(&config_path);
+
+    let docs_root = config
+        .docs
+        .resolve_root_dirs(config_dir)?
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| config_dir.to_path_buf());
 
     // Determine paths to process
     let paths = if let Some(path) = args.path {
         vec![path]
     } else {
-        vec![config_dir.join(&config.docs.root)]
+        vec![docs_root.clone()]
     };
 
     // Find markdown files
     let files = find_markdown_files(&paths)?;
-    let docs_root = config_dir.join(&config.docs.root);
 
     // Parse sections filter
     let filter_sections: Option<HashSet<String>> = args
@@ -481,6 +568,7 @@ pub fn execute(args: MigrateArgs) -> Result<()> {
                 path: analysis.path.clone(),
                 status: MigrationStatus::Skipped,
                 message: "would be modified".to_string(),
+                sections_renamed: analysis.renamed_sections.clone(),
                 sections_added: analysis
                     .missing_sections
                     .iter()
@@ -505,6 +593,7 @@ pub fn execute(args: MigrateArgs) -> Result<()> {
                 path: analysis.path.clone(),
                 status: MigrationStatus::Skipped,
                 message: "user declined".to_string(),
+                sections_renamed: Vec::new(),
                 sections_added: Vec::new(),
                 backup_path: None,
             });
@@ -520,6 +609,7 @@ pub fn execute(args: MigrateArgs) -> Result<()> {
                     path: analysis.path.clone(),
                     status: MigrationStatus::Failed,
                     message: format!("failed to read file: {}", e),
+                    sections_renamed: Vec::new(),
                     sections_added: Vec::new(),
                     backup_path: None,
                 });
@@ -537,6 +627,7 @@ pub fn execute(args: MigrateArgs) -> Result<()> {
                         path: analysis.path.clone(),
                         status: MigrationStatus::Failed,
                         message: format!("failed to create backup: {}", e),
+                        sections_renamed: Vec::new(),
                         sections_added: Vec::new(),
                         backup_path: None,
                     });
@@ -548,8 +639,8 @@ pub fn execute(args: MigrateArgs) -> Result<()> {
             None
         };
 
-        // Insert missing sections
-        let new_content = insert_sections(&content, &analysis.missing_sections);
+        // Rename legacy headings and insert missing sections
+        let new_content = migrate_content(&content, &analysis);
 
         // Write back
         match fs::write(&full_path, &new_content) {
@@ -557,7 +648,8 @@ pub fn execute(args: MigrateArgs) -> Result<()> {
                 report.files.push(FileResult {
                     path: analysis.path.clone(),
                     status: MigrationStatus::Modified,
-                    message: format!("added {} section(s)", analysis.missing_sections.len()),
+                    message: change_summary(&analysis),
+                    sections_renamed: analysis.renamed_sections.clone(),
                     sections_added: analysis
                         .missing_sections
                         .iter()
@@ -572,6 +664,7 @@ pub fn execute(args: MigrateArgs) -> Result<()> {
                     path: analysis.path.clone(),
                     status: MigrationStatus::Failed,
                     message: format!("failed to write file: {}", e),
+                    sections_renamed: Vec::new(),
                     sections_added: Vec::new(),
                     backup_path,
                 });
@@ -610,6 +703,9 @@ fn output_text_report(report: &MigrationReport, dry_run: bool) {
 
         for file in &report.files {
             println!("{}", file.path.display());
+            for rename in &file.sections_renamed {
+                println!("  ~ Rename ## {} to ## {}", rename.from, rename.to);
+            }
             for section in &file.sections_added {
                 println!("  + Add ## {} section", section);
             }
@@ -886,6 +982,56 @@ example
         assert!(analysis.is_none());
     }
 
+    #[test]
+    fn test_migrate_renames_legacy_headings_idempotently() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = create_test_config(&temp_dir);
+        let content = "# Auth Component\n\n## Purpose\nAuth.\n\n## Tests\n```bash\n## Tests stays inside code\ncargo test\n```\n";
+        let path = create_test_doc(&temp_dir, "docs/components/auth.md", content);
+        let args = || MigrateArgs {
+            path: None,
+            format: MigrateOutputFormat::Json,
+            dry_run: false,
+            sections: None,
+            interactive: false,
+            backup: false,
+            config: Some(config_path.clone()),
+        };
+
+        let analysis = analyze_file(&path, &temp_dir.path().join("docs"), &None)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            analysis.renamed_sections,
+            vec![SectionRename {
+                from: "Tests".to_string(),
+                to: "Verification".to_string(),
+            }]
+        );
+        // The renamed section isn't also stubbed out
+        assert!(
+            analysis
+                .missing_sections
+                .iter()
+                .all(|s| s.name != "Verification")
+        );
+
+        execute(args()).unwrap();
+        let migrated = fs::read_to_string(&path).unwrap();
+        assert!(migrated.contains("\n## Verification\n```bash\n## Tests stays inside code\n"));
+        assert!(!migrated.contains("\n## Tests\n"));
+        assert!(migrated.contains("\n## Interface\n"));
+
+        // A second run finds nothing left to do
+        assert!(
+            analyze_file(&path, &temp_dir.path().join("docs"), &None)
+                .unwrap()
+                .is_none()
+        );
+        execute(args()).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), migrated);
+    }
+
     #[test]
     fn test_create_backup() {
         let temp_dir = TempDir::new().unwrap();
@@ -928,6 +1074,7 @@ example
                 path: PathBuf::from("test.md"),
                 status: MigrationStatus::Modified,
                 message: "added 2 section(s)".to_string(),
+                sections_renamed: vec![],
                 sections_added: vec!["Purpose".to_string(), "Verification".to_string()],
                 backup_path: Some(PathBuf::from("test.md.bak")),
             }],
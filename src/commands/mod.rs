@@ -4,16 +4,20 @@ pub mod adopt;
 pub mod build;
 pub mod changed;
 pub mod check;
+pub mod completions;
 pub mod config;
 pub mod coverage;
 pub mod coverage_changed;
 pub mod doctor;
+pub mod graph;
 pub mod hooks;
 pub mod index;
 pub mod init;
 pub mod lint;
+pub mod lsp;
 pub mod migrate;
 pub mod new;
 pub mod prompt;
+pub mod stats;
 pub mod status;
 pub mod verify;
@@ -2,35 +2,110 @@
 
 use anyhow::{Context, Result, bail};
 use std::fs;
+use std::io::{self, BufRead, Write};
 use std::path::{Path, PathBuf};
 
+use crate::config::{PaveConfig, TemplatesSection};
+use crate::parser::CodeBlockTracker;
+use crate::paths::PathsMatcher;
 use crate::templates::{TemplateType, get_template};
 
 /// Arguments for the `pave new` command.
 pub struct NewArgs {
-    /// Document type: component, runbook, adr
-    pub doc_type: TemplateType,
+    /// Document type: component, runbook, adr (prompted for with `interactive`)
+    pub doc_type: Option<TemplateType>,
     /// Name for the document (used in filename and title)
-    pub name: String,
+    pub name: Option<String>,
     /// Where to create the file (optional, uses default if not specified)
     pub output: Option<PathBuf>,
+    /// Prompt for the title, type, owner, and Paths glob.
+    pub interactive: bool,
+    /// Print the rendered document instead of writing it.
+    pub dry_run: bool,
+    /// Existing document whose structure the new one copies.
+    pub from: Option<PathBuf>,
+    /// List the built-in and custom templates instead of creating a doc.
+    pub list_templates: bool,
+    /// Explicit config file path (from `--config`).
+    pub config: Option<PathBuf>,
+}
+
+/// Everything needed to render a new document.
+#[derive(Debug, Clone, PartialEq)]
+struct DocDetails {
+    doc_type: TemplateType,
+    /// File stem for the default output path.
+    name: String,
+    /// Title substituted into the template heading.
+    title: String,
+    /// Owner recorded in the front matter.
+    owner: Option<String>,
+    /// Glob written to the `## Paths` section.
+    paths: Option<String>,
 }
 
 /// Execute the `pave new` command.
 pub fn execute(args: NewArgs) -> Result<()> {
+    run(args, &mut io::stdout())
+}
+
+/// Run `pave new`, writing prompts and any `--dry-run` preview to `out`.
+fn run(args: NewArgs, out: &mut impl Write) -> Result<()> {
+    if args.list_templates {
+        return list_templates(args.config.as_deref(), out);
+    }
+
+    let details = if args.interactive {
+        let stdin = io::stdin();
+        prompt_details(
+            &mut stdin.lock(),
+            out,
+            Path::new("."),
+            args.doc_type,
+            args.name.as_deref(),
+        )?
+    } else {
+        let (Some(doc_type), Some(name)) = (args.doc_type, args.name) else {
+            bail!("A document type and name are required unless --interactive is set");
+        };
+        DocDetails {
+            doc_type,
+            title: to_title_case(&name),
+            name,
+            owner: None,
+            paths: None,
+        }
+    };
+
     // Determine output path
     let output_path = args
         .output
-        .unwrap_or_else(|| default_output_path(&args.doc_type, &args.name));
+        .unwrap_or_else(|| default_output_path(&details.doc_type, &details.name));
 
     // Check if file already exists
     if output_path.exists() {
         bail!("File already exists: {}", output_path.display());
     }
 
-    // Get template and replace placeholders
-    let template = get_template(args.doc_type);
-    let content = substitute_placeholders(template, &args.name, args.doc_type);
+    let content = match &args.from {
+        Some(source) => {
+            let source_content = fs::read_to_string(source)
+                .with_context(|| format!("Failed to read {}", source.display()))?;
+            clone_structure(&source_content, &details)?
+        }
+        None => render_doc(&details)?,
+    };
+
+    // Preview: the document goes to `out` so it can be piped, the path to stderr
+    if args.dry_run {
+        out.write_all(content.as_bytes())?;
+        eprintln!(
+            "Would create {} at {}",
+            type_name(details.doc_type),
+            output_path.display()
+        );
+        return Ok(());
+    }
 
     // Create parent directories if needed
     if let Some(parent) = output_path.parent() {
@@ -45,7 +120,7 @@ pub fn execute(args: NewArgs) -> Result<()> {
     // Print success message
     println!(
         "Created {} at {}",
-        type_name(args.doc_type),
+        type_name(details.doc_type),
         output_path.display()
     );
     println!("\nNext steps:");
@@ -55,6 +130,285 @@ pub fn execute(args: NewArgs) -> Result<()> {
     Ok(())
 }
 
+/// Print the built-in templates, then any custom ones in `docs.templates`.
+///
+/// Without a config file only the built-ins are listed; an explicit
+/// `--config` that doesn't exist is still an error.
+fn list_templates(config: Option<&Path>, out: &mut impl Write) -> Result<()> {
+    writeln!(out, "Built-in templates:")?;
+    for template in TemplateType::all() {
+        writeln!(
+            out,
+            "  {:<12} {}",
+            template.default_filename().trim_end_matches(".md"),
+            template.description()
+        )?;
+    }
+
+    let config_path = match crate::config::find_config(config) {
+        Ok(path) => path,
+        Err(_) if config.is_none() => return Ok(()),
+        Err(err) => return Err(err.into()),
+    };
+    let config = PaveConfig::load(&config_path)?;
+    let Some(templates_dir) = &config.docs.templates else {
+        return Ok(());
+    };
+    let config_dir = &crate::config::config_base_dir(&config_path);
+    let custom = custom_templates(&config_dir.join(templates_dir), &config.templates)?;
+    if custom.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(out, "\nCustom templates ({}):", templates_dir.display())?;
+    for (name, overrides) in custom {
+        match overrides {
+            Some(template) => writeln!(
+                out,
+                "  {:<12} overrides built-in {}",
+                name,
+                template.default_filename().trim_end_matches(".md")
+            )?,
+            None => writeln!(out, "  {}", name)?,
+        }
+    }
+    Ok(())
+}
+
+/// Markdown files in `dir`, sorted by name, each with the built-in template
+/// it replaces: the one whose `[templates]` filename (or default filename)
+/// it has. A missing directory has no templates.
+fn custom_templates(
+    dir: &Path,
+    filenames: &TemplatesSection,
+) -> Result<Vec<(String, Option<TemplateType>)>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => {
+            return Err(err).with_context(|| format!("Failed to read {}", dir.display()));
+        }
+    };
+
+    let mut templates = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if !path.is_file() || path.extension().is_none_or(|ext| ext != "md") {
+            continue;
+        }
+        let name = path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        let overrides = TemplateType::all().iter().copied().find(|template| {
+            let configured = match template {
+                TemplateType::Component => &filenames.component,
+                TemplateType::Runbook => &filenames.runbook,
+                TemplateType::Adr => &filenames.adr,
+            };
+            configured.as_deref().unwrap_or(template.default_filename()) == name
+        });
+        templates.push((name, overrides));
+    }
+    templates.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(templates)
+}
+
+/// Ask for a document's details, one line of `input` per answer.
+///
+/// `doc_type` and `name` (from the command line) become the defaults. A Paths
+/// glob that matches no files under `root` is kept but warned about.
+fn prompt_details(
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+    root: &Path,
+    doc_type: Option<TemplateType>,
+    name: Option<&str>,
+) -> Result<DocDetails> {
+    let default_title = name.map(to_title_case);
+    let title = loop {
+        let answer = ask(input, output, "Title", default_title.as_deref())?;
+        if !answer.is_empty() {
+            break answer;
+        }
+        writeln!(output, "A title is required.")?;
+    };
+
+    let default_type = type_name(doc_type.unwrap_or(TemplateType::Component)).to_lowercase();
+    let doc_type = loop {
+        let answer = ask(
+            input,
+            output,
+            "Type (component, runbook, adr)",
+            Some(&default_type),
+        )?;
+        match parse_template_type(&answer) {
+            Some(doc_type) => break doc_type,
+            None => writeln!(
+                output,
+                "Unknown type `{}`; choose component, runbook, or adr.",
+                answer
+            )?,
+        }
+    };
+
+    let owner = Some(ask(input, output, "Owner (optional)", None)?).filter(|o| !o.is_empty());
+
+    let paths = Some(ask(
+        input,
+        output,
+        "Paths glob, e.g. src/auth/** (optional)",
+        None,
+    )?)
+    .filter(|p| !p.is_empty());
+    if let Some(glob) = &paths
+        && PathsMatcher::new(&[glob]).matched_files(root).is_empty()
+    {
+        writeln!(output, "Warning: `{}` matches no files", glob)?;
+    }
+
+    Ok(DocDetails {
+        doc_type,
+        name: name.map_or_else(|| slugify(&title), str::to_string),
+        title,
+        owner,
+        paths,
+    })
+}
+
+/// Print `question` (with its default in brackets) and read the answer.
+///
+/// An empty answer yields the default (or an empty string).
+fn ask(
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+    question: &str,
+    default: Option<&str>,
+) -> Result<String> {
+    match default {
+        Some(default) => write!(output, "{} [{}]: ", question, default)?,
+        None => write!(output, "{}: ", question)?,
+    }
+    output.flush()?;
+
+    let mut line = String::new();
+    if input
+        .read_line(&mut line)
+        .context("Failed to read answer")?
+        == 0
+    {
+        bail!("Input ended before `{}` was answered", question);
+    }
+    match line.trim() {
+        "" => Ok(default.unwrap_or_default().to_string()),
+        answer => Ok(answer.to_string()),
+    }
+}
+
+/// Parse a template type name as typed at a prompt.
+fn parse_template_type(answer: &str) -> Option<TemplateType> {
+    TemplateType::all()
+        .iter()
+        .copied()
+        .find(|t| type_name(*t).eq_ignore_ascii_case(answer))
+}
+
+/// Render the template with the title, front matter, and Paths filled in.
+fn render_doc(details: &DocDetails) -> Result<String> {
+    let body = substitute_placeholders(
+        get_template(details.doc_type),
+        &details.title,
+        details.doc_type,
+    );
+
+    let mut content = String::new();
+    if let Some(owner) = &details.owner {
+        let front_matter = serde_yaml::to_string(&serde_yaml::Mapping::from_iter([(
+            "owner".into(),
+            owner.as_str().into(),
+        )]))
+        .context("Failed to render front matter")?;
+        content.push_str(&format!("---\n{}---\n\n", front_matter));
+    }
+    content.push_str(&body);
+    if let Some(glob) = &details.paths {
+        content.truncate(content.trim_end().len());
+        content.push_str(&format!("\n\n## Paths\n\n- `{}`\n", glob));
+    }
+    Ok(content)
+}
+
+/// Render a new document with the same structure as `source`.
+///
+/// Every heading is kept, with the title replaced, but the prose beneath is
+/// dropped; only the `## Paths` list survives. Front matter keeps its keys,
+/// with `id` reset to the new name and `date` to today.
+fn clone_structure(source: &str, details: &DocDetails) -> Result<String> {
+    let (front_matter, body) = split_front_matter(source);
+
+    let mut content = String::new();
+    if let Some(yaml) = front_matter {
+        let mut mapping: serde_yaml::Mapping = serde_yaml::from_str::<Option<_>>(yaml)
+            .context("Failed to parse front matter")?
+            .unwrap_or_default();
+        for (key, value) in [("id", details.name.clone()), ("date", today())] {
+            if mapping.contains_key(key) {
+                mapping.insert(key.into(), value.into());
+            }
+        }
+        let yaml = serde_yaml::to_string(&mapping).context("Failed to render front matter")?;
+        content.push_str(&format!("---\n{}---\n\n", yaml));
+    }
+
+    let mut blocks = vec![format!("# {}", details.title)];
+    let mut tracker = CodeBlockTracker::new();
+    let mut in_paths = false;
+    for line in body.lines() {
+        let in_code = tracker.process_line(line) || tracker.in_code_block();
+        match heading_level(line).filter(|_| !in_code) {
+            // The source's own title is replaced by the new one
+            Some(1) if blocks.len() == 1 && !in_paths => {}
+            Some(level) => {
+                let text = line.trim()[level..].trim();
+                in_paths = level == 2 && text.eq_ignore_ascii_case("Paths");
+                blocks.push(line.trim().to_string());
+            }
+            None if in_paths && !line.trim().is_empty() => {
+                let block = blocks.last_mut().expect("Paths heading was pushed");
+                block.push_str(if block.contains("\n") { "\n" } else { "\n\n" });
+                block.push_str(line.trim_end());
+            }
+            None => {}
+        }
+    }
+    content.push_str(&blocks.join("\n\n"));
+    content.push('\n');
+    Ok(content)
+}
+
+/// Split a leading `---` front matter block from the rest of `content`.
+fn split_front_matter(content: &str) -> (Option<&str>, &str) {
+    let Some(after_open) = content.trim_start().strip_prefix("---\n") else {
+        return (None, content);
+    };
+    if let Some(rest) = after_open.strip_prefix("---") {
+        return (Some(""), rest);
+    }
+    match after_open.find("\n---") {
+        Some(close) => (Some(&after_open[..=close]), &after_open[close + 4..]),
+        None => (None, content),
+    }
+}
+
+/// Level of an ATX heading line (`## Title` is 2), if `line` is one.
+fn heading_level(line: &str) -> Option<usize> {
+    let trimmed = line.trim_start();
+    let level = trimmed.chars().take_while(|&c| c == '#').count();
+    let rest = &trimmed[level..];
+    ((1..=6).contains(&level) && (rest.is_empty() || rest.starts_with(' '))).then_some(level)
+}
+
 /// Returns the default output path for a given document type and name.
 fn default_output_path(doc_type: &TemplateType, name: &str) -> PathBuf {
     let subdir = match doc_type {
@@ -65,18 +419,32 @@ fn default_output_path(doc_type: &TemplateType, name: &str) -> PathBuf {
     Path::new("docs").join(subdir).join(format!("{}.md", name))
 }
 
-/// Substitutes placeholders in the template.
-fn substitute_placeholders(template: &str, name: &str, doc_type: TemplateType) -> String {
-    let title = to_title_case(name);
-
-    // Replace the specific placeholder used in each template
+/// Substitutes the title for the placeholder used in each template, and
+/// today's date for `{Date}`.
+fn substitute_placeholders(template: &str, title: &str, doc_type: TemplateType) -> String {
+    let template = template.replace("{Date}", &today());
     match doc_type {
-        TemplateType::Component => template.replace("{Component Name}", &title),
-        TemplateType::Runbook => template.replace("{Task Name}", &title),
-        TemplateType::Adr => template.replace("{Title}", &title),
+        TemplateType::Component => template.replace("{Component Name}", title),
+        TemplateType::Runbook => template.replace("{Task Name}", title),
+        TemplateType::Adr => template.replace("{Title}", title),
     }
 }
 
+/// Today's local date as `YYYY-MM-DD`.
+fn today() -> String {
+    chrono::Local::now().format("%Y-%m-%d").to_string()
+}
+
+/// Converts a free-form title into a kebab-case file name.
+fn slugify(title: &str) -> String {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
 /// Converts a kebab-case or snake_case name to Title Case.
 fn to_title_case(name: &str) -> String {
     name.split(['-', '_'])
@@ -145,21 +513,21 @@ mod tests {
     #[test]
     fn substitute_placeholders_component() {
         let template = "# {Component Name}\n\nSome content";
-        let result = substitute_placeholders(template, "auth-service", TemplateType::Component);
+        let result = substitute_placeholders(template, "Auth Service", TemplateType::Component);
         assert!(result.starts_with("# Auth Service\n"));
     }
 
     #[test]
     fn substitute_placeholders_runbook() {
         let template = "# Runbook: {Task Name}\n\nSome content";
-        let result = substitute_placeholders(template, "deploy-production", TemplateType::Runbook);
+        let result = substitute_placeholders(template, "Deploy Production", TemplateType::Runbook);
         assert!(result.starts_with("# Runbook: Deploy Production\n"));
     }
 
     #[test]
     fn substitute_placeholders_adr() {
         let template = "# ADR: {Title}\n\nSome content";
-        let result = substitute_placeholders(template, "use-postgresql", TemplateType::Adr);
+        let result = substitute_placeholders(template, "Use Postgresql", TemplateType::Adr);
         assert!(result.starts_with("# ADR: Use Postgresql\n"));
     }
 
@@ -169,9 +537,14 @@ mod tests {
         let output_path = temp_dir.path().join("test-component.md");
 
         let args = NewArgs {
-            doc_type: TemplateType::Component,
-            name: "test-component".to_string(),
+            doc_type: Some(TemplateType::Component),
+            name: Some("test-component".to_string()),
             output: Some(output_path.clone()),
+            interactive: false,
+            dry_run: false,
+            from: None,
+            list_templates: false,
+            config: None,
         };
 
         execute(args).unwrap();
@@ -188,9 +561,14 @@ mod tests {
         let output_path = temp_dir.path().join("test-runbook.md");
 
         let args = NewArgs {
-            doc_type: TemplateType::Runbook,
-            name: "test-runbook".to_string(),
+            doc_type: Some(TemplateType::Runbook),
+            name: Some("test-runbook".to_string()),
             output: Some(output_path.clone()),
+            interactive: false,
+            dry_run: false,
+            from: None,
+            list_templates: false,
+            config: None,
         };
 
         execute(args).unwrap();
@@ -207,9 +585,14 @@ mod tests {
         let output_path = temp_dir.path().join("test-adr.md");
 
         let args = NewArgs {
-            doc_type: TemplateType::Adr,
-            name: "test-adr".to_string(),
+            doc_type: Some(TemplateType::Adr),
+            name: Some("test-adr".to_string()),
             output: Some(output_path.clone()),
+            interactive: false,
+            dry_run: false,
+            from: None,
+            list_templates: false,
+            config: None,
         };
 
         execute(args).unwrap();
@@ -226,9 +609,14 @@ mod tests {
         let output_path = temp_dir.path().join("nested").join("dir").join("doc.md");
 
         let args = NewArgs {
-            doc_type: TemplateType::Component,
-            name: "test".to_string(),
+            doc_type: Some(TemplateType::Component),
+            name: Some("test".to_string()),
             output: Some(output_path.clone()),
+            interactive: false,
+            dry_run: false,
+            from: None,
+            list_templates: false,
+            config: None,
         };
 
         execute(args).unwrap();
@@ -242,13 +630,187 @@ mod tests {
         fs::write(&output_path, "existing content").unwrap();
 
         let args = NewArgs {
-            doc_type: TemplateType::Component,
-            name: "existing".to_string(),
+            doc_type: Some(TemplateType::Component),
+            name: Some("existing".to_string()),
             output: Some(output_path),
+            interactive: false,
+            dry_run: false,
+            from: None,
+            list_templates: false,
+            config: None,
         };
 
         let result = execute(args);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("already exists"));
     }
+
+    #[test]
+    fn slugify_makes_kebab_case_file_names() {
+        assert_eq!(slugify("Auth Service"), "auth-service");
+        assert_eq!(slugify("  Use Postgres (v15)! "), "use-postgres-v15");
+    }
+
+    #[test]
+    fn prompt_details_reads_scripted_answers() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("src/auth")).unwrap();
+        fs::write(temp_dir.path().join("src/auth/mod.rs"), "").unwrap();
+
+        let mut input = io::Cursor::new("Auth Service\nrunbok\nrunbook\n@alice\nsrc/auth/**\n");
+        let mut output = Vec::new();
+        let details = prompt_details(&mut input, &mut output, temp_dir.path(), None, None).unwrap();
+
+        assert_eq!(
+            details,
+            DocDetails {
+                doc_type: TemplateType::Runbook,
+                name: "auth-service".to_string(),
+                title: "Auth Service".to_string(),
+                owner: Some("@alice".to_string()),
+                paths: Some("src/auth/**".to_string()),
+            }
+        );
+        let transcript = String::from_utf8(output).unwrap();
+        assert!(transcript.contains("Type (component, runbook, adr) [component]: "));
+        assert!(transcript.contains("Unknown type `runbok`"));
+        assert!(!transcript.contains("Warning"));
+
+        let content = render_doc(&details).unwrap();
+        assert!(content.starts_with("---\nowner: '@alice'\n---\n\n# Runbook: Auth Service\n"));
+        assert!(content.ends_with("\n\n## Paths\n\n- `src/auth/**`\n"));
+    }
+
+    #[test]
+    fn prompt_details_uses_defaults_and_warns_on_unmatched_glob() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let mut input = io::Cursor::new("\n\n\nsrc/missing/**\n");
+        let mut output = Vec::new();
+        let details = prompt_details(
+            &mut input,
+            &mut output,
+            temp_dir.path(),
+            Some(TemplateType::Adr),
+            Some("use-postgres"),
+        )
+        .unwrap();
+
+        assert_eq!(details.title, "Use Postgres");
+        assert_eq!(details.name, "use-postgres");
+        assert_eq!(details.doc_type, TemplateType::Adr);
+        assert_eq!(details.owner, None);
+        assert!(
+            String::from_utf8(output)
+                .unwrap()
+                .contains("Warning: `src/missing/**` matches no files")
+        );
+
+        let content = render_doc(&details).unwrap();
+        assert!(content.starts_with("# ADR: Use Postgres\n"));
+    }
+
+    #[test]
+    fn prompt_details_errors_when_input_ends() {
+        let mut input = io::Cursor::new("Auth\n");
+        let err =
+            prompt_details(&mut input, &mut Vec::new(), Path::new("."), None, None).unwrap_err();
+        assert!(err.to_string().contains("Input ended before `Type"));
+    }
+
+    #[test]
+    fn dry_run_prints_the_doc_without_writing_it() {
+        let temp_dir = TempDir::new().unwrap();
+        let output_path = temp_dir.path().join("docs").join("use-postgres.md");
+
+        let args = NewArgs {
+            doc_type: Some(TemplateType::Adr),
+            name: Some("use-postgres".to_string()),
+            output: Some(output_path.clone()),
+            interactive: false,
+            dry_run: true,
+            from: None,
+            list_templates: false,
+            config: None,
+        };
+
+        let mut out = Vec::new();
+        run(args, &mut out).unwrap();
+
+        assert!(!output_path.exists());
+        assert!(!output_path.parent().unwrap().exists());
+        let rendered = String::from_utf8(out).unwrap();
+        assert!(rendered.starts_with("# ADR: Use Postgres\n"));
+        assert!(rendered.contains(&format!("Proposed on {}", today())));
+        assert!(!rendered.contains("{Date}"));
+    }
+
+    #[test]
+    fn from_clones_headings_with_empty_bodies() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("auth.md");
+        fs::write(
+            &source,
+            "---\nid: auth\nowner: platform\ndate: 2020-01-01\n---\n\n# Auth Service\n\n## Purpose\n\nHandles login.\n\n## Interface\n\n### Endpoints\n\n```bash\n# not a heading\ncurl /login\n```\n\n## Paths\n\n- `src/auth/**`\n- `src/session.rs`\n",
+        )
+        .unwrap();
+        let output_path = temp_dir.path().join("billing.md");
+
+        let args = NewArgs {
+            doc_type: Some(TemplateType::Component),
+            name: Some("billing".to_string()),
+            output: Some(output_path.clone()),
+            interactive: false,
+            dry_run: false,
+            from: Some(source),
+            list_templates: false,
+            config: None,
+        };
+        run(args, &mut Vec::new()).unwrap();
+
+        let content = fs::read_to_string(&output_path).unwrap();
+        assert_eq!(
+            content,
+            format!(
+                "---\nid: billing\nowner: platform\ndate: {}\n---\n\n# Billing\n\n## Purpose\n\n## Interface\n\n### Endpoints\n\n## Paths\n\n- `src/auth/**`\n- `src/session.rs`\n",
+                today()
+            )
+        );
+    }
+
+    #[test]
+    fn list_templates_includes_built_ins_and_custom_templates() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("docs/templates")).unwrap();
+        fs::write(root.join("docs/templates/component.md"), "# {{name}}\n").unwrap();
+        fs::write(root.join("docs/templates/api.md"), "# {{name}}\n").unwrap();
+        fs::write(root.join("docs/templates/notes.txt"), "").unwrap();
+        fs::write(
+            root.join(".pave.toml"),
+            "[pave]\nversion = \"0.1\"\n\n[docs]\nroot = \"docs\"\ntemplates = \"docs/templates\"\n",
+        )
+        .unwrap();
+
+        let args = NewArgs {
+            doc_type: None,
+            name: None,
+            output: None,
+            interactive: false,
+            dry_run: false,
+            from: None,
+            list_templates: true,
+            config: Some(root.join(".pave.toml")),
+        };
+        let mut out = Vec::new();
+        run(args, &mut out).unwrap();
+        let listing = String::from_utf8(out).unwrap();
+
+        assert!(listing.contains("  component    Services, libraries, and modules\n"));
+        assert!(listing.contains("  adr          Architecture decision records\n"));
+        assert!(listing.contains("Custom templates (docs/templates):\n"));
+        assert!(listing.contains("  api.md\n"));
+        assert!(listing.contains("  component.md overrides built-in component\n"));
+        assert!(!listing.contains("notes.txt"));
+    }
 }
@@ -5,10 +5,15 @@
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
-use crate::config::{CONFIG_FILENAME, PaveConfig, RulesSection};
+use crate::commands::changed;
+use crate::config::{PaveConfig, RulesSection};
+use crate::parser::ParsedDoc;
+use crate::paths::PathsMatcher;
 use crate::templates::{TemplateType, get_template};
+use crate::verification::extract_verification_spec;
 
 /// Output format for the generated prompt.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -20,6 +25,18 @@ pub enum OutputFormat {
     Json,
 }
 
+/// How documents and sections are framed inside the generated prompt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PromptFormat {
+    /// Markdown headings and fenced blocks (default).
+    #[default]
+    Markdown,
+    /// `<document path=...>` and `<section name=...>` tags.
+    Xml,
+    /// A [`StructuredPrompt`] serialized as JSON.
+    Json,
+}
+
 /// Options for generating a prompt.
 #[derive(Debug, Clone)]
 pub struct PromptOptions {
@@ -33,6 +50,12 @@ pub struct PromptOptions {
     pub context_paths: Vec<String>,
     /// Output format.
     pub output_format: OutputFormat,
+    /// Framing of the prompt itself.
+    pub prompt_format: PromptFormat,
+    /// Explicit config file path (from `--config`).
+    pub config: Option<PathBuf>,
+    /// Prepend the system preamble from [`system_preamble`].
+    pub system: bool,
 }
 
 /// JSON output structure for programmatic use.
@@ -48,95 +71,319 @@ pub struct PromptOutput {
     pub context_files: Vec<String>,
 }
 
+/// Options for bundling the docs that cover a source path.
+#[derive(Debug, Clone)]
+pub struct PathsPromptOptions {
+    /// Glob selecting the source files to bundle docs for.
+    pub glob: String,
+    /// Output format.
+    pub output_format: OutputFormat,
+    /// Framing of the prompt itself.
+    pub prompt_format: PromptFormat,
+    /// Inline the source files each doc's `## Paths` match beneath it.
+    pub include_source: bool,
+    /// Cap on inlined source across the whole prompt, in bytes.
+    pub max_source_bytes: usize,
+    /// Explicit config file path (from `--config`).
+    pub config: Option<PathBuf>,
+    /// Prepend the system preamble from [`system_preamble`].
+    pub system: bool,
+}
+
+/// Default cap on source inlined by `prompt --include-source`, in bytes.
+pub const DEFAULT_MAX_SOURCE_BYTES: usize = 100_000;
+
+/// JSON output structure for `prompt --paths`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PathsPromptOutput {
+    /// The full generated prompt.
+    pub prompt: String,
+    /// Docs included, in prompt order.
+    pub docs: Vec<String>,
+    /// Source files inlined by `--include-source`, in prompt order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<String>,
+}
+
+/// Built-in preamble for `prompt --system`, replaced by `prompt.system_file`.
+pub const DEFAULT_SYSTEM_PROMPT: &str = "\
+This project is documented with the PAVED framework. Each doc is Markdown with \
+`##` sections: Purpose says what the thing is for, Paths lists the code it \
+covers, Interface and Configuration describe how it is used, Examples show \
+real input and output, and Gotchas and Decisions record pitfalls and the \
+reasons behind the design.
+
+The commands under `## Verification` are authoritative: `pave verify` runs \
+them, so where the prose disagrees with what they check, trust the commands. \
+When a change alters behavior a doc describes, update the doc in the same \
+change and keep its Verification commands passing.
+";
+
+/// A prompt broken into named parts, used for the `xml` and `json` formats.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructuredPrompt {
+    /// System preamble from `--system`, ahead of everything else.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system: Option<String>,
+    /// Opening instructions for the agent.
+    pub instructions: String,
+    /// Documents and files included as context, in prompt order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub documents: Vec<PromptDocument>,
+    /// Named prompt sections, ending with the task.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sections: Vec<PromptSection>,
+}
+
+/// A document or file included in a structured prompt.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PromptDocument {
+    /// Path of the document, as given or relative to the project root.
+    pub path: String,
+    /// The document's H1 title, for markdown documents.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The document's `##` sections, for markdown documents.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sections: Vec<PromptSection>,
+    /// Raw file content, for files that aren't split into sections.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+}
+
+/// A named block of prompt text.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PromptSection {
+    /// Section name, such as `Purpose` or `Task`.
+    pub name: String,
+    /// Section body without its heading.
+    pub content: String,
+}
+
+impl PromptDocument {
+    /// Split a markdown document into its title and `##` sections.
+    fn markdown(path: &str, content: &str) -> Result<Self> {
+        let parsed = ParsedDoc::parse_content(PathBuf::from(path), content)?;
+        Ok(Self {
+            path: path.to_string(),
+            title: parsed.title,
+            sections: parsed
+                .sections
+                .into_iter()
+                .map(|s| PromptSection::new(&s.name, &s.content))
+                .collect(),
+            content: None,
+        })
+    }
+
+    /// Include a file verbatim.
+    fn raw(path: &str, content: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            title: None,
+            sections: Vec::new(),
+            content: Some(content.trim_matches('\n').to_string()),
+        }
+    }
+}
+
+impl PromptSection {
+    fn new(name: &str, content: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            content: content.trim_matches('\n').to_string(),
+        }
+    }
+}
+
+impl StructuredPrompt {
+    /// Render with `<document>` and `<section>` tags.
+    fn to_xml(&self) -> String {
+        let mut out = String::from("<prompt>\n");
+        if let Some(system) = &self.system {
+            push_element(&mut out, "<system>", system, "</system>");
+        }
+        push_element(
+            &mut out,
+            "<instructions>",
+            &self.instructions,
+            "</instructions>",
+        );
+        if !self.documents.is_empty() {
+            out.push_str("<documents>\n");
+            for doc in &self.documents {
+                out.push_str(&format!("<document path=\"{}\"", xml_escape(&doc.path)));
+                if let Some(title) = &doc.title {
+                    out.push_str(&format!(" title=\"{}\"", xml_escape(title)));
+                }
+                out.push_str(">\n");
+                if let Some(content) = &doc.content {
+                    out.push_str(&xml_escape(content));
+                    out.push('\n');
+                }
+                for section in &doc.sections {
+                    push_section(&mut out, section);
+                }
+                out.push_str("</document>\n");
+            }
+            out.push_str("</documents>\n");
+        }
+        for section in &self.sections {
+            push_section(&mut out, section);
+        }
+        out.push_str("</prompt>\n");
+        out
+    }
+
+    /// Render as pretty-printed JSON.
+    fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).context("failed to serialize JSON prompt")
+    }
+}
+
+fn push_section(out: &mut String, section: &PromptSection) {
+    let open = format!("<section name=\"{}\">", xml_escape(&section.name));
+    push_element(out, &open, &section.content, "</section>");
+}
+
+fn push_element(out: &mut String, open: &str, content: &str, close: &str) {
+    out.push_str(open);
+    out.push('\n');
+    if !content.is_empty() {
+        out.push_str(&xml_escape(content));
+        out.push('\n');
+    }
+    out.push_str(close);
+    out.push('\n');
+}
+
+/// Escape text for use in XML content or a double-quoted attribute.
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
 /// Generate a prompt for AI agents to create PAVED documentation.
 pub fn generate_prompt(options: &PromptOptions) -> Result<String> {
-    let config = load_config_or_default()?;
+    let (config, project_root) = crate::config::load_project(options.config.as_deref())?;
+    let system = system_preamble(options.system, &config, &project_root)?;
     let template = get_template(options.doc_type);
     let rules = format_rules(&config.rules);
     let paved_sections = get_paved_sections(options.doc_type);
     let doc_type_name = get_doc_type_name(options.doc_type);
 
-    let mut prompt = String::new();
-
-    // Header
-    prompt.push_str(&format!(
-        "You are documenting a software {} using the PAVED framework.\n\n",
-        doc_type_name
-    ));
-
-    // PAVED Structure section
-    prompt.push_str("## PAVED Structure\n");
-    prompt.push_str("Your document MUST include these sections:\n");
-    prompt.push_str(&paved_sections);
-    prompt.push('\n');
-
-    // Project Rules section
-    prompt.push_str("## Project Rules\n");
-    for rule in &rules {
-        prompt.push_str(&format!("- {}\n", rule));
-    }
-    prompt.push('\n');
-
-    // Template section
-    prompt.push_str("## Template\n");
-    prompt.push_str("Use this template as the starting structure:\n\n");
-    prompt.push_str("```markdown\n");
-    prompt.push_str(template);
-    prompt.push_str("```\n\n");
-
-    // Context section (if update or context files provided)
-    let mut has_context = false;
-    if options.update_path.is_some() || !options.context_paths.is_empty() {
-        prompt.push_str("## Context\n");
-        has_context = true;
-    }
-
-    // Include existing document content if updating
-    if let Some(update_path) = &options.update_path {
-        let existing_content = std::fs::read_to_string(update_path)
-            .with_context(|| format!("failed to read existing document: {}", update_path))?;
-        prompt.push_str("### Existing Document (to update)\n");
-        prompt.push_str("```markdown\n");
-        prompt.push_str(&existing_content);
-        prompt.push_str("```\n\n");
-    }
-
-    // Include context files
+    let existing = match &options.update_path {
+        Some(update_path) => Some(
+            std::fs::read_to_string(update_path)
+                .with_context(|| format!("failed to read existing document: {}", update_path))?,
+        ),
+        None => None,
+    };
+    let mut context_files = Vec::new();
     for path in &options.context_paths {
         let content = std::fs::read_to_string(path)
             .with_context(|| format!("failed to read context file: {}", path))?;
-        prompt.push_str(&format!("### Context: {}\n", path));
-        prompt.push_str("```\n");
-        prompt.push_str(&content);
-        prompt.push_str("```\n\n");
+        context_files.push((path, content));
     }
 
-    if has_context {
-        prompt.push('\n');
-    }
+    let header = format!(
+        "You are documenting a software {} using the PAVED framework.\n",
+        doc_type_name
+    );
+    let task = task_text(options, doc_type_name);
 
-    // Task section
-    prompt.push_str("## Task\n");
-    if let Some(name) = &options.name {
-        if options.update_path.is_some() {
-            prompt.push_str(&format!(
-                "Update the PAVED {} document for: {}\n",
-                doc_type_name, name
-            ));
-        } else {
-            prompt.push_str(&format!(
-                "Create a PAVED {} document for: {}\n",
-                doc_type_name, name
-            ));
+    let prompt = match options.prompt_format {
+        PromptFormat::Markdown => {
+            let mut prompt = system_markdown(system.as_deref());
+            prompt.push_str(&header);
+            prompt.push('\n');
+
+            // PAVED Structure section
+            prompt.push_str("## PAVED Structure\n");
+            prompt.push_str("Your document MUST include these sections:\n");
+            prompt.push_str(&paved_sections);
+            prompt.push('\n');
+
+            // Project Rules section
+            prompt.push_str("## Project Rules\n");
+            for rule in &rules {
+                prompt.push_str(&format!("- {}\n", rule));
+            }
+            prompt.push('\n');
+
+            // Template section
+            prompt.push_str("## Template\n");
+            prompt.push_str("Use this template as the starting structure:\n\n");
+            prompt.push_str("```markdown\n");
+            prompt.push_str(template);
+            prompt.push_str("```\n\n");
+
+            // Context section (if update or context files provided)
+            let has_context = existing.is_some() || !context_files.is_empty();
+            if has_context {
+                prompt.push_str("## Context\n");
+            }
+
+            // Include existing document content if updating
+            if let Some(existing) = &existing {
+                prompt.push_str("### Existing Document (to update)\n");
+                prompt.push_str("```markdown\n");
+                prompt.push_str(existing);
+                prompt.push_str("```\n\n");
+            }
+
+            // Include context files
+            for (path, content) in &context_files {
+                prompt.push_str(&format!("### Context: {}\n", path));
+                prompt.push_str("```\n");
+                prompt.push_str(content);
+                prompt.push_str("```\n\n");
+            }
+
+            if has_context {
+                prompt.push('\n');
+            }
+
+            // Task section
+            prompt.push_str("## Task\n");
+            prompt.push_str(&task);
+            prompt
         }
-    } else if options.update_path.is_some() {
-        prompt.push_str(&format!(
-            "Update the PAVED {} document shown above.\n",
-            doc_type_name
-        ));
-    } else {
-        prompt.push_str(&format!("Create a PAVED {} document.\n", doc_type_name));
-    }
+        PromptFormat::Xml | PromptFormat::Json => {
+            let mut documents = Vec::new();
+            if let (Some(path), Some(existing)) = (&options.update_path, &existing) {
+                documents.push(PromptDocument::markdown(path, existing)?);
+            }
+            for (path, content) in &context_files {
+                documents.push(PromptDocument::raw(path, content));
+            }
+            let rule_list: Vec<String> = rules.iter().map(|r| format!("- {}", r)).collect();
+            let structured = StructuredPrompt {
+                system,
+                instructions: header.trim_end().to_string(),
+                documents,
+                sections: vec![
+                    PromptSection::new(
+                        "PAVED Structure",
+                        &format!(
+                            "Your document MUST include these sections:\n{}",
+                            paved_sections
+                        ),
+                    ),
+                    PromptSection::new("Project Rules", &rule_list.join("\n")),
+                    PromptSection::new("Template", template),
+                    PromptSection::new("Task", &task),
+                ],
+            };
+            if options.prompt_format == PromptFormat::Xml {
+                structured.to_xml()
+            } else {
+                structured.to_json()?
+            }
+        }
+    };
 
     match options.output_format {
         OutputFormat::Text => Ok(prompt),
@@ -152,15 +399,277 @@ pub fn generate_prompt(options: &PromptOptions) -> Result<String> {
     }
 }
 
-/// Load configuration from .pave.toml or return defaults if not found.
-fn load_config_or_default() -> Result<PaveConfig> {
-    if Path::new(CONFIG_FILENAME).exists() {
-        PaveConfig::load(CONFIG_FILENAME)
-    } else {
-        Ok(PaveConfig::default())
+/// The task line telling the agent whether to create or update the doc.
+fn task_text(options: &PromptOptions, doc_type_name: &str) -> String {
+    match (&options.name, options.update_path.is_some()) {
+        (Some(name), true) => format!(
+            "Update the PAVED {} document for: {}\n",
+            doc_type_name, name
+        ),
+        (Some(name), false) => format!("Create a PAVED {} document for: {}\n", doc_type_name, name),
+        (None, true) => format!("Update the PAVED {} document shown above.\n", doc_type_name),
+        (None, false) => format!("Create a PAVED {} document.\n", doc_type_name),
     }
 }
 
+/// Generate a prompt bundling every doc whose `## Paths` cover files matching a glob.
+///
+/// Docs are concatenated alphabetically by path so the prompt is stable.
+pub fn generate_paths_prompt(options: &PathsPromptOptions) -> Result<String> {
+    let (config, project_root) = crate::config::load_project(options.config.as_deref())?;
+    let system = system_preamble(options.system, &config, &project_root)?;
+
+    let docs = find_docs_covering(&options.glob, &config, &project_root)?;
+    if docs.is_empty() {
+        anyhow::bail!(
+            "No docs cover files matching `{}`\n\nHint: check the glob is relative to the project root, or run `pave coverage` to list uncovered files",
+            options.glob
+        );
+    }
+
+    let header = format!(
+        "You are working on code matching `{}` in a project documented with the PAVED framework.\n\
+         The documents below describe that code. Follow them, and keep them accurate if your change alters the behavior they describe.\n",
+        options.glob
+    );
+    let mut contents = Vec::new();
+    let mut sources = SourceBudget::new(options.max_source_bytes);
+    for doc in &docs {
+        let content = std::fs::read_to_string(project_root.join(&doc.doc_path))
+            .with_context(|| format!("failed to read document: {}", doc.doc_path.display()))?;
+        let doc_sources = if options.include_source {
+            sources.take(&doc.patterns, &project_root)
+        } else {
+            Vec::new()
+        };
+        contents.push((doc.doc_path.display().to_string(), content, doc_sources));
+    }
+    let verification = verification_text(
+        contents
+            .iter()
+            .map(|(path, content, _)| (path.as_str(), content.as_str())),
+        &config,
+    );
+
+    let prompt = match options.prompt_format {
+        PromptFormat::Markdown => {
+            let mut prompt = system_markdown(system.as_deref());
+            prompt.push_str(&header);
+            prompt.push('\n');
+            for (path, content, doc_sources) in &contents {
+                prompt.push_str(&format!("## Document: {}\n", path));
+                prompt.push_str("```markdown\n");
+                prompt.push_str(content);
+                if !content.ends_with('\n') {
+                    prompt.push('\n');
+                }
+                prompt.push_str("```\n\n");
+                for (source_path, source) in doc_sources {
+                    let fence = fence_for(source);
+                    let language = Path::new(source_path)
+                        .extension()
+                        .map(|ext| ext.to_string_lossy().to_string())
+                        .unwrap_or_default();
+                    prompt.push_str(&format!("### Source: {}\n", source_path));
+                    prompt.push_str(&format!("{}{}\n{}", fence, language, source));
+                    if !source.ends_with('\n') {
+                        prompt.push('\n');
+                    }
+                    prompt.push_str(&format!("{}\n\n", fence));
+                }
+            }
+            if let Some(text) = &verification {
+                prompt.push_str(&format!("## {}\n{}", VERIFY_SECTION, text));
+            }
+            prompt
+        }
+        PromptFormat::Xml | PromptFormat::Json => {
+            let mut documents = Vec::new();
+            for (path, content, doc_sources) in &contents {
+                documents.push(PromptDocument::markdown(path, content)?);
+                documents.extend(
+                    doc_sources
+                        .iter()
+                        .map(|(source_path, source)| PromptDocument::raw(source_path, source)),
+                );
+            }
+            let structured = StructuredPrompt {
+                system,
+                instructions: header.trim_end().to_string(),
+                documents,
+                sections: verification
+                    .iter()
+                    .map(|text| PromptSection::new(VERIFY_SECTION, text))
+                    .collect(),
+            };
+            if options.prompt_format == PromptFormat::Xml {
+                structured.to_xml()
+            } else {
+                structured.to_json()?
+            }
+        }
+    };
+
+    match options.output_format {
+        OutputFormat::Text => Ok(prompt),
+        OutputFormat::Json => {
+            let output = PathsPromptOutput {
+                prompt,
+                docs: docs
+                    .iter()
+                    .map(|d| d.doc_path.display().to_string())
+                    .collect(),
+                sources: contents
+                    .iter()
+                    .flat_map(|(_, _, doc_sources)| doc_sources.iter().map(|(p, _)| p.clone()))
+                    .collect(),
+            };
+            serde_json::to_string_pretty(&output).context("failed to serialize JSON output")
+        }
+    }
+}
+
+/// Heading of the `--paths` prompt section listing the docs' checks.
+const VERIFY_SECTION: &str = "Verify Your Change";
+
+/// The Verification commands of `docs` (path and content pairs) as fenced
+/// blocks that keep their explanatory comments, grouped by doc. `None` when
+/// no doc has any.
+fn verification_text<'a>(
+    docs: impl IntoIterator<Item = (&'a str, &'a str)>,
+    config: &PaveConfig,
+) -> Option<String> {
+    let options = config.verification.parse_options();
+    let mut text = String::new();
+    for (path, content) in docs {
+        let Ok(doc) = ParsedDoc::parse_content_with(PathBuf::from(path), content, &options) else {
+            continue;
+        };
+        let Some(spec) = extract_verification_spec(&doc) else {
+            continue;
+        };
+        text.push_str(&format!("\nFrom {}:\n", path));
+        for item in &spec.items {
+            text.push_str(&item.prompt_block());
+        }
+    }
+    if text.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "Check your change with these commands; `pave verify` runs them.\n{}",
+        text
+    ))
+}
+
+/// The `--system` preamble: the text of `prompt.system_file` if set, else
+/// [`DEFAULT_SYSTEM_PROMPT`]. `None` when `--system` wasn't given.
+fn system_preamble(
+    enabled: bool,
+    config: &PaveConfig,
+    project_root: &Path,
+) -> Result<Option<String>> {
+    if !enabled {
+        return Ok(None);
+    }
+    let text = match &config.prompt.system_file {
+        Some(file) => {
+            let path = project_root.join(file);
+            std::fs::read_to_string(&path)
+                .with_context(|| format!("failed to read prompt.system_file: {}", path.display()))?
+        }
+        None => DEFAULT_SYSTEM_PROMPT.to_string(),
+    };
+    Ok(Some(text.trim_matches('\n').to_string()))
+}
+
+/// The preamble as a leading `## System` section, or nothing.
+fn system_markdown(system: Option<&str>) -> String {
+    system
+        .map(|text| format!("## System\n{}\n\n", text))
+        .unwrap_or_default()
+}
+
+/// Find docs covering at least one file matched by `glob`, sorted by path.
+fn find_docs_covering(
+    glob: &str,
+    config: &PaveConfig,
+    project_root: &Path,
+) -> Result<Vec<changed::DocMapping>> {
+    let files = PathsMatcher::new(&[glob]).matched_files(project_root);
+    let docs_roots = config.docs.resolve_root_dirs(project_root)?;
+
+    let mut docs: Vec<changed::DocMapping> = changed::load_doc_mappings(&docs_roots, project_root)?
+        .into_iter()
+        .filter(|doc| {
+            let matcher = PathsMatcher::new(&doc.patterns);
+            files.iter().any(|f| matcher.matches(f))
+        })
+        .collect();
+    docs.sort_by(|a, b| a.doc_path.cmp(&b.doc_path));
+    Ok(docs)
+}
+
+/// Source inlined by `--include-source`, shared across every doc so the
+/// prompt stays under one byte cap and no file appears twice.
+struct SourceBudget {
+    remaining: usize,
+    seen: HashSet<PathBuf>,
+}
+
+impl SourceBudget {
+    fn new(max_bytes: usize) -> Self {
+        Self {
+            remaining: max_bytes,
+            seen: HashSet::new(),
+        }
+    }
+
+    /// The `(path, content)` of each not-yet-included file `patterns` match.
+    ///
+    /// Content past the cap is cut off with a `[... truncated N bytes ...]`
+    /// marker; files that aren't UTF-8 text are skipped.
+    fn take(&mut self, patterns: &[String], project_root: &Path) -> Vec<(String, String)> {
+        let mut sources = Vec::new();
+        for file in PathsMatcher::new(patterns).matched_files(project_root) {
+            if !self.seen.insert(file.clone()) {
+                continue;
+            }
+            let Ok(content) = std::fs::read_to_string(project_root.join(&file)) else {
+                continue;
+            };
+            let path = file.to_string_lossy().replace('\\', "/");
+            if content.len() <= self.remaining {
+                self.remaining -= content.len();
+                sources.push((path, content));
+                continue;
+            }
+
+            let mut cut = self.remaining;
+            while !content.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            self.remaining = 0;
+            let mut kept = content[..cut].to_string();
+            if !kept.is_empty() && !kept.ends_with('\n') {
+                kept.push('\n');
+            }
+            kept.push_str(&format!(
+                "[... truncated {} bytes ...]\n",
+                content.len() - cut
+            ));
+            sources.push((path, kept));
+        }
+        sources
+    }
+}
+
+/// A code fence longer than any backtick run in `content`.
+fn fence_for(content: &str) -> String {
+    let longest = content.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
 /// Format rules section from configuration.
 fn format_rules(rules: &RulesSection) -> Vec<String> {
     let mut formatted = Vec::new();
@@ -232,6 +741,9 @@ mod tests {
             update_path: None,
             context_paths: vec![],
             output_format: OutputFormat::Text,
+            prompt_format: PromptFormat::Markdown,
+            config: None,
+            system: false,
         };
 
         let prompt = generate_prompt(&options).unwrap();
@@ -254,6 +766,9 @@ mod tests {
             update_path: None,
             context_paths: vec![],
             output_format: OutputFormat::Text,
+            prompt_format: PromptFormat::Markdown,
+            config: None,
+            system: false,
         };
 
         let prompt = generate_prompt(&options).unwrap();
@@ -274,6 +789,9 @@ mod tests {
             update_path: None,
             context_paths: vec![],
             output_format: OutputFormat::Text,
+            prompt_format: PromptFormat::Markdown,
+            config: None,
+            system: false,
         };
 
         let prompt = generate_prompt(&options).unwrap();
@@ -293,6 +811,9 @@ mod tests {
             update_path: None,
             context_paths: vec![],
             output_format: OutputFormat::Text,
+            prompt_format: PromptFormat::Markdown,
+            config: None,
+            system: false,
         };
 
         let prompt = generate_prompt(&options).unwrap();
@@ -311,6 +832,9 @@ mod tests {
             update_path: None,
             context_paths: vec![],
             output_format: OutputFormat::Text,
+            prompt_format: PromptFormat::Markdown,
+            config: None,
+            system: false,
         };
 
         let prompt = generate_prompt(&options).unwrap();
@@ -327,6 +851,9 @@ mod tests {
             update_path: None,
             context_paths: vec![],
             output_format: OutputFormat::Text,
+            prompt_format: PromptFormat::Markdown,
+            config: None,
+            system: false,
         };
 
         let prompt = generate_prompt(&options).unwrap();
@@ -343,6 +870,9 @@ mod tests {
             update_path: None,
             context_paths: vec![],
             output_format: OutputFormat::Json,
+            prompt_format: PromptFormat::Markdown,
+            config: None,
+            system: false,
         };
 
         let output = generate_prompt(&options).unwrap();
@@ -369,6 +899,9 @@ mod tests {
             update_path: Some(temp_file.to_string_lossy().to_string()),
             context_paths: vec![],
             output_format: OutputFormat::Text,
+            prompt_format: PromptFormat::Markdown,
+            config: None,
+            system: false,
         };
 
         let prompt = generate_prompt(&options).unwrap();
@@ -394,6 +927,7 @@ mod tests {
             warn_empty_paths: false,
             gradual: false,
             gradual_until: None,
+            section_casing: false,
         };
 
         let formatted = format_rules(&rules);
@@ -402,4 +936,276 @@ mod tests {
         assert!(!formatted.iter().any(|r| r.contains("Verification section")));
         assert!(formatted.iter().any(|r| r.contains("Examples")));
     }
+
+    fn paths_fixture() -> tempfile::TempDir {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(
+            root.join(".pave.toml"),
+            "[pave]\nversion = \"0.1\"\n\n[docs]\nroot = \"docs\"\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("docs/components")).unwrap();
+        std::fs::create_dir_all(root.join("src/auth")).unwrap();
+        std::fs::write(root.join("src/auth/login.rs"), "").unwrap();
+        std::fs::write(root.join("src/db.rs"), "").unwrap();
+        temp_dir
+    }
+
+    fn paths_options(root: &Path, glob: &str) -> PathsPromptOptions {
+        PathsPromptOptions {
+            glob: glob.to_string(),
+            output_format: OutputFormat::Text,
+            prompt_format: PromptFormat::Markdown,
+            include_source: false,
+            max_source_bytes: DEFAULT_MAX_SOURCE_BYTES,
+            config: Some(root.join(".pave.toml")),
+            system: false,
+        }
+    }
+
+    #[test]
+    fn paths_prompt_bundles_overlapping_docs_in_order() {
+        let temp_dir = paths_fixture();
+        let root = temp_dir.path();
+        std::fs::write(
+            root.join("docs/components/session.md"),
+            "# Session\n\n## Paths\n- `src/auth/**`\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("docs/components/auth.md"),
+            "# Auth\n\n## Paths\n- `src/auth/`\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("docs/components/db.md"),
+            "# Database\n\n## Paths\n- `src/db.rs`\n",
+        )
+        .unwrap();
+
+        let prompt = generate_paths_prompt(&paths_options(root, "src/auth/**")).unwrap();
+
+        let auth = prompt.find("## Document: docs/components/auth.md").unwrap();
+        let session = prompt
+            .find("## Document: docs/components/session.md")
+            .unwrap();
+        assert!(auth < session);
+        assert!(prompt.contains("# Auth"));
+        assert!(prompt.contains("# Session"));
+        assert!(!prompt.contains("# Database"));
+    }
+
+    #[test]
+    fn paths_prompt_ends_with_verification_commands_and_their_comments() {
+        let temp_dir = paths_fixture();
+        let root = temp_dir.path();
+        std::fs::write(
+            root.join("docs/components/auth.md"),
+            "# Auth\n\n## Paths\n- `src/auth/`\n\n## Verification\n```bash\n# Login must reject bad passwords\ncargo test auth\n```\n",
+        )
+        .unwrap();
+
+        let prompt = generate_paths_prompt(&paths_options(root, "src/auth/**")).unwrap();
+        let section = &prompt[prompt.find("## Verify Your Change\n").unwrap()..];
+        assert!(section.contains(
+            "From docs/components/auth.md:\n```bash\n# Login must reject bad passwords\ncargo test auth\n```\n"
+        ));
+
+        let prompt = generate_paths_prompt(&PathsPromptOptions {
+            prompt_format: PromptFormat::Json,
+            ..paths_options(root, "src/auth/**")
+        })
+        .unwrap();
+        let parsed: StructuredPrompt = serde_json::from_str(&prompt).unwrap();
+        assert_eq!(parsed.sections.len(), 1);
+        assert_eq!(parsed.sections[0].name, "Verify Your Change");
+        assert!(parsed.sections[0].content.contains("# Login must reject"));
+    }
+
+    #[test]
+    fn include_source_inlines_matched_files_up_to_the_byte_cap() {
+        let temp_dir = paths_fixture();
+        let root = temp_dir.path();
+        std::fs::write(
+            root.join("docs/components/auth.md"),
+            "# Auth\n\n## Paths\n- `src/auth/`\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("src/auth/login.rs"), "pub fn login() {}\n").unwrap();
+        std::fs::write(root.join("src/auth/session.rs"), "pub fn session() {}\n").unwrap();
+
+        let options = PathsPromptOptions {
+            include_source: true,
+            ..paths_options(root, "src/auth/**")
+        };
+        let prompt = generate_paths_prompt(&options).unwrap();
+        let doc = prompt.find("## Document: docs/components/auth.md").unwrap();
+        let login = prompt
+            .find("### Source: src/auth/login.rs\n```rs\npub fn login() {}\n```\n")
+            .unwrap();
+        assert!(doc < login);
+        assert!(prompt.contains("pub fn session() {}"));
+
+        // 18 bytes fit login.rs; session.rs is cut after its first 4 bytes
+        let prompt = generate_paths_prompt(&PathsPromptOptions {
+            max_source_bytes: 22,
+            ..options
+        })
+        .unwrap();
+        assert!(prompt.contains("pub fn login() {}\n"));
+        assert!(prompt.contains(
+            "### Source: src/auth/session.rs\n```rs\npub \n[... truncated 16 bytes ...]\n```\n"
+        ));
+        assert!(!prompt.contains("session() {}"));
+    }
+
+    #[test]
+    fn system_preamble_leads_the_prompt_and_reads_system_file() {
+        let temp_dir = paths_fixture();
+        let root = temp_dir.path();
+        write_format_docs(root);
+
+        let plain = generate_paths_prompt(&paths_options(root, "src/auth/**")).unwrap();
+        assert!(!plain.contains("## System"));
+
+        let options = PathsPromptOptions {
+            system: true,
+            ..paths_options(root, "src/auth/**")
+        };
+        let prompt = generate_paths_prompt(&options).unwrap();
+        assert!(prompt.starts_with("## System\nThis project is documented with the PAVED"));
+        let authoritative = prompt.find("are authoritative").unwrap();
+        let doc = prompt.find("## Document: docs/components/auth.md").unwrap();
+        assert!(authoritative < doc);
+
+        let mut config = std::fs::read_to_string(root.join(".pave.toml")).unwrap();
+        config.push_str("\n[prompt]\nsystem_file = \"agents/system.md\"\n");
+        std::fs::write(root.join(".pave.toml"), config).unwrap();
+        std::fs::create_dir_all(root.join("agents")).unwrap();
+        std::fs::write(root.join("agents/system.md"), "Docs win over guesses.\n").unwrap();
+
+        let prompt = generate_paths_prompt(&options).unwrap();
+        assert!(prompt.starts_with("## System\nDocs win over guesses.\n\n"));
+        assert!(!prompt.contains("are authoritative"));
+
+        let xml = generate_paths_prompt(&PathsPromptOptions {
+            prompt_format: PromptFormat::Xml,
+            ..options
+        })
+        .unwrap();
+        let system = xml
+            .find("<system>\nDocs win over guesses.\n</system>")
+            .unwrap();
+        assert!(system < xml.find("<document ").unwrap());
+    }
+
+    #[test]
+    fn paths_prompt_errors_when_nothing_matches() {
+        let temp_dir = paths_fixture();
+        let root = temp_dir.path();
+
+        let err = generate_paths_prompt(&paths_options(root, "src/auth/**")).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("No docs cover files matching `src/auth/**`"));
+        assert!(message.contains("Hint:"));
+    }
+
+    fn write_format_docs(root: &Path) {
+        std::fs::write(
+            root.join("docs/components/auth.md"),
+            "# Auth\n\n## Purpose\nHandles <login> & sessions.\n\n## Paths\n- `src/auth/`\n",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn xml_format_nests_sections_inside_documents() {
+        let temp_dir = paths_fixture();
+        let root = temp_dir.path();
+        write_format_docs(root);
+
+        let prompt = generate_paths_prompt(&PathsPromptOptions {
+            prompt_format: PromptFormat::Xml,
+            ..paths_options(root, "src/auth/**")
+        })
+        .unwrap();
+
+        let doc_open = prompt
+            .find("<document path=\"docs/components/auth.md\" title=\"Auth\">")
+            .unwrap();
+        let purpose = prompt.find("<section name=\"Purpose\">").unwrap();
+        let body = prompt
+            .find("Handles &lt;login&gt; &amp; sessions.")
+            .unwrap();
+        let paths = prompt.find("<section name=\"Paths\">").unwrap();
+        let doc_close = prompt.find("</document>").unwrap();
+        assert!(doc_open < purpose && purpose < body && body < paths && paths < doc_close);
+        assert_eq!(prompt.matches("<section ").count(), 2);
+        assert_eq!(prompt.matches("</section>").count(), 2);
+        assert!(prompt.starts_with("<prompt>\n<instructions>\n"));
+        assert!(prompt.ends_with("</documents>\n</prompt>\n"));
+    }
+
+    #[test]
+    fn json_format_round_trips_section_names_and_content() {
+        let temp_dir = paths_fixture();
+        let root = temp_dir.path();
+        write_format_docs(root);
+
+        let prompt = generate_paths_prompt(&PathsPromptOptions {
+            prompt_format: PromptFormat::Json,
+            ..paths_options(root, "src/auth/**")
+        })
+        .unwrap();
+
+        let parsed: StructuredPrompt = serde_json::from_str(&prompt).unwrap();
+        assert_eq!(parsed.documents.len(), 1);
+        let doc = &parsed.documents[0];
+        assert_eq!(doc.path, "docs/components/auth.md");
+        assert_eq!(doc.title.as_deref(), Some("Auth"));
+        assert_eq!(
+            doc.sections,
+            vec![
+                PromptSection::new("Purpose", "Handles <login> & sessions."),
+                PromptSection::new("Paths", "- `src/auth/`"),
+            ]
+        );
+        assert_eq!(serde_json::to_string_pretty(&parsed).unwrap(), prompt);
+    }
+
+    #[test]
+    fn structured_formats_end_with_the_task() {
+        let options = PromptOptions {
+            doc_type: TemplateType::Runbook,
+            name: Some("deploy".to_string()),
+            update_path: None,
+            context_paths: vec![],
+            output_format: OutputFormat::Text,
+            prompt_format: PromptFormat::Json,
+            config: None,
+            system: false,
+        };
+
+        let parsed: StructuredPrompt =
+            serde_json::from_str(&generate_prompt(&options).unwrap()).unwrap();
+        let names: Vec<&str> = parsed.sections.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["PAVED Structure", "Project Rules", "Template", "Task"]
+        );
+        assert_eq!(
+            parsed.sections[3].content,
+            "Create a PAVED runbook document for: deploy"
+        );
+
+        let xml = generate_prompt(&PromptOptions {
+            prompt_format: PromptFormat::Xml,
+            ..options
+        })
+        .unwrap();
+        assert!(xml.ends_with(
+            "<section name=\"Task\">\nCreate a PAVED runbook document for: deploy\n</section>\n</prompt>\n"
+        ));
+    }
 }
@@ -0,0 +1,183 @@
+//! Implementation of the `pave stats` command for summarizing the docs corpus.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::cli::StatsOutputFormat;
+use crate::commands::coverage;
+use crate::config::PaveConfig;
+use crate::parser::ParsedDoc;
+use crate::paths::walk_dirs;
+use crate::verification::extract_verification_spec;
+
+/// Arguments for the `pave stats` command.
+pub struct StatsArgs {
+    /// Output format.
+    pub format: StatsOutputFormat,
+    /// Explicit config file path (from `--config`).
+    pub config: Option<PathBuf>,
+}
+
+/// Summary numbers for the docs corpus.
+#[derive(Debug, Serialize)]
+pub struct StatsResults {
+    /// Number of documents (excluding `index.md` and templates).
+    pub docs: usize,
+    /// Lines across all documents.
+    pub total_lines: usize,
+    /// Mean lines per document (0 without documents).
+    pub average_lines: f64,
+    /// Documents whose Verification section has runnable commands.
+    pub with_verification: usize,
+    /// Percentage of code files covered by a doc's `## Paths`.
+    pub coverage_percent: f64,
+    /// The configured `rules.max_lines`.
+    pub max_lines: u32,
+    /// Documents longer than `max_lines`.
+    pub over_max_lines: usize,
+}
+
+/// Execute the `pave stats` command.
+pub fn execute(args: StatsArgs) -> Result<()> {
+    let config_path = crate::config::find_config(args.config.as_deref())?;
+    let config = PaveConfig::load(&config_path)?;
+    let config_dir = &crate::config::config_base_dir(&config_path);
+
+    let results = compute_stats(&config, config_dir)?;
+
+    match args.format {
+        StatsOutputFormat::Text => output_text(&results),
+        StatsOutputFormat::Json => {
+            let json =
+                serde_json::to_string_pretty(&results).context("Failed to serialize results")?;
+            println!("{}", json);
+        }
+    }
+    Ok(())
+}
+
+/// Compute corpus statistics for the project rooted at `config_dir`.
+fn compute_stats(config: &PaveConfig, config_dir: &Path) -> Result<StatsResults> {
+    let files = walk_dirs(&config.docs.resolve_root_dirs(config_dir)?, true, |path| {
+        path.is_dir() && path.file_name().is_some_and(|n| n == "templates")
+    });
+
+    let parse_options = config.verification.parse_options();
+    let max_lines = config.rules.max_lines;
+    let mut docs = 0;
+    let mut total_lines = 0;
+    let mut with_verification = 0;
+    let mut over_max_lines = 0;
+    for path in files.iter().filter(|path| {
+        path.extension().is_some_and(|ext| ext == "md")
+            && path.file_name().is_some_and(|n| n != "index.md")
+    }) {
+        let doc = ParsedDoc::parse_with(path, &parse_options)?;
+        docs += 1;
+        total_lines += doc.line_count;
+        if extract_verification_spec(&doc).is_some() {
+            with_verification += 1;
+        }
+        if doc.line_count > max_lines as usize {
+            over_max_lines += 1;
+        }
+    }
+
+    Ok(StatsResults {
+        docs,
+        total_lines,
+        average_lines: if docs == 0 {
+            0.0
+        } else {
+            total_lines as f64 / docs as f64
+        },
+        with_verification,
+        coverage_percent: coverage::overall_percentage(config, config_dir)?,
+        max_lines,
+        over_max_lines,
+    })
+}
+
+/// Output statistics in text format.
+fn output_text(results: &StatsResults) {
+    println!("Documentation Stats");
+    println!("===================");
+    println!();
+    println!("Documents:          {}", results.docs);
+    println!("Total lines:        {}", results.total_lines);
+    println!("Average lines:      {:.1}", results.average_lines);
+    println!(
+        "With verification:  {} of {}",
+        results.with_verification, results.docs
+    );
+    println!("Code coverage:      {:.1}%", results.coverage_percent);
+    println!(
+        "{:<20}{}",
+        format!("Over {} lines:", results.max_lines),
+        results.over_max_lines
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn compute_stats_counts_fixture_repo() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("docs/components")).unwrap();
+        fs::create_dir_all(root.join("docs/templates")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        for name in ["a.rs", "b.rs", "c.rs", "d.rs"] {
+            fs::write(root.join("src").join(name), "").unwrap();
+        }
+        // 10 lines, verified, covers a.rs
+        fs::write(
+            root.join("docs/components/a.md"),
+            "# A\n\n## Verification\n\n```bash\ntrue\n```\n\n## Paths\n- `src/a.rs`\n",
+        )
+        .unwrap();
+        // 12 lines, over the limit, verification without commands
+        fs::write(
+            root.join("docs/components/b.md"),
+            format!(
+                "# B\n\n## Verification\n\nRead it.\n{}",
+                "filler\n".repeat(7)
+            ),
+        )
+        .unwrap();
+        // Not counted
+        fs::write(root.join("docs/index.md"), "# Index\n").unwrap();
+        fs::write(root.join("docs/templates/t.md"), "# T\n").unwrap();
+
+        let mut config = PaveConfig::default();
+        config.rules.max_lines = 10;
+        let stats = compute_stats(&config, root).unwrap();
+
+        assert_eq!(stats.docs, 2);
+        assert_eq!(stats.total_lines, 22);
+        assert_eq!(stats.average_lines, 11.0);
+        assert_eq!(stats.with_verification, 1);
+        assert_eq!(stats.over_max_lines, 1);
+        assert_eq!(stats.coverage_percent, 25.0);
+
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(json["docs"], 2);
+        assert_eq!(json["max_lines"], 10);
+    }
+
+    #[test]
+    fn compute_stats_handles_empty_docs_root() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("docs")).unwrap();
+
+        let stats = compute_stats(&PaveConfig::default(), temp_dir.path()).unwrap();
+        assert_eq!(stats.docs, 0);
+        assert_eq!(stats.average_lines, 0.0);
+        assert_eq!(stats.coverage_percent, 100.0);
+    }
+}
@@ -3,13 +3,12 @@
 use anyhow::{Context, Result};
 use serde::Serialize;
 use std::collections::{HashMap, HashSet};
-use std::env;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use crate::cli::StatusOutputFormat;
 use crate::commands::hooks::{PAVE_HOOK_MARKER, find_git_hooks_dir_from};
-use crate::config::{CONFIG_FILENAME, PaveConfig};
+use crate::config::PaveConfig;
 use crate::parser::ParsedDoc;
 use crate::rules::{DocType, RulesEngine, detect_doc_type};
 
@@ -26,6 +25,8 @@ pub struct StatusArgs {
     pub changed: bool,
     /// Base ref for --changed comparison.
     pub base: Option<String>,
+    /// Explicit config file path (from `--config`).
+    pub config: Option<PathBuf>,
 }
 
 /// Statistics about document compliance by type.
@@ -136,14 +137,13 @@ impl StatusResults {
 /// Execute the `pave status` command.
 pub fn execute(args: StatusArgs) -> Result<()> {
     // Find and load config
-    let config_path = find_config()?;
+    let config_path = crate::config::find_config(args.config.as_deref())?;
     let config = PaveConfig::load(&config_path)?;
-    let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+    let config_dir = &crate::config::config_base_dir(&config_path);
 
     // Determine paths to check
-    let docs_root = config_dir.join(&config.docs.root);
     let paths = if args.paths.is_empty() {
-        vec![docs_root.clone()]
+        config.docs.resolve_root_dirs(config_dir)?
     } else {
         args.paths.clone()
     };
@@ -336,27 +336,6 @@ fn check_hooks_installed(config_dir: &Path) -> bool {
     false
 }
 
-/// Find the .pave.toml config file by walking up from the current directory.
-fn find_config() -> Result<PathBuf> {
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let mut dir = current_dir.as_path();
-
-    loop {
-        let config_path = dir.join(CONFIG_FILENAME);
-        if config_path.exists() {
-            return Ok(config_path);
-        }
-
-        match dir.parent() {
-            Some(parent) => dir = parent,
-            None => anyhow::bail!(
-                "No {} found in current directory or any parent directory",
-                CONFIG_FILENAME
-            ),
-        }
-    }
-}
-
 /// Find all markdown files in the given paths.
 fn find_markdown_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
     let mut files = Vec::new();
@@ -378,6 +357,7 @@ fn find_markdown_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
 
     // Sort for consistent output
     files.sort();
+    files.dedup();
     Ok(files)
 }
 
@@ -1,20 +1,23 @@
 //! Implementation of the `pave verify` command for running verification commands.
 
 use anyhow::{Context, Result};
-use regex::Regex;
 use serde::Serialize;
-use std::env;
+use std::collections::HashSet;
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::process::Command;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use crate::cli::OutputFormat;
-use crate::config::{CONFIG_FILENAME, PaveConfig, RulesSection};
+use crate::cache::{self, VerifyCache};
+use crate::cli::{CaptureMode, MissingShell, OutputFormat, PrintCommand};
+use crate::color::{self, Palette};
+use crate::commands::changed::{determine_base_ref, impacted_doc_paths, staged_impacted_doc_paths};
+use crate::config::{PaveConfig, RulesSection};
 use crate::parser::ParsedDoc;
+use crate::paths::PathsMatcher;
 use crate::verification::{
-    OutputMatcher, VerificationItem, VerificationSpec, extract_verification_spec,
+    ExitCodes, VerificationItem, VerificationSpec, extract_verification_spec,
 };
+use crate::verify::{self, Capture, ItemResult, MatchOutcome, RunOptions, Shell};
 
 /// Arguments for the `pave verify` command.
 pub struct VerifyArgs {
@@ -24,10 +27,72 @@ pub struct VerifyArgs {
     pub format: OutputFormat,
     /// Path to write JSON report.
     pub report: Option<PathBuf>,
-    /// Timeout per command in seconds.
-    pub timeout: u32,
+    /// Append the report to `report` as one JSON line instead of overwriting.
+    pub append: bool,
+    /// Path to write only the aggregate counts to, as JSON.
+    pub summary_json: Option<PathBuf>,
+    /// Directory to save every command's full stdout and stderr in.
+    pub out_dir: Option<PathBuf>,
+    /// Timeout per command in seconds, overriding doc and config timeouts.
+    /// `Some(0)` disables timeouts.
+    pub timeout: Option<u32>,
     /// Continue running after first failure.
     pub keep_going: bool,
+    /// Stop the run once this many items have failed.
+    pub bail_after: Option<usize>,
+    /// Maximum number of items to run at once.
+    pub jobs: usize,
+    /// Ignore cached results and re-run every command.
+    pub no_cache: bool,
+    /// Skip commands that passed within this window, whatever their inputs.
+    pub since: Option<Duration>,
+    /// Only verify docs impacted by changes since the base ref.
+    pub changed: bool,
+    /// Base ref for `--changed` comparison.
+    pub base: Option<String>,
+    /// With `--changed`, use the staged changes instead of the base ref.
+    pub staged: bool,
+    /// Show captured output for every command, not just failures.
+    pub verbose: bool,
+    /// Run identical commands in a document only once.
+    pub dedupe: bool,
+    /// Print a numbered list of verification items instead of running them.
+    pub list: bool,
+    /// Run only the item with this number from `--list`.
+    pub only: Option<usize>,
+    /// Run only the items that failed in the previous run.
+    pub only_failed: bool,
+    /// Print the docs that have verification items instead of running them.
+    pub list_docs: bool,
+    /// Print the docs with an item that failed in the previous run.
+    pub list_failing_docs: bool,
+    /// Interpreter to run commands under, overriding `verification.shell`.
+    pub shell: Option<String>,
+    /// Whether a missing shell fails the run or skips the commands needing it.
+    pub on_missing_shell: MissingShell,
+    /// Which output streams to capture from each command.
+    pub capture: CaptureMode,
+    /// Run each item in a fresh temporary directory unless it sets its own.
+    pub isolated: bool,
+    /// Print a reproducible one-liner for failed items, or for every item.
+    pub print_command: Option<PrintCommand>,
+    /// Show environment values in printed commands instead of masking
+    /// secret-looking ones.
+    pub show_env: bool,
+    /// Strict output matching, overriding `rules.strict_output_matching`.
+    pub strict_output: Option<bool>,
+    /// Report timed-out commands as inconclusive instead of failing the run.
+    pub allow_timeout: bool,
+    /// Run only items bearing at least one of these tags.
+    pub tags: Vec<String>,
+    /// Skip items bearing any of these tags.
+    pub exclude_tags: Vec<String>,
+    /// Commands run once before any doc is verified.
+    pub before: Vec<String>,
+    /// Commands run once after every doc, even when verification fails.
+    pub after: Vec<String>,
+    /// Explicit config file path (from `--config`).
+    pub config: Option<PathBuf>,
 }
 
 /// Status of a verification command execution.
@@ -57,6 +122,9 @@ pub struct OutputMismatch {
 pub struct CommandResult {
     /// The command that was run.
     pub command: String,
+    /// The item's `# name:` label, if it has one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
     /// Status of the command.
     pub status: VerifyStatus,
     /// Exit code if the command completed.
@@ -64,6 +132,10 @@ pub struct CommandResult {
     pub exit_code: Option<i32>,
     /// Expected exit code.
     pub expected_exit_code: i32,
+    /// Accepted exit codes from an `# expect-exit:` set or range, such as
+    /// `"0,1"`; when present it decides instead of `expected_exit_code`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_exit_codes: Option<ExitCodes>,
     /// Standard output.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stdout: Option<String>,
@@ -79,9 +151,154 @@ pub struct CommandResult {
     /// Working directory used for the command.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub working_dir: Option<PathBuf>,
+    /// Temporary directory the command ran in under `--isolated`, since removed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub isolated_dir: Option<PathBuf>,
     /// Environment variables set for the command.
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub env_vars: Vec<(String, String)>,
+    /// Whether the result was reused from the cache instead of running.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub cached: bool,
+    /// Lines of every block that ran this command, when deduplicated.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub deduped_lines: Vec<usize>,
+    /// How the command was started, if it ran.
+    #[serde(skip)]
+    pub launch: Option<Launch>,
+    /// Copy-pasteable one-liner reproducing the command (`--print-command`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reproduce: Option<String>,
+}
+
+/// How a verification command was started: enough to rerun it by hand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Launch {
+    /// Absolute directory the command ran in; `None` for an `--isolated`
+    /// scratch directory, which is gone by now.
+    pub dir: Option<PathBuf>,
+    /// Environment variables set for the command.
+    pub env: Vec<(String, String)>,
+    /// Shell and its arguments, or a shebang interpreter line split into words.
+    pub program: Vec<String>,
+    /// Whether the command is a shebang script fed to `program` on stdin.
+    pub script: bool,
+}
+
+impl Launch {
+    /// Describe how `run_item` starts `item` with `options`.
+    fn new(item: &VerificationItem, options: &RunOptions, isolated: bool) -> Self {
+        let dir = match (&item.working_dir, &options.working_dir) {
+            (Some(dir), _) => Some(dir),
+            (None, _) if isolated => None,
+            (None, dir) => dir.as_ref(),
+        };
+        let program = match &item.interpreter {
+            Some(interpreter) => interpreter.split_whitespace().map(String::from).collect(),
+            None => std::iter::once(options.shell.program.clone())
+                .chain(options.shell.args.iter().cloned())
+                .collect(),
+        };
+        Self {
+            dir: dir.map(|dir| absolute_dir(dir)),
+            env: options.env.iter().chain(&item.env_vars).cloned().collect(),
+            program,
+            script: item.interpreter.is_some(),
+        }
+    }
+
+    /// Render a POSIX shell one-liner that reruns `command`, such as
+    /// `cd /repo && FOO=1 bash -c 'make test'`. Values of secret-looking
+    /// variables are masked unless `show_env`.
+    fn render(&self, command: &str, show_env: bool) -> String {
+        let mut line = match &self.dir {
+            Some(dir) => format!("cd {} && ", shell_quote(&dir.to_string_lossy())),
+            None => "cd \"$(mktemp -d)\" && ".to_string(),
+        };
+        if self.script {
+            line.push_str(&format!("printf '%s\\n' {} | ", shell_quote(command)));
+        }
+        for (key, value) in &self.env {
+            let value = if show_env || !is_sensitive_env(key) {
+                value.as_str()
+            } else {
+                "***"
+            };
+            line.push_str(&format!("{}={} ", key, shell_quote(value)));
+        }
+        let program: Vec<String> = self.program.iter().map(|word| shell_quote(word)).collect();
+        line.push_str(&program.join(" "));
+        if self.script {
+            line.push_str(" /dev/stdin");
+        } else {
+            line.push(' ');
+            line.push_str(&shell_quote(command));
+        }
+        line
+    }
+}
+
+/// Make `dir` absolute against the current directory, so a printed `cd`
+/// works from anywhere.
+fn absolute_dir(dir: &Path) -> PathBuf {
+    let dir = if dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        dir
+    };
+    std::path::absolute(dir).unwrap_or_else(|_| dir.to_path_buf())
+}
+
+/// Whether an environment variable's name suggests its value is a secret.
+fn is_sensitive_env(key: &str) -> bool {
+    const MARKERS: [&str; 7] = [
+        "TOKEN",
+        "SECRET",
+        "PASSWORD",
+        "PASSWD",
+        "KEY",
+        "CREDENTIAL",
+        "AUTH",
+    ];
+    let key = key.to_ascii_uppercase();
+    MARKERS.iter().any(|marker| key.contains(marker))
+}
+
+/// Quote `word` for a POSIX shell, leaving plain words as they are.
+fn shell_quote(word: &str) -> String {
+    let plain = !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-./:=,+@%".contains(c));
+    if plain {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', "'\\''"))
+    }
+}
+
+impl CommandResult {
+    /// What to call the command in human-readable output: its label if it
+    /// has one, otherwise the command itself.
+    fn display_name(&self) -> &str {
+        self.label.as_deref().unwrap_or(&self.command)
+    }
+
+    /// Returns true if `code` is one of the expected exit codes.
+    fn expects_exit_code(&self, code: i32) -> bool {
+        match &self.expected_exit_codes {
+            Some(codes) => codes.contains(code),
+            None => code == self.expected_exit_code,
+        }
+    }
+
+    /// The expected exit codes as shown in human-readable output.
+    fn expected_exit_description(&self) -> String {
+        match &self.expected_exit_codes {
+            Some(codes) => codes.to_string(),
+            None => self.expected_exit_code.to_string(),
+        }
+    }
 }
 
 /// Result of verifying a single document.
@@ -95,6 +312,17 @@ pub struct DocumentResult {
     pub commands: Vec<CommandResult>,
     /// Overall status of the document.
     pub status: VerifyStatus,
+    /// Commands left out by `# pave: skip` or a `skip` fence.
+    #[serde(skip_serializing_if = "is_zero")]
+    pub skipped: usize,
+    /// Whether the doc is listed in `verification.allow_failure`, so its
+    /// failures don't fail the run.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub allow_failure: bool,
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
 }
 
 impl DocumentResult {
@@ -104,15 +332,20 @@ impl DocumentResult {
             section_line: spec.section_line,
             commands: Vec::new(),
             status: VerifyStatus::Pass,
+            skipped: spec.skipped,
+            allow_failure: false,
         }
     }
 
     fn add_result(&mut self, result: CommandResult) {
-        // Fail/Timeout always override other statuses
+        // Fail overrides everything, Timeout everything but Fail
         // Warn only upgrades from Pass
         match result.status {
-            VerifyStatus::Fail | VerifyStatus::Timeout => {
-                self.status = result.status;
+            VerifyStatus::Fail => self.status = VerifyStatus::Fail,
+            VerifyStatus::Timeout => {
+                if self.status != VerifyStatus::Fail {
+                    self.status = VerifyStatus::Timeout;
+                }
             }
             VerifyStatus::Warn => {
                 if self.status == VerifyStatus::Pass {
@@ -124,9 +357,18 @@ impl DocumentResult {
         self.commands.push(result);
     }
 
-    fn is_success(&self) -> bool {
-        // Pass and Warn are both considered success (warnings don't fail verification)
-        self.status == VerifyStatus::Pass || self.status == VerifyStatus::Warn
+    /// Pass and Warn are both success (warnings don't fail verification);
+    /// Timeout is too when timeouts are allowed. Anything goes in a doc
+    /// that is allowed to fail.
+    fn is_success(&self, allow_timeout: bool) -> bool {
+        if self.allow_failure {
+            return true;
+        }
+        match self.status {
+            VerifyStatus::Pass | VerifyStatus::Warn => true,
+            VerifyStatus::Timeout => allow_timeout,
+            VerifyStatus::Fail | VerifyStatus::Skipped => false,
+        }
     }
 }
 
@@ -137,12 +379,25 @@ pub struct VerifyResults {
     pub documents_verified: usize,
     /// Number of commands executed.
     pub commands_executed: usize,
+    /// Number of commands skipped because a cached pass was still valid.
+    pub commands_cached: usize,
     /// Number of commands that passed.
     pub commands_passed: usize,
     /// Number of commands that had warnings (output mismatch but not strict).
     pub commands_warned: usize,
-    /// Number of commands that failed.
+    /// Number of commands that failed (wrong exit code or, under strict
+    /// matching, wrong output).
     pub commands_failed: usize,
+    /// Number of commands that timed out, counted apart from failures.
+    pub commands_timed_out: usize,
+    /// Number of failures and timeouts in docs allowed to fail, counted
+    /// apart from both.
+    pub commands_allowed_failures: usize,
+    /// Number of commands left out by skip annotations (never run).
+    pub commands_skipped: usize,
+    /// Number of commands never run because `--bail-after` stopped the run.
+    #[serde(skip_serializing_if = "is_zero")]
+    pub commands_not_run: usize,
     /// Results per document.
     pub documents: Vec<DocumentResult>,
 }
@@ -152,60 +407,160 @@ impl VerifyResults {
         Self {
             documents_verified: 0,
             commands_executed: 0,
+            commands_cached: 0,
             commands_passed: 0,
             commands_warned: 0,
             commands_failed: 0,
+            commands_timed_out: 0,
+            commands_allowed_failures: 0,
+            commands_skipped: 0,
+            commands_not_run: 0,
             documents: Vec::new(),
         }
     }
 
     fn add_document(&mut self, doc_result: DocumentResult) {
         for cmd in &doc_result.commands {
-            self.commands_executed += 1;
+            if cmd.cached {
+                self.commands_cached += 1;
+            } else {
+                self.commands_executed += 1;
+            }
             match cmd.status {
                 VerifyStatus::Pass => self.commands_passed += 1,
                 VerifyStatus::Warn => self.commands_warned += 1,
-                VerifyStatus::Fail | VerifyStatus::Timeout => self.commands_failed += 1,
+                VerifyStatus::Fail | VerifyStatus::Timeout if doc_result.allow_failure => {
+                    self.commands_allowed_failures += 1
+                }
+                VerifyStatus::Fail => self.commands_failed += 1,
+                VerifyStatus::Timeout => self.commands_timed_out += 1,
                 VerifyStatus::Skipped => {}
             }
         }
+        self.commands_skipped += doc_result.skipped;
         self.documents_verified += 1;
         self.documents.push(doc_result);
     }
 
-    fn is_success(&self) -> bool {
-        self.commands_failed == 0
+    /// Whether the run passed; timeouts only fail it unless `allow_timeout`.
+    fn is_success(&self, allow_timeout: bool) -> bool {
+        self.commands_failed == 0 && (allow_timeout || self.commands_timed_out == 0)
+    }
+
+    /// Drop captured output from commands that didn't fail.
+    fn drop_passing_output(&mut self) {
+        for cmd in self.documents.iter_mut().flat_map(|d| &mut d.commands) {
+            if !matches!(cmd.status, VerifyStatus::Fail | VerifyStatus::Timeout) {
+                cmd.stdout = None;
+                cmd.stderr = None;
+                cmd.isolated_dir = None;
+            }
+        }
+    }
+
+    /// Fill in `reproduce` for the commands `when` selects that ran.
+    fn add_reproduce_lines(&mut self, when: PrintCommand, show_env: bool) {
+        for cmd in self.documents.iter_mut().flat_map(|d| &mut d.commands) {
+            let selected = match when {
+                PrintCommand::All => true,
+                PrintCommand::Failed => {
+                    matches!(cmd.status, VerifyStatus::Fail | VerifyStatus::Timeout)
+                }
+            };
+            if selected && let Some(launch) = &cmd.launch {
+                cmd.reproduce = Some(launch.render(&cmd.command, show_env));
+            }
+        }
     }
 }
 
 /// Execute the `pave verify` command.
 pub fn execute(args: VerifyArgs) -> Result<()> {
+    // Durations come from the monotonic clock; only the report's timestamp
+    // uses wall-clock time, which can jump backwards
+    let started = Instant::now();
+
     // Find and load config
-    let config_path = find_config()?;
-    let config = PaveConfig::load(&config_path)?;
-    let config_dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+    let config_path = crate::config::find_config(args.config.as_deref())?;
+    let mut config = PaveConfig::load(&config_path)?;
+    if let Some(strict) = args.strict_output {
+        config.rules.strict_output_matching = strict;
+    }
+    let config_dir = &crate::config::config_base_dir(&config_path);
+
+    // Fail fast if the interpreter isn't installed, before running anything
+    let shell = args
+        .shell
+        .as_deref()
+        .or(config.verification.shell.as_deref())
+        .map(Shell::new)
+        .unwrap_or_default();
+    let missing_shell = match shell.resolve() {
+        Ok(_) => None,
+        Err(err) if args.on_missing_shell == MissingShell::Skip => Some(err),
+        Err(err) => return Err(err.into()),
+    };
 
     // Determine paths to verify
     let paths = if args.paths.is_empty() {
-        vec![config_dir.join(&config.docs.root)]
+        config.docs.resolve_root_dirs(config_dir)?
     } else {
         args.paths.clone()
     };
 
-    // Find all markdown files
-    let files = find_markdown_files(&paths)?;
+    // Find all markdown files; templates are scaffolds, so a scan of the
+    // docs roots leaves them out as `pave check` does
+    let mut files = find_markdown_files(&paths)?;
+    if args.paths.is_empty()
+        && let Some(templates_dir) = config.docs.templates_dir(config_dir)
+    {
+        files.retain(|file| !file.starts_with(&templates_dir));
+    }
 
     if files.is_empty() {
         eprintln!("No markdown files found to verify");
         return Ok(());
     }
 
-    // Collect verification specs from all documents
-    let mut specs: Vec<VerificationSpec> = Vec::new();
+    // Narrow to the docs impacted by the diff
+    let files = if args.changed {
+        let docs_roots = config.docs.resolve_root_dirs(config_dir)?;
+        let impacted = if args.staged {
+            filter_changed_docs(files, config_dir, || {
+                staged_impacted_doc_paths(config_dir, &docs_roots)
+            })
+        } else {
+            filter_changed_docs(files, config_dir, || {
+                determine_base_ref(args.base.as_deref(), config_dir)
+                    .and_then(|base_ref| impacted_doc_paths(config_dir, &docs_roots, &base_ref))
+            })
+        };
+        if impacted.is_empty() {
+            eprintln!("No docs impacted by changes");
+            return Ok(());
+        }
+        impacted
+    } else {
+        files
+    };
+
+    // Collect verification specs from all documents, with a hash of each
+    // document's declared inputs for cache lookups
+    let dedupe = args.dedupe || config.verification.dedupe;
+    let parse_options = config.verification.parse_options();
+    let mut specs: Vec<(VerificationSpec, Option<String>)> = Vec::new();
     for file in &files {
-        let doc = ParsedDoc::parse(file)?;
-        if let Some(spec) = extract_verification_spec(&doc) {
-            specs.push(spec);
+        let doc = ParsedDoc::parse_with(file, &parse_options)?;
+        if let Some(mut spec) = extract_verification_spec(&doc) {
+            if let Some(base_dir) = &config.verification.base_dir {
+                spec.apply_base_dir(base_dir);
+            }
+            if dedupe {
+                spec.dedupe();
+            }
+            apply_timeout_override(&mut spec, args.timeout);
+            let inputs_hash = cache::hash_inputs(config_dir, file, &cache::input_patterns(&doc));
+            specs.push((spec, inputs_hash));
         }
     }
 
@@ -214,21 +569,205 @@ pub fn execute(args: VerifyArgs) -> Result<()> {
         return Ok(());
     }
 
+    // Without a shell only shebang items can run, and no --before/--after
+    let (before, after) = match &missing_shell {
+        Some(err) => {
+            let skipped = skip_shell_items(&mut specs) + args.before.len() + args.after.len();
+            eprintln!(
+                "Warning: {}; skipping {} command{} that need{} it (--on-missing-shell skip)",
+                err.to_string().split(';').next().unwrap_or_default(),
+                skipped,
+                if skipped == 1 { "" } else { "s" },
+                if skipped == 1 { "s" } else { "" }
+            );
+            (&[][..], &[][..])
+        }
+        None => (&args.before[..], &args.after[..]),
+    };
+
+    let options = RunOptions {
+        jobs: args.jobs,
+        default_timeout: Some(Duration::from_secs(
+            config.verification.default_timeout_secs as u64,
+        )),
+        shell,
+        capture: match args.capture {
+            CaptureMode::None => Capture::None,
+            CaptureMode::Stdout => Capture::Stdout,
+            CaptureMode::All => Capture::All,
+        },
+        isolated: args.isolated,
+        ..Default::default()
+    };
+
+    let mut verify_cache = VerifyCache::load(config_dir);
+    let allow_failure = PathsMatcher::new(&config.verification.allow_failure);
+
+    if args.only_failed || args.list_failing_docs {
+        if !VerifyCache::exists(config_dir) {
+            anyhow::bail!(
+                "No previous verification results found; run `pave verify` once before using {}",
+                if args.only_failed {
+                    "--only-failed"
+                } else {
+                    "--list-failing-docs"
+                }
+            );
+        }
+        let item_options = item_options(&options, config_dir, &config.rules);
+        retain_failed_items(&mut specs, &verify_cache, &item_options, config_dir);
+        if specs.is_empty() {
+            eprintln!("No items failed in the last run");
+            return Ok(());
+        }
+    }
+
+    if !args.tags.is_empty() || !args.exclude_tags.is_empty() {
+        retain_tagged_items(&mut specs, &args.tags, &args.exclude_tags);
+        if specs.is_empty() {
+            eprintln!("No verification items match the tag filters");
+            return Ok(());
+        }
+    }
+
+    if args.list {
+        for line in list_items(specs.iter().map(|(spec, _)| spec)) {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+
+    if args.list_docs || args.list_failing_docs {
+        for line in list_docs(specs.iter().map(|(spec, _)| spec)) {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+
+    if let Some(index) = args.only {
+        select_item(&mut specs, index)?;
+    }
+
     // Run verifications
     let mut results = VerifyResults::new();
-    let timeout = Duration::from_secs(args.timeout as u64);
+    if options.capture == Capture::None {
+        let forced = specs
+            .iter()
+            .flat_map(|(spec, _)| &spec.items)
+            .filter(|item| options.capture.for_item(item) != Capture::None)
+            .count();
+        if forced > 0 {
+            eprintln!(
+                "Note: capturing stdout of {} command{} with expected output despite --capture none",
+                forced,
+                if forced == 1 { "" } else { "s" }
+            );
+        }
+    }
+
+    if !run_global_hooks(
+        "--before",
+        before,
+        true,
+        &options,
+        config_dir,
+        &config.rules,
+    ) {
+        run_global_hooks("--after", after, false, &options, config_dir, &config.rules);
+        anyhow::bail!("A --before command failed; no docs were verified");
+    }
+
+    // Failures so far in docs that aren't allowed to fail (for --bail-after)
+    let mut failures = 0;
+    for (index, (spec, inputs_hash)) in specs.iter().enumerate() {
+        let max_failures = match args.bail_after {
+            Some(budget) => Some(budget - failures),
+            None if args.keep_going => None,
+            None => Some(1),
+        };
+        let mut doc_cache = DocCache {
+            cache: &mut verify_cache,
+            inputs_hash: inputs_hash.as_deref(),
+            reuse: !args.no_cache,
+            since: args.since,
+        };
+        let mut doc_result = run_verification(
+            spec,
+            &options,
+            max_failures,
+            args.allow_timeout,
+            config_dir,
+            &config.rules,
+            Some(&mut doc_cache),
+        )?;
+        doc_result.allow_failure = allow_failure.matches(
+            spec.source_file
+                .strip_prefix(config_dir)
+                .unwrap_or(&spec.source_file),
+        );
+        if !doc_result.allow_failure {
+            failures += doc_result
+                .commands
+                .iter()
+                .filter(|cmd| is_failure(cmd.status, args.allow_timeout))
+                .count();
+        }
+        let should_stop = match args.bail_after {
+            Some(budget) => failures >= budget,
+            None => !doc_result.is_success(args.allow_timeout) && !args.keep_going,
+        };
 
-    for spec in &specs {
-        let doc_result =
-            run_verification(spec, timeout, args.keep_going, config_dir, &config.rules)?;
-        let should_stop = !doc_result.is_success() && !args.keep_going;
+        if should_stop && args.bail_after.is_some() {
+            let skipped_here = doc_result
+                .commands
+                .iter()
+                .filter(|cmd| cmd.status == VerifyStatus::Skipped && !cmd.cached)
+                .count();
+            let later: usize = specs[index + 1..]
+                .iter()
+                .map(|(spec, _)| spec.items.len())
+                .sum();
+            results.commands_not_run = skipped_here + later;
+        }
         results.add_document(doc_result);
 
         if should_stop {
+            if args.bail_after.is_some() {
+                eprintln!(
+                    "Stopped after {} failure{} (--bail-after); {} command{} not run",
+                    failures,
+                    if failures == 1 { "" } else { "s" },
+                    results.commands_not_run,
+                    if results.commands_not_run == 1 {
+                        ""
+                    } else {
+                        "s"
+                    }
+                );
+            }
             break;
         }
     }
 
+    let after_ok = run_global_hooks("--after", after, false, &options, config_dir, &config.rules);
+
+    if let Err(err) = verify_cache.save(config_dir) {
+        eprintln!("Warning: {:#}", err);
+    }
+
+    // Save full output before it's trimmed for the console
+    if let Some(dir) = &args.out_dir {
+        write_out_dir(&results, dir)?;
+    }
+
+    // Only failures keep their output unless --verbose was given
+    if !args.verbose {
+        results.drop_passing_output();
+    }
+    if let Some(when) = args.print_command {
+        results.add_reproduce_lines(when, args.show_env);
+    }
+
     // Output results in the requested format
     match args.format {
         OutputFormat::Text => output_text(&results),
@@ -238,257 +777,573 @@ pub fn execute(args: VerifyArgs) -> Result<()> {
 
     // Write report file if requested
     if let Some(report_path) = &args.report {
-        write_report(&results, report_path)?;
+        let report = RunReport {
+            timestamp: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            duration_ms: started.elapsed().as_millis() as u64,
+            git_sha: git_head_sha(config_dir),
+            results: &results,
+        };
+        write_report(&report, report_path, args.append)?;
+    }
+
+    if let Some(summary_path) = &args.summary_json {
+        let summary = RunSummary::new(&results, started.elapsed().as_millis() as u64);
+        let json = serde_json::to_string_pretty(&summary).context("Failed to serialize summary")?;
+        std::fs::write(summary_path, json + "\n")
+            .with_context(|| format!("Failed to write {}", summary_path.display()))?;
     }
 
     // Return error if verifications failed
-    if results.is_success() {
-        Ok(())
-    } else {
+    if !results.is_success(args.allow_timeout) {
         anyhow::bail!(
-            "Verification failed: {} of {} command{} failed",
+            "Verification failed: {} of {} command{} failed, {} timed out",
             results.commands_failed,
             results.commands_executed,
             if results.commands_executed == 1 {
                 ""
             } else {
                 "s"
-            }
+            },
+            results.commands_timed_out
         );
     }
+    if !after_ok {
+        anyhow::bail!("An --after command failed");
+    }
+    Ok(())
+}
+
+/// The path of each doc in `specs`, in the order they run.
+fn list_docs<'a>(specs: impl IntoIterator<Item = &'a VerificationSpec>) -> Vec<String> {
+    specs
+        .into_iter()
+        .map(|spec| spec.source_file.display().to_string())
+        .collect()
+}
+
+/// Number every verification item across `specs`, one line per item.
+///
+/// Lines read `<n>\t<file>:<section line>\t<command>`, numbered from 1 in
+/// the order items run. Multi-line commands show only their first line.
+fn list_items<'a>(specs: impl IntoIterator<Item = &'a VerificationSpec>) -> Vec<String> {
+    specs
+        .into_iter()
+        .flat_map(|spec| spec.items.iter().map(move |item| (spec, item)))
+        .enumerate()
+        .map(|(idx, (spec, item))| {
+            format!(
+                "{}\t{}:{}\t{}",
+                idx + 1,
+                spec.source_file.display(),
+                spec.section_line,
+                item.command.lines().next().unwrap_or_default()
+            )
+        })
+        .collect()
+}
+
+/// Narrow `specs` to the single item numbered `index` by [`list_items`].
+fn select_item(specs: &mut Vec<(VerificationSpec, Option<String>)>, index: usize) -> Result<()> {
+    let total: usize = specs.iter().map(|(spec, _)| spec.items.len()).sum();
+    let target = index
+        .checked_sub(1)
+        .filter(|&i| i < total)
+        .with_context(|| {
+            format!(
+                "No verification item #{} ({} item{} found; see `pave verify --list`)",
+                index,
+                total,
+                if total == 1 { "" } else { "s" }
+            )
+        })?;
+
+    let mut seen = 0;
+    specs.retain_mut(|(spec, _)| {
+        let range = seen..seen + spec.items.len();
+        seen = range.end;
+        if !range.contains(&target) {
+            return false;
+        }
+        spec.items = vec![spec.items.swap_remove(target - range.start)];
+        true
+    });
+    Ok(())
+}
+
+/// Keep only the docs that `impacted` reports, relative to `config_dir`.
+///
+/// Falls back to every doc, with a warning, when git can't compute the diff.
+fn filter_changed_docs(
+    files: Vec<PathBuf>,
+    config_dir: &Path,
+    impacted: impl FnOnce() -> Result<HashSet<PathBuf>>,
+) -> Vec<PathBuf> {
+    match impacted() {
+        Ok(impacted) => files
+            .into_iter()
+            .filter(|f| impacted.contains(f.strip_prefix(config_dir).unwrap_or(f)))
+            .collect(),
+        Err(err) => {
+            let reason = err.to_string();
+            eprintln!(
+                "Warning: could not determine changed docs ({}); verifying all docs",
+                reason.lines().next().unwrap_or_default().trim()
+            );
+            files
+        }
+    }
+}
+
+/// Cache state used while verifying one document.
+struct DocCache<'a> {
+    /// The project's verification cache.
+    cache: &'a mut VerifyCache,
+    /// Hash of the document and the files matched by its `## Paths`; `None`
+    /// when it declares no inputs, so passes are never reused.
+    inputs_hash: Option<&'a str>,
+    /// Whether cached passes may be reused (false with `--no-cache`).
+    reuse: bool,
+    /// Skip items that passed within this window (`--since`).
+    since: Option<Duration>,
 }
 
 /// Run verification commands for a single document.
+///
+/// Items run through [`verify::run_spec`], so `options.jobs` of them may run
+/// at once, respecting the spec's [`verify::serial_groups`]. After
+/// `max_failures` failed items the rest are reported as skipped; `None` runs
+/// every item. With a failure budget, items run in batches of `jobs`, so a
+/// batch already started finishes before the run stops. When `cache` is
+/// provided, items that passed with unchanged inputs are reported as cached
+/// instead of being re-run, items that passed within the `--since` window are
+/// reported as skipped, and new outcomes are recorded.
+///
+/// The spec's setup commands run before the first item that isn't served
+/// from the cache; if any ran, its teardown commands run after the items
+/// whatever their outcome.
 fn run_verification(
     spec: &VerificationSpec,
-    timeout: Duration,
-    keep_going: bool,
+    options: &RunOptions,
+    max_failures: Option<usize>,
+    allow_timeout: bool,
     working_dir: &Path,
     rules: &RulesSection,
+    mut cache: Option<&mut DocCache>,
 ) -> Result<DocumentResult> {
     let mut doc_result = DocumentResult::new(spec);
+    let relative_source = spec
+        .source_file
+        .strip_prefix(working_dir)
+        .unwrap_or(&spec.source_file);
+
+    let item_options = item_options(options, working_dir, rules);
+    let keys: Vec<String> = spec
+        .items
+        .iter()
+        .map(|item| cache::item_key(relative_source, item, &item_options))
+        .collect();
+
+    // Items served from the cache are settled up front; the rest run below
+    let mut results: Vec<Option<CommandResult>> = spec
+        .items
+        .iter()
+        .zip(&keys)
+        .map(|(item, key)| {
+            let ctx = cache.as_deref()?;
+            if ctx.reuse
+                && ctx
+                    .inputs_hash
+                    .is_some_and(|hash| ctx.cache.is_fresh(key, hash))
+            {
+                tracing::debug!(command = %item.command, "verify cache hit: reusing pass");
+                Some(cached_result(item, VerifyStatus::Pass))
+            } else if ctx
+                .since
+                .is_some_and(|window| ctx.cache.passed_within(key, window))
+            {
+                tracing::debug!(command = %item.command, "verify cache hit: recently passed");
+                Some(cached_result(item, VerifyStatus::Skipped))
+            } else {
+                None
+            }
+        })
+        .collect();
+    let pending: Vec<usize> = (0..results.len())
+        .filter(|&idx| results[idx].is_none())
+        .collect();
+
+    // Setup waits for an item that actually runs, so a doc whose items are
+    // all cached doesn't pay for it
+    let setup_ran = !pending.is_empty();
+    let mut setup_results = Vec::new();
+    let setup_ok = setup_ran
+        && spec.setup.iter().all(|hook| {
+            let result = run_hook(hook, "setup", options, working_dir, rules);
+            let ok = !is_failure(result.status, allow_timeout);
+            setup_results.push(result);
+            ok
+        });
 
-    for item in &spec.items {
-        let cmd_result = run_command(item, timeout, working_dir, rules);
-        // Fail/Timeout stop execution unless keep_going; Warn does not stop execution
-        let is_failure =
-            cmd_result.status == VerifyStatus::Fail || cmd_result.status == VerifyStatus::Timeout;
-        doc_result.add_result(cmd_result);
-
-        if is_failure && !keep_going {
-            // Mark remaining commands as skipped
-            for remaining in spec.items.iter().skip(doc_result.commands.len()) {
-                doc_result.add_result(CommandResult {
-                    command: remaining.command.clone(),
-                    status: VerifyStatus::Skipped,
-                    exit_code: None,
-                    expected_exit_code: remaining.expected_exit_code.unwrap_or(0),
-                    stdout: None,
-                    stderr: None,
-                    duration_ms: None,
-                    output_mismatch: None,
-                    working_dir: remaining.working_dir.clone(),
-                    env_vars: remaining.env_vars.clone(),
-                });
+    let mut remaining: &[usize] = if setup_ok { &pending } else { &[] };
+    let batch_size = match max_failures {
+        Some(_) => options.jobs.max(1),
+        None => remaining.len().max(1),
+    };
+    let mut failures = 0;
+    while !remaining.is_empty() && max_failures.is_none_or(|max| failures < max) {
+        let (batch, rest) = remaining.split_at(batch_size.min(remaining.len()));
+        remaining = rest;
+
+        let batch_spec = VerificationSpec {
+            source_file: spec.source_file.clone(),
+            section_line: spec.section_line,
+            items: batch.iter().map(|&idx| spec.items[idx].clone()).collect(),
+            skipped: 0,
+            serial: spec.serial,
+            setup: Vec::new(),
+            teardown: Vec::new(),
+        };
+        let ran = verify::run_spec(&batch_spec, &item_options);
+        for (&idx, result) in batch.iter().zip(ran) {
+            let cmd_result = command_result(&spec.items[idx], result, &item_options, rules);
+            if let Some(ctx) = cache.as_deref_mut() {
+                let key = keys[idx].clone();
+                match cmd_result.status {
+                    // Passes without inputs are still recorded for --since and
+                    // --only-failed; they just never match as fresh
+                    VerifyStatus::Pass => ctx
+                        .cache
+                        .record_pass(key, ctx.inputs_hash.unwrap_or_default().to_string()),
+                    VerifyStatus::Fail | VerifyStatus::Timeout => ctx.cache.record_failure(key),
+                    _ => ctx.cache.invalidate(&key),
+                }
             }
-            break;
+
+            // Fail (and Timeout, unless allowed) count toward max_failures;
+            // Warn does not stop execution
+            if is_failure(cmd_result.status, allow_timeout) {
+                failures += 1;
+            }
+            results[idx] = Some(cmd_result);
+        }
+    }
+
+    // Setup results go just before the first item that ran; items left
+    // without a result were skipped by a failed setup or the failure budget
+    let first_run = pending.first().copied();
+    for (idx, (item, result)) in spec.items.iter().zip(results).enumerate() {
+        if Some(idx) == first_run {
+            for setup_result in setup_results.drain(..) {
+                doc_result.add_result(setup_result);
+            }
+        }
+        doc_result.add_result(result.unwrap_or_else(|| not_run_result(item)));
+    }
+
+    if setup_ran {
+        for hook in &spec.teardown {
+            doc_result.add_result(run_hook(hook, "teardown", options, working_dir, rules));
         }
     }
 
     Ok(doc_result)
 }
 
-/// Check if the output matches the expected pattern.
-/// Returns (matches, strategy_name) tuple.
-fn check_output_match(matcher: &OutputMatcher, stdout: &str) -> (bool, &'static str) {
-    match matcher {
-        OutputMatcher::Contains(substring) => (stdout.contains(substring), "contains"),
-        OutputMatcher::Regex(pattern) => {
-            let matches = Regex::new(pattern)
-                .map(|re| re.is_match(stdout))
-                .unwrap_or(false);
-            (matches, "regex")
+/// Run a `## Setup` or `## Teardown` command, labelled with its phase.
+///
+/// Hooks always run in `working_dir`, even under `--isolated`, since they
+/// prepare state for the whole doc.
+fn run_hook(
+    item: &VerificationItem,
+    phase: &str,
+    options: &RunOptions,
+    working_dir: &Path,
+    rules: &RulesSection,
+) -> CommandResult {
+    let options = RunOptions {
+        isolated: false,
+        ..options.clone()
+    };
+    let mut result = run_command(item, &options, working_dir, rules);
+    let name = item.label.as_deref().unwrap_or(&item.command);
+    result.label = Some(format!("{}: {}", phase, name));
+    result
+}
+
+/// Drop the items and hooks that run through the shell, counting the items
+/// as skipped. Shebang items run under their own interpreters and are kept,
+/// unless a setup hook needed the shell.
+///
+/// Returns how many items were dropped.
+fn skip_shell_items(specs: &mut [(VerificationSpec, Option<String>)]) -> usize {
+    let mut skipped = 0;
+    for (spec, _) in specs {
+        let before = spec.items.len();
+        if spec.setup.iter().any(|hook| hook.interpreter.is_none()) {
+            spec.items.clear();
+        } else {
+            spec.items.retain(|item| item.interpreter.is_some());
+        }
+        spec.setup.retain(|hook| hook.interpreter.is_some());
+        spec.teardown.retain(|hook| hook.interpreter.is_some());
+        spec.skipped += before - spec.items.len();
+        skipped += before - spec.items.len();
+    }
+    skipped
+}
+
+/// Run `--before` or `--after` commands in order from the project root,
+/// reporting failures on stderr.
+///
+/// Returns false if any command failed. With `stop_on_failure` the commands
+/// after a failure are not run.
+fn run_global_hooks(
+    flag: &str,
+    commands: &[String],
+    stop_on_failure: bool,
+    options: &RunOptions,
+    working_dir: &Path,
+    rules: &RulesSection,
+) -> bool {
+    let options = RunOptions {
+        isolated: false,
+        ..options.clone()
+    };
+    let mut ok = true;
+    for command in commands {
+        let item = VerificationItem {
+            command: command.clone(),
+            raw: command.clone(),
+            ..Default::default()
+        };
+        let result = run_command(&item, &options, working_dir, rules);
+        if !is_failure(result.status, false) {
+            continue;
+        }
+        let outcome = match (result.status, result.exit_code) {
+            (VerifyStatus::Timeout, _) => "timed out".to_string(),
+            (_, Some(code)) => format!("exited with {}", code),
+            _ => "could not run".to_string(),
+        };
+        eprintln!("{} command `{}` {}", flag, command, outcome);
+        if let Some(stderr) = &result.stderr {
+            eprintln!("{}", truncate_lines(stderr, 20));
+        }
+        ok = false;
+        if stop_on_failure {
+            break;
+        }
+    }
+    ok
+}
+
+/// Build the result for an item that wasn't run because the doc stopped early.
+fn not_run_result(item: &VerificationItem) -> CommandResult {
+    CommandResult {
+        command: item.command.clone(),
+        label: item.label.clone(),
+        status: VerifyStatus::Skipped,
+        exit_code: None,
+        expected_exit_code: item.expected_exit_code.unwrap_or(0),
+        expected_exit_codes: item.expected_exit_codes.clone(),
+        stdout: None,
+        stderr: None,
+        duration_ms: None,
+        output_mismatch: None,
+        working_dir: item.working_dir.clone(),
+        isolated_dir: None,
+        env_vars: item.env_vars.clone(),
+        cached: false,
+        deduped_lines: deduped_lines(item),
+        launch: None,
+        reproduce: None,
+    }
+}
+
+/// Whether a command with `status` counts as failed: Fail always, Timeout
+/// unless timeouts are allowed.
+fn is_failure(status: VerifyStatus, allow_timeout: bool) -> bool {
+    match status {
+        VerifyStatus::Fail => true,
+        VerifyStatus::Timeout => !allow_timeout,
+        _ => false,
+    }
+}
+
+/// Keep only the items that failed when last run with `options`, dropping
+/// specs left empty.
+fn retain_failed_items(
+    specs: &mut Vec<(VerificationSpec, Option<String>)>,
+    verify_cache: &VerifyCache,
+    options: &RunOptions,
+    project_root: &Path,
+) {
+    for (spec, _) in specs.iter_mut() {
+        let relative_source = spec
+            .source_file
+            .strip_prefix(project_root)
+            .unwrap_or(&spec.source_file)
+            .to_path_buf();
+        spec.items.retain(|item| {
+            verify_cache.failed_last_run(&cache::item_key(&relative_source, item, options))
+        });
+    }
+    specs.retain(|(spec, _)| !spec.items.is_empty());
+}
+
+/// Keep only the items selected by `--tag` and `--exclude-tag`, dropping
+/// specs left empty.
+///
+/// An item is kept when it bears any of `tags` (or `tags` is empty) and none
+/// of `exclude_tags`.
+fn retain_tagged_items(
+    specs: &mut Vec<(VerificationSpec, Option<String>)>,
+    tags: &[String],
+    exclude_tags: &[String],
+) {
+    for (spec, _) in specs.iter_mut() {
+        spec.items.retain(|item| {
+            (tags.is_empty() || item.tags.iter().any(|t| tags.contains(t)))
+                && !item.tags.iter().any(|t| exclude_tags.contains(t))
+        });
+    }
+    specs.retain(|(spec, _)| !spec.items.is_empty());
+}
+
+/// Build the result for an item that isn't re-run because of an earlier pass.
+///
+/// `status` is `Pass` when the cached pass is still valid for the item's
+/// inputs and `Skipped` when it merely falls within the `--since` window.
+fn cached_result(item: &VerificationItem, status: VerifyStatus) -> CommandResult {
+    CommandResult {
+        command: item.command.clone(),
+        label: item.label.clone(),
+        status,
+        exit_code: None,
+        expected_exit_code: item.expected_exit_code.unwrap_or(0),
+        expected_exit_codes: item.expected_exit_codes.clone(),
+        stdout: None,
+        stderr: None,
+        duration_ms: None,
+        output_mismatch: None,
+        working_dir: item.working_dir.clone(),
+        isolated_dir: None,
+        env_vars: item.env_vars.clone(),
+        cached: true,
+        deduped_lines: deduped_lines(item),
+        launch: None,
+        reproduce: None,
+    }
+}
+
+/// Source lines to report for an item that stands in for several blocks.
+fn deduped_lines(item: &VerificationItem) -> Vec<usize> {
+    if item.source_lines.len() > 1 {
+        item.source_lines.clone()
+    } else {
+        Vec::new()
+    }
+}
+
+/// Make a `--timeout` value apply to every item, over any doc or config timeout.
+fn apply_timeout_override(spec: &mut VerificationSpec, timeout: Option<u32>) {
+    if let Some(secs) = timeout {
+        let hooks = spec.setup.iter_mut().chain(&mut spec.teardown);
+        for item in spec.items.iter_mut().chain(hooks) {
+            item.timeout_secs = Some(secs);
         }
-        OutputMatcher::Exact(expected) => (stdout.trim() == expected.trim(), "exact"),
-        OutputMatcher::ExitCodeOnly => (true, "exit_code_only"),
     }
 }
 
-/// Get the expected string from an OutputMatcher.
-fn get_expected_string(matcher: &OutputMatcher) -> String {
-    match matcher {
-        OutputMatcher::Contains(s) => s.clone(),
-        OutputMatcher::Regex(s) => s.clone(),
-        OutputMatcher::Exact(s) => s.clone(),
-        OutputMatcher::ExitCodeOnly => String::new(),
+/// The options items run with: `options` in `working_dir`, matching output
+/// as strictly as `rules` say.
+fn item_options(options: &RunOptions, working_dir: &Path, rules: &RulesSection) -> RunOptions {
+    RunOptions {
+        working_dir: Some(working_dir.to_path_buf()),
+        strict_output: rules.strict_output_matching,
+        ..options.clone()
     }
 }
 
-/// Run a single verification command.
+/// Run a single verification command and classify the result for reporting.
+///
+/// `options` supply the shell and the timeout for items without their own;
+/// commands run in `working_dir` unless the item sets its own directory.
 fn run_command(
     item: &VerificationItem,
-    timeout: Duration,
+    options: &RunOptions,
     working_dir: &Path,
     rules: &RulesSection,
 ) -> CommandResult {
-    let expected_exit_code = item.expected_exit_code.unwrap_or(0);
-    let start = std::time::Instant::now();
-
-    // Use item's working_dir if specified, otherwise use config_dir
-    let cmd_working_dir = item.working_dir.as_deref().unwrap_or(working_dir);
-
-    // Build the command
-    let mut cmd = Command::new("sh");
-    cmd.arg("-c")
-        .arg(&item.command)
-        .current_dir(cmd_working_dir);
-
-    // Set environment variables
-    for (key, value) in &item.env_vars {
-        cmd.env(key, value);
-    }
-
-    // Execute command via shell
-    let output = cmd.output();
-
-    let duration_ms = start.elapsed().as_millis() as u64;
-
-    // Track the working dir and env vars for the result (only if non-default)
-    let result_working_dir = item.working_dir.clone();
-    let result_env_vars = item.env_vars.clone();
-
-    match output {
-        Ok(output) => {
-            let exit_code = output.status.code();
-            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-            let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-
-            // Check if timed out (heuristic: check if duration exceeds timeout)
-            if duration_ms >= timeout.as_millis() as u64 {
-                return CommandResult {
-                    command: item.command.clone(),
-                    status: VerifyStatus::Timeout,
-                    exit_code,
-                    expected_exit_code,
-                    stdout: Some(stdout),
-                    stderr: Some(stderr),
-                    duration_ms: Some(duration_ms),
-                    output_mismatch: None,
-                    working_dir: result_working_dir,
-                    env_vars: result_env_vars,
-                };
-            }
-
-            // Check exit code first
-            let exit_code_matches = exit_code == Some(expected_exit_code);
-
-            // If exit code doesn't match, fail immediately
-            if !exit_code_matches {
-                return CommandResult {
-                    command: item.command.clone(),
-                    status: VerifyStatus::Fail,
-                    exit_code,
-                    expected_exit_code,
-                    stdout: if stdout.is_empty() {
-                        None
-                    } else {
-                        Some(stdout)
-                    },
-                    stderr: if stderr.is_empty() {
-                        None
-                    } else {
-                        Some(stderr)
-                    },
-                    duration_ms: Some(duration_ms),
-                    output_mismatch: None,
-                    working_dir: result_working_dir,
-                    env_vars: result_env_vars,
-                };
-            }
+    let options = item_options(options, working_dir, rules);
+    let result = verify::run_item(item, &options);
+    command_result(item, result, &options, rules)
+}
 
-            // Check output matching if expected_output is specified and not skipped
-            let (status, output_mismatch) = if rules.skip_output_matching {
-                // Skip output matching entirely
-                (VerifyStatus::Pass, None)
-            } else if let Some(ref matcher) = item.expected_output {
-                let (matches, strategy) = check_output_match(matcher, &stdout);
-                if matches {
-                    (VerifyStatus::Pass, None)
-                } else {
-                    // Output doesn't match
-                    let mismatch = OutputMismatch {
-                        expected: get_expected_string(matcher),
-                        strategy: strategy.to_string(),
-                        actual: stdout.clone(),
-                    };
-                    if rules.strict_output_matching {
-                        // Strict mode: fail on mismatch
-                        (VerifyStatus::Fail, Some(mismatch))
-                    } else {
-                        // Default mode: warn on mismatch
-                        (VerifyStatus::Warn, Some(mismatch))
-                    }
-                }
-            } else {
-                // No expected output, just pass
-                (VerifyStatus::Pass, None)
-            };
+/// Classify the outcome of running `item` with `options` for reporting.
+fn command_result(
+    item: &VerificationItem,
+    result: ItemResult,
+    options: &RunOptions,
+    rules: &RulesSection,
+) -> CommandResult {
+    let expected_exit_code = result.expected_exit_code();
+    let duration_ms = result.duration.as_millis() as u64;
+
+    let mut command_result = CommandResult {
+        command: item.command.clone(),
+        label: item.label.clone(),
+        status: VerifyStatus::Fail,
+        exit_code: result.exit_code,
+        expected_exit_code,
+        expected_exit_codes: item.expected_exit_codes.clone(),
+        stdout: None,
+        stderr: None,
+        duration_ms: Some(duration_ms),
+        output_mismatch: None,
+        working_dir: item.working_dir.clone(),
+        isolated_dir: result.isolated_dir.clone(),
+        env_vars: item.env_vars.clone(),
+        cached: false,
+        deduped_lines: deduped_lines(item),
+        launch: Some(Launch::new(item, options, result.isolated_dir.is_some())),
+        reproduce: None,
+    };
 
-            CommandResult {
-                command: item.command.clone(),
-                status,
-                exit_code,
-                expected_exit_code,
-                stdout: if stdout.is_empty() {
-                    None
-                } else {
-                    Some(stdout)
-                },
-                stderr: if stderr.is_empty() {
-                    None
-                } else {
-                    Some(stderr)
-                },
-                duration_ms: Some(duration_ms),
-                output_mismatch,
-                working_dir: result_working_dir,
-                env_vars: result_env_vars,
-            }
-        }
-        Err(e) => CommandResult {
-            command: item.command.clone(),
-            status: VerifyStatus::Fail,
-            exit_code: None,
-            expected_exit_code,
-            stdout: None,
-            stderr: Some(format!("Failed to execute command: {}", e)),
-            duration_ms: Some(duration_ms),
-            output_mismatch: None,
-            working_dir: result_working_dir,
-            env_vars: result_env_vars,
-        },
+    if let Some(error) = result.error {
+        command_result.stderr = Some(format!("Failed to execute command: {}", error));
+        return command_result;
     }
-}
 
-/// Find the .pave.toml config file by walking up from the current directory.
-fn find_config() -> Result<PathBuf> {
-    let current_dir = env::current_dir().context("Failed to get current directory")?;
-    let mut dir = current_dir.as_path();
+    if result.timed_out {
+        command_result.status = VerifyStatus::Timeout;
+        command_result.stdout = Some(result.stdout);
+        command_result.stderr = Some(result.stderr);
+        return command_result;
+    }
 
-    loop {
-        let config_path = dir.join(CONFIG_FILENAME);
-        if config_path.exists() {
-            return Ok(config_path);
+    command_result.status = if !result.exit_code_matches() {
+        VerifyStatus::Fail
+    } else if rules.skip_output_matching {
+        VerifyStatus::Pass
+    } else if let MatchOutcome::Mismatched { strategy, expected } = result.output_match {
+        command_result.output_mismatch = Some(OutputMismatch {
+            expected,
+            strategy: strategy.to_string(),
+            actual: result.stdout.clone(),
+        });
+        if rules.strict_output_matching {
+            VerifyStatus::Fail
+        } else {
+            VerifyStatus::Warn
         }
+    } else {
+        VerifyStatus::Pass
+    };
 
-        match dir.parent() {
-            Some(parent) => dir = parent,
-            None => anyhow::bail!(
-                "No {} found in current directory or any parent directory",
-                CONFIG_FILENAME
-            ),
-        }
-    }
+    command_result.stdout = Some(result.stdout).filter(|s| !s.is_empty());
+    command_result.stderr = Some(result.stderr).filter(|s| !s.is_empty());
+    command_result
 }
 
 /// Find all markdown files in the given paths.
@@ -509,6 +1364,8 @@ fn find_markdown_files(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
 
     // Sort for consistent output
     files.sort();
+    files.dedup();
+    tracing::debug!(files = files.len(), "found markdown files to verify");
     Ok(files)
 }
 
@@ -534,17 +1391,25 @@ fn collect_markdown_files_recursive(dir: &Path, files: &mut Vec<PathBuf>) -> Res
 /// Print a debugging suggestion for a failed command.
 fn print_debug_suggestion(cmd: &CommandResult) {
     println!("    suggestion: Try running manually:");
+    println!("      {}", debug_suggestion(cmd));
+}
+
+/// The command line suggested for rerunning a failed command by hand, in the
+/// same order as `--print-command`: `cd`, then env vars, then the command.
+fn debug_suggestion(cmd: &CommandResult) -> String {
     let mut suggestion = String::new();
-    // Add env vars
-    for (key, value) in &cmd.env_vars {
-        suggestion.push_str(&format!("{}={} ", key, value));
-    }
-    // Add cd if working_dir is set
+    // Add cd if working_dir is set, or to a scratch dir like --isolated
     if let Some(ref wd) = cmd.working_dir {
         suggestion.push_str(&format!("cd {} && ", wd.display()));
+    } else if cmd.isolated_dir.is_some() {
+        suggestion.push_str("cd \"$(mktemp -d)\" && ");
+    }
+    // Env vars go after the cd so they apply to the command itself
+    for (key, value) in &cmd.env_vars {
+        suggestion.push_str(&format!("{}={} ", key, value));
     }
     suggestion.push_str(&cmd.command);
-    println!("      {}", suggestion);
+    suggestion
 }
 
 /// Truncate a string to a maximum number of lines.
@@ -564,16 +1429,17 @@ fn truncate_lines(s: &str, max_lines: usize) -> String {
 
 /// Output results in text format.
 fn output_text(results: &VerifyResults) {
+    let palette = color::palette();
     for doc in &results.documents {
         println!("{}:{}", doc.file.display(), doc.section_line);
 
         for cmd in &doc.commands {
-            let status_str = match cmd.status {
-                VerifyStatus::Pass => "PASS",
-                VerifyStatus::Warn => "WARN",
-                VerifyStatus::Fail => "FAIL",
-                VerifyStatus::Timeout => "TIMEOUT",
-                VerifyStatus::Skipped => "SKIPPED",
+            let status_str = if doc.allow_failure
+                && matches!(cmd.status, VerifyStatus::Fail | VerifyStatus::Timeout)
+            {
+                palette.yellow("ALLOWED FAILURE")
+            } else {
+                status_label(cmd.status, &palette)
             };
 
             let duration_str = cmd
@@ -581,7 +1447,30 @@ fn output_text(results: &VerifyResults) {
                 .map(|d| format!(" ({:.2}s)", d as f64 / 1000.0))
                 .unwrap_or_default();
 
-            println!("  [{}]{} {}", status_str, duration_str, cmd.command);
+            let cached_str = match (cmd.cached, cmd.status) {
+                (true, VerifyStatus::Skipped) => " (passed recently)",
+                (true, _) => " (cached)",
+                (false, _) => "",
+            };
+
+            println!(
+                "  [{}]{}{} {}",
+                status_str,
+                duration_str,
+                cached_str,
+                cmd.display_name()
+            );
+            if !cmd.deduped_lines.is_empty() {
+                let lines: Vec<String> = cmd.deduped_lines.iter().map(|l| l.to_string()).collect();
+                println!("    deduplicated: lines {}", lines.join(", "));
+            }
+            // Kept for passing commands only with --verbose
+            if let Some(ref dir) = cmd.isolated_dir {
+                println!("    isolated: {}", dir.display());
+            }
+            if let Some(ref line) = cmd.reproduce {
+                println!("    reproduce: {}", line);
+            }
 
             // Show failure details
             if cmd.status == VerifyStatus::Fail || cmd.status == VerifyStatus::Timeout {
@@ -596,32 +1485,23 @@ fn output_text(results: &VerifyResults) {
                     }
                 }
                 if let Some(code) = cmd.exit_code
-                    && code != cmd.expected_exit_code
+                    && !cmd.expects_exit_code(code)
                 {
                     println!(
                         "    exit code: {} (expected {})",
-                        code, cmd.expected_exit_code
+                        code,
+                        cmd.expected_exit_description()
                     );
                 }
                 // Always show full stdout/stderr for failed commands to aid debugging
-                if let Some(stdout) = &cmd.stdout
-                    && !stdout.is_empty()
-                {
-                    println!("    stdout:");
-                    for line in stdout.lines() {
-                        println!("      {}", line);
-                    }
+                print_captured_output(cmd);
+                // Print debugging suggestion, unless the exact command was printed
+                if cmd.reproduce.is_none() {
+                    print_debug_suggestion(cmd);
                 }
-                if let Some(stderr) = &cmd.stderr
-                    && !stderr.is_empty()
-                {
-                    println!("    stderr:");
-                    for line in stderr.lines() {
-                        println!("      {}", line);
-                    }
-                }
-                // Print debugging suggestion
-                print_debug_suggestion(cmd);
+            } else {
+                // Passing commands only keep their output with --verbose
+                print_captured_output(cmd);
             }
 
             // Show output mismatch details for both warnings and failures
@@ -648,26 +1528,94 @@ fn output_text(results: &VerifyResults) {
         }
     );
 
-    if results.commands_failed == 0 && results.commands_warned == 0 {
-        println!(
-            "{} command{} passed",
+    let mut cached_note = if results.commands_cached > 0 {
+        format!(
+            " ({} executed, {} cached)",
+            results.commands_executed, results.commands_cached
+        )
+    } else {
+        String::new()
+    };
+    if results.commands_skipped > 0 {
+        cached_note.push_str(&format!(", {} skipped", results.commands_skipped));
+    }
+    if results.commands_allowed_failures > 0 {
+        cached_note.push_str(&format!(
+            ", {} allowed failure{}",
+            results.commands_allowed_failures,
+            if results.commands_allowed_failures == 1 {
+                ""
+            } else {
+                "s"
+            }
+        ));
+    }
+
+    let has_failures = results.commands_failed > 0 || results.commands_timed_out > 0;
+    if !has_failures && results.commands_warned == 0 && results.commands_allowed_failures == 0 {
+        let summary = format!(
+            "{} command{} passed{}",
             results.commands_passed,
             if results.commands_passed == 1 {
                 ""
             } else {
                 "s"
-            }
+            },
+            cached_note
         );
-    } else if results.commands_failed == 0 {
-        println!(
-            "{} passed, {} warned",
-            results.commands_passed, results.commands_warned
+        println!("{}", palette.green(&summary));
+    } else if !has_failures {
+        let summary = format!(
+            "{} passed, {} warned{}",
+            results.commands_passed, results.commands_warned, cached_note
         );
+        println!("{}", palette.yellow(&summary));
     } else {
-        println!(
-            "{} passed, {} warned, {} failed",
-            results.commands_passed, results.commands_warned, results.commands_failed
+        let summary = format!(
+            "{} passed, {} warned, {} failed, {} timed out{}",
+            results.commands_passed,
+            results.commands_warned,
+            results.commands_failed,
+            results.commands_timed_out,
+            cached_note
         );
+        // Timeouts alone are inconclusive rather than failures
+        if results.commands_failed > 0 {
+            println!("{}", palette.red(&summary));
+        } else {
+            println!("{}", palette.yellow(&summary));
+        }
+    }
+}
+
+/// Print a command's captured stdout and stderr, if any.
+fn print_captured_output(cmd: &CommandResult) {
+    if let Some(stdout) = &cmd.stdout
+        && !stdout.is_empty()
+    {
+        println!("    stdout:");
+        for line in stdout.lines() {
+            println!("      {}", line);
+        }
+    }
+    if let Some(stderr) = &cmd.stderr
+        && !stderr.is_empty()
+    {
+        println!("    stderr:");
+        for line in stderr.lines() {
+            println!("      {}", line);
+        }
+    }
+}
+
+/// Format a command status label, colored by outcome.
+fn status_label(status: VerifyStatus, palette: &Palette) -> String {
+    match status {
+        VerifyStatus::Pass => palette.green("PASS"),
+        VerifyStatus::Warn => palette.yellow("WARN"),
+        VerifyStatus::Fail => palette.red("FAIL"),
+        VerifyStatus::Timeout => palette.red("TIMEOUT"),
+        VerifyStatus::Skipped => "SKIPPED".to_string(),
     }
 }
 
@@ -683,7 +1631,10 @@ fn output_github(results: &VerifyResults) {
     for doc in &results.documents {
         for cmd in &doc.commands {
             if cmd.status != VerifyStatus::Pass {
+                let allowed = doc.allow_failure
+                    && matches!(cmd.status, VerifyStatus::Fail | VerifyStatus::Timeout);
                 let level = match cmd.status {
+                    _ if allowed => "warning",
                     VerifyStatus::Fail | VerifyStatus::Timeout => "error",
                     VerifyStatus::Warn | VerifyStatus::Skipped => "warning",
                     VerifyStatus::Pass => continue,
@@ -701,7 +1652,9 @@ fn output_github(results: &VerifyResults) {
                         } else {
                             format!(
                                 "Command failed: {} (exit code: {:?}, expected: {})",
-                                cmd.command, cmd.exit_code, cmd.expected_exit_code
+                                cmd.display_name(),
+                                cmd.exit_code,
+                                cmd.expected_exit_description()
                             )
                         }
                     }
@@ -714,17 +1667,22 @@ fn output_github(results: &VerifyResults) {
                                 mismatch.actual.trim().lines().next().unwrap_or("")
                             )
                         } else {
-                            format!("Command warning: {}", cmd.command)
+                            format!("Command warning: {}", cmd.display_name())
                         }
                     }
                     VerifyStatus::Timeout => {
-                        format!("Command timed out: {}", cmd.command)
+                        format!("Command timed out: {}", cmd.display_name())
                     }
                     VerifyStatus::Skipped => {
-                        format!("Command skipped: {}", cmd.command)
+                        format!("Command skipped: {}", cmd.display_name())
                     }
                     VerifyStatus::Pass => continue,
                 };
+                let message = if allowed {
+                    format!("Allowed failure: {}", message)
+                } else {
+                    message
+                };
 
                 println!(
                     "::{} file={},line={}::{}",
@@ -738,20 +1696,178 @@ fn output_github(results: &VerifyResults) {
     }
 }
 
-/// Write JSON report to file.
-fn write_report(results: &VerifyResults, path: &Path) -> Result<()> {
-    let json = serde_json::to_string_pretty(results).context("Failed to serialize results")?;
-    let mut file = std::fs::File::create(path)
-        .with_context(|| format!("Failed to create {}", path.display()))?;
+/// A run summary as written by `--report`: the results plus when and at
+/// which commit they were produced.
+#[derive(Serialize)]
+struct RunReport<'a> {
+    /// When the run finished, as an ISO-8601 UTC timestamp.
+    timestamp: String,
+    /// How long the whole run took, in milliseconds.
+    duration_ms: u64,
+    /// `HEAD` of the project's git repository, if there is one.
+    git_sha: Option<String>,
+    #[serde(flatten)]
+    results: &'a VerifyResults,
+}
+
+/// The aggregate counts written by `--summary-json`.
+///
+/// Every command lands in exactly one bucket, so `total` is the sum of the
+/// other three.
+#[derive(Debug, Serialize, PartialEq)]
+struct RunSummary {
+    total: usize,
+    /// Passed, including cached passes and output-mismatch warnings.
+    passed: usize,
+    /// Failed or timed out, including in docs allowed to fail.
+    failed: usize,
+    /// Left out by skip annotations or never run.
+    skipped: usize,
+    duration_ms: u64,
+}
+
+impl RunSummary {
+    fn new(results: &VerifyResults, duration_ms: u64) -> Self {
+        let not_run = results
+            .documents
+            .iter()
+            .flat_map(|doc| &doc.commands)
+            .filter(|cmd| cmd.status == VerifyStatus::Skipped)
+            .count();
+        let passed = results.commands_passed + results.commands_warned;
+        let failed = results.commands_failed
+            + results.commands_timed_out
+            + results.commands_allowed_failures;
+        let skipped = results.commands_skipped + not_run;
+        Self {
+            total: passed + failed + skipped,
+            passed,
+            failed,
+            skipped,
+            duration_ms,
+        }
+    }
+}
+
+/// The commit checked out in `dir`, or `None` outside a git repository.
+fn git_head_sha(dir: &Path) -> Option<String> {
+    let output = std::process::Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Write the JSON report to file.
+///
+/// With `append`, the report is added to the file as a single JSON line
+/// (JSONL), so successive runs build up a history.
+fn write_report(report: &RunReport, path: &Path, append: bool) -> Result<()> {
+    let mut file = if append {
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+    } else {
+        std::fs::File::create(path)
+    }
+    .with_context(|| format!("Failed to create {}", path.display()))?;
+    let json = if append {
+        serde_json::to_string(report).map(|line| line + "\n")
+    } else {
+        serde_json::to_string_pretty(report)
+    }
+    .context("Failed to serialize results")?;
     file.write_all(json.as_bytes())
         .with_context(|| format!("Failed to write to {}", path.display()))?;
     eprintln!("Report written to {}", path.display());
     Ok(())
 }
 
+/// One command's entry in `--out-dir`'s `index.json`.
+#[derive(Serialize)]
+struct OutDirEntry<'a> {
+    /// Document the command came from.
+    file: &'a Path,
+    command: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<&'a str>,
+    status: VerifyStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exit_code: Option<i32>,
+    /// Output file names, relative to the out dir.
+    stdout: String,
+    stderr: String,
+}
+
+/// Write every command's stdout and stderr to `NNN-<command>.stdout` and
+/// `.stderr` files in `dir`, numbered in run order, plus an `index.json`
+/// mapping each command to its files and outcome.
+fn write_out_dir(results: &VerifyResults, dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let write = |name: &str, content: &str| {
+        let path = dir.join(name);
+        std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    };
+
+    let mut index = Vec::new();
+    let commands = results
+        .documents
+        .iter()
+        .flat_map(|doc| doc.commands.iter().map(move |cmd| (doc, cmd)));
+    for (number, (doc, cmd)) in commands.enumerate() {
+        let stem = format!("{:03}-{}", number + 1, sanitize_command(&cmd.command));
+        let entry = OutDirEntry {
+            file: &doc.file,
+            command: &cmd.command,
+            label: cmd.label.as_deref(),
+            status: cmd.status,
+            exit_code: cmd.exit_code,
+            stdout: format!("{}.stdout", stem),
+            stderr: format!("{}.stderr", stem),
+        };
+        write(&entry.stdout, cmd.stdout.as_deref().unwrap_or_default())?;
+        write(&entry.stderr, cmd.stderr.as_deref().unwrap_or_default())?;
+        index.push(entry);
+    }
+
+    let json = serde_json::to_string_pretty(&index).context("Failed to serialize index")?;
+    write("index.json", &(json + "\n"))?;
+    eprintln!("Command output written to {}", dir.display());
+    Ok(())
+}
+
+/// A file-name-safe slug of a command's first line, at most 40 characters.
+fn sanitize_command(command: &str) -> String {
+    let first_line = command.lines().next().unwrap_or_default();
+    let mut slug = String::new();
+    for c in first_line.chars() {
+        if c.is_ascii_alphanumeric() || c == '.' || c == '_' {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+        if slug.len() >= 40 {
+            break;
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "command".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::verification::OutputMatcher;
     use std::fs;
     use tempfile::TempDir;
 
@@ -846,39 +1962,57 @@ Example here.
         let spec = VerificationSpec {
             source_file: PathBuf::from("test.md"),
             section_line: 10,
+            skipped: 0,
+            serial: false,
+            setup: vec![],
+            teardown: vec![],
             items: vec![],
         };
 
         let mut doc_result = DocumentResult::new(&spec);
-        assert!(doc_result.is_success());
+        assert!(doc_result.is_success(false));
 
         doc_result.add_result(CommandResult {
             command: "echo ok".to_string(),
             status: VerifyStatus::Pass,
             exit_code: Some(0),
             expected_exit_code: 0,
+            expected_exit_codes: None,
             stdout: None,
             stderr: None,
             duration_ms: Some(10),
             output_mismatch: None,
             working_dir: None,
+            isolated_dir: None,
             env_vars: Vec::new(),
+            cached: false,
+            deduped_lines: Vec::new(),
+            launch: None,
+            reproduce: None,
+            label: None,
         });
-        assert!(doc_result.is_success());
+        assert!(doc_result.is_success(false));
 
         doc_result.add_result(CommandResult {
             command: "false".to_string(),
             status: VerifyStatus::Fail,
             exit_code: Some(1),
             expected_exit_code: 0,
+            expected_exit_codes: None,
             stdout: None,
             stderr: None,
             duration_ms: Some(5),
             output_mismatch: None,
             working_dir: None,
+            isolated_dir: None,
             env_vars: Vec::new(),
+            cached: false,
+            deduped_lines: Vec::new(),
+            launch: None,
+            reproduce: None,
+            label: None,
         });
-        assert!(!doc_result.is_success());
+        assert!(!doc_result.is_success(false));
     }
 
     #[test]
@@ -886,6 +2020,10 @@ Example here.
         let spec = VerificationSpec {
             source_file: PathBuf::from("test.md"),
             section_line: 10,
+            skipped: 0,
+            serial: false,
+            setup: vec![],
+            teardown: vec![],
             items: vec![],
         };
 
@@ -897,12 +2035,19 @@ Example here.
             status: VerifyStatus::Pass,
             exit_code: Some(0),
             expected_exit_code: 0,
+            expected_exit_codes: None,
             stdout: None,
             stderr: None,
             duration_ms: Some(10),
             output_mismatch: None,
             working_dir: None,
+            isolated_dir: None,
             env_vars: Vec::new(),
+            cached: false,
+            deduped_lines: Vec::new(),
+            launch: None,
+            reproduce: None,
+            label: None,
         });
 
         doc_result.add_result(CommandResult {
@@ -910,12 +2055,19 @@ Example here.
             status: VerifyStatus::Fail,
             exit_code: Some(1),
             expected_exit_code: 0,
+            expected_exit_codes: None,
             stdout: None,
             stderr: None,
             duration_ms: Some(5),
             output_mismatch: None,
             working_dir: None,
+            isolated_dir: None,
             env_vars: Vec::new(),
+            cached: false,
+            deduped_lines: Vec::new(),
+            launch: None,
+            reproduce: None,
+            label: None,
         });
 
         results.add_document(doc_result);
@@ -924,7 +2076,7 @@ Example here.
         assert_eq!(results.commands_executed, 2);
         assert_eq!(results.commands_passed, 1);
         assert_eq!(results.commands_failed, 1);
-        assert!(!results.is_success());
+        assert!(!results.is_success(false));
     }
 
     #[test]
@@ -933,14 +2085,20 @@ Example here.
             command: "echo hello".to_string(),
             working_dir: None,
             expected_exit_code: Some(0),
+            expected_exit_codes: None,
             expected_output: None,
             timeout_secs: Some(30),
             env_vars: Vec::new(),
+            source_lines: Vec::new(),
+            label: None,
+            tags: Vec::new(),
+            raw: String::new(),
+            interpreter: None,
         };
 
         let result = run_command(
             &item,
-            Duration::from_secs(30),
+            &RunOptions::default(),
             Path::new("."),
             &default_rules(),
         );
@@ -956,14 +2114,20 @@ Example here.
             command: "exit 1".to_string(),
             working_dir: None,
             expected_exit_code: Some(0),
+            expected_exit_codes: None,
             expected_output: None,
             timeout_secs: Some(30),
             env_vars: Vec::new(),
+            source_lines: Vec::new(),
+            label: None,
+            tags: Vec::new(),
+            raw: String::new(),
+            interpreter: None,
         };
 
         let result = run_command(
             &item,
-            Duration::from_secs(30),
+            &RunOptions::default(),
             Path::new("."),
             &default_rules(),
         );
@@ -979,14 +2143,20 @@ Example here.
             command: "exit 1".to_string(),
             working_dir: None,
             expected_exit_code: Some(1),
+            expected_exit_codes: None,
             expected_output: None,
             timeout_secs: Some(30),
             env_vars: Vec::new(),
+            source_lines: Vec::new(),
+            label: None,
+            tags: Vec::new(),
+            raw: String::new(),
+            interpreter: None,
         };
 
         let result = run_command(
             &item,
-            Duration::from_secs(30),
+            &RunOptions::default(),
             Path::new("."),
             &default_rules(),
         );
@@ -995,11 +2165,69 @@ Example here.
         assert_eq!(result.exit_code, Some(1));
     }
 
+    #[test]
+    fn run_command_applies_default_timeout() {
+        let item = VerificationItem {
+            command: "sleep 5".to_string(),
+            timeout_secs: None,
+            ..Default::default()
+        };
+
+        let result = run_command(
+            &item,
+            &RunOptions {
+                default_timeout: Some(Duration::from_millis(200)),
+                ..Default::default()
+            },
+            Path::new("."),
+            &default_rules(),
+        );
+
+        assert_eq!(result.status, VerifyStatus::Timeout);
+    }
+
+    #[test]
+    fn timeout_override_wins_over_default() {
+        let mut spec = VerificationSpec {
+            source_file: PathBuf::from("test.md"),
+            section_line: 1,
+            skipped: 0,
+            serial: false,
+            setup: vec![],
+            teardown: vec![],
+            items: vec![VerificationItem {
+                command: "sleep 0.5".to_string(),
+                timeout_secs: None,
+                ..Default::default()
+            }],
+        };
+
+        // `--timeout 0` disables the timeout even though the default is shorter
+        apply_timeout_override(&mut spec, Some(0));
+        let result = run_command(
+            &spec.items[0],
+            &RunOptions {
+                default_timeout: Some(Duration::from_millis(200)),
+                ..Default::default()
+            },
+            Path::new("."),
+            &default_rules(),
+        );
+        assert_eq!(result.status, VerifyStatus::Pass);
+
+        apply_timeout_override(&mut spec, None);
+        assert_eq!(spec.items[0].timeout_secs, Some(0));
+    }
+
     #[test]
     fn json_output_is_valid() {
         let spec = VerificationSpec {
             source_file: PathBuf::from("test.md"),
             section_line: 10,
+            skipped: 0,
+            serial: false,
+            setup: vec![],
+            teardown: vec![],
             items: vec![],
         };
 
@@ -1010,12 +2238,19 @@ Example here.
             status: VerifyStatus::Pass,
             exit_code: Some(0),
             expected_exit_code: 0,
+            expected_exit_codes: None,
             stdout: Some("ok\n".to_string()),
             stderr: None,
             duration_ms: Some(10),
             output_mismatch: None,
             working_dir: None,
+            isolated_dir: None,
             env_vars: Vec::new(),
+            cached: false,
+            deduped_lines: Vec::new(),
+            launch: None,
+            reproduce: None,
+            label: None,
         });
         results.add_document(doc_result);
 
@@ -1027,6 +2262,50 @@ Example here.
         assert_eq!(parsed["documents"][0]["commands"][0]["status"], "pass");
     }
 
+    #[test]
+    fn passing_output_is_kept_only_when_verbose() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_config(&temp_dir);
+        let doc_path = create_doc_with_verification(
+            &temp_dir,
+            "mixed.md",
+            &["echo version-1.2.3", "echo broken && false"],
+        );
+        let doc = ParsedDoc::parse(&doc_path).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+
+        for verbose in [false, true] {
+            let mut results = VerifyResults::new();
+            results.add_document(
+                run_verification(
+                    &spec,
+                    &RunOptions::default(),
+                    None,
+                    false,
+                    temp_dir.path(),
+                    &default_rules(),
+                    None,
+                )
+                .unwrap(),
+            );
+            if !verbose {
+                results.drop_passing_output();
+            }
+
+            let json = serde_json::to_value(&results).unwrap();
+            let commands = &json["documents"][0]["commands"];
+            assert_eq!(commands[0]["status"], "pass");
+            assert_eq!(
+                commands[0]["stdout"]
+                    .as_str()
+                    .is_some_and(|s| s.contains("version-1.2.3")),
+                verbose
+            );
+            // Failures always keep their output
+            assert!(commands[1]["stdout"].as_str().unwrap().contains("broken"));
+        }
+    }
+
     #[test]
     fn find_markdown_files_collects_recursively() {
         let temp_dir = TempDir::new().unwrap();
@@ -1054,14 +2333,16 @@ Example here.
 
         let doc_result = run_verification(
             &spec,
-            Duration::from_secs(30),
-            true,
+            &RunOptions::default(),
+            None,
+            false,
             temp_dir.path(),
             &default_rules(),
+            None,
         )
         .unwrap();
 
-        assert!(doc_result.is_success());
+        assert!(doc_result.is_success(false));
         assert_eq!(doc_result.commands.len(), 2);
         assert!(
             doc_result
@@ -1083,14 +2364,16 @@ Example here.
 
         let doc_result = run_verification(
             &spec,
-            Duration::from_secs(30),
-            true,
+            &RunOptions::default(),
+            None,
+            false,
             temp_dir.path(),
             &default_rules(),
+            None,
         )
         .unwrap();
 
-        assert!(!doc_result.is_success());
+        assert!(!doc_result.is_success(false));
         assert_eq!(doc_result.commands[0].status, VerifyStatus::Pass);
         assert_eq!(doc_result.commands[1].status, VerifyStatus::Fail);
     }
@@ -1110,14 +2393,16 @@ Example here.
 
         let doc_result = run_verification(
             &spec,
-            Duration::from_secs(30),
+            &RunOptions::default(),
+            Some(1),
             false,
             temp_dir.path(),
             &default_rules(),
+            None,
         )
         .unwrap();
 
-        assert!(!doc_result.is_success());
+        assert!(!doc_result.is_success(false));
         assert_eq!(doc_result.commands.len(), 2);
         assert_eq!(doc_result.commands[0].status, VerifyStatus::Fail);
         assert_eq!(doc_result.commands[1].status, VerifyStatus::Skipped);
@@ -1138,14 +2423,16 @@ Example here.
 
         let doc_result = run_verification(
             &spec,
-            Duration::from_secs(30),
-            true,
+            &RunOptions::default(),
+            None,
+            false,
             temp_dir.path(),
             &default_rules(),
+            None,
         )
         .unwrap();
 
-        assert!(!doc_result.is_success());
+        assert!(!doc_result.is_success(false));
         assert_eq!(doc_result.commands.len(), 3);
         assert_eq!(doc_result.commands[0].status, VerifyStatus::Fail);
         assert_eq!(doc_result.commands[1].status, VerifyStatus::Pass);
@@ -1158,14 +2445,20 @@ Example here.
             command: "echo actual".to_string(),
             working_dir: None,
             expected_exit_code: Some(0),
+            expected_exit_codes: None,
             expected_output: Some(OutputMatcher::Contains("expected".to_string())),
             timeout_secs: Some(30),
             env_vars: Vec::new(),
+            source_lines: Vec::new(),
+            label: None,
+            tags: Vec::new(),
+            raw: String::new(),
+            interpreter: None,
         };
 
         let result = run_command(
             &item,
-            Duration::from_secs(30),
+            &RunOptions::default(),
             Path::new("."),
             &default_rules(),
         );
@@ -1184,14 +2477,20 @@ Example here.
             command: "echo actual".to_string(),
             working_dir: None,
             expected_exit_code: Some(0),
+            expected_exit_codes: None,
             expected_output: Some(OutputMatcher::Contains("expected".to_string())),
             timeout_secs: Some(30),
             env_vars: Vec::new(),
+            source_lines: Vec::new(),
+            label: None,
+            tags: Vec::new(),
+            raw: String::new(),
+            interpreter: None,
         };
 
         let result = run_command(
             &item,
-            Duration::from_secs(30),
+            &RunOptions::default(),
             Path::new("."),
             &strict_rules(),
         );
@@ -1200,20 +2499,161 @@ Example here.
         assert!(result.output_mismatch.is_some());
     }
 
+    #[test]
+    fn strict_output_flag_overrides_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_path = create_test_config(&temp_dir);
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(
+            root.join("docs/a.md"),
+            "# A\n\n## Verification\n\n```bash\n$ echo hello world\n```\n```output\nhello\n```\n",
+        )
+        .unwrap();
+        let run = |strict_output| {
+            let report = root.join("report.json");
+            execute(VerifyArgs {
+                report: Some(report.clone()),
+                strict_output,
+                ..shell_args(config_path.clone(), None)
+            })
+            .unwrap_or(());
+            let json: serde_json::Value =
+                serde_json::from_str(&fs::read_to_string(report).unwrap()).unwrap();
+            json["documents"][0]["commands"][0]["status"].clone()
+        };
+
+        // Substring match passes leniently, but isn't the whole output
+        assert_eq!(run(None), "pass");
+        assert_eq!(run(Some(true)), "fail");
+
+        let mut config = fs::read_to_string(&config_path).unwrap();
+        config = config.replace("[rules]\n", "[rules]\nstrict_output_matching = true\n");
+        fs::write(&config_path, config).unwrap();
+        assert_eq!(run(None), "fail");
+        assert_eq!(run(Some(false)), "pass");
+    }
+
+    #[test]
+    fn labels_replace_commands_in_human_output_only() {
+        let item = VerificationItem {
+            command: "echo labeled".to_string(),
+            label: Some("Say hello".to_string()),
+            ..Default::default()
+        };
+        let result = run_command(
+            &item,
+            &RunOptions::default(),
+            Path::new("."),
+            &default_rules(),
+        );
+        assert_eq!(result.display_name(), "Say hello");
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["command"], "echo labeled");
+        assert_eq!(json["label"], "Say hello");
+
+        let unlabeled = CommandResult {
+            label: None,
+            ..result
+        };
+        assert_eq!(unlabeled.display_name(), "echo labeled");
+        assert!(
+            serde_json::to_value(&unlabeled)
+                .unwrap()
+                .get("label")
+                .is_none()
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn timeout_policy_decides_whether_timeouts_fail() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_path = create_test_config(&temp_dir);
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(
+            root.join("docs/slow.md"),
+            "# Slow\n\n## Verification\n\n```bash\nsleep 5\n```\n\n```bash\ntouch after\n```\n",
+        )
+        .unwrap();
+        let report = root.join("report.json");
+        let run = |allow_timeout| {
+            let outcome = execute(VerifyArgs {
+                report: Some(report.clone()),
+                timeout: Some(1),
+                allow_timeout,
+                ..shell_args(config_path.clone(), None)
+            });
+            let json: serde_json::Value =
+                serde_json::from_str(&fs::read_to_string(&report).unwrap()).unwrap();
+            (outcome, json)
+        };
+
+        // Default: the timeout fails the run and stops the document
+        let (outcome, json) = run(false);
+        assert!(outcome.unwrap_err().to_string().contains("1 timed out"));
+        assert_eq!(json["commands_timed_out"], 1);
+        assert_eq!(json["commands_failed"], 0);
+        assert!(!root.join("after").exists());
+
+        // --allow-timeout: inconclusive, later commands still run
+        let (outcome, json) = run(true);
+        assert!(outcome.is_ok());
+        assert_eq!(json["commands_timed_out"], 1);
+        assert_eq!(json["commands_passed"], 1);
+        assert_eq!(json["documents"][0]["commands"][0]["status"], "timeout");
+        assert!(root.join("after").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn allowed_timeout_does_not_stop_later_docs() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_path = create_test_config(&temp_dir);
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(
+            root.join("docs/a.md"),
+            "# A\n\n## Verification\n\n```bash\nsleep 5\n```\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("docs/b.md"),
+            "# B\n\n## Verification\n\n```bash\ntouch ran-b\n```\n",
+        )
+        .unwrap();
+
+        execute(VerifyArgs {
+            timeout: Some(1),
+            allow_timeout: true,
+            ..shell_args(config_path, None)
+        })
+        .unwrap();
+        assert!(root.join("ran-b").exists());
+    }
+
     #[test]
     fn output_mismatch_ignored_with_skip_mode() {
         let item = VerificationItem {
             command: "echo actual".to_string(),
             working_dir: None,
             expected_exit_code: Some(0),
+            expected_exit_codes: None,
             expected_output: Some(OutputMatcher::Contains("expected".to_string())),
             timeout_secs: Some(30),
             env_vars: Vec::new(),
+            source_lines: Vec::new(),
+            label: None,
+            tags: Vec::new(),
+            raw: String::new(),
+            interpreter: None,
         };
 
         let result = run_command(
             &item,
-            Duration::from_secs(30),
+            &RunOptions::default(),
             Path::new("."),
             &skip_output_rules(),
         );
@@ -1228,14 +2668,20 @@ Example here.
             command: "echo hello world".to_string(),
             working_dir: None,
             expected_exit_code: Some(0),
+            expected_exit_codes: None,
             expected_output: Some(OutputMatcher::Contains("hello".to_string())),
             timeout_secs: Some(30),
             env_vars: Vec::new(),
+            source_lines: Vec::new(),
+            label: None,
+            tags: Vec::new(),
+            raw: String::new(),
+            interpreter: None,
         };
 
         let result = run_command(
             &item,
-            Duration::from_secs(30),
+            &RunOptions::default(),
             Path::new("."),
             &default_rules(),
         );
@@ -1249,6 +2695,10 @@ Example here.
         let spec = VerificationSpec {
             source_file: PathBuf::from("test.md"),
             section_line: 10,
+            skipped: 0,
+            serial: false,
+            setup: vec![],
+            teardown: vec![],
             items: vec![],
         };
 
@@ -1258,6 +2708,7 @@ Example here.
             status: VerifyStatus::Warn,
             exit_code: Some(0),
             expected_exit_code: 0,
+            expected_exit_codes: None,
             stdout: Some("actual".to_string()),
             stderr: None,
             duration_ms: Some(10),
@@ -1267,11 +2718,17 @@ Example here.
                 actual: "actual".to_string(),
             }),
             working_dir: None,
+            isolated_dir: None,
             env_vars: Vec::new(),
+            cached: false,
+            deduped_lines: Vec::new(),
+            launch: None,
+            reproduce: None,
+            label: None,
         });
 
         // Warn is still considered success
-        assert!(doc_result.is_success());
+        assert!(doc_result.is_success(false));
         // Verify the document status is Warn
         assert_eq!(doc_result.status, VerifyStatus::Warn);
     }
@@ -1281,6 +2738,10 @@ Example here.
         let spec = VerificationSpec {
             source_file: PathBuf::from("test.md"),
             section_line: 10,
+            skipped: 0,
+            serial: false,
+            setup: vec![],
+            teardown: vec![],
             items: vec![],
         };
 
@@ -1292,12 +2753,19 @@ Example here.
             status: VerifyStatus::Pass,
             exit_code: Some(0),
             expected_exit_code: 0,
+            expected_exit_codes: None,
             stdout: None,
             stderr: None,
             duration_ms: Some(10),
             output_mismatch: None,
             working_dir: None,
+            isolated_dir: None,
             env_vars: Vec::new(),
+            cached: false,
+            deduped_lines: Vec::new(),
+            launch: None,
+            reproduce: None,
+            label: None,
         });
 
         doc_result.add_result(CommandResult {
@@ -1305,6 +2773,7 @@ Example here.
             status: VerifyStatus::Warn,
             exit_code: Some(0),
             expected_exit_code: 0,
+            expected_exit_codes: None,
             stdout: Some("actual".to_string()),
             stderr: None,
             duration_ms: Some(5),
@@ -1314,7 +2783,13 @@ Example here.
                 actual: "actual".to_string(),
             }),
             working_dir: None,
+            isolated_dir: None,
             env_vars: Vec::new(),
+            cached: false,
+            deduped_lines: Vec::new(),
+            launch: None,
+            reproduce: None,
+            label: None,
         });
 
         results.add_document(doc_result);
@@ -1322,6 +2797,1316 @@ Example here.
         assert_eq!(results.commands_passed, 1);
         assert_eq!(results.commands_warned, 1);
         assert_eq!(results.commands_failed, 0);
-        assert!(results.is_success());
+        assert!(results.is_success(false));
+    }
+
+    #[test]
+    fn status_label_respects_palette() {
+        let plain = Palette::new(false);
+        assert_eq!(status_label(VerifyStatus::Pass, &plain), "PASS");
+        assert_eq!(status_label(VerifyStatus::Fail, &plain), "FAIL");
+
+        let colored = Palette::new(true);
+        assert_eq!(
+            status_label(VerifyStatus::Pass, &colored),
+            "\x1b[32mPASS\x1b[0m"
+        );
+        assert_eq!(
+            status_label(VerifyStatus::Fail, &colored),
+            "\x1b[31mFAIL\x1b[0m"
+        );
+    }
+
+    fn run_with_cache(
+        root: &Path,
+        doc_path: &Path,
+        cache: &mut VerifyCache,
+        reuse: bool,
+        since: Option<Duration>,
+    ) -> DocumentResult {
+        let doc = ParsedDoc::parse(doc_path).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+        let inputs_hash = cache::hash_inputs(root, doc_path, &cache::input_patterns(&doc));
+        let mut doc_cache = DocCache {
+            cache,
+            inputs_hash: inputs_hash.as_deref(),
+            reuse,
+            since,
+        };
+        run_verification(
+            &spec,
+            &RunOptions::default(),
+            None,
+            false,
+            root,
+            &default_rules(),
+            Some(&mut doc_cache),
+        )
+        .unwrap()
+    }
+
+    fn create_doc_with_inputs(temp_dir: &TempDir) -> PathBuf {
+        fs::create_dir_all(temp_dir.path().join("src")).unwrap();
+        fs::write(temp_dir.path().join("src/input.txt"), "v1").unwrap();
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(&docs_dir).unwrap();
+        let path = docs_dir.join("cached.md");
+        fs::write(
+            &path,
+            "# Cached\n\n## Verification\n```bash\ntrue\n```\n\n## Paths\n- `src/*.txt`\n",
+        )
+        .unwrap();
+        path
+    }
+
+    #[test]
+    fn verify_cache_reuses_pass_on_second_run() {
+        let temp_dir = TempDir::new().unwrap();
+        let doc_path = create_doc_with_inputs(&temp_dir);
+        let mut verify_cache = VerifyCache::default();
+
+        let first = run_with_cache(temp_dir.path(), &doc_path, &mut verify_cache, true, None);
+        assert!(!first.commands[0].cached);
+
+        let second = run_with_cache(temp_dir.path(), &doc_path, &mut verify_cache, true, None);
+        assert!(second.commands[0].cached);
+        assert_eq!(second.commands[0].status, VerifyStatus::Pass);
+
+        let mut results = VerifyResults::new();
+        results.add_document(second);
+        assert_eq!(results.commands_cached, 1);
+        assert_eq!(results.commands_executed, 0);
+    }
+
+    #[test]
+    fn verify_cache_never_reuses_pass_without_inputs() {
+        let temp_dir = TempDir::new().unwrap();
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(&docs_dir).unwrap();
+        let mut verify_cache = VerifyCache::default();
+
+        // No `## Paths`, and a `## Paths` that matches nothing
+        for (name, paths) in [
+            ("bare.md", ""),
+            ("unmatched.md", "\n## Paths\n- `src/*.rs`\n"),
+        ] {
+            let doc_path = docs_dir.join(name);
+            fs::write(
+                &doc_path,
+                format!("# Doc\n\n## Verification\n```bash\ntrue\n```\n{}", paths),
+            )
+            .unwrap();
+
+            for _ in 0..2 {
+                let result =
+                    run_with_cache(temp_dir.path(), &doc_path, &mut verify_cache, true, None);
+                assert!(!result.commands[0].cached, "{name}");
+                assert_eq!(result.commands[0].status, VerifyStatus::Pass);
+            }
+        }
+    }
+
+    #[test]
+    fn verify_cache_invalidated_by_doc_change() {
+        let temp_dir = TempDir::new().unwrap();
+        let doc_path = create_doc_with_inputs(&temp_dir);
+        let mut verify_cache = VerifyCache::default();
+
+        run_with_cache(temp_dir.path(), &doc_path, &mut verify_cache, true, None);
+        let content = fs::read_to_string(&doc_path).unwrap();
+        fs::write(&doc_path, content.replace("# Cached", "# Cached doc")).unwrap();
+
+        let second = run_with_cache(temp_dir.path(), &doc_path, &mut verify_cache, true, None);
+        assert!(!second.commands[0].cached);
+    }
+
+    #[test]
+    fn verify_cache_invalidated_by_matched_file_change() {
+        let temp_dir = TempDir::new().unwrap();
+        let doc_path = create_doc_with_inputs(&temp_dir);
+        let mut verify_cache = VerifyCache::default();
+
+        run_with_cache(temp_dir.path(), &doc_path, &mut verify_cache, true, None);
+        fs::write(temp_dir.path().join("src/input.txt"), "v2").unwrap();
+
+        let rerun = run_with_cache(temp_dir.path(), &doc_path, &mut verify_cache, true, None);
+        assert!(!rerun.commands[0].cached);
+    }
+
+    #[test]
+    fn verify_no_cache_forces_rerun() {
+        let temp_dir = TempDir::new().unwrap();
+        let doc_path = create_doc_with_inputs(&temp_dir);
+        let mut verify_cache = VerifyCache::default();
+
+        run_with_cache(temp_dir.path(), &doc_path, &mut verify_cache, true, None);
+        let rerun = run_with_cache(temp_dir.path(), &doc_path, &mut verify_cache, false, None);
+        assert!(!rerun.commands[0].cached);
+    }
+
+    #[test]
+    fn verify_since_skips_recent_passes_until_window_ends() {
+        let temp_dir = TempDir::new().unwrap();
+        let doc_path = create_doc_with_inputs(&temp_dir);
+        let mut verify_cache = VerifyCache::default();
+        let window = Some(Duration::from_secs(10 * 60));
+
+        run_with_cache(temp_dir.path(), &doc_path, &mut verify_cache, true, window);
+        // Changed inputs defeat the hash cache but not the time window
+        fs::write(temp_dir.path().join("src/input.txt"), "v2").unwrap();
+        let second = run_with_cache(temp_dir.path(), &doc_path, &mut verify_cache, true, window);
+        assert_eq!(second.commands[0].status, VerifyStatus::Skipped);
+        assert!(second.commands[0].cached);
+        assert!(second.is_success(false));
+
+        // Once the last pass falls outside the window, the command re-runs
+        let doc = ParsedDoc::parse(&doc_path).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+        let options = item_options(&RunOptions::default(), temp_dir.path(), &default_rules());
+        let key = cache::item_key(Path::new("docs/cached.md"), &spec.items[0], &options);
+        verify_cache.record_pass_at(
+            key,
+            "stale".to_string(),
+            std::time::SystemTime::now() - Duration::from_secs(20 * 60),
+        );
+        let third = run_with_cache(temp_dir.path(), &doc_path, &mut verify_cache, true, window);
+        assert_eq!(third.commands[0].status, VerifyStatus::Pass);
+        assert!(!third.commands[0].cached);
+    }
+
+    fn listed_specs() -> Vec<(VerificationSpec, Option<String>)> {
+        [
+            (
+                "docs/a.md",
+                "# A\n\n## Verification\n```bash\necho one\n```\n```bash\necho two\n```\n",
+            ),
+            (
+                "docs/b.md",
+                "# B\n\n## Verification\n```bash\ncat <<EOF\nthree\nEOF\n```\n",
+            ),
+        ]
+        .into_iter()
+        .map(|(path, content)| {
+            let doc = ParsedDoc::parse_content(PathBuf::from(path), content).unwrap();
+            (extract_verification_spec(&doc).unwrap(), None)
+        })
+        .collect()
+    }
+
+    #[test]
+    fn list_items_numbers_every_item_across_docs() {
+        let specs = listed_specs();
+        assert_eq!(
+            list_items(specs.iter().map(|(spec, _)| spec)),
+            vec![
+                "1\tdocs/a.md:3\techo one",
+                "2\tdocs/a.md:3\techo two",
+                "3\tdocs/b.md:3\tcat <<EOF",
+            ]
+        );
+    }
+
+    #[test]
+    fn only_runs_the_selected_item() {
+        let mut specs = listed_specs();
+        select_item(&mut specs, 2).unwrap();
+        assert_eq!(specs.len(), 1);
+
+        let temp_dir = TempDir::new().unwrap();
+        let result = run_verification(
+            &specs[0].0,
+            &RunOptions::default(),
+            Some(1),
+            false,
+            temp_dir.path(),
+            &default_rules(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(result.commands.len(), 1);
+        assert_eq!(result.commands[0].command, "echo two");
+        assert_eq!(result.commands[0].status, VerifyStatus::Pass);
+
+        for index in [0, 4] {
+            let err = select_item(&mut listed_specs(), index).unwrap_err();
+            assert!(err.to_string().contains("3 items found"), "{err}");
+        }
+    }
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = std::process::Command::new("git")
+            .args([
+                "-c",
+                "user.name=pave",
+                "-c",
+                "user.email=pave@example.com",
+                "-c",
+                "commit.gpgsign=false",
+            ])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    fn verify_changed_runs_only_impacted_docs() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_path = create_test_config(&temp_dir);
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(root.join("src/a.rs"), "// a").unwrap();
+        fs::write(root.join("src/b.rs"), "// b").unwrap();
+        for name in ["a", "b"] {
+            fs::write(
+                root.join(format!("docs/{}.md", name)),
+                format!(
+                    "# {name}\n\n## Verification\n\n```bash\ntouch ran-{name}\n```\n\n## Paths\n\n- `src/{name}.rs`\n"
+                ),
+            )
+            .unwrap();
+        }
+        fs::write(root.join(".gitignore"), "ran-*\n.pave/\n").unwrap();
+
+        git(root, &["init", "-q"]);
+        git(root, &["add", "-A"]);
+        git(root, &["commit", "-q", "-m", "initial"]);
+        fs::write(root.join("src/a.rs"), "// a changed").unwrap();
+        git(root, &["commit", "-q", "-am", "change a"]);
+
+        execute(VerifyArgs {
+            paths: vec![],
+            format: OutputFormat::Json,
+            report: None,
+            append: false,
+            summary_json: None,
+            out_dir: None,
+            timeout: None,
+            keep_going: true,
+            bail_after: None,
+            jobs: 1,
+            no_cache: true,
+            since: None,
+            changed: true,
+            base: Some("HEAD~1".to_string()),
+            staged: false,
+            verbose: false,
+            dedupe: false,
+            list: false,
+            only: None,
+            only_failed: false,
+            list_docs: false,
+            list_failing_docs: false,
+            shell: None,
+            capture: CaptureMode::All,
+            on_missing_shell: MissingShell::Error,
+            isolated: false,
+            print_command: None,
+            show_env: false,
+            strict_output: None,
+            allow_timeout: false,
+            tags: vec![],
+            exclude_tags: vec![],
+            before: vec![],
+            after: vec![],
+            config: Some(config_path),
+        })
+        .unwrap();
+
+        assert!(root.join("ran-a").exists());
+        assert!(!root.join("ran-b").exists());
+    }
+
+    #[test]
+    fn verify_changed_staged_runs_only_docs_impacted_by_the_index() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_path = create_test_config(&temp_dir);
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        for name in ["a", "b", "c"] {
+            fs::write(root.join(format!("src/{}.rs", name)), "// v1").unwrap();
+            fs::write(
+                root.join(format!("docs/{}.md", name)),
+                format!(
+                    "# {name}\n\n## Verification\n\n```bash\ntouch ran-{name}\n```\n\n## Paths\n\n- `src/{name}.rs`\n"
+                ),
+            )
+            .unwrap();
+        }
+        fs::write(root.join(".gitignore"), "ran-*\n.pave/\n").unwrap();
+
+        git(root, &["init", "-q"]);
+        git(root, &["add", "-A"]);
+        git(root, &["commit", "-q", "-m", "initial"]);
+        // Stage a code change for a and a doc-only edit for b; c's change stays unstaged
+        fs::write(root.join("src/a.rs"), "// v2").unwrap();
+        fs::write(root.join("src/c.rs"), "// v2").unwrap();
+        let doc_b = fs::read_to_string(root.join("docs/b.md")).unwrap();
+        fs::write(root.join("docs/b.md"), format!("{}\nMore.\n", doc_b)).unwrap();
+        git(root, &["add", "src/a.rs", "docs/b.md"]);
+
+        execute(VerifyArgs {
+            changed: true,
+            staged: true,
+            keep_going: true,
+            ..shell_args(config_path, None)
+        })
+        .unwrap();
+
+        assert!(root.join("ran-a").exists());
+        assert!(root.join("ran-b").exists());
+        assert!(!root.join("ran-c").exists());
+    }
+
+    fn shell_args(config: PathBuf, shell: Option<&str>) -> VerifyArgs {
+        VerifyArgs {
+            paths: vec![],
+            format: OutputFormat::Json,
+            report: None,
+            append: false,
+            summary_json: None,
+            out_dir: None,
+            timeout: None,
+            keep_going: false,
+            bail_after: None,
+            jobs: 1,
+            no_cache: true,
+            since: None,
+            changed: false,
+            base: None,
+            staged: false,
+            verbose: false,
+            dedupe: false,
+            list: false,
+            only: None,
+            only_failed: false,
+            list_docs: false,
+            list_failing_docs: false,
+            shell: shell.map(str::to_string),
+            capture: CaptureMode::All,
+            on_missing_shell: MissingShell::Error,
+            isolated: false,
+            print_command: None,
+            show_env: false,
+            strict_output: None,
+            allow_timeout: false,
+            tags: vec![],
+            exclude_tags: vec![],
+            before: vec![],
+            after: vec![],
+            config: Some(config),
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn verify_runs_commands_under_configured_shell() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_path = create_test_config(&temp_dir);
+        let mut config = fs::read_to_string(&config_path).unwrap();
+        config.push_str("\n[verification]\nshell = \"bash\"\n");
+        fs::write(&config_path, config).unwrap();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(
+            root.join("docs/bash.md"),
+            "# Bash\n\n## Verification\n\n```bash\n[[ -n \"$BASH_VERSION\" ]] && touch ran-bash\n```\n",
+        )
+        .unwrap();
+
+        execute(shell_args(config_path.clone(), None)).unwrap();
+        assert!(root.join("ran-bash").exists());
+
+        fs::remove_file(root.join("ran-bash")).unwrap();
+        execute(shell_args(config_path, Some("bash"))).unwrap();
+        assert!(root.join("ran-bash").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn setup_runs_first_and_teardown_runs_after_a_failure() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_path = create_test_config(&temp_dir);
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(
+            root.join("docs/hooks.md"),
+            "# Hooks\n\n## Verification\n\n```bash\necho item >> log\n```\n\n```bash\nfalse\n```\n\n## Setup\n\n```bash\necho setup >> log\n```\n\n## Teardown\n\n```bash\necho teardown >> log\n```\n",
+        )
+        .unwrap();
+        let report = root.join("report.json");
+
+        let err = execute(VerifyArgs {
+            report: Some(report.clone()),
+            ..shell_args(config_path, None)
+        })
+        .unwrap_err();
+
+        assert!(err.to_string().starts_with("Verification failed"));
+        assert_eq!(
+            fs::read_to_string(root.join("log")).unwrap(),
+            "setup\nitem\nteardown\n"
+        );
+        let report: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(report).unwrap()).unwrap();
+        let labels: Vec<&str> = report["documents"][0]["commands"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|cmd| cmd["label"].as_str().unwrap_or("-"))
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                "setup: echo setup >> log",
+                "-",
+                "-",
+                "teardown: echo teardown >> log"
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn failed_setup_skips_items_but_still_tears_down() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_path = create_test_config(&temp_dir);
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(
+            root.join("docs/hooks.md"),
+            "# Hooks\n\n## Setup\n\n```bash\nexit 3\n```\n\n## Verification\n\n```bash\ntouch ran\n```\n\n## Teardown\n\n```bash\ntouch cleaned\n```\n",
+        )
+        .unwrap();
+
+        assert!(execute(shell_args(config_path, None)).is_err());
+        assert!(!root.join("ran").exists());
+        assert!(root.join("cleaned").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn before_and_after_hooks_wrap_every_doc() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_path = create_test_config(&temp_dir);
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(
+            root.join("docs/a.md"),
+            "# A\n\n## Setup\n\n```bash\necho setup-a >> log\n```\n\n## Verification\n\n```bash\necho a >> log\n```\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("docs/b.md"),
+            "# B\n\n## Verification\n\n```bash\necho b >> log; false\n```\n",
+        )
+        .unwrap();
+
+        let err = execute(VerifyArgs {
+            keep_going: true,
+            before: vec!["echo before >> log".to_string()],
+            after: vec!["echo after >> log".to_string()],
+            ..shell_args(config_path.clone(), None)
+        })
+        .unwrap_err();
+        assert!(err.to_string().starts_with("Verification failed"));
+        assert_eq!(
+            fs::read_to_string(root.join("log")).unwrap(),
+            "before\nsetup-a\na\nb\nafter\n"
+        );
+
+        fs::remove_file(root.join("log")).unwrap();
+        let err = execute(VerifyArgs {
+            before: vec!["exit 1".to_string(), "echo never >> log".to_string()],
+            after: vec!["echo after >> log".to_string()],
+            ..shell_args(config_path, None)
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("--before"));
+        assert_eq!(fs::read_to_string(root.join("log")).unwrap(), "after\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn summary_json_counts_match_the_full_report() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_path = create_test_config(&temp_dir);
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(
+            root.join("docs/mixed.md"),
+            "# Mixed\n\n## Verification\n\n```bash\ntrue\n```\n\n```bash\nfalse\n```\n\n```bash skip\nrm -rf /\n```\n\n```bash\necho ok\n```\n",
+        )
+        .unwrap();
+        let report = root.join("report.json");
+        let summary = root.join("summary.json");
+
+        let result = execute(VerifyArgs {
+            keep_going: true,
+            report: Some(report.clone()),
+            summary_json: Some(summary.clone()),
+            ..shell_args(config_path, None)
+        });
+        assert!(result.is_err());
+
+        let report: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(report).unwrap()).unwrap();
+        let summary: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(summary).unwrap()).unwrap();
+        assert_eq!(summary["total"], 4);
+        assert_eq!(summary["passed"], report["commands_passed"]);
+        assert_eq!(summary["failed"], report["commands_failed"]);
+        assert_eq!(summary["skipped"], report["commands_skipped"]);
+        assert_eq!(
+            (summary["passed"].as_u64(), summary["failed"].as_u64()),
+            (Some(2), Some(1))
+        );
+        assert!(summary["duration_ms"].is_u64());
+        assert_eq!(summary.as_object().unwrap().len(), 5);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn bail_after_stops_the_run_after_that_many_failures() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_path = create_test_config(&temp_dir);
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(
+            root.join("docs/a.md"),
+            "# A\n\n## Verification\n\n```bash\nexit 1\n```\n\n```bash\nexit 2\n```\n\n```bash\nexit 3\n```\n\n```bash\ntouch ran-a\n```\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("docs/b.md"),
+            "# B\n\n## Verification\n\n```bash\ntouch ran-b\n```\n",
+        )
+        .unwrap();
+        let report = root.join("report.json");
+
+        let err = execute(VerifyArgs {
+            bail_after: Some(2),
+            report: Some(report.clone()),
+            ..shell_args(config_path, None)
+        })
+        .unwrap_err();
+
+        assert!(err.to_string().starts_with("Verification failed: 2 of"));
+        assert!(!root.join("ran-a").exists());
+        assert!(!root.join("ran-b").exists());
+        let report: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(report).unwrap()).unwrap();
+        assert_eq!(report["commands_failed"], 2);
+        assert_eq!(report["commands_not_run"], 3);
+        let statuses: Vec<&str> = report["documents"][0]["commands"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|cmd| cmd["status"].as_str().unwrap())
+            .collect();
+        assert_eq!(statuses, vec!["fail", "fail", "skipped", "skipped"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn jobs_run_items_through_run_spec_in_batches_when_bailing() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_path = create_test_config(&temp_dir);
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(
+            root.join("docs/a.md"),
+            "# A\n\n## Verification\n\n```bash\nexit 1\n```\n\n```bash\ntouch second\n```\n\n```bash\ntouch third\n```\n",
+        )
+        .unwrap();
+        let report = root.join("report.json");
+
+        // The first batch of two runs to completion before the failure stops the doc
+        assert!(
+            execute(VerifyArgs {
+                jobs: 2,
+                report: Some(report.clone()),
+                ..shell_args(config_path, None)
+            })
+            .is_err()
+        );
+        assert!(root.join("second").exists());
+        assert!(!root.join("third").exists());
+        let report: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(report).unwrap()).unwrap();
+        let statuses: Vec<&str> = report["documents"][0]["commands"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|cmd| cmd["status"].as_str().unwrap())
+            .collect();
+        assert_eq!(statuses, vec!["fail", "pass", "skipped"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn verify_runs_commands_in_configured_base_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_path = create_test_config(&temp_dir);
+        let mut config = fs::read_to_string(&config_path).unwrap();
+        config.push_str("\n[verification]\nbase_dir = \".\"\n");
+        fs::write(&config_path, config).unwrap();
+        fs::create_dir_all(root.join("docs/runbooks")).unwrap();
+        fs::write(
+            root.join("docs/runbooks/deploy.md"),
+            format!(
+                "# Deploy\n\n## Verification\n\n```bash\ntouch first\n```\n\n```bash\ntouch second\n```\n\n<!-- pave:working_dir {} -->\n```bash\ntouch third\n```\n",
+                root.join("docs").display()
+            ),
+        )
+        .unwrap();
+
+        execute(shell_args(config_path, None)).unwrap();
+        assert!(root.join("docs/runbooks/first").exists());
+        assert!(root.join("docs/runbooks/second").exists());
+        // The per-item working_dir wins over the base
+        assert!(root.join("docs/third").exists());
+        assert!(!root.join("docs/runbooks/third").exists());
+    }
+
+    #[test]
+    fn verify_spans_docs_roots_once_per_doc() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_path = root.join(".pave.toml");
+        fs::write(
+            &config_path,
+            "[pave]\nversion = \"0.1\"\n\n[docs]\nroot = [\"docs\", \"crates/api/docs\", \"./docs/components\"]\n",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("docs/components")).unwrap();
+        fs::create_dir_all(root.join("crates/api/docs")).unwrap();
+        fs::write(
+            root.join("docs/components/a.md"),
+            "# A\n\n## Verification\n\n```bash\necho a >> ran.log\n```\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("crates/api/docs/b.md"),
+            "# B\n\n## Verification\n\n```bash\necho b >> ran.log\n```\n",
+        )
+        .unwrap();
+        let report = root.join("report.json");
+
+        execute(VerifyArgs {
+            report: Some(report.clone()),
+            ..shell_args(config_path, None)
+        })
+        .unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(report).unwrap()).unwrap();
+        assert_eq!(json["documents_verified"], 2);
+        assert_eq!(json["commands_passed"], 2);
+        assert_eq!(fs::read_to_string(root.join("ran.log")).unwrap(), "b\na\n");
+    }
+
+    #[test]
+    fn verify_reports_skip_annotated_commands() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_path = create_test_config(&temp_dir);
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(
+            root.join("docs/a.md"),
+            "# A\n\n## Verification\n\n```bash skip\ntouch skipped-block\n```\n\n```bash\ntouch ran\ntouch skipped-line # pave: skip\n```\n",
+        )
+        .unwrap();
+        let report = root.join("report.json");
+
+        execute(VerifyArgs {
+            report: Some(report.clone()),
+            ..shell_args(config_path, None)
+        })
+        .unwrap();
+
+        assert!(root.join("ran").exists());
+        assert!(!root.join("skipped-block").exists());
+        assert!(!root.join("skipped-line").exists());
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(report).unwrap()).unwrap();
+        assert_eq!(json["commands_passed"], 1);
+        assert_eq!(json["commands_skipped"], 2);
+        assert_eq!(json["documents"][0]["skipped"], 2);
+    }
+
+    #[test]
+    fn verify_report_durations_are_monotonic_milliseconds() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_path = create_test_config(&temp_dir);
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(
+            root.join("docs/a.md"),
+            "# A\n\n## Verification\n\n```bash\nsleep 0.1\n```\n",
+        )
+        .unwrap();
+        let report = root.join("verify.json");
+        execute(VerifyArgs {
+            report: Some(report.clone()),
+            ..shell_args(config_path, None)
+        })
+        .unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&report).unwrap()).unwrap();
+        let command_ms = json["documents"][0]["commands"][0]["duration_ms"]
+            .as_u64()
+            .expect("command duration is a non-negative integer");
+        assert!((100..60_000).contains(&command_ms), "{command_ms}");
+        let run_ms = json["duration_ms"]
+            .as_u64()
+            .expect("run duration is a non-negative integer");
+        assert!(run_ms >= command_ms, "{run_ms} < {command_ms}");
+    }
+
+    #[test]
+    fn verify_out_dir_saves_each_commands_output_and_an_index() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_path = create_test_config(&temp_dir);
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(
+            root.join("docs/a.md"),
+            "# A\n\n## Verification\n\n```bash\necho to-out; echo to-err >&2\n```\n\n```bash\nfalse\n```\n",
+        )
+        .unwrap();
+        let out_dir = root.join("artifacts");
+        assert!(
+            execute(VerifyArgs {
+                out_dir: Some(out_dir.clone()),
+                keep_going: true,
+                ..shell_args(config_path, None)
+            })
+            .is_err()
+        );
+
+        // Passing output is kept in full even though the console drops it
+        assert_eq!(
+            fs::read_to_string(out_dir.join("001-echo-to-out-echo-to-err-2.stdout")).unwrap(),
+            "to-out\n"
+        );
+        assert_eq!(
+            fs::read_to_string(out_dir.join("001-echo-to-out-echo-to-err-2.stderr")).unwrap(),
+            "to-err\n"
+        );
+        assert!(out_dir.join("002-false.stdout").exists());
+
+        let index: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(out_dir.join("index.json")).unwrap()).unwrap();
+        assert_eq!(index[0]["command"], "echo to-out; echo to-err >&2");
+        assert_eq!(index[0]["status"], "pass");
+        assert_eq!(index[0]["stderr"], "001-echo-to-out-echo-to-err-2.stderr");
+        assert_eq!(index[1]["status"], "fail");
+        assert_eq!(index[1]["exit_code"], 1);
+    }
+
+    #[test]
+    fn verify_report_file_records_counts_and_timestamp() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_path = create_test_config(&temp_dir);
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(
+            root.join("docs/a.md"),
+            "# A\n\n## Verification\n\n```bash\ntrue\n```\n\n```bash\nfalse\n```\n",
+        )
+        .unwrap();
+        let report = root.join("reports/verify.json");
+        fs::create_dir_all(root.join("reports")).unwrap();
+        let args = |append| VerifyArgs {
+            report: Some(report.clone()),
+            append,
+            keep_going: true,
+            bail_after: None,
+            ..shell_args(config_path.clone(), None)
+        };
+
+        assert!(execute(args(false)).is_err());
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&report).unwrap()).unwrap();
+        assert_eq!(json["commands_passed"], 1);
+        assert_eq!(json["commands_failed"], 1);
+        let timestamp = json["timestamp"].as_str().unwrap();
+        assert!(chrono::DateTime::parse_from_rfc3339(timestamp).is_ok());
+        assert!(json.get("git_sha").is_some());
+
+        // --append adds one JSON object per line
+        fs::remove_file(&report).unwrap();
+        assert!(execute(args(true)).is_err());
+        assert!(execute(args(true)).is_err());
+        let content = fs::read_to_string(&report).unwrap();
+        let appended: Vec<serde_json::Value> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(appended.len(), 2);
+        assert!(appended.iter().all(|run| run["commands_failed"] == 1));
+    }
+
+    #[test]
+    fn allow_failure_docs_report_failures_without_failing_the_run() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_path = create_test_config(&temp_dir);
+        let mut config = fs::read_to_string(&config_path).unwrap();
+        config.push_str("\n[verification]\nallow_failure = [\"docs/flaky/**\"]\n");
+        fs::write(&config_path, config).unwrap();
+        fs::create_dir_all(root.join("docs/flaky")).unwrap();
+        fs::write(
+            root.join("docs/flaky/integration.md"),
+            "# Flaky\n\n## Verification\n\n```bash\nfalse\n```\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("docs/stable.md"),
+            "# Stable\n\n## Verification\n\n```bash\ntrue\n```\n",
+        )
+        .unwrap();
+        let report = root.join("verify.json");
+        let args = || VerifyArgs {
+            report: Some(report.clone()),
+            ..shell_args(config_path.clone(), None)
+        };
+
+        execute(args()).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&report).unwrap()).unwrap();
+        assert_eq!(json["commands_passed"], 1);
+        assert_eq!(json["commands_failed"], 0);
+        assert_eq!(json["commands_allowed_failures"], 1);
+        let flaky = json["documents"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|d| d["file"].as_str().unwrap().ends_with("integration.md"))
+            .unwrap();
+        assert_eq!(flaky["allow_failure"], true);
+        assert_eq!(flaky["commands"][0]["status"], "fail");
+        assert_eq!(flaky["commands"][0]["command"], "false");
+
+        // The same failure outside the allow-list fails the run
+        fs::write(
+            root.join("docs/stable.md"),
+            "# Stable\n\n## Verification\n\n```bash\nfalse\n```\n",
+        )
+        .unwrap();
+        let err = execute(args()).unwrap_err().to_string();
+        assert!(err.contains("1 of 2 commands failed"), "{}", err);
+    }
+
+    #[test]
+    fn tag_filters_select_items_by_annotation() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_path = create_test_config(&temp_dir);
+        fs::create_dir_all(root.join("docs")).unwrap();
+        let touch = |name: &str| format!("touch {}", root.join(name).display());
+        fs::write(
+            root.join("docs/a.md"),
+            format!(
+                "# A\n\n## Verification\n\n```bash\n# tags: smoke\n{}\n```\n\n```bash\n# tags: slow, integration\n{}\n```\n\n```bash\n{}\n```\n",
+                touch("ran-smoke"),
+                touch("ran-slow"),
+                touch("ran-untagged")
+            ),
+        )
+        .unwrap();
+        let ran = |name: &str| root.join(name).exists();
+        let reset = || {
+            for name in ["ran-smoke", "ran-slow", "ran-untagged"] {
+                let _ = fs::remove_file(root.join(name));
+            }
+        };
+
+        let doc = ParsedDoc::parse(&root.join("docs/a.md")).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+        let tags: Vec<&[String]> = spec.items.iter().map(|i| i.tags.as_slice()).collect();
+        assert_eq!(
+            tags,
+            vec![
+                &["smoke".to_string()][..],
+                &["slow".to_string(), "integration".to_string()][..],
+                &[][..],
+            ]
+        );
+
+        execute(VerifyArgs {
+            tags: vec!["smoke".to_string()],
+            ..shell_args(config_path.clone(), None)
+        })
+        .unwrap();
+        assert!(ran("ran-smoke") && !ran("ran-slow") && !ran("ran-untagged"));
+
+        reset();
+        execute(VerifyArgs {
+            exclude_tags: vec!["integration".to_string()],
+            ..shell_args(config_path.clone(), None)
+        })
+        .unwrap();
+        assert!(ran("ran-smoke") && !ran("ran-slow") && ran("ran-untagged"));
+
+        reset();
+        execute(VerifyArgs {
+            tags: vec!["smoke".to_string(), "slow".to_string()],
+            exclude_tags: vec!["smoke".to_string()],
+            ..shell_args(config_path, None)
+        })
+        .unwrap();
+        assert!(!ran("ran-smoke") && ran("ran-slow") && !ran("ran-untagged"));
+    }
+
+    #[test]
+    fn verify_rejects_missing_shell_before_running() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_path = create_test_config(&temp_dir);
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(
+            root.join("docs/a.md"),
+            "# A\n\n## Verification\n\n```bash\ntouch ran\n```\n",
+        )
+        .unwrap();
+
+        let err = execute(shell_args(config_path, Some("/no/such/shell"))).unwrap_err();
+        assert!(
+            err.to_string().contains("Shell `/no/such/shell` not found"),
+            "{err}"
+        );
+        assert!(!root.join("ran").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn missing_shell_skips_shell_commands_under_skip_policy() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_path = create_test_config(&temp_dir);
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(
+            root.join("docs/a.md"),
+            "# A\n\n## Verification\n\n```bash\ntouch ran\n```\n\n```sh\n#!/bin/sh\ntouch ran-script\n```\n",
+        )
+        .unwrap();
+        let summary = root.join("summary.json");
+
+        execute(VerifyArgs {
+            on_missing_shell: MissingShell::Skip,
+            before: vec!["touch ran-before".to_string()],
+            summary_json: Some(summary.clone()),
+            ..shell_args(config_path, Some("/no/such/shell"))
+        })
+        .unwrap();
+
+        assert!(!root.join("ran").exists());
+        assert!(!root.join("ran-before").exists());
+        assert!(root.join("ran-script").exists());
+        let summary: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(summary).unwrap()).unwrap();
+        assert_eq!(
+            (summary["passed"].as_u64(), summary["skipped"].as_u64()),
+            (Some(1), Some(1))
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn isolated_items_do_not_see_files_from_earlier_items() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_path = create_test_config(&temp_dir);
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(
+            root.join("docs/a.md"),
+            "# A\n\n## Setup\n\n```bash\ntouch from-setup\n```\n\n## Verification\n\n```bash\ntouch leaked\n```\n\n```bash\ntest ! -e leaked && test ! -e from-setup\n```\n",
+        )
+        .unwrap();
+        let args = |isolated| VerifyArgs {
+            isolated,
+            ..shell_args(config_path.clone(), None)
+        };
+
+        execute(args(true)).unwrap();
+        assert!(!root.join("leaked").exists());
+        // Setup still runs in the project root
+        assert!(root.join("from-setup").exists());
+
+        // Without --isolated the second item sees the first one's file
+        assert!(execute(args(false)).is_err());
+        assert!(root.join("leaked").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn list_docs_and_list_failing_docs_against_mixed_outcomes() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_path = create_test_config(&temp_dir);
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(
+            root.join("docs/a-pass.md"),
+            "# A\n\n## Verification\n\n```bash\ntrue\n```\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("docs/b-fail.md"),
+            "# B\n\n## Verification\n\n```bash\ntrue\n```\n\n```bash\nfalse\n```\n",
+        )
+        .unwrap();
+        fs::write(root.join("docs/c-none.md"), "# C\n\n## Purpose\nProse.\n").unwrap();
+        let specs = || -> Vec<(VerificationSpec, Option<String>)> {
+            find_markdown_files(&[root.join("docs")])
+                .unwrap()
+                .iter()
+                .filter_map(|file| extract_verification_spec(&ParsedDoc::parse(file).unwrap()))
+                .map(|spec| (spec, None))
+                .collect()
+        };
+        let listing = |specs: &[(VerificationSpec, Option<String>)]| -> Vec<String> {
+            list_docs(specs.iter().map(|(spec, _)| spec))
+        };
+        let doc = |name: &str| root.join("docs").join(name).display().to_string();
+
+        assert_eq!(listing(&specs()), vec![doc("a-pass.md"), doc("b-fail.md")]);
+        assert!(
+            execute(VerifyArgs {
+                list_docs: true,
+                ..shell_args(config_path.clone(), None)
+            })
+            .is_ok()
+        );
+        // Nothing recorded yet to list failures from
+        assert!(
+            execute(VerifyArgs {
+                list_failing_docs: true,
+                ..shell_args(config_path.clone(), None)
+            })
+            .is_err()
+        );
+
+        let run = VerifyArgs {
+            keep_going: true,
+            ..shell_args(config_path.clone(), None)
+        };
+        assert!(execute(run).is_err());
+
+        let mut failing = specs();
+        // The options execute ran the items with, which are part of the keys
+        let options = RunOptions {
+            default_timeout: Some(Duration::from_secs(
+                PaveConfig::default().verification.default_timeout_secs as u64,
+            )),
+            ..Default::default()
+        };
+        let options = item_options(&options, root, &default_rules());
+        retain_failed_items(&mut failing, &VerifyCache::load(root), &options, root);
+        assert_eq!(listing(&failing), vec![doc("b-fail.md")]);
+        assert!(
+            execute(VerifyArgs {
+                list_failing_docs: true,
+                ..shell_args(config_path, None)
+            })
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn verify_only_failed_reruns_just_the_failing_item() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_path = create_test_config(&temp_dir);
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(
+            root.join("docs/mixed.md"),
+            "# Mixed\n\n## Verification\n\n```bash\ntouch ran-1\n```\n\n```bash\ntouch ran-2 && test -f fixed\n```\n\n```bash\ntouch ran-3\n```\n",
+        )
+        .unwrap();
+        let only_failed = || VerifyArgs {
+            only_failed: true,
+            keep_going: true,
+            bail_after: None,
+            ..shell_args(config_path.clone(), None)
+        };
+        let clear_markers = || {
+            for n in 1..=3 {
+                let _ = fs::remove_file(root.join(format!("ran-{n}")));
+            }
+        };
+
+        let err = execute(only_failed()).unwrap_err();
+        assert!(err.to_string().contains("run `pave verify` once"), "{err}");
+
+        let first_run = VerifyArgs {
+            keep_going: true,
+            bail_after: None,
+            ..shell_args(config_path.clone(), None)
+        };
+        assert!(execute(first_run).is_err());
+        clear_markers();
+
+        // Still failing: only the failing item runs, and it stays recorded
+        assert!(execute(only_failed()).is_err());
+        assert!(!root.join("ran-1").exists());
+        assert!(root.join("ran-2").exists());
+        assert!(!root.join("ran-3").exists());
+        clear_markers();
+
+        // Once it passes, nothing is left to re-run
+        fs::write(root.join("fixed"), "").unwrap();
+        execute(only_failed()).unwrap();
+        assert!(root.join("ran-2").exists());
+        clear_markers();
+        execute(only_failed()).unwrap();
+        assert!(!root.join("ran-2").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn default_scan_skips_templates_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let config_path = root.join(".pave.toml");
+        fs::write(
+            &config_path,
+            "[pave]\nversion = \"0.1\"\n\n[docs]\nroot = \"docs\"\ntemplates = \"docs/templates\"\n",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("docs/templates")).unwrap();
+        fs::write(
+            root.join("docs/a.md"),
+            "# A\n\n## Verification\n\n```bash\ntrue\n```\n",
+        )
+        .unwrap();
+        let template = root.join("docs/templates/adr.md");
+        fs::write(
+            &template,
+            "# {Title}\n\n## Verification\n\n```bash\nfalse\n```\n",
+        )
+        .unwrap();
+
+        execute(shell_args(config_path.clone(), None)).unwrap();
+
+        // Naming the template explicitly still verifies it
+        assert!(
+            execute(VerifyArgs {
+                paths: vec![template],
+                ..shell_args(config_path, None)
+            })
+            .is_err()
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn print_command_reproduces_failing_item_with_cwd_and_shell() {
+        let temp_dir = TempDir::new().unwrap();
+        let options = RunOptions {
+            shell: Shell::new("sh"),
+            ..Default::default()
+        };
+        let item = |command: &str| VerificationItem {
+            command: command.to_string(),
+            raw: command.to_string(),
+            env_vars: vec![
+                ("API_TOKEN".to_string(), "hunter2".to_string()),
+                ("GREETING".to_string(), "hi there".to_string()),
+            ],
+            ..Default::default()
+        };
+        let spec = VerificationSpec {
+            source_file: PathBuf::from("test.md"),
+            section_line: 1,
+            skipped: 0,
+            serial: false,
+            setup: vec![],
+            teardown: vec![],
+            items: vec![],
+        };
+        let results = |when: PrintCommand, show_env: bool| {
+            let mut doc_result = DocumentResult::new(&spec);
+            for command in ["exit 3", "true"] {
+                doc_result.add_result(run_command(
+                    &item(command),
+                    &options,
+                    temp_dir.path(),
+                    &default_rules(),
+                ));
+            }
+            let mut results = VerifyResults::new();
+            results.add_document(doc_result);
+            results.add_reproduce_lines(when, show_env);
+            results.documents.remove(0).commands
+        };
+
+        let commands = results(PrintCommand::Failed, false);
+        let cwd = std::path::absolute(temp_dir.path()).unwrap();
+        assert_eq!(
+            commands[0].reproduce.as_deref(),
+            Some(
+                format!(
+                    "cd {} && API_TOKEN='***' GREETING='hi there' sh -c 'exit 3'",
+                    cwd.display()
+                )
+                .as_str()
+            )
+        );
+        assert_eq!(commands[1].reproduce, None);
+
+        let commands = results(PrintCommand::All, true);
+        assert!(
+            commands[0]
+                .reproduce
+                .as_deref()
+                .is_some_and(|line| line.contains("API_TOKEN=hunter2 "))
+        );
+        assert!(
+            commands[1]
+                .reproduce
+                .as_deref()
+                .is_some_and(|line| line.ends_with("sh -c true"))
+        );
+    }
+
+    #[test]
+    fn launch_renders_shebang_scripts_and_isolated_dirs() {
+        let launch = Launch {
+            dir: None,
+            env: vec![],
+            program: vec!["/usr/bin/env".to_string(), "python3".to_string()],
+            script: true,
+        };
+        assert_eq!(
+            launch.render("print('ok')", false),
+            "cd \"$(mktemp -d)\" && printf '%s\\n' 'print('\\''ok'\\'')' | /usr/bin/env python3 /dev/stdin"
+        );
+        assert!(is_sensitive_env("aws_secret_access_key"));
+        assert!(!is_sensitive_env("RUST_LOG"));
+    }
+
+    #[test]
+    fn debug_suggestion_sets_env_after_cd() {
+        let item = VerificationItem {
+            command: "make test".to_string(),
+            working_dir: Some(PathBuf::from("/srv/app")),
+            env_vars: vec![
+                ("RUST_LOG".to_string(), "debug".to_string()),
+                ("PORT".to_string(), "8080".to_string()),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            debug_suggestion(&not_run_result(&item)),
+            "cd /srv/app && RUST_LOG=debug PORT=8080 make test"
+        );
     }
 }
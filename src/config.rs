@@ -3,13 +3,20 @@
 //! This module defines the `.pave.toml` configuration schema and provides
 //! functions for loading, validating, and saving configuration files.
 
-use anyhow::{Context, Result};
+use crate::error::{Error, Result};
+use crate::parser::ParseOptions;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
 /// The default configuration filename.
 pub const CONFIG_FILENAME: &str = ".pave.toml";
 
+/// Prefix for environment variables that override config values.
+///
+/// `PAVE_<SECTION>_<KEY>` maps to `<section>.<key>`, e.g. `PAVE_DOCS_ROOT`
+/// overrides `docs.root` and `PAVE_RULES_MAX_LINES` overrides `rules.max_lines`.
+pub const ENV_PREFIX: &str = "PAVE_";
+
 /// Root configuration structure for a pave project.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub struct PaveConfig {
@@ -32,6 +39,15 @@ pub struct PaveConfig {
     /// Lint configuration.
     #[serde(default)]
     pub lint: LintSection,
+    /// Verification configuration.
+    #[serde(default)]
+    pub verification: VerificationSection,
+    /// `pave check` configuration.
+    #[serde(default)]
+    pub check: CheckSection,
+    /// `pave prompt` configuration.
+    #[serde(default)]
+    pub prompt: PromptSection,
 }
 
 /// Pave tool metadata section.
@@ -42,15 +58,160 @@ pub struct PaveSection {
 }
 
 /// Documentation paths section.
+///
+/// `root` is either a single directory or a list of them. The first listed
+/// directory is the primary root, where new docs, the index, and templates
+/// live; commands that scan docs read every root.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(from = "RawDocsSection", into = "RawDocsSection")]
 pub struct DocsSection {
-    /// Root directory for documentation.
+    /// Primary root directory for documentation.
     pub root: PathBuf,
+    /// Further documentation roots listed after the primary one.
+    pub extra_roots: Vec<PathBuf>,
     /// Directory where templates are stored (optional).
-    #[serde(default)]
     pub templates: Option<PathBuf>,
 }
 
+/// `[docs]` as written in `.pave.toml`.
+#[derive(Serialize, Deserialize)]
+struct RawDocsSection {
+    root: DocsRoot,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    templates: Option<PathBuf>,
+}
+
+/// `docs.root`: one directory or a list of directories.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum DocsRoot {
+    One(PathBuf),
+    Many(Vec<PathBuf>),
+}
+
+impl From<RawDocsSection> for DocsSection {
+    fn from(raw: RawDocsSection) -> Self {
+        let mut roots = match raw.root {
+            DocsRoot::One(root) => vec![root],
+            DocsRoot::Many(roots) => roots,
+        };
+        // An empty list leaves an empty primary root for `validate` to reject.
+        let root = if roots.is_empty() {
+            PathBuf::new()
+        } else {
+            roots.remove(0)
+        };
+        Self {
+            root,
+            extra_roots: roots,
+            templates: raw.templates,
+        }
+    }
+}
+
+impl From<DocsSection> for RawDocsSection {
+    fn from(docs: DocsSection) -> Self {
+        let root = if docs.extra_roots.is_empty() {
+            DocsRoot::One(docs.root)
+        } else {
+            DocsRoot::Many(std::iter::once(docs.root).chain(docs.extra_roots).collect())
+        };
+        Self {
+            root,
+            templates: docs.templates,
+        }
+    }
+}
+
+impl DocsSection {
+    /// Every configured documentation root, primary first, as written.
+    pub fn roots(&self) -> impl Iterator<Item = &PathBuf> {
+        std::iter::once(&self.root).chain(&self.extra_roots)
+    }
+
+    /// Every documentation root resolved against `config_dir`, primary first.
+    ///
+    /// A leading `~` expands to `$HOME`; other relative roots are relative to
+    /// the config file's directory. Roots listed twice (even spelled
+    /// differently, like `docs` and `./docs`) appear once. Nothing is checked
+    /// on disk; see [`DocsSection::resolve_root_dirs`].
+    pub fn root_dirs(&self, config_dir: &Path) -> Vec<PathBuf> {
+        let home = std::env::var_os("HOME").map(PathBuf::from);
+        let mut dirs: Vec<PathBuf> = Vec::new();
+        for root in self.roots() {
+            let dir = resolve_root(root, config_dir, home.as_deref());
+            if !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+        }
+        dirs
+    }
+
+    /// The templates directory resolved against `config_dir` the same way as
+    /// the roots, if one is configured.
+    pub fn templates_dir(&self, config_dir: &Path) -> Option<PathBuf> {
+        let home = std::env::var_os("HOME").map(PathBuf::from);
+        self.templates
+            .as_deref()
+            .map(|templates| resolve_root(templates, config_dir, home.as_deref()))
+    }
+
+    /// Like [`DocsSection::root_dirs`], but fails unless every root is an
+    /// existing directory, so a mistyped `docs.root` is reported instead of
+    /// silently finding no docs.
+    pub fn resolve_root_dirs(&self, config_dir: &Path) -> Result<Vec<PathBuf>> {
+        for root in self.roots() {
+            Self::resolve_root_dir(root, config_dir)?;
+        }
+        Ok(self.root_dirs(config_dir))
+    }
+
+    /// Resolve a single configured `root` against `config_dir`, failing
+    /// unless it is an existing directory.
+    pub fn resolve_root_dir(root: &Path, config_dir: &Path) -> Result<PathBuf> {
+        let home = std::env::var_os("HOME").map(PathBuf::from);
+        let dir = resolve_root(root, config_dir, home.as_deref());
+        check_root_dir(root, &dir)?;
+        Ok(dir)
+    }
+}
+
+/// Resolve one configured docs root: a leading `~` expands to `home`, and
+/// other relative paths are joined onto `config_dir`.
+fn resolve_root(root: &Path, config_dir: &Path, home: Option<&Path>) -> PathBuf {
+    let joined = match (root.strip_prefix("~"), home) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => config_dir.join(root),
+    };
+    let dir: PathBuf = joined
+        .components()
+        .filter(|c| !matches!(c, std::path::Component::CurDir))
+        .collect();
+    if dir.as_os_str().is_empty() {
+        PathBuf::from(".")
+    } else {
+        dir
+    }
+}
+
+/// Fail if the resolved docs root `dir` (configured as `root`) is missing or
+/// not a directory.
+fn check_root_dir(root: &Path, dir: &Path) -> Result<()> {
+    let problem = if !dir.exists() {
+        "does not exist"
+    } else if !dir.is_dir() {
+        "is not a directory"
+    } else {
+        return Ok(());
+    };
+    Err(Error::config(format!(
+        "docs.root `{}` {} (resolved to {}); create it or update docs.root",
+        root.display(),
+        problem,
+        dir.display()
+    )))
+}
+
 /// Validation rules section.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct RulesSection {
@@ -95,6 +256,11 @@ pub struct RulesSection {
     /// After this date, gradual mode is ignored.
     #[serde(default)]
     pub gradual_until: Option<String>,
+    /// Warn when a section heading only matches a canonical section name
+    /// case-insensitively or with trailing punctuation (`## verification:`).
+    /// Always on under `pave check --strict`.
+    #[serde(default)]
+    pub section_casing: bool,
 }
 
 /// Document-type-specific validation rules.
@@ -131,6 +297,10 @@ pub struct MappingSection {
     /// Global path patterns to exclude from mapping.
     #[serde(default)]
     pub exclude: Vec<String>,
+    /// Minimum coverage percentage for `pave coverage` when `--threshold`
+    /// isn't given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coverage_threshold: Option<u32>,
 }
 
 /// Git hooks configuration section.
@@ -141,6 +311,121 @@ pub struct HooksSection {
     pub run_verify: bool,
 }
 
+/// Verification configuration section.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VerificationSection {
+    /// Run identical commands in a document only once (default: false).
+    #[serde(default)]
+    pub dedupe: bool,
+    /// Timeout for commands without their own, in seconds; 0 disables it
+    /// (default: 30).
+    #[serde(default = "default_timeout_secs")]
+    pub default_timeout_secs: u32,
+    /// Interpreter commands run under, e.g. `bash` or `pwsh` (default: `sh`
+    /// on Unix, `cmd` on Windows).
+    #[serde(default)]
+    pub shell: Option<String>,
+    /// Fence languages whose blocks are run (default: `bash`, `sh`, `shell`).
+    #[serde(default = "default_languages")]
+    pub languages: Vec<String>,
+    /// Default working directory for commands, relative to each doc's own
+    /// directory (default: unset, so commands run from the project root).
+    #[serde(default)]
+    pub base_dir: Option<PathBuf>,
+    /// Docs, as `## Paths`-style globs relative to the project root, whose
+    /// failing commands are reported but don't fail `pave verify`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow_failure: Vec<String>,
+    /// Run only fences tagged `pave` (```` ```pave ```` or
+    /// ```` ```bash pave ````), ignoring `languages` (default: false).
+    #[serde(default)]
+    pub require_pave_tag: bool,
+}
+
+fn default_languages() -> Vec<String> {
+    ParseOptions::default().executable_languages
+}
+
+impl VerificationSection {
+    /// Parser options that treat the configured languages (or, with
+    /// `require_pave_tag`, only `pave`-tagged fences) as executable.
+    pub fn parse_options(&self) -> ParseOptions {
+        ParseOptions {
+            executable_languages: self.languages.clone(),
+            require_pave_tag: self.require_pave_tag,
+        }
+    }
+}
+
+fn default_timeout_secs() -> u32 {
+    crate::verification::DEFAULT_TIMEOUT_SECS
+}
+
+impl Default for VerificationSection {
+    fn default() -> Self {
+        Self {
+            dedupe: false,
+            default_timeout_secs: default_timeout_secs(),
+            shell: None,
+            languages: default_languages(),
+            base_dir: None,
+            allow_failure: Vec::new(),
+            require_pave_tag: false,
+        }
+    }
+}
+
+/// `pave check` configuration section.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CheckSection {
+    /// Sections every component doc must have (default: `["Purpose"]`).
+    /// Runbooks and ADRs are checked by `rules.type_specific` instead.
+    #[serde(default = "default_required_sections")]
+    pub required_sections: Vec<String>,
+    /// Section names whose exact spelling `rules.section_casing` enforces, in
+    /// addition to the sections of the built-in templates.
+    #[serde(default)]
+    pub canonical_sections: Vec<String>,
+    /// Rule ids (such as `max-lines`) whose issues `pave check` never
+    /// reports, unless named with `--rule`.
+    #[serde(default)]
+    pub disabled_rules: Vec<String>,
+    /// Opt-in rule ids (`trailing-whitespace`, `mixed-indentation`) that
+    /// `pave check` reports; they're off unless listed here or named with
+    /// `--rule`.
+    #[serde(default)]
+    pub enabled_rules: Vec<String>,
+    /// Longest a `##` section may run, heading included, before
+    /// `pave check` warns (default: no limit).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_section_lines: Option<usize>,
+}
+
+fn default_required_sections() -> Vec<String> {
+    vec!["Purpose".to_string()]
+}
+
+impl Default for CheckSection {
+    fn default() -> Self {
+        Self {
+            required_sections: default_required_sections(),
+            canonical_sections: Vec::new(),
+            disabled_rules: Vec::new(),
+            enabled_rules: Vec::new(),
+            max_section_lines: None,
+        }
+    }
+}
+
+/// `pave prompt` configuration section.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct PromptSection {
+    /// File whose text `pave prompt --system` uses as the system preamble
+    /// instead of the built-in one, relative to the project root.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_file: Option<PathBuf>,
+}
+
 /// Lint configuration section.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct LintSection {
@@ -193,6 +478,7 @@ impl Default for DocsSection {
     fn default() -> Self {
         Self {
             root: PathBuf::from("docs"),
+            extra_roots: Vec::new(),
             templates: None,
         }
     }
@@ -212,22 +498,32 @@ impl Default for RulesSection {
             warn_empty_paths: false,
             gradual: false,
             gradual_until: None,
+            section_casing: false,
         }
     }
 }
 
 impl PaveConfig {
     /// Load configuration from a file path.
+    ///
+    /// A file naming a base config with `extends` (or `include`) is merged
+    /// over that base, as described in [`merge_values`]. Environment
+    /// variable overrides (`PAVE_<SECTION>_<KEY>`) are layered over the
+    /// values from the files.
     pub fn load(path: impl AsRef<Path>) -> Result<Self> {
-        let path = path.as_ref();
-        let content = std::fs::read_to_string(path)
-            .with_context(|| format!("failed to read config file: {}", path.display()))?;
-        Self::parse(&content)
+        let mut value = load_value(path.as_ref(), &mut Vec::new())?;
+        apply_env_overrides(&mut value, std::env::vars());
+        let config: PaveConfig = value
+            .try_into()
+            .map_err(|e| Error::config_with_source("failed to parse config file", e))?;
+        config.validate()?;
+        Ok(config)
     }
 
     /// Parse configuration from a TOML string.
     pub fn parse(content: &str) -> Result<Self> {
-        let config: PaveConfig = toml::from_str(content).context("failed to parse config file")?;
+        let config: PaveConfig = toml::from_str(content)
+            .map_err(|e| Error::config_with_source("failed to parse config file", e))?;
         config.validate()?;
         Ok(config)
     }
@@ -235,30 +531,601 @@ impl PaveConfig {
     /// Save configuration to a file path.
     pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
         let path = path.as_ref();
-        let content = toml::to_string_pretty(self).context("failed to serialize config")?;
-        std::fs::write(path, content)
-            .with_context(|| format!("failed to write config file: {}", path.display()))?;
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| Error::config_with_source("failed to serialize config", e))?;
+        std::fs::write(path, content).map_err(|e| {
+            Error::io(
+                format!("failed to write config file: {}", path.display()),
+                e,
+            )
+        })?;
         Ok(())
     }
 
     /// Validate the configuration values.
     pub fn validate(&self) -> Result<()> {
         if self.pave.version.is_empty() {
-            anyhow::bail!("pave.version cannot be empty");
+            return Err(Error::config("pave.version cannot be empty"));
         }
 
-        if self.docs.root.as_os_str().is_empty() {
-            anyhow::bail!("docs.root cannot be empty");
+        if self.docs.roots().any(|root| root.as_os_str().is_empty()) {
+            return Err(Error::config("docs.root cannot be empty"));
         }
 
         if self.rules.max_lines == 0 {
-            anyhow::bail!("rules.max_lines must be greater than 0");
+            return Err(Error::config("rules.max_lines must be greater than 0"));
         }
 
         Ok(())
     }
 }
 
+/// Top-level keys naming a base config that a file is layered over.
+const EXTENDS_KEYS: [&str; 2] = ["extends", "include"];
+
+/// Array entry standing for the base config's entries in a file that
+/// extends another, e.g. `exclude = ["...", "vendor/"]`.
+pub const INHERIT_MARKER: &str = "...";
+
+/// Read a config file as TOML, merged over the chain of configs it extends.
+///
+/// `chain` holds the files currently being loaded, to detect cycles; pass an
+/// empty one. Environment overrides are not applied.
+pub(crate) fn load_value(path: &Path, chain: &mut Vec<PathBuf>) -> Result<toml::Value> {
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    if let Some(start) = chain.iter().position(|seen| *seen == canonical) {
+        let cycle: Vec<String> = chain[start..]
+            .iter()
+            .chain([&canonical])
+            .map(|p| p.display().to_string())
+            .collect();
+        return Err(Error::config(format!(
+            "config extends itself: {}",
+            cycle.join(" -> ")
+        )));
+    }
+
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| Error::io(format!("failed to read config file: {}", path.display()), e))?;
+    let mut value: toml::Value = toml::from_str(&content).map_err(|e| {
+        Error::config_with_source(
+            format!("failed to parse config file: {}", path.display()),
+            e,
+        )
+    })?;
+    let Some(base) = take_extends(&mut value)? else {
+        return Ok(value);
+    };
+
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    let config_dir = path.parent().unwrap_or_else(|| Path::new("."));
+    let base_path = resolve_root(Path::new(&base), config_dir, home.as_deref());
+    chain.push(canonical);
+    let mut merged = load_value(&base_path, chain)?;
+    chain.pop();
+    merge_values(&mut merged, value);
+    Ok(merged)
+}
+
+/// Remove and return the base config path named by `extends` or `include`.
+fn take_extends(value: &mut toml::Value) -> Result<Option<String>> {
+    let Some(table) = value.as_table_mut() else {
+        return Ok(None);
+    };
+    let mut found = EXTENDS_KEYS
+        .iter()
+        .filter_map(|key| table.remove(*key).map(|v| (*key, v)));
+    let Some((key, base)) = found.next() else {
+        return Ok(None);
+    };
+    if found.next().is_some() {
+        return Err(Error::config("set either `extends` or `include`, not both"));
+    }
+    match base {
+        toml::Value::String(path) => Ok(Some(path)),
+        _ => Err(Error::config(format!(
+            "`{}` must be the path of a config file",
+            key
+        ))),
+    }
+}
+
+/// Merge `overlay` over `base`.
+///
+/// Tables merge key by key and any other value in `overlay` replaces the
+/// one in `base`. Arrays are replaced too, except that an
+/// [`INHERIT_MARKER`] entry is replaced by the base array's entries, so
+/// `["...", "x"]` appends `x` to the base list.
+pub fn merge_values(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base), toml::Value::Table(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_values(existing, value),
+                    None => {
+                        let mut value = value;
+                        strip_inherit_markers(&mut value);
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (toml::Value::Array(base), toml::Value::Array(overlay)) => {
+            let inherited = std::mem::take(base);
+            for entry in overlay {
+                if entry.as_str() == Some(INHERIT_MARKER) {
+                    base.extend(inherited.iter().cloned());
+                } else {
+                    base.push(entry);
+                }
+            }
+        }
+        (base, mut overlay) => {
+            strip_inherit_markers(&mut overlay);
+            *base = overlay;
+        }
+    }
+}
+
+/// Drop [`INHERIT_MARKER`] entries from arrays with no base to inherit.
+fn strip_inherit_markers(value: &mut toml::Value) {
+    match value {
+        toml::Value::Array(entries) => {
+            entries.retain(|entry| entry.as_str() != Some(INHERIT_MARKER));
+        }
+        toml::Value::Table(table) => table.iter_mut().for_each(|(_, v)| strip_inherit_markers(v)),
+        _ => {}
+    }
+}
+
+/// A problem found in a config file by [`check_config`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigProblem {
+    /// 1-indexed line in the config file, when known.
+    pub line: Option<usize>,
+    /// Dotted key the problem is about, if it concerns a single key.
+    pub key: Option<String>,
+    /// Description of the problem.
+    pub message: String,
+}
+
+/// Every key `.pave.toml` accepts, as dotted paths to leaf values.
+///
+/// [`check_config`] reports keys missing from this table as unknown. Keep it
+/// in step with the section structs; a test checks it covers every field the
+/// defaults serialize.
+const KNOWN_KEYS: &[&str] = &[
+    "pave.version",
+    "docs.root",
+    "docs.templates",
+    "rules.max_lines",
+    "rules.require_verification",
+    "rules.require_examples",
+    "rules.require_verification_commands",
+    "rules.strict_output_matching",
+    "rules.skip_output_matching",
+    "rules.type_specific.runbooks",
+    "rules.type_specific.adrs",
+    "rules.type_specific.components",
+    "rules.validate_paths",
+    "rules.warn_empty_paths",
+    "rules.gradual",
+    "rules.gradual_until",
+    "rules.section_casing",
+    "templates.component",
+    "templates.runbook",
+    "templates.adr",
+    "mapping.exclude",
+    "mapping.coverage_threshold",
+    "hooks.run_verify",
+    "lint.enable",
+    "lint.disable",
+    "lint.max_paragraph_words",
+    "lint.external_links",
+    "verification.dedupe",
+    "verification.default_timeout_secs",
+    "verification.shell",
+    "verification.languages",
+    "verification.base_dir",
+    "verification.allow_failure",
+    "verification.require_pave_tag",
+    "check.required_sections",
+    "check.canonical_sections",
+    "check.disabled_rules",
+    "check.enabled_rules",
+    "check.max_section_lines",
+    "prompt.system_file",
+];
+
+/// Whether `key` is a dotted config key or table the schema knows, such as
+/// `rules.max_lines` or `rules`.
+pub fn is_known_key(key: &str) -> bool {
+    let path: Vec<String> = key.split('.').map(String::from).collect();
+    KNOWN_KEYS.contains(&key) || is_known_table(&path)
+}
+
+/// Whether `path` names a table holding known keys, such as `rules` or
+/// `rules.type_specific`.
+fn is_known_table(path: &[String]) -> bool {
+    let prefix = format!("{}.", path.join("."));
+    KNOWN_KEYS.iter().any(|key| key.starts_with(&prefix))
+}
+
+/// Check a config file's contents against the schema.
+///
+/// Reports TOML syntax errors, unknown keys, values of the wrong type, and
+/// values [`PaveConfig::validate`] rejects. Unlike [`PaveConfig::parse`],
+/// which stops at the first error and ignores unknown keys, this reports
+/// every problem it can, with line numbers. Environment overrides are not
+/// applied, and a file that `extends` another is checked on its own keys
+/// only.
+pub fn check_config(content: &str) -> Vec<ConfigProblem> {
+    let line_of = |offset: usize| content[..offset].matches('\n').count() + 1;
+
+    let table = match toml::de::DeTable::parse(content) {
+        Ok(table) => table,
+        Err(err) => {
+            return vec![ConfigProblem {
+                line: err.span().map(|span| line_of(span.start)),
+                key: None,
+                message: err.message().to_string(),
+            }];
+        }
+    };
+    let value: toml::Value = match toml::from_str(content) {
+        Ok(value) => value,
+        Err(err) => {
+            return vec![ConfigProblem {
+                line: err.span().map(|span| line_of(span.start)),
+                key: None,
+                message: err.message().to_string(),
+            }];
+        }
+    };
+
+    // Check each key on its own against the defaults, so one bad key
+    // doesn't hide the others
+    let schema = toml::Value::try_from(PaveConfig::default())
+        .unwrap_or_else(|_| toml::Value::Table(toml::map::Map::new()));
+    let mut problems = Vec::new();
+    let mut keys = Vec::new();
+    collect_keys(table.get_ref(), &mut Vec::new(), &mut keys);
+    for (path, offset) in keys {
+        if let [key] = path.as_slice()
+            && EXTENDS_KEYS.contains(&key.as_str())
+        {
+            continue;
+        }
+        let key = path.join(".");
+        if !KNOWN_KEYS.contains(&key.as_str()) {
+            problems.push(ConfigProblem {
+                line: Some(line_of(offset)),
+                message: format!("unknown key `{}`", key),
+                key: Some(key),
+            });
+            continue;
+        }
+        let mut candidate = schema.clone();
+        let inserted =
+            lookup(&value, &path).is_some_and(|user| insert(&mut candidate, &path, user.clone()));
+        let message = match (inserted, candidate.try_into::<PaveConfig>()) {
+            (true, Err(err)) => format!("invalid value for `{}`: {}", key, err.message()),
+            (true, Ok(_)) => continue,
+            (false, _) => format!("invalid value for `{}`", key),
+        };
+        problems.push(ConfigProblem {
+            line: Some(line_of(offset)),
+            key: Some(key),
+            message,
+        });
+    }
+
+    // A file that extends another is only complete once merged
+    let extends = EXTENDS_KEYS.iter().any(|key| value.get(key).is_some());
+    if problems.is_empty() && !extends {
+        let result = value
+            .try_into::<PaveConfig>()
+            .map_err(|err| err.message().to_string())
+            .and_then(|config| config.validate().map_err(|err| err.to_string()));
+        if let Err(message) = result {
+            problems.push(ConfigProblem {
+                line: None,
+                key: None,
+                message,
+            });
+        }
+    }
+
+    problems
+}
+
+/// Collect the leaf keys of a parsed config with the byte offset of each key.
+///
+/// Tables are descended into only where [`KNOWN_KEYS`] has a table, so an
+/// unknown table is reported once rather than key by key.
+fn collect_keys(
+    table: &toml::de::DeTable<'_>,
+    path: &mut Vec<String>,
+    keys: &mut Vec<(Vec<String>, usize)>,
+) {
+    for (key, value) in table {
+        path.push(key.get_ref().to_string());
+        match value.get_ref() {
+            toml::de::DeValue::Table(inner) if is_known_table(path) => {
+                collect_keys(inner, path, keys);
+            }
+            _ => keys.push((path.clone(), key.span().start)),
+        }
+        path.pop();
+    }
+}
+
+/// Locate the config file to use.
+///
+/// An explicit path (from `--config`) must exist. Otherwise the config is
+/// discovered by walking up from the current directory.
+pub fn find_config(explicit: Option<&Path>) -> Result<PathBuf> {
+    if let Some(path) = explicit {
+        if !path.is_file() {
+            return Err(Error::ConfigNotFound {
+                path: Some(path.to_path_buf()),
+            });
+        }
+        return Ok(path.to_path_buf());
+    }
+
+    let cwd =
+        std::env::current_dir().map_err(|e| Error::io("Failed to get current directory", e))?;
+    find_config_from(&cwd)
+}
+
+/// Discover the config file for a directory.
+///
+/// Walks up from `start` looking for `.pave.toml`, stopping after the
+/// repository root (the first directory containing `.git`). Falls back to the
+/// global config when no project config is found.
+pub fn find_config_from(start: &Path) -> Result<PathBuf> {
+    discover_config(start, global_config_path().as_deref())
+        .ok_or(Error::ConfigNotFound { path: None })
+}
+
+/// Walk up from `start` to the repository boundary, then try `global`.
+fn discover_config(start: &Path, global: Option<&Path>) -> Option<PathBuf> {
+    let mut dir = start;
+    loop {
+        let config_path = dir.join(CONFIG_FILENAME);
+        if config_path.is_file() {
+            return Some(config_path);
+        }
+
+        // Don't escape the enclosing repository
+        if dir.join(".git").exists() {
+            break;
+        }
+
+        match dir.parent() {
+            Some(parent) => dir = parent,
+            None => break,
+        }
+    }
+
+    global.filter(|path| path.is_file()).map(Path::to_path_buf)
+}
+
+/// Path to the user-level config file.
+///
+/// Uses `$XDG_CONFIG_HOME/pave/config.toml`, or `~/.config/pave/config.toml`
+/// when `XDG_CONFIG_HOME` is unset.
+pub fn global_config_path() -> Option<PathBuf> {
+    let base = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+    Some(base.join("pave").join("config.toml"))
+}
+
+/// Whether `config_path` is the user-level config from [`global_config_path`].
+pub fn is_global_config(config_path: &Path) -> bool {
+    global_config_path().is_some_and(|global| global == config_path)
+}
+
+/// Directory that relative paths in the config at `config_path` (such as
+/// `docs.root`) resolve against.
+///
+/// That is the config file's own directory, except for the global config,
+/// which applies to whatever project the command runs in: its paths resolve
+/// against the current directory.
+pub fn config_base_dir(config_path: &Path) -> PathBuf {
+    if is_global_config(config_path)
+        && let Ok(cwd) = std::env::current_dir()
+    {
+        return cwd;
+    }
+    config_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .to_path_buf()
+}
+
+/// Load the config, falling back to defaults when none can be discovered.
+///
+/// An explicit path that does not exist is still an error.
+pub fn load_or_default(explicit: Option<&Path>) -> Result<PaveConfig> {
+    match find_config(explicit) {
+        Ok(path) => PaveConfig::load(path),
+        Err(err) if explicit.is_some() => Err(err),
+        Err(_) => Ok(PaveConfig::default()),
+    }
+}
+
+/// Like [`load_or_default`], but also returns the directory the config's
+/// relative paths resolve against (see [`config_base_dir`]), which is the
+/// current directory when no config is found.
+pub fn load_project(explicit: Option<&Path>) -> Result<(PaveConfig, PathBuf)> {
+    match find_config(explicit) {
+        Ok(path) => Ok((PaveConfig::load(&path)?, config_base_dir(&path))),
+        Err(err) if explicit.is_some() => Err(err),
+        Err(_) => Ok((PaveConfig::default(), PathBuf::from("."))),
+    }
+}
+
+/// Layer `PAVE_*` environment variables over a parsed config table.
+///
+/// Each variable named `PAVE_<SECTION>_<KEY>` whose section is a known config
+/// section is written to `<section>.<key>`. Nested tables are resolved by
+/// name, so `PAVE_RULES_TYPE_SPECIFIC_RUNBOOKS` maps to
+/// `rules.type_specific.runbooks`. Values are converted to the type of the
+/// existing (or default) value; arrays are comma-separated.
+///
+/// Returns the dotted keys that were overridden.
+pub fn apply_env_overrides<I>(value: &mut toml::Value, vars: I) -> Vec<String>
+where
+    I: IntoIterator<Item = (String, String)>,
+{
+    let schema = toml::Value::try_from(PaveConfig::default())
+        .unwrap_or_else(|_| toml::Value::Table(toml::map::Map::new()));
+    let mut applied = Vec::new();
+
+    let mut vars: Vec<(String, String)> = vars
+        .into_iter()
+        .filter(|(name, _)| name.starts_with(ENV_PREFIX))
+        .collect();
+    vars.sort();
+
+    for (name, raw) in vars {
+        let Some(path) = env_key_path(&name[ENV_PREFIX.len()..], value, &schema) else {
+            continue;
+        };
+        let hint = lookup(value, &path)
+            .or_else(|| lookup(&schema, &path))
+            .cloned();
+        if insert(value, &path, parse_env_value(&raw, hint.as_ref())) {
+            applied.push(path.join("."));
+        }
+    }
+
+    applied
+}
+
+/// Resolve an environment variable suffix (e.g. `RULES_MAX_LINES`) into a key path.
+fn env_key_path(suffix: &str, value: &toml::Value, schema: &toml::Value) -> Option<Vec<String>> {
+    let lower = suffix.to_lowercase();
+    let (section, rest) = lower.split_once('_')?;
+    if rest.is_empty() || !(is_table_at(schema, &[section]) || is_table_at(value, &[section])) {
+        return None;
+    }
+
+    let mut path = vec![section.to_string()];
+    let mut rest = rest.to_string();
+
+    // Descend into nested tables whose name prefixes the remaining key
+    loop {
+        let nested = rest
+            .match_indices('_')
+            .map(|(idx, _)| &rest[..idx])
+            .find(|candidate| {
+                let mut probe = path.clone();
+                probe.push(candidate.to_string());
+                let probe: Vec<&str> = probe.iter().map(String::as_str).collect();
+                is_table_at(schema, &probe) || is_table_at(value, &probe)
+            })
+            .map(str::to_string);
+
+        match nested {
+            Some(table) => {
+                rest = rest[table.len() + 1..].to_string();
+                path.push(table);
+            }
+            None => break,
+        }
+    }
+
+    path.push(rest);
+    Some(path)
+}
+
+fn is_table_at(value: &toml::Value, path: &[&str]) -> bool {
+    let mut current = value;
+    for part in path {
+        match current.get(part) {
+            Some(next) => current = next,
+            None => return false,
+        }
+    }
+    current.is_table()
+}
+
+fn lookup<'a>(value: &'a toml::Value, path: &[String]) -> Option<&'a toml::Value> {
+    let mut current = value;
+    for part in path {
+        current = current.get(part.as_str())?;
+    }
+    Some(current)
+}
+
+/// Insert a value at the given key path, creating intermediate tables.
+fn insert(value: &mut toml::Value, path: &[String], new_value: toml::Value) -> bool {
+    let Some((last, parents)) = path.split_last() else {
+        return false;
+    };
+
+    let mut current = value;
+    for part in parents {
+        let Some(table) = current.as_table_mut() else {
+            return false;
+        };
+        current = table
+            .entry(part.clone())
+            .or_insert_with(|| toml::Value::Table(toml::map::Map::new()));
+    }
+
+    match current.as_table_mut() {
+        Some(table) => {
+            table.insert(last.clone(), new_value);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Convert a raw environment value using the type of the value it replaces.
+fn parse_env_value(raw: &str, hint: Option<&toml::Value>) -> toml::Value {
+    match hint {
+        Some(toml::Value::String(_)) => toml::Value::String(raw.to_string()),
+        Some(toml::Value::Array(_)) => toml::Value::Array(
+            raw.split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| toml::Value::String(item.to_string()))
+                .collect(),
+        ),
+        _ => parse_value(raw),
+    }
+}
+
+/// Parse a string value into an appropriate TOML Value.
+pub fn parse_value(s: &str) -> toml::Value {
+    // Try to parse as integer
+    if let Ok(i) = s.parse::<i64>() {
+        return toml::Value::Integer(i);
+    }
+
+    // Try to parse as float
+    if let Ok(f) = s.parse::<f64>() {
+        return toml::Value::Float(f);
+    }
+
+    // Try to parse as boolean
+    match s.to_lowercase().as_str() {
+        "true" => return toml::Value::Boolean(true),
+        "false" => return toml::Value::Boolean(false),
+        _ => {}
+    }
+
+    // Default to string
+    toml::Value::String(s.to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -394,6 +1261,94 @@ root = ""
         );
     }
 
+    #[test]
+    fn resolve_root_expands_home_and_follows_the_config_dir() {
+        let home = Path::new("/home/dev");
+        assert_eq!(
+            resolve_root(Path::new("~/notes"), Path::new("/project"), Some(home)),
+            PathBuf::from("/home/dev/notes")
+        );
+        assert_eq!(
+            resolve_root(Path::new("./docs"), Path::new("/project/sub"), Some(home)),
+            PathBuf::from("/project/sub/docs")
+        );
+        assert_eq!(
+            resolve_root(Path::new("/abs/docs"), Path::new("/project"), Some(home)),
+            PathBuf::from("/abs/docs")
+        );
+        // Without a home directory `~` is treated as a plain relative name
+        assert_eq!(
+            resolve_root(Path::new("~/notes"), Path::new("/project"), None),
+            PathBuf::from("/project/~/notes")
+        );
+    }
+
+    #[test]
+    fn resolve_root_dirs_rejects_missing_and_non_directory_roots() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::create_dir(temp.path().join("docs")).unwrap();
+        std::fs::write(temp.path().join("README.md"), "# Readme\n").unwrap();
+
+        let mut docs = DocsSection::default();
+        assert_eq!(
+            docs.resolve_root_dirs(temp.path()).unwrap(),
+            vec![temp.path().join("docs")]
+        );
+
+        docs.root = PathBuf::from("missing");
+        let err = docs.resolve_root_dirs(temp.path()).unwrap_err().to_string();
+        assert!(err.contains("docs.root `missing` does not exist"), "{err}");
+
+        docs.root = PathBuf::from("README.md");
+        let err = docs.resolve_root_dirs(temp.path()).unwrap_err().to_string();
+        assert!(
+            err.contains("docs.root `README.md` is not a directory"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn parse_config_with_docs_root_list() {
+        let toml = r#"
+[pave]
+version = "0.1"
+
+[docs]
+root = ["docs", "crates/api/docs", "./docs"]
+"#;
+        let config = PaveConfig::parse(toml).unwrap();
+        assert_eq!(config.docs.root, PathBuf::from("docs"));
+        assert_eq!(
+            config.docs.root_dirs(Path::new("/project")),
+            vec![
+                PathBuf::from("/project/docs"),
+                PathBuf::from("/project/crates/api/docs"),
+            ]
+        );
+
+        // The list form survives a round trip; a single root stays a string
+        let saved = toml::to_string(&config).unwrap();
+        assert!(saved.contains(r#"root = ["docs", "crates/api/docs", "./docs"]"#));
+        assert!(
+            toml::to_string(&PaveConfig::default())
+                .unwrap()
+                .contains(r#"root = "docs""#)
+        );
+    }
+
+    #[test]
+    fn reject_config_with_empty_docs_root_list() {
+        let toml = r#"
+[pave]
+version = "0.1"
+
+[docs]
+root = []
+"#;
+        let err = PaveConfig::parse(toml).unwrap_err();
+        assert!(err.to_string().contains("docs.root cannot be empty"));
+    }
+
     #[test]
     fn parse_config_with_custom_rules() {
         let toml = r#"
@@ -471,6 +1426,132 @@ run_verify = true
         assert!(config.hooks.run_verify);
     }
 
+    fn problem_keys(content: &str) -> Vec<(Option<usize>, Option<String>)> {
+        check_config(content)
+            .into_iter()
+            .map(|p| (p.line, p.key))
+            .collect()
+    }
+
+    #[test]
+    fn check_config_accepts_valid_config() {
+        let toml = "[pave]\nversion = \"0.1\"\n\n[docs]\nroot = \"docs\"\ntemplates = \"docs/templates\"\n\n[rules]\nmax_lines = 200\ngradual_until = \"2030-01-01\"\n\n[rules.type_specific]\nrunbooks = true\n";
+        assert_eq!(check_config(toml), Vec::new());
+    }
+
+    #[test]
+    fn check_config_flags_unknown_keys_with_lines() {
+        let toml = "[pave]\nversion = \"0.1\"\n\n[docs]\nroot = \"docs\"\nrooot = \"x\"\n\n[extras]\nfoo = 1\n";
+        assert_eq!(
+            problem_keys(toml),
+            vec![
+                (Some(6), Some("docs.rooot".to_string())),
+                (Some(8), Some("extras".to_string())),
+            ]
+        );
+        assert_eq!(check_config(toml)[0].message, "unknown key `docs.rooot`");
+    }
+
+    #[test]
+    fn check_config_accepts_keys_at_their_defaults() {
+        let toml = "[pave]\nversion = \"0.1\"\n[docs]\nroot = \"docs\"\n[verification]\nallow_failure = []\nshell = \"sh\"\n[prompt]\nsystem_file = \"prompt.md\"\n";
+        assert_eq!(check_config(toml), Vec::new());
+    }
+
+    #[test]
+    fn known_keys_cover_every_config_field() {
+        // Fill in the optional keys the defaults leave out
+        let mut config = PaveConfig::default();
+        config.docs.templates = Some(PathBuf::from("templates"));
+        config.rules.gradual_until = Some("2030-01-01".to_string());
+        config.templates.component = Some("c.md".to_string());
+        config.templates.runbook = Some("r.md".to_string());
+        config.templates.adr = Some("a.md".to_string());
+        config.mapping.coverage_threshold = Some(80);
+        config.verification.shell = Some("sh".to_string());
+        config.verification.base_dir = Some(PathBuf::from("."));
+        config.verification.allow_failure = vec!["docs/*.md".to_string()];
+        config.check.max_section_lines = Some(50);
+        config.prompt.system_file = Some(PathBuf::from("prompt.md"));
+        let content = toml::to_string(&config).unwrap();
+
+        let table = toml::de::DeTable::parse(&content).unwrap();
+        let mut keys = Vec::new();
+        collect_keys(table.get_ref(), &mut Vec::new(), &mut keys);
+        let mut keys: Vec<String> = keys.into_iter().map(|(path, _)| path.join(".")).collect();
+        let mut known: Vec<String> = KNOWN_KEYS.iter().map(|key| key.to_string()).collect();
+        keys.sort();
+        known.sort();
+        assert_eq!(keys, known);
+    }
+
+    #[test]
+    fn check_config_flags_wrong_types_with_lines() {
+        let toml = "[pave]\nversion = \"0.1\"\n\n[docs]\nroot = \"docs\"\n\n[rules]\nmax_lines = \"lots\"\nrequire_examples = 1\n";
+        let problems = check_config(toml);
+        assert_eq!(
+            problem_keys(toml),
+            vec![
+                (Some(8), Some("rules.max_lines".to_string())),
+                (Some(9), Some("rules.require_examples".to_string())),
+            ]
+        );
+        assert!(
+            problems[0]
+                .message
+                .starts_with("invalid value for `rules.max_lines`")
+        );
+    }
+
+    #[test]
+    fn check_config_reports_syntax_and_semantic_errors() {
+        let problems = check_config("[pave\nversion = \"0.1\"\n");
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, Some(1));
+
+        let problems = check_config(
+            "[pave]\nversion = \"0.1\"\n[docs]\nroot = \"docs\"\n[rules]\nmax_lines = 0\n",
+        );
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].message.contains("max_lines"));
+    }
+
+    #[test]
+    fn parse_config_with_verification_timeout() {
+        let toml = r#"
+[pave]
+version = "0.1"
+
+[docs]
+root = "docs"
+
+[verification]
+default_timeout_secs = 120
+"#;
+        let config = PaveConfig::parse(toml).unwrap();
+        assert_eq!(config.verification.default_timeout_secs, 120);
+        assert!(!config.verification.dedupe);
+
+        let config =
+            PaveConfig::parse("[pave]\nversion = \"0.1\"\n[docs]\nroot = \"docs\"\n").unwrap();
+        assert_eq!(config.verification.default_timeout_secs, 30);
+    }
+
+    #[test]
+    fn parse_config_with_verification_languages() {
+        let config = PaveConfig::parse(
+            "[pave]\nversion = \"0.1\"\n[docs]\nroot = \"docs\"\n[verification]\nlanguages = [\"bash\", \"zsh\"]\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.verification.parse_options().executable_languages,
+            vec!["bash", "zsh"]
+        );
+
+        let defaults = PaveConfig::default().verification.parse_options();
+        assert_eq!(defaults, ParseOptions::default());
+    }
+
     #[test]
     fn parse_config_without_hooks_uses_default() {
         let toml = r#"
@@ -665,4 +1746,199 @@ root = "docs"
         let deserialized = PaveConfig::parse(&serialized).unwrap();
         assert_eq!(config, deserialized);
     }
+
+    fn env(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn env_overrides_replace_file_values() {
+        let mut value: toml::Value = toml::from_str(
+            r#"
+[pave]
+version = "0.1"
+
+[docs]
+root = "docs"
+
+[rules]
+max_lines = 300
+"#,
+        )
+        .unwrap();
+
+        let applied = apply_env_overrides(
+            &mut value,
+            env(&[
+                ("PAVE_DOCS_ROOT", "documentation"),
+                ("PAVE_RULES_MAX_LINES", "500"),
+                ("PAVE_PAVE_VERSION", "0.2"),
+            ]),
+        );
+
+        assert_eq!(applied.len(), 3);
+        let config: PaveConfig = value.try_into().unwrap();
+        assert_eq!(config.docs.root, PathBuf::from("documentation"));
+        assert_eq!(config.rules.max_lines, 500);
+        // Strings stay strings even when they look numeric
+        assert_eq!(config.pave.version, "0.2");
+    }
+
+    #[test]
+    fn env_overrides_resolve_nested_tables_and_arrays() {
+        let mut value: toml::Value = toml::from_str(
+            r#"
+[pave]
+version = "0.1"
+
+[docs]
+root = "docs"
+"#,
+        )
+        .unwrap();
+
+        apply_env_overrides(
+            &mut value,
+            env(&[
+                ("PAVE_RULES_TYPE_SPECIFIC_RUNBOOKS", "true"),
+                ("PAVE_MAPPING_EXCLUDE", "target/, *.tmp"),
+            ]),
+        );
+
+        let config: PaveConfig = value.try_into().unwrap();
+        assert!(config.rules.type_specific.runbooks);
+        assert_eq!(config.mapping.exclude, vec!["target/", "*.tmp"]);
+    }
+
+    #[test]
+    fn env_overrides_ignore_unrelated_variables() {
+        let mut value: toml::Value = toml::from_str(
+            r#"
+[pave]
+version = "0.1"
+
+[docs]
+root = "docs"
+"#,
+        )
+        .unwrap();
+        let original = value.clone();
+
+        let applied = apply_env_overrides(
+            &mut value,
+            env(&[("PAVE_CONFIG", "other.toml"), ("HOME", "/root")]),
+        );
+
+        assert!(applied.is_empty());
+        assert_eq!(value, original);
+    }
+
+    #[test]
+    fn extends_merges_child_over_base() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let shared = temp.path().join("shared");
+        let repo = temp.path().join("repo");
+        std::fs::create_dir_all(&shared).unwrap();
+        std::fs::create_dir_all(&repo).unwrap();
+        std::fs::write(
+            shared.join("base.pave.toml"),
+            "[pave]\nversion = \"0.1\"\n\n[docs]\nroot = \"docs\"\n\n[rules]\nmax_lines = 200\nrequire_examples = false\n\n[mapping]\nexclude = [\"target/\"]\n\n[check]\nrequired_sections = [\"Purpose\"]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            repo.join(CONFIG_FILENAME),
+            "extends = \"../shared/base.pave.toml\"\n\n[rules]\nmax_lines = 400\n\n[mapping]\nexclude = [\"...\", \"vendor/\"]\n\n[check]\nrequired_sections = [\"Overview\"]\n",
+        )
+        .unwrap();
+
+        let config = PaveConfig::load(repo.join(CONFIG_FILENAME)).unwrap();
+        assert_eq!(config.rules.max_lines, 400);
+        assert!(!config.rules.require_examples);
+        assert_eq!(config.mapping.exclude, vec!["target/", "vendor/"]);
+        assert_eq!(config.check.required_sections, vec!["Overview"]);
+        assert!(
+            check_config(&std::fs::read_to_string(repo.join(CONFIG_FILENAME)).unwrap()).is_empty()
+        );
+    }
+
+    #[test]
+    fn extends_cycle_is_a_clear_error() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join("a.toml"), "include = \"b.toml\"\n").unwrap();
+        std::fs::write(temp.path().join("b.toml"), "extends = \"a.toml\"\n").unwrap();
+
+        let err = PaveConfig::load(temp.path().join("a.toml"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("config extends itself"), "{err}");
+        assert!(
+            err.contains("a.toml -> ") && err.ends_with("a.toml"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn merge_values_drops_markers_without_a_base_list() {
+        let mut base: toml::Value = toml::from_str("[mapping]\n").unwrap();
+        let overlay: toml::Value =
+            toml::from_str("[mapping]\nexclude = [\"...\", \"vendor/\"]\n").unwrap();
+        merge_values(&mut base, overlay);
+        assert_eq!(base["mapping"]["exclude"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn discover_config_finds_ancestor_from_nested_directory() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::create_dir(root.join(".git")).unwrap();
+        std::fs::write(root.join(CONFIG_FILENAME), "").unwrap();
+        let nested = root.join("docs").join("components");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        let found = discover_config(&nested, None).unwrap();
+        assert_eq!(found, root.join(CONFIG_FILENAME));
+    }
+
+    #[test]
+    fn discover_config_stops_at_git_boundary() {
+        let temp = tempfile::TempDir::new().unwrap();
+        // Config above the repository should not be picked up
+        std::fs::write(temp.path().join(CONFIG_FILENAME), "").unwrap();
+        let repo = temp.path().join("repo");
+        std::fs::create_dir_all(repo.join(".git")).unwrap();
+        let nested = repo.join("src");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        assert_eq!(discover_config(&nested, None), None);
+    }
+
+    #[test]
+    fn discover_config_falls_back_to_global() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let repo = temp.path().join("repo");
+        std::fs::create_dir_all(repo.join(".git")).unwrap();
+        let global = temp.path().join("global.toml");
+        std::fs::write(&global, "").unwrap();
+
+        assert_eq!(discover_config(&repo, Some(&global)), Some(global.clone()));
+
+        let missing = temp.path().join("missing.toml");
+        assert_eq!(discover_config(&repo, Some(&missing)), None);
+    }
+
+    #[test]
+    fn config_base_dir_uses_cwd_for_global_config() {
+        let project = Path::new("/repo/sub").join(CONFIG_FILENAME);
+        assert_eq!(config_base_dir(&project), PathBuf::from("/repo/sub"));
+        assert_eq!(
+            config_base_dir(Path::new(CONFIG_FILENAME)),
+            PathBuf::from("")
+        );
+        if let Some(global) = global_config_path() {
+            assert_eq!(config_base_dir(&global), std::env::current_dir().unwrap());
+        }
+    }
 }
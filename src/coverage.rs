@@ -0,0 +1,704 @@
+//! Code-to-documentation coverage analysis.
+//!
+//! This is the engine behind `pave coverage`, exposed so reporting tools can
+//! read coverage numbers without parsing the CLI's output. A code file is
+//! covered when some doc's `## Paths` section matches it. Entries limited to
+//! line ranges (`src/big.rs:L10-L80`) cover the file only once the ranges of
+//! every doc together span it, give or take [`LINE_RANGE_TOLERANCE`] lines;
+//! until then it is reported as partially covered.
+//!
+//! ```no_run
+//! use pave::config::PaveConfig;
+//! use std::path::Path;
+//!
+//! let config = PaveConfig::load(Path::new(".pave.toml"))?;
+//! let report = pave::coverage::compute(&config, Path::new("."))?;
+//! println!("{:.1}% of {} files covered", report.percentage, report.total);
+//! for file in &report.uncovered_files {
+//!     println!("uncovered: {}", file.display());
+//! }
+//! # Ok::<(), pave::Error>(())
+//! ```
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::config::PaveConfig;
+use crate::error::{Error, Result};
+use crate::parser::ParsedDoc;
+use crate::paths::{PathsMatcher, Selection, walk_dirs, walk_files};
+
+/// Non-blank lines a file may leave outside its `## Paths` line ranges and
+/// still count as covered.
+pub const LINE_RANGE_TOLERANCE: usize = 5;
+
+/// Which code files [`compute_with`] analyzes.
+#[derive(Debug, Clone)]
+pub struct CoverageOptions {
+    /// Directory whose code files are analyzed (default: the project root).
+    pub path: Option<PathBuf>,
+    /// Only consider code files matching these patterns.
+    pub include: Vec<String>,
+    /// Skip code files matching these patterns, on top of `mapping.exclude`.
+    pub exclude: Vec<String>,
+    /// Leave out files excluded by `.gitignore` and `.ignore`.
+    pub respect_ignore: bool,
+}
+
+impl Default for CoverageOptions {
+    fn default() -> Self {
+        Self {
+            path: None,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            respect_ignore: true,
+        }
+    }
+}
+
+/// Coverage of a project's code files by its docs.
+///
+/// Paths are relative to the analyzed directory, doc paths to the project
+/// root.
+#[derive(Debug, Clone, Serialize)]
+pub struct CoverageReport {
+    /// Number of code files analyzed.
+    pub total: usize,
+    /// Number of code files some doc covers.
+    pub covered: usize,
+    /// Percentage of code files covered (100 when there are none).
+    pub percentage: f64,
+    /// Covered files, each with the docs that cover it.
+    pub covered_files: Vec<CoveredFile>,
+    /// Files whose line-range `## Paths` entries leave too much of them
+    /// undocumented. They count as neither covered nor uncovered.
+    pub partially_covered: Vec<PartialFile>,
+    /// Code files no doc covers.
+    pub uncovered_files: Vec<PathBuf>,
+    /// Every doc with a `## Paths` section and the code files it covers,
+    /// fully or partially.
+    pub per_doc: BTreeMap<PathBuf, Vec<PathBuf>>,
+    /// Number of `status: draft` docs left out of the analysis.
+    pub drafts_skipped: usize,
+}
+
+/// Compute coverage for the project whose config lives in `root`, the way a
+/// bare `pave coverage` does: `mapping.exclude` applies and ignored files
+/// are left out.
+pub fn compute(config: &PaveConfig, root: &Path) -> Result<CoverageReport> {
+    compute_with(config, root, &CoverageOptions::default())
+}
+
+/// Compute coverage for the project rooted at `root`, narrowed by `options`.
+///
+/// Draft docs count toward neither side: code files only drafts cover are
+/// left out.
+pub fn compute_with(
+    config: &PaveConfig,
+    root: &Path,
+    options: &CoverageOptions,
+) -> Result<CoverageReport> {
+    let docs_roots = config.docs.resolve_root_dirs(root)?;
+    let analyze_path = options.path.as_deref().unwrap_or(root);
+
+    let mut exclude = config.mapping.exclude.clone();
+    exclude.extend(options.exclude.iter().cloned());
+    let mut code_files = collect_code_files(
+        analyze_path,
+        &options.include,
+        &exclude,
+        options.respect_ignore,
+    )?;
+    let (doc_mappings, drafts_skipped) = without_drafts(
+        &mut code_files,
+        load_doc_mappings(&docs_roots, root, options.respect_ignore)?,
+    );
+
+    let (covered, mut partially_covered, uncovered) =
+        analyze_coverage(&code_files, &doc_mappings, analyze_path);
+    let covered_files = find_covering_docs(&covered, &doc_mappings, root);
+    let partial_paths: Vec<PathBuf> = partially_covered.iter().map(|f| f.path.clone()).collect();
+    for (file, covering) in
+        partially_covered
+            .iter_mut()
+            .zip(find_covering_docs(&partial_paths, &doc_mappings, root))
+    {
+        file.docs = covering.docs;
+    }
+
+    let mut per_doc: BTreeMap<PathBuf, Vec<PathBuf>> = doc_mappings
+        .iter()
+        .map(|doc| {
+            let path = doc.path.strip_prefix(root).unwrap_or(&doc.path);
+            (path.to_path_buf(), Vec::new())
+        })
+        .collect();
+    let covering = covered_files
+        .iter()
+        .map(|file| (&file.path, &file.docs))
+        .chain(
+            partially_covered
+                .iter()
+                .map(|file| (&file.path, &file.docs)),
+        );
+    for (path, docs) in covering {
+        for doc in docs {
+            if let Some(files) = per_doc.get_mut(doc) {
+                files.push(path.clone());
+            }
+        }
+    }
+    for files in per_doc.values_mut() {
+        files.sort();
+    }
+
+    let total = code_files.len();
+    let percentage = if total == 0 {
+        100.0
+    } else {
+        (covered.len() as f64 / total as f64) * 100.0
+    };
+
+    Ok(CoverageReport {
+        total,
+        covered: covered.len(),
+        percentage,
+        covered_files,
+        partially_covered,
+        uncovered_files: uncovered,
+        per_doc,
+        drafts_skipped,
+    })
+}
+
+/// A covered file and the docs whose `## Paths` cover it.
+#[derive(Debug, Clone, Serialize)]
+pub struct CoveredFile {
+    /// Path to the covered file.
+    pub path: PathBuf,
+    /// Docs covering the file, relative to the project root.
+    pub docs: Vec<PathBuf>,
+}
+
+/// A file only partly spanned by the line ranges of the docs covering it.
+#[derive(Debug, Clone, Serialize)]
+pub struct PartialFile {
+    /// Path to the file.
+    pub path: PathBuf,
+    /// Docs with ranges in the file, relative to the project root.
+    pub docs: Vec<PathBuf>,
+    /// Non-blank lines inside some range.
+    pub covered_lines: usize,
+    /// Non-blank lines in the file.
+    pub total_lines: usize,
+    /// Stretches no range covers, as `L81-L120` (or `L81` for one line).
+    pub uncovered_ranges: Vec<String>,
+}
+
+/// A documentation file with its path mappings.
+#[derive(Debug, Clone)]
+pub(crate) struct DocMapping {
+    /// Path to the documentation file.
+    pub(crate) path: PathBuf,
+    /// Glob patterns for code paths this doc covers.
+    pub(crate) patterns: Vec<String>,
+    /// Whether the doc's frontmatter marks it `status: draft`.
+    pub(crate) draft: bool,
+}
+
+/// Set aside draft docs, and drop code files that only drafts cover from
+/// `code_files` so in-progress docs neither raise nor lower coverage.
+///
+/// Returns the remaining docs and how many drafts were set aside.
+pub(crate) fn without_drafts(
+    code_files: &mut Vec<PathBuf>,
+    doc_mappings: Vec<DocMapping>,
+) -> (Vec<DocMapping>, usize) {
+    let (drafts, docs): (Vec<DocMapping>, Vec<DocMapping>) =
+        doc_mappings.into_iter().partition(|doc| doc.draft);
+    if !drafts.is_empty() {
+        let draft_matchers: Vec<PathsMatcher> = drafts
+            .iter()
+            .map(|doc| PathsMatcher::new(&doc.patterns))
+            .collect();
+        let doc_matchers: Vec<PathsMatcher> = docs
+            .iter()
+            .map(|doc| PathsMatcher::new(&doc.patterns))
+            .collect();
+        code_files.retain(|file| {
+            !draft_matchers.iter().any(|m| m.matches(file))
+                || doc_matchers.iter().any(|m| m.matches(file))
+        });
+    }
+    (docs, drafts.len())
+}
+
+/// Collect code files from the given path, applying include/exclude patterns.
+///
+/// Hidden entries and common build/dependency directories are always skipped;
+/// files excluded by `.gitignore` and `.ignore` are skipped when
+/// `respect_ignore` is set.
+pub(crate) fn collect_code_files(
+    root: &Path,
+    include: &[String],
+    exclude: &[String],
+    respect_ignore: bool,
+) -> Result<Vec<PathBuf>> {
+    let include = PathsMatcher::new(include);
+    let exclude = PathsMatcher::new(exclude);
+    let base = root.to_path_buf();
+
+    let files = walk_files(root, respect_ignore, move |path| {
+        let relative = path.strip_prefix(&base).unwrap_or(path);
+        // Check exclusions first
+        if exclude.matches(relative) {
+            return true;
+        }
+        // Skip hidden entries and common non-code directories
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            return false;
+        };
+        name.starts_with('.')
+            || (path.is_dir()
+                && matches!(
+                    name,
+                    "target" | "node_modules" | "dist" | "build" | "__pycache__"
+                ))
+    });
+
+    Ok(files
+        .iter()
+        .filter(|path| is_code_file(path))
+        .map(|path| path.strip_prefix(root).unwrap_or(path).to_path_buf())
+        // If include patterns specified, file must match at least one
+        .filter(|relative| include.is_empty() || include.matches(relative))
+        .collect())
+}
+
+/// Check if a file is a code file based on extension.
+fn is_code_file(path: &Path) -> bool {
+    let code_extensions = [
+        "rs", "py", "js", "ts", "jsx", "tsx", "go", "java", "c", "cpp", "h", "hpp", "rb", "php",
+        "swift", "kt", "scala", "sh", "bash", "zsh", "pl", "pm", "lua", "ex", "exs", "erl", "hrl",
+        "hs", "ml", "mli", "fs", "fsi", "clj", "cljs", "lisp", "el", "vim", "sql",
+    ];
+
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| code_extensions.contains(&ext))
+        .unwrap_or(false)
+}
+
+/// Load all documentation files under `docs_roots` with their path mappings.
+///
+/// A doc under several roots is loaded once. `templates/` directories and `index.md` files are skipped, as are files
+/// excluded by `.gitignore` and `.ignore` when `respect_ignore` is set.
+pub(crate) fn load_doc_mappings(
+    docs_roots: &[PathBuf],
+    config_dir: &Path,
+    respect_ignore: bool,
+) -> Result<Vec<DocMapping>> {
+    let files = walk_dirs(docs_roots, respect_ignore, |path| {
+        path.is_dir() && path.file_name().is_some_and(|n| n == "templates")
+    });
+
+    let mut mappings = Vec::new();
+    for path in files {
+        if path.extension().is_some_and(|ext| ext == "md")
+            && path.file_name().is_some_and(|n| n != "index.md")
+            && let Some(doc_mapping) = parse_doc_mapping(&path, config_dir)?
+        {
+            mappings.push(doc_mapping);
+        }
+    }
+    Ok(mappings)
+}
+
+/// Parse a documentation file to extract path mappings.
+fn parse_doc_mapping(path: &Path, _config_dir: &Path) -> Result<Option<DocMapping>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| Error::io(format!("Failed to read file: {}", path.display()), e))?;
+
+    let doc = ParsedDoc::parse_content(path.to_path_buf(), &content)?;
+    let patterns = doc.paths_patterns();
+
+    // Only include docs that have path mappings
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+
+    // Malformed frontmatter is `pave check`'s to report; treat it as final
+    let draft = doc.is_draft();
+
+    Ok(Some(DocMapping {
+        path: path.to_path_buf(),
+        patterns,
+        draft,
+    }))
+}
+
+/// Analyze coverage of code files against doc patterns.
+///
+/// Returns the covered, partially covered, and uncovered files. Files only
+/// line ranges select are read from `base` to measure the ranges; the
+/// partial files' `docs` are left empty.
+pub(crate) fn analyze_coverage(
+    code_files: &[PathBuf],
+    doc_mappings: &[DocMapping],
+    base: &Path,
+) -> (Vec<PathBuf>, Vec<PartialFile>, Vec<PathBuf>) {
+    let matchers: Vec<PathsMatcher> = doc_mappings
+        .iter()
+        .map(|doc| PathsMatcher::new(&doc.patterns))
+        .collect();
+    let mut covered = Vec::new();
+    let mut partial = Vec::new();
+    let mut uncovered = Vec::new();
+
+    // Evaluate each doc separately so one doc's `!` patterns can't
+    // exclude files another doc covers
+    for file in code_files {
+        let mut ranges = Vec::new();
+        let mut whole = false;
+        for selection in matchers.iter().filter_map(|m| m.selection(file)) {
+            match selection {
+                Selection::Whole => whole = true,
+                Selection::Lines(lines) => ranges.extend(lines),
+            }
+        }
+        if whole {
+            covered.push(file.clone());
+        } else if ranges.is_empty() {
+            uncovered.push(file.clone());
+        } else {
+            match uncovered_lines(base, file, &ranges) {
+                Some(gaps) => partial.push(gaps),
+                None => covered.push(file.clone()),
+            }
+        }
+    }
+
+    (covered, partial, uncovered)
+}
+
+/// Measure how much of `file` falls outside `ranges`, returning it as a
+/// [`PartialFile`] when that's more than [`LINE_RANGE_TOLERANCE`] non-blank
+/// lines.
+fn uncovered_lines(base: &Path, file: &Path, ranges: &[(usize, usize)]) -> Option<PartialFile> {
+    // An unreadable file can't be measured, so the ranges are taken on trust
+    let content = std::fs::read_to_string(base.join(file)).unwrap_or_default();
+    let in_range = |line: usize| {
+        ranges
+            .iter()
+            .any(|&(start, end)| (start..=end).contains(&line))
+    };
+
+    let mut total_lines = 0;
+    let mut missing = 0;
+    let mut gaps: Vec<(usize, usize)> = Vec::new();
+    for (idx, line) in content.lines().enumerate() {
+        let number = idx + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+        total_lines += 1;
+        if in_range(number) {
+            continue;
+        }
+        missing += 1;
+        match gaps.last_mut() {
+            // Only blank lines since the last gap, so it carries on
+            Some((_, end)) if !(*end + 1..number).any(in_range) => *end = number,
+            _ => gaps.push((number, number)),
+        }
+    }
+
+    (missing > LINE_RANGE_TOLERANCE).then(|| PartialFile {
+        path: file.to_path_buf(),
+        docs: Vec::new(),
+        covered_lines: total_lines - missing,
+        total_lines,
+        uncovered_ranges: gaps
+            .iter()
+            .map(|&(start, end)| {
+                if start == end {
+                    format!("L{}", start)
+                } else {
+                    format!("L{}-L{}", start, end)
+                }
+            })
+            .collect(),
+    })
+}
+
+/// Pair each covered file with the docs that cover it.
+fn find_covering_docs(
+    covered: &[PathBuf],
+    doc_mappings: &[DocMapping],
+    config_dir: &Path,
+) -> Vec<CoveredFile> {
+    let matchers: Vec<(PathBuf, PathsMatcher)> = doc_mappings
+        .iter()
+        .map(|doc| {
+            let path = doc.path.strip_prefix(config_dir).unwrap_or(&doc.path);
+            (path.to_path_buf(), PathsMatcher::new(&doc.patterns))
+        })
+        .collect();
+
+    covered
+        .iter()
+        .map(|file| CoveredFile {
+            path: file.clone(),
+            docs: matchers
+                .iter()
+                .filter(|(_, matcher)| matcher.matches(file))
+                .map(|(doc, _)| doc.clone())
+                .collect(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_compute_reports_totals_uncovered_files_and_docs() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::create_dir_all(root.join("src/gen")).unwrap();
+        for name in ["src/a.rs", "src/b.rs", "src/c.rs", "src/gen/d.rs"] {
+            fs::write(root.join(name), "").unwrap();
+        }
+        fs::write(
+            root.join("docs/core.md"),
+            "# Core\n\n## Paths\n- `src/a.rs`\n- `src/b.rs`\n- `src/c.rs`\n",
+        )
+        .unwrap();
+        fs::write(root.join("docs/a.md"), "# A\n\n## Paths\n- `src/a.rs`\n").unwrap();
+        fs::write(
+            root.join("docs/stale.md"),
+            "# Stale\n\n## Paths\n- `lib/**`\n",
+        )
+        .unwrap();
+        let config = PaveConfig::default();
+
+        let report = compute(&config, root).unwrap();
+
+        assert_eq!(report.total, 4);
+        assert_eq!(report.covered, 3);
+        assert_eq!(report.percentage, 75.0);
+        assert_eq!(report.uncovered_files, vec![PathBuf::from("src/gen/d.rs")]);
+        let a = report
+            .covered_files
+            .iter()
+            .find(|f| f.path == Path::new("src/a.rs"))
+            .unwrap();
+        assert_eq!(
+            a.docs,
+            vec![PathBuf::from("docs/a.md"), PathBuf::from("docs/core.md")]
+        );
+        let mut core = report.per_doc[Path::new("docs/core.md")].clone();
+        core.sort();
+        assert_eq!(
+            core,
+            vec![
+                PathBuf::from("src/a.rs"),
+                PathBuf::from("src/b.rs"),
+                PathBuf::from("src/c.rs"),
+            ]
+        );
+        assert_eq!(
+            report.per_doc[Path::new("docs/a.md")],
+            vec![PathBuf::from("src/a.rs")]
+        );
+        assert!(report.per_doc[Path::new("docs/stale.md")].is_empty());
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["uncovered_files"][0], "src/gen/d.rs");
+    }
+
+    #[test]
+    fn test_compute_unions_line_ranges_across_docs() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        let body: String = (1..=100).map(|n| format!("let x{n} = {n};\n")).collect();
+        fs::write(root.join("src/big.rs"), &body).unwrap();
+        fs::write(root.join("src/half.rs"), &body).unwrap();
+        fs::write(
+            root.join("docs/parse.md"),
+            "# Parse\n\n## Paths\n- `src/big.rs:L1-L60`\n- `src/half.rs:L1-L50`\n",
+        )
+        .unwrap();
+        // Leaves lines 99-100 to the tolerance
+        fs::write(
+            root.join("docs/emit.md"),
+            "# Emit\n\n## Paths\n- `src/big.rs:L55-L98`\n- `src/half.rs:L60`\n",
+        )
+        .unwrap();
+
+        let report = compute(&PaveConfig::default(), root).unwrap();
+
+        assert_eq!(report.total, 2);
+        assert_eq!(report.covered, 1);
+        assert_eq!(report.percentage, 50.0);
+        assert!(report.uncovered_files.is_empty());
+        let big = &report.covered_files[0];
+        assert_eq!(big.path, PathBuf::from("src/big.rs"));
+        assert_eq!(big.docs.len(), 2);
+
+        assert_eq!(report.partially_covered.len(), 1);
+        let half = &report.partially_covered[0];
+        assert_eq!(half.path, PathBuf::from("src/half.rs"));
+        assert_eq!((half.covered_lines, half.total_lines), (51, 100));
+        assert_eq!(half.uncovered_ranges, vec!["L51-L59", "L61-L100"]);
+        assert_eq!(
+            half.docs,
+            vec![
+                PathBuf::from("docs/emit.md"),
+                PathBuf::from("docs/parse.md")
+            ]
+        );
+        assert_eq!(
+            report.per_doc[Path::new("docs/parse.md")],
+            vec![PathBuf::from("src/big.rs"), PathBuf::from("src/half.rs")]
+        );
+    }
+
+    #[test]
+    fn test_is_code_file() {
+        assert!(is_code_file(Path::new("src/main.rs")));
+        assert!(is_code_file(Path::new("lib/utils.py")));
+        assert!(is_code_file(Path::new("app/index.ts")));
+        assert!(!is_code_file(Path::new("docs/readme.md")));
+        assert!(!is_code_file(Path::new("config.toml")));
+        assert!(!is_code_file(Path::new("data.json")));
+    }
+
+    #[test]
+    fn test_analyze_coverage() {
+        let code_files = vec![
+            PathBuf::from("src/cli.rs"),
+            PathBuf::from("src/main.rs"),
+            PathBuf::from("src/utils.rs"),
+        ];
+
+        let doc_mappings = vec![DocMapping {
+            path: PathBuf::from("docs/cli.md"),
+            patterns: vec!["src/cli.rs".to_string(), "src/main.rs".to_string()],
+            draft: false,
+        }];
+
+        let (covered, _, uncovered) = analyze_coverage(&code_files, &doc_mappings, Path::new("."));
+
+        assert_eq!(covered.len(), 2);
+        assert!(covered.contains(&PathBuf::from("src/cli.rs")));
+        assert!(covered.contains(&PathBuf::from("src/main.rs")));
+        assert_eq!(uncovered.len(), 1);
+        assert!(uncovered.contains(&PathBuf::from("src/utils.rs")));
+    }
+
+    #[test]
+    fn test_analyze_coverage_respects_negated_paths() {
+        let code_files = vec![
+            PathBuf::from("src/lib.rs"),
+            PathBuf::from("src/generated/api.rs"),
+        ];
+
+        let doc_mappings = vec![DocMapping {
+            path: PathBuf::from("docs/lib.md"),
+            patterns: vec!["src/**".to_string(), "!src/generated/**".to_string()],
+            draft: false,
+        }];
+
+        let (covered, _, uncovered) = analyze_coverage(&code_files, &doc_mappings, Path::new("."));
+
+        assert_eq!(covered, vec![PathBuf::from("src/lib.rs")]);
+        assert_eq!(uncovered, vec![PathBuf::from("src/generated/api.rs")]);
+    }
+
+    #[test]
+    fn test_parse_doc_mapping() {
+        let temp_dir = TempDir::new().unwrap();
+        let doc_path = temp_dir.path().join("test.md");
+
+        let content = r#"# Test Component
+
+## Purpose
+Test document.
+
+## Paths
+- `src/test.rs`
+- `src/lib/*.rs`
+
+## Examples
+Example content.
+"#;
+        fs::write(&doc_path, content).unwrap();
+
+        let mapping = parse_doc_mapping(&doc_path, temp_dir.path())
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(mapping.patterns.len(), 2);
+        assert_eq!(mapping.patterns[0], "src/test.rs");
+        assert_eq!(mapping.patterns[1], "src/lib/*.rs");
+    }
+
+    #[test]
+    fn test_parse_doc_mapping_no_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let doc_path = temp_dir.path().join("test.md");
+
+        let content = r#"# Test
+
+## Purpose
+No paths section.
+"#;
+        fs::write(&doc_path, content).unwrap();
+
+        let mapping = parse_doc_mapping(&doc_path, temp_dir.path()).unwrap();
+        assert!(mapping.is_none());
+    }
+
+    #[test]
+    fn test_load_doc_mappings() {
+        let temp_dir = TempDir::new().unwrap();
+        let docs_dir = temp_dir.path().join("docs");
+        fs::create_dir_all(&docs_dir).unwrap();
+
+        // Create a doc with paths
+        let doc_with_paths = r#"# Component
+
+## Paths
+- `src/*.rs`
+
+## Purpose
+Has paths.
+"#;
+        fs::write(docs_dir.join("component.md"), doc_with_paths).unwrap();
+
+        // Create a doc without paths
+        let doc_without_paths = r#"# Other
+
+## Purpose
+No paths section.
+"#;
+        fs::write(docs_dir.join("other.md"), doc_without_paths).unwrap();
+
+        // Create an index.md (should be skipped)
+        fs::write(docs_dir.join("index.md"), "# Index").unwrap();
+
+        let mappings = load_doc_mappings(&[docs_dir], temp_dir.path(), true).unwrap();
+
+        // Should only include the doc with paths, not the one without or index.md
+        assert_eq!(mappings.len(), 1);
+        assert!(mappings[0].patterns.contains(&"src/*.rs".to_string()));
+    }
+}
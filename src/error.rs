@@ -0,0 +1,102 @@
+//! Error type for pave's library APIs.
+//!
+//! The parser, config, and verification modules return [`Error`] so callers
+//! can match on specific failure modes. The CLI converts these into `anyhow`
+//! errors at the command boundary.
+
+use std::path::PathBuf;
+
+use crate::config::CONFIG_FILENAME;
+
+/// Result type for pave's library APIs.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Errors returned by pave's library APIs.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// A document could not be parsed.
+    #[error("{}:{line}: {message}", file.display())]
+    Parse {
+        /// Document that failed to parse.
+        file: PathBuf,
+        /// 1-based line number where the problem was found.
+        line: usize,
+        /// Description of the problem.
+        message: String,
+    },
+
+    /// No config file exists at the explicit path, or none could be discovered.
+    #[error("{}", config_not_found_message(path.as_ref()))]
+    ConfigNotFound {
+        /// The explicit path that was requested, if any.
+        path: Option<PathBuf>,
+    },
+
+    /// A config file could not be parsed, serialized, or failed validation.
+    #[error("{message}")]
+    Config {
+        /// Description of the problem.
+        message: String,
+        /// Underlying TOML error, if any.
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+
+    /// A verification command did not pass.
+    #[error("verification failed for `{command}`: {message}")]
+    Verification {
+        /// The command that failed.
+        command: String,
+        /// Why it failed.
+        message: String,
+    },
+
+    /// A filesystem operation failed.
+    #[error("{message}")]
+    Io {
+        /// What pave was doing when the error occurred.
+        message: String,
+        /// The underlying IO error.
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+impl Error {
+    /// Build a config error without an underlying cause.
+    pub(crate) fn config(message: impl Into<String>) -> Self {
+        Error::Config {
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    /// Build a config error wrapping an underlying cause.
+    pub(crate) fn config_with_source(
+        message: impl Into<String>,
+        source: impl std::error::Error + Send + Sync + 'static,
+    ) -> Self {
+        Error::Config {
+            message: message.into(),
+            source: Some(Box::new(source)),
+        }
+    }
+
+    /// Build an IO error with a description of the failed operation.
+    pub(crate) fn io(message: impl Into<String>, source: std::io::Error) -> Self {
+        Error::Io {
+            message: message.into(),
+            source,
+        }
+    }
+}
+
+fn config_not_found_message(path: Option<&PathBuf>) -> String {
+    match path {
+        Some(path) => format!("Config file not found: {}", path.display()),
+        None => format!(
+            "No {} found in current directory or any parent directory",
+            CONFIG_FILENAME
+        ),
+    }
+}
@@ -1,7 +1,16 @@
+pub mod cache;
 pub mod cli;
+pub mod color;
 pub mod commands;
 pub mod config;
+pub mod coverage;
+pub mod error;
+pub mod logging;
 pub mod parser;
+pub mod paths;
 pub mod rules;
 pub mod templates;
 pub mod verification;
+pub mod verify;
+
+pub use error::{Error, Result};
@@ -0,0 +1,107 @@
+//! Diagnostic logging.
+//!
+//! Commands emit [`tracing`] events (files scanned, globs compiled, commands
+//! extracted, cache hits) to stderr, so stdout stays clean for reports and
+//! JSON output. `--log-level` wins over `RUST_LOG`; the default is `warn`.
+
+use tracing_subscriber::EnvFilter;
+
+use crate::cli::LogLevel;
+
+/// Filter used when neither `--log-level` nor `RUST_LOG` is set.
+pub const DEFAULT_FILTER: &str = "warn";
+
+/// Install the stderr logger for this process.
+pub fn init(level: Option<LogLevel>) {
+    let rust_log = std::env::var("RUST_LOG").ok();
+    // Ignore the error if a subscriber is already installed (e.g. in tests).
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(filter(level, rust_log.as_deref()))
+        .with_writer(std::io::stderr)
+        .with_target(false)
+        .try_init();
+}
+
+/// Build the log filter.
+///
+/// An explicit `--log-level` applies to pave's own events and keeps other
+/// crates at the default; otherwise a valid `RUST_LOG` is used as-is.
+pub fn filter(level: Option<LogLevel>, rust_log: Option<&str>) -> EnvFilter {
+    if let Some(level) = level {
+        return EnvFilter::new(format!("{DEFAULT_FILTER},pave={}", level_name(level)));
+    }
+    rust_log
+        .filter(|spec| !spec.trim().is_empty())
+        .and_then(|spec| EnvFilter::try_new(spec).ok())
+        .unwrap_or_else(|| EnvFilter::new(DEFAULT_FILTER))
+}
+
+fn level_name(level: LogLevel) -> &'static str {
+    match level {
+        LogLevel::Error => "error",
+        LogLevel::Warn => "warn",
+        LogLevel::Info => "info",
+        LogLevel::Debug => "debug",
+        LogLevel::Trace => "trace",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+
+    use crate::parser::ParsedDoc;
+    use crate::verification::extract_verification_spec;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn filter_prefers_flag_then_rust_log_then_default() {
+        assert_eq!(
+            filter(Some(LogLevel::Debug), Some("error")).to_string(),
+            "pave=debug,warn"
+        );
+        assert_eq!(filter(None, Some("info")).to_string(), "info");
+        assert_eq!(filter(None, Some("")).to_string(), "warn");
+        assert_eq!(filter(None, None).to_string(), "warn");
+    }
+
+    #[test]
+    fn debug_level_logs_extracted_commands_without_touching_output() {
+        let content =
+            "# Doc\n\n## Verification\n\n```bash\necho one\n```\n\n```bash\necho two\n```\n";
+        let doc = ParsedDoc::parse_content(PathBuf::from("docs/doc.md"), content).unwrap();
+        let quiet = extract_verification_spec(&doc).unwrap();
+
+        let logs = Buffer::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_env_filter(filter(Some(LogLevel::Debug), None))
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .finish();
+        let logged = tracing::subscriber::with_default(subscriber, || {
+            extract_verification_spec(&doc).unwrap()
+        });
+
+        let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        assert!(logs.contains("commands=2"), "{logs}");
+        assert!(logs.contains("docs/doc.md"), "{logs}");
+        assert_eq!(logged.items, quiet.items);
+    }
+}
@@ -1,30 +1,40 @@
 use anyhow::Result;
 use clap::Parser;
 use pave::cli::{
-    AdoptOutputFormat, Cli, Command, ConfigCommand, DocType, HooksCommand, MigrateOutputFormat,
-    PromptOutputFormat,
+    AdoptOutputFormat, ChangedOutputFormat, Cli, Command, ConfigCommand, DocType, HooksCommand,
+    MigrateOutputFormat, PromptFramingFormat, PromptOutputFormat,
 };
 use pave::commands::adopt::{self, AdoptArgs};
 use pave::commands::build;
 use pave::commands::changed::{self, ChangedArgs};
 use pave::commands::check::{self, CheckArgs};
+use pave::commands::completions;
 use pave::commands::config;
 use pave::commands::coverage::{self, CoverageArgs};
 use pave::commands::coverage_changed::{self, CoverageChangedArgs};
 use pave::commands::doctor::{self, DoctorArgs};
+use pave::commands::graph::{self, GraphArgs};
 use pave::commands::hooks;
 use pave::commands::index;
 use pave::commands::init;
 use pave::commands::lint::{self, LintArgs};
+use pave::commands::lsp::{self, LspArgs};
 use pave::commands::migrate::{self, MigrateArgs};
 use pave::commands::new::{self, NewArgs};
-use pave::commands::prompt::{OutputFormat, PromptOptions, generate_prompt};
+use pave::commands::prompt::{
+    OutputFormat, PathsPromptOptions, PromptFormat, PromptOptions, generate_paths_prompt,
+    generate_prompt,
+};
+use pave::commands::stats::{self, StatsArgs};
 use pave::commands::status::{self, StatusArgs};
 use pave::commands::verify::{self, VerifyArgs};
 use pave::templates::TemplateType;
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    let config_path = cli.config;
+    pave::color::init(cli.color);
+    pave::logging::init(cli.log_level);
 
     match cli.command {
         Command::Adopt {
@@ -48,6 +58,7 @@ fn main() -> Result<()> {
                 docs_root: args.docs_root,
                 skip_hooks: args.skip_hooks,
                 force: args.force,
+                preset: args.preset,
                 working_dir: None,
             })?;
         }
@@ -58,6 +69,18 @@ fn main() -> Result<()> {
             gradual,
             changed,
             base,
+            watch,
+            fix,
+            orphans,
+            stdin,
+            stdin_path,
+            no_ignore,
+            baseline,
+            write_baseline,
+            quiet,
+            rules,
+            ignore_rules,
+            max_section_lines,
         } => {
             check::execute(CheckArgs {
                 paths,
@@ -66,17 +89,39 @@ fn main() -> Result<()> {
                 gradual,
                 changed,
                 base,
+                watch,
+                fix,
+                orphans,
+                stdin,
+                stdin_path,
+                no_ignore,
+                baseline,
+                write_baseline,
+                quiet,
+                rules,
+                ignore_rules,
+                max_section_lines,
+                config: config_path,
             })?;
         }
         Command::New {
             doc_type,
             name,
             output,
+            interactive,
+            dry_run,
+            from,
+            list_templates,
         } => {
             new::execute(NewArgs {
-                doc_type: doc_type.into(),
+                doc_type: doc_type.map(Into::into),
                 name,
                 output,
+                interactive,
+                dry_run,
+                from,
+                list_templates,
+                config: config_path,
             })?;
         }
         Command::Prompt {
@@ -84,8 +129,38 @@ fn main() -> Result<()> {
             name,
             update,
             context,
+            paths,
             output,
+            format,
+            include_source,
+            max_source_bytes,
+            system,
         } => {
+            let output_format = match output {
+                PromptOutputFormat::Text => OutputFormat::Text,
+                PromptOutputFormat::Json => OutputFormat::Json,
+            };
+            let prompt_format = match format {
+                PromptFramingFormat::Markdown => PromptFormat::Markdown,
+                PromptFramingFormat::Xml => PromptFormat::Xml,
+                PromptFramingFormat::Json => PromptFormat::Json,
+            };
+            if let Some(glob) = paths {
+                let prompt = generate_paths_prompt(&PathsPromptOptions {
+                    glob,
+                    output_format,
+                    prompt_format,
+                    include_source,
+                    max_source_bytes,
+                    config: config_path,
+                    system,
+                })?;
+                print!("{}", prompt);
+                return Ok(());
+            }
+
+            // clap requires a doc type unless --paths is given
+            let doc_type = doc_type.expect("doc type is required without --paths");
             let options = PromptOptions {
                 doc_type: match doc_type {
                     DocType::Component => TemplateType::Component,
@@ -98,10 +173,10 @@ fn main() -> Result<()> {
                     .into_iter()
                     .map(|p| p.to_string_lossy().to_string())
                     .collect(),
-                output_format: match output {
-                    PromptOutputFormat::Text => OutputFormat::Text,
-                    PromptOutputFormat::Json => OutputFormat::Json,
-                },
+                output_format,
+                prompt_format,
+                config: config_path,
+                system,
             };
 
             let prompt = generate_prompt(&options)?;
@@ -115,7 +190,8 @@ fn main() -> Result<()> {
             } => {
                 // Use --verify flag if specified, otherwise check config
                 let run_verify = verify
-                    || pave::config::PaveConfig::load(pave::config::CONFIG_FILENAME)
+                    || pave::config::find_config(config_path.as_deref())
+                        .and_then(pave::config::PaveConfig::load)
                         .map(|c| c.hooks.run_verify)
                         .unwrap_or(false);
                 hooks::install(hook, force, run_verify)?;
@@ -125,50 +201,142 @@ fn main() -> Result<()> {
             }
         },
         Command::Config(cmd) => match cmd {
-            ConfigCommand::Get { key } => {
-                config::get(&key)?;
+            ConfigCommand::Get { key, json } => {
+                config::get(&key, json, config_path.as_deref())?;
             }
             ConfigCommand::Set { key, value } => {
-                config::set(&key, &value)?;
+                config::set(&key, &value, config_path.as_deref())?;
+            }
+            ConfigCommand::Unset { key } => {
+                config::unset(&key, config_path.as_deref())?;
             }
             ConfigCommand::List => {
-                config::list()?;
+                config::list(config_path.as_deref())?;
             }
             ConfigCommand::Path => {
-                config::path()?;
+                config::path(config_path.as_deref())?;
+            }
+            ConfigCommand::Edit => {
+                config::edit(config_path.as_deref())?;
+            }
+            ConfigCommand::Validate => {
+                config::validate(config_path.as_deref())?;
+            }
+            ConfigCommand::Export => {
+                config::export(config_path.as_deref())?;
+            }
+            ConfigCommand::Import { file, force } => {
+                config::import(&file, force, config_path.as_deref())?;
             }
         },
-        Command::Index { output, update } => {
-            index::run(&output, update)?;
+        Command::Index {
+            output,
+            update,
+            check,
+        } => {
+            index::run(output.as_deref(), update, check, config_path.as_deref())?;
         }
         Command::Changed {
             base,
             format,
+            json,
             strict,
         } => {
             changed::execute(ChangedArgs {
                 base,
-                format,
+                format: if json {
+                    ChangedOutputFormat::Json
+                } else {
+                    format
+                },
                 strict,
+                config: config_path,
             })?;
         }
         Command::Verify {
             paths,
             format,
             report,
+            append,
+            summary_json,
+            out_dir,
             timeout,
             keep_going,
+            bail_after,
+            jobs,
+            no_cache,
+            since,
+            changed,
+            base,
+            staged,
+            verbose,
+            dedupe,
+            list,
+            only,
+            only_failed,
+            list_docs,
+            list_failing_docs,
+            shell,
+            capture,
+            on_missing_shell,
+            isolated,
+            print_command,
+            show_env,
+            strict_output,
+            no_strict_output,
+            allow_timeout,
+            tags,
+            exclude_tags,
+            before,
+            after,
         } => {
             verify::execute(VerifyArgs {
                 paths,
                 format,
                 report,
+                append,
+                summary_json,
+                out_dir,
                 timeout,
                 keep_going,
+                bail_after: bail_after.map(|n| n as usize),
+                jobs: jobs as usize,
+                no_cache,
+                since,
+                changed,
+                base,
+                staged,
+                verbose,
+                dedupe,
+                list,
+                only,
+                only_failed,
+                list_docs,
+                list_failing_docs,
+                shell,
+                capture,
+                on_missing_shell,
+                isolated,
+                print_command,
+                show_env,
+                strict_output: match (strict_output, no_strict_output) {
+                    (true, _) => Some(true),
+                    (_, true) => Some(false),
+                    _ => None,
+                },
+                allow_timeout,
+                tags,
+                exclude_tags,
+                before,
+                after,
+                config: config_path,
             })?;
         }
         Command::Build { output } => {
-            build::execute(build::BuildArgs { output })?;
+            build::execute(build::BuildArgs {
+                output,
+                config: config_path,
+            })?;
         }
         Command::Coverage {
             path,
@@ -176,6 +344,11 @@ fn main() -> Result<()> {
             threshold,
             include,
             exclude,
+            html,
+            no_ignore,
+            by_extension,
+            threshold_ext,
+            min_docs_per_file,
         } => {
             coverage::execute(CoverageArgs {
                 path,
@@ -183,6 +356,12 @@ fn main() -> Result<()> {
                 threshold,
                 include,
                 exclude,
+                html,
+                no_ignore,
+                by_extension,
+                threshold_ext,
+                min_docs_per_file,
+                config: config_path,
             })?;
         }
         Command::CoverageChanged {
@@ -196,6 +375,7 @@ fn main() -> Result<()> {
                 format,
                 include,
                 exclude,
+                config: config_path,
             })?;
         }
         Command::Lint {
@@ -211,10 +391,28 @@ fn main() -> Result<()> {
                 fix,
                 rules,
                 external_links,
+                config: config_path,
             })?;
         }
         Command::Doctor { paths, format } => {
-            doctor::execute(DoctorArgs { paths, format })?;
+            doctor::execute(DoctorArgs {
+                paths,
+                format,
+                config: config_path,
+            })?;
+        }
+        Command::Graph { format, dirs } => {
+            graph::execute(GraphArgs {
+                format,
+                dirs,
+                config: config_path,
+            })?;
+        }
+        Command::Stats { format } => {
+            stats::execute(StatsArgs {
+                format,
+                config: config_path,
+            })?;
         }
         Command::Status {
             paths,
@@ -227,6 +425,7 @@ fn main() -> Result<()> {
                 format,
                 changed,
                 base,
+                config: config_path,
             })?;
         }
         Command::Migrate {
@@ -247,8 +446,17 @@ fn main() -> Result<()> {
                 sections,
                 interactive,
                 backup,
+                config: config_path,
             })?;
         }
+        Command::Lsp => {
+            lsp::execute(LspArgs {
+                config: config_path,
+            })?;
+        }
+        Command::Completions { shell } => {
+            completions::execute(shell);
+        }
     }
 
     Ok(())
@@ -2,11 +2,63 @@
 //!
 //! This module parses markdown documents and extracts structured information
 //! about their sections, code blocks, and commands for validation purposes.
-
-use anyhow::{Context, Result};
+//!
+//! It is part of pave's public API for tools (such as editor extensions) that
+//! want to read docs the same way `pave` does. Use [`parse_file`] or
+//! [`ParsedDoc::parse_content`]. Parsing is lenient: malformed frontmatter
+//! is recorded in [`ParsedDoc::frontmatter_issue`] rather than failing, and
+//! [`parse_file`] returns [`Error::Parse`] only for a file that isn't UTF-8
+//! text. Sections, subsections, and code blocks carry 1-indexed line
+//! numbers, and code blocks record whether `pave verify` would run them.
+//!
+//! ```
+//! use std::path::PathBuf;
+//! use pave::parser::ParsedDoc;
+//!
+//! let content = "# Auth\n\n## Purpose\nLogin.\n\n## Verification\n```bash\ncargo test auth\n```\n";
+//! let doc = ParsedDoc::parse_content(PathBuf::from("docs/auth.md"), content)?;
+//!
+//! let verification = doc.get_section("Verification").expect("has Verification");
+//! assert_eq!(verification.start_line, 6);
+//! let block = &verification.code_blocks[0];
+//! assert!(block.is_executable);
+//! assert_eq!(block.start_line, 7);
+//! assert_eq!(block.content, "cargo test auth");
+//! # Ok::<(), pave::Error>(())
+//! ```
+
+use crate::error::{Error, Result};
 use serde::Deserialize;
 use std::path::{Path, PathBuf};
 
+/// Fence languages treated as executable when no list is configured.
+pub const DEFAULT_EXECUTABLE_LANGUAGES: &[&str] = &["bash", "sh", "shell"];
+
+/// Options controlling how documents are parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseOptions {
+    /// Fence languages whose blocks are executable, compared
+    /// case-insensitively (default: [`DEFAULT_EXECUTABLE_LANGUAGES`]). Blocks
+    /// with shell prompts or a `<!-- pave:run -->` marker are executable
+    /// regardless.
+    pub executable_languages: Vec<String>,
+    /// Treat only fences tagged `pave` as executable, ignoring languages,
+    /// prompts, and `<!-- pave:run -->` markers (default: false).
+    pub require_pave_tag: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            executable_languages: DEFAULT_EXECUTABLE_LANGUAGES
+                .iter()
+                .map(|lang| lang.to_string())
+                .collect(),
+            require_pave_tag: false,
+        }
+    }
+}
+
 /// Pave-specific frontmatter configuration.
 #[derive(Debug, Clone, Deserialize, PartialEq, Default)]
 pub struct PaveFrontmatter {
@@ -24,21 +76,70 @@ struct FrontmatterWrapper {
     /// Pave-specific configuration.
     #[serde(default)]
     pave: Option<PaveFrontmatter>,
+    /// Document lifecycle status, such as `draft`.
+    #[serde(default)]
+    status: Option<serde_yaml::Value>,
+}
+
+/// A frontmatter block that isn't valid YAML, found while parsing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontmatterIssue {
+    /// 1-indexed line the YAML error is reported at.
+    pub line: usize,
+    /// The YAML parser's description of the problem.
+    pub message: String,
+}
+
+/// A malformed code fence found while parsing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FenceIssue {
+    /// 1-indexed line the problem is reported at.
+    pub line: usize,
+    /// What is wrong with the fence.
+    pub kind: FenceIssueKind,
+}
+
+/// The kinds of [`FenceIssue`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum FenceIssueKind {
+    /// A fence opened here is never closed, so it runs to the end of the doc.
+    Unclosed,
+    /// A `##` heading sits inside a block tagged with a code language, which
+    /// usually means the block's closing fence is missing or misplaced.
+    HeadingInside {
+        /// The heading text.
+        heading: String,
+    },
+    /// The opening fence's info string contains a backtick, as in
+    /// `` ```bash``` ``, which opens a block instead of forming an empty one.
+    BacktickInInfo,
 }
 
 /// A parsed PAVED document with extracted structure.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ParsedDoc {
     /// Path to the source file.
     pub path: PathBuf,
     /// H1 heading (document title), if present.
     pub title: Option<String>,
+    /// 1-indexed line of the H1 heading, if present.
+    pub title_line: Option<usize>,
     /// Extracted H2 sections.
     pub sections: Vec<Section>,
     /// Total number of lines in the document.
     pub line_count: usize,
     /// Pave-specific frontmatter configuration.
     pub frontmatter: Option<PaveFrontmatter>,
+    /// Top-level frontmatter `status` (e.g. `draft`), if it is a string.
+    pub status: Option<String>,
+    /// Malformed frontmatter, if any. The doc is then parsed as if it had
+    /// no frontmatter.
+    pub frontmatter_issue: Option<FrontmatterIssue>,
+    /// Malformed code fences, in document order.
+    pub fence_issues: Vec<FenceIssue>,
+    /// Check rule ids the doc suppresses with a `pave: ignore-rule`
+    /// directive.
+    pub ignored_rules: Vec<String>,
 }
 
 /// Strategy for matching expected output.
@@ -78,30 +179,107 @@ pub struct CodeBlock {
     pub working_dir: Option<String>,
     /// Environment variables to set for this code block.
     pub env_vars: Vec<(String, String)>,
+    /// Whether the fence is marked `skip` (e.g. ```` ```bash skip ````), so
+    /// `pave verify` leaves the block out.
+    pub skip: bool,
+    /// Whether the fence is tagged `pave` (```` ```pave ```` or
+    /// ```` ```bash pave ````), which always makes the block executable.
+    pub pave_tagged: bool,
 }
 
 /// A section of a PAVED document (H2 heading and its content).
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Section {
     /// Section name (the H2 heading text without "## ").
     pub name: String,
     /// Line number where the section starts (1-indexed).
     pub start_line: usize,
+    /// Last line of the section, just before the next H2 heading or at the
+    /// end of the document (1-indexed, inclusive).
+    pub end_line: usize,
+    /// Line number of the first line of `content`: just after the heading,
+    /// or after its underline for setext headings (1-indexed).
+    pub content_start_line: usize,
     /// Content of the section (excluding the heading itself).
     pub content: String,
     /// Whether the section contains code blocks (triple backticks).
     pub has_code_blocks: bool,
     /// Whether the section contains executable commands.
     pub has_commands: bool,
-    /// Extracted code blocks from this section.
+    /// Extracted code blocks from this section, including those in subsections.
+    pub code_blocks: Vec<CodeBlock>,
+    /// H3 subsections nested within this section.
+    pub subsections: Vec<Subsection>,
+}
+
+/// An H3 subsection nested within a section.
+#[derive(Debug, Clone)]
+pub struct Subsection {
+    /// Subsection name (the H3 heading text without "### ").
+    pub name: String,
+    /// Line number where the subsection starts (1-indexed).
+    pub start_line: usize,
+    /// Content of the subsection (excluding the heading itself).
+    pub content: String,
+    /// Extracted code blocks from this subsection.
     pub code_blocks: Vec<CodeBlock>,
 }
 
+/// A heading found while scanning a document.
+struct Heading {
+    /// 0-indexed line of the heading text.
+    idx: usize,
+    /// Heading level (1 for `#` or `=` underlines, 2 for `##` or `-` underlines).
+    level: usize,
+    /// Heading text without markers.
+    text: String,
+    /// Lines the heading occupies (2 for setext headings).
+    line_span: usize,
+}
+
+/// Parse the markdown file at `path`.
+///
+/// Equivalent to [`ParsedDoc::parse`]. Returns [`Error::Io`] if the file
+/// can't be read and [`Error::Parse`] if it isn't valid UTF-8.
+pub fn parse_file(path: impl AsRef<Path>) -> Result<ParsedDoc> {
+    ParsedDoc::parse(path.as_ref())
+}
+
 impl Section {
+    /// Number of lines the section spans, heading included.
+    pub fn line_count(&self) -> usize {
+        self.end_line + 1 - self.start_line
+    }
+
+    /// The entries of a `## Paths` section with their line numbers
+    /// (1-indexed): each `-` or `*` bullet, with surrounding backticks
+    /// removed. Bullets inside code blocks are not entries.
+    pub fn path_entries(&self) -> Vec<(usize, String)> {
+        let mut entries = Vec::new();
+        let mut tracker = CodeBlockTracker::new();
+        for (idx, line) in self.content.lines().enumerate() {
+            let trimmed = line.trim();
+            if tracker.process_line(trimmed) || tracker.in_code_block() {
+                continue;
+            }
+            if let Some(entry) = trimmed
+                .strip_prefix("- ")
+                .or_else(|| trimmed.strip_prefix("* "))
+            {
+                let entry = entry.trim().trim_matches('`');
+                if !entry.is_empty() {
+                    entries.push((self.content_start_line + idx, entry.to_string()));
+                }
+            }
+        }
+        entries
+    }
+
     /// Returns only the code blocks that are marked as executable.
     ///
-    /// Executable blocks are those with shell language tags (bash, sh, shell, zsh),
-    /// content with shell prompts ($ or >), or preceded by a `<!-- pave:run -->` marker.
+    /// Executable blocks are those with an executable language tag (see
+    /// [`ParseOptions`]), content with shell prompts ($ or >), or preceded by a
+    /// `<!-- pave:run -->` marker.
     pub fn executable_commands(&self) -> Vec<&CodeBlock> {
         self.code_blocks
             .iter()
@@ -110,118 +288,370 @@ impl Section {
     }
 }
 
+/// Whether the section heading `heading` names the section `name`.
+///
+/// Case is ignored, as is trailing punctuation, so `## verification:` and
+/// `## VERIFICATION` both name the Verification section.
+pub fn section_name_matches(heading: &str, name: &str) -> bool {
+    normalize_section_name(heading).eq_ignore_ascii_case(normalize_section_name(name))
+}
+
+fn normalize_section_name(name: &str) -> &str {
+    name.trim()
+        .trim_end_matches([':', '.', '!', '?', ';', ','])
+        .trim_end()
+}
+
 impl ParsedDoc {
     /// Parse a markdown file into a structured document.
     pub fn parse(path: &Path) -> Result<Self> {
-        let content = std::fs::read_to_string(path)
-            .with_context(|| format!("Failed to read file: {}", path.display()))?;
-        Self::parse_content(path.to_path_buf(), &content)
+        Self::parse_with(path, &ParseOptions::default())
+    }
+
+    /// Parse a markdown file with the given options.
+    pub fn parse_with(path: &Path, options: &ParseOptions) -> Result<Self> {
+        let bytes = std::fs::read(path)
+            .map_err(|e| Error::io(format!("Failed to read file: {}", path.display()), e))?;
+        let content = String::from_utf8(bytes).map_err(|e| {
+            let valid = &e.as_bytes()[..e.utf8_error().valid_up_to()];
+            Error::Parse {
+                file: path.to_path_buf(),
+                line: valid.iter().filter(|&&b| b == b'\n').count() + 1,
+                message: "invalid UTF-8".to_string(),
+            }
+        })?;
+        Self::parse_content_with(path.to_path_buf(), &content, options)
     }
 
     /// Parse markdown content into a structured document.
     pub fn parse_content(path: PathBuf, content: &str) -> Result<Self> {
+        Self::parse_content_with(path, content, &ParseOptions::default())
+    }
+
+    /// Parse markdown content with the given options.
+    pub fn parse_content_with(
+        path: PathBuf,
+        content: &str,
+        options: &ParseOptions,
+    ) -> Result<Self> {
         let lines: Vec<&str> = content.lines().collect();
         let line_count = lines.len();
 
-        let frontmatter = Self::extract_frontmatter(content);
-        let title = Self::extract_title(&lines);
-        let sections = Self::extract_sections(&lines);
+        let (wrapper, frontmatter_issue) = match Self::extract_frontmatter(content) {
+            Ok(wrapper) => (wrapper, None),
+            Err(issue) => (FrontmatterWrapper::default(), Some(issue)),
+        };
+        let status = wrapper
+            .status
+            .as_ref()
+            .and_then(|s| s.as_str())
+            .map(|s| s.trim().to_string());
+        let (title, title_line) = Self::extract_title(&lines).unzip();
+        let sections = Self::extract_sections(&lines, options);
+        let fence_issues = Self::scan_fences(&lines);
+        let ignored_rules = Self::scan_ignored_rules(&lines);
 
         Ok(ParsedDoc {
             path,
             title,
+            title_line,
             sections,
             line_count,
-            frontmatter,
+            frontmatter: wrapper.pave,
+            status,
+            frontmatter_issue,
+            fence_issues,
+            ignored_rules,
         })
     }
 
-    /// Check if the document has a section with the given name (case-insensitive).
+    /// Whether the frontmatter marks this document as a draft
+    /// (`status: draft`, case-insensitive).
+    pub fn is_draft(&self) -> bool {
+        self.status
+            .as_deref()
+            .is_some_and(|s| s.eq_ignore_ascii_case("draft"))
+    }
+
+    /// Check if the document has a section with the given name, matched as
+    /// by [`section_name_matches`].
     pub fn has_section(&self, name: &str) -> bool {
-        self.sections
-            .iter()
-            .any(|s| s.name.eq_ignore_ascii_case(name))
+        self.get_section(name).is_some()
     }
 
-    /// Get a section by name (case-insensitive).
+    /// Get a section by name, matched as by [`section_name_matches`].
     pub fn get_section(&self, name: &str) -> Option<&Section> {
         self.sections
             .iter()
-            .find(|s| s.name.eq_ignore_ascii_case(name))
+            .find(|s| section_name_matches(&s.name, name))
+    }
+
+    /// The patterns in the doc's `## Paths` section (see
+    /// [`Section::path_entries`]), ready for [`crate::paths::PathsMatcher`].
+    /// Empty when the doc has no such section.
+    pub fn paths_patterns(&self) -> Vec<String> {
+        self.get_section("Paths")
+            .map(|section| {
+                section
+                    .path_entries()
+                    .into_iter()
+                    .map(|(_, entry)| entry)
+                    .collect()
+            })
+            .unwrap_or_default()
     }
 
-    /// Extract the H1 title from the document.
-    fn extract_title(lines: &[&str]) -> Option<String> {
-        for line in lines {
-            let trimmed = line.trim();
-            if let Some(title) = trimmed.strip_prefix("# ") {
-                // Ensure it's not an H2 (## ) heading
-                if !title.starts_with("# ") {
-                    return Some(title.trim().to_string());
-                }
-            }
-        }
-        None
+    /// Get the H3 subsections of a section by name (case-insensitive).
+    ///
+    /// Returns an empty slice if the section is missing or has no subsections.
+    pub fn get_subsections(&self, name: &str) -> &[Subsection] {
+        self.get_section(name)
+            .map(|s| s.subsections.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Extract the H1 title and its 1-indexed line from the document.
+    ///
+    /// Recognizes both ATX (`# Title`) and setext (`Title` underlined with `=`)
+    /// headings.
+    fn extract_title(lines: &[&str]) -> Option<(String, usize)> {
+        Self::scan_headings(lines)
+            .into_iter()
+            .find(|h| h.level == 1)
+            .map(|h| (h.text, h.idx + 1))
     }
 
     /// Extract all H2 sections from the document.
-    fn extract_sections(lines: &[&str]) -> Vec<Section> {
+    ///
+    /// Recognizes both ATX (`## Section`) and setext (`Section` underlined
+    /// with `-`) headings.
+    fn extract_sections(lines: &[&str], options: &ParseOptions) -> Vec<Section> {
         let mut sections = Vec::new();
-        let mut section_starts: Vec<(usize, String)> = Vec::new();
+        let headings = Self::scan_headings(lines);
+        let section_starts: Vec<&Heading> = headings.iter().filter(|h| h.level == 2).collect();
+
+        // Extract content for each section
+        for (i, heading) in section_starts.iter().enumerate() {
+            let start_idx = heading.idx;
+            let end_idx = if i + 1 < section_starts.len() {
+                section_starts[i + 1].idx
+            } else {
+                lines.len()
+            };
+
+            // Content starts after the heading (and its underline, for setext)
+            let content_start = start_idx + heading.line_span;
+            let content_lines = &lines[content_start..end_idx];
+            let content = content_lines.join("\n");
+
+            let has_code_blocks = Self::detect_code_blocks(content_lines);
+            let has_commands = Self::detect_commands(content_lines);
+            // Base line for content is content_start + 1 (1-indexed)
+            let code_blocks = Self::extract_code_blocks(content_lines, content_start + 1, options);
+            let subsections =
+                Self::extract_subsections(lines, &headings, start_idx, end_idx, options);
+
+            sections.push(Section {
+                name: heading.text.clone(),
+                start_line: start_idx + 1, // Convert to 1-indexed
+                end_line: end_idx,
+                content_start_line: content_start + 1,
+                content,
+                has_code_blocks,
+                has_commands,
+                code_blocks,
+                subsections,
+            });
+        }
+
+        sections
+    }
+
+    /// Extract the H3 subsections between a section heading and its end.
+    ///
+    /// Each subsection runs until the next H3 heading or the end of the
+    /// enclosing section. Deeper headings stay part of their subsection.
+    fn extract_subsections(
+        lines: &[&str],
+        headings: &[Heading],
+        section_idx: usize,
+        section_end: usize,
+        options: &ParseOptions,
+    ) -> Vec<Subsection> {
+        let starts: Vec<&Heading> = headings
+            .iter()
+            .filter(|h| h.level == 3 && h.idx > section_idx && h.idx < section_end)
+            .collect();
+
+        starts
+            .iter()
+            .enumerate()
+            .map(|(i, heading)| {
+                let end_idx = starts.get(i + 1).map(|h| h.idx).unwrap_or(section_end);
+                let content_start = heading.idx + heading.line_span;
+                let content_lines = &lines[content_start..end_idx];
+
+                Subsection {
+                    name: heading.text.clone(),
+                    start_line: heading.idx + 1,
+                    content: content_lines.join("\n"),
+                    code_blocks: Self::extract_code_blocks(
+                        content_lines,
+                        content_start + 1,
+                        options,
+                    ),
+                }
+            })
+            .collect()
+    }
+
+    /// Find all headings in the document, skipping frontmatter and code blocks.
+    fn scan_headings(lines: &[&str]) -> Vec<Heading> {
+        let mut headings = Vec::new();
         let mut in_code_block = false;
+        let mut idx = Self::frontmatter_end(lines);
 
-        // Find all H2 headings and their positions, skipping content inside code blocks
-        for (idx, line) in lines.iter().enumerate() {
-            let trimmed = line.trim();
+        while idx < lines.len() {
+            let trimmed = lines[idx].trim();
 
             // Track code block state
             if trimmed.starts_with("```") {
                 in_code_block = !in_code_block;
+                idx += 1;
                 continue;
             }
 
             // Skip headings inside code blocks
             if in_code_block {
+                idx += 1;
                 continue;
             }
 
-            if let Some(heading) = trimmed.strip_prefix("## ") {
-                // Ensure it's not an H3 (### ) heading
-                if !heading.starts_with("# ") {
-                    let name = heading.trim().to_string();
-                    section_starts.push((idx, name));
-                }
+            if let Some((level, text)) = Self::parse_atx_heading(trimmed) {
+                headings.push(Heading {
+                    idx,
+                    level,
+                    text,
+                    line_span: 1,
+                });
+                idx += 1;
+                continue;
             }
+
+            let underline = lines.get(idx + 1).map(|l| l.trim()).unwrap_or("");
+            if let Some(level) = Self::setext_level(trimmed, underline) {
+                headings.push(Heading {
+                    idx,
+                    level,
+                    text: trimmed.to_string(),
+                    line_span: 2,
+                });
+                idx += 2;
+                continue;
+            }
+
+            idx += 1;
         }
 
-        // Extract content for each section
-        for (i, (start_idx, name)) in section_starts.iter().enumerate() {
-            let end_idx = if i + 1 < section_starts.len() {
-                section_starts[i + 1].0
-            } else {
-                lines.len()
-            };
+        headings
+    }
 
-            // Content starts after the heading line
-            let content_lines = &lines[start_idx + 1..end_idx];
-            let content = content_lines.join("\n");
+    /// Find unclosed fences and blocks that look like they swallowed
+    /// document structure.
+    fn scan_fences(lines: &[&str]) -> Vec<FenceIssue> {
+        let mut issues = Vec::new();
+        let mut tracker = CodeBlockTracker::new();
+        // Opening line (0-indexed) and whether the block may hold prose
+        let mut open: Option<(usize, bool)> = None;
+        let mut flagged_heading = false;
+
+        for (idx, line) in lines.iter().enumerate().skip(Self::frontmatter_end(lines)) {
+            if tracker.process_line(line) {
+                if tracker.in_code_block() {
+                    let info = line.trim_start().trim_start_matches('`').trim();
+                    let lang = info.split_whitespace().next().unwrap_or("");
+                    let prose = matches!(lang, "" | "markdown" | "md" | "text" | "txt");
+                    open = Some((idx, prose));
+                    flagged_heading = false;
+                    if info.contains('`') {
+                        issues.push(FenceIssue {
+                            line: idx + 1,
+                            kind: FenceIssueKind::BacktickInInfo,
+                        });
+                    }
+                } else {
+                    open = None;
+                }
+                continue;
+            }
 
-            let has_code_blocks = Self::detect_code_blocks(content_lines);
-            let has_commands = Self::detect_commands(content_lines);
-            // Base line for content is start_idx + 2 (1-indexed: line after heading)
-            let code_blocks = Self::extract_code_blocks(content_lines, start_idx + 2);
+            if let Some((_, prose)) = open
+                && !prose
+                && !flagged_heading
+                && let Some((2, heading)) = Self::parse_atx_heading(line.trim())
+            {
+                flagged_heading = true;
+                issues.push(FenceIssue {
+                    line: idx + 1,
+                    kind: FenceIssueKind::HeadingInside { heading },
+                });
+            }
+        }
 
-            sections.push(Section {
-                name: name.clone(),
-                start_line: start_idx + 1, // Convert to 1-indexed
-                content,
-                has_code_blocks,
-                has_commands,
-                code_blocks,
+        if let Some((idx, _)) = open {
+            issues.push(FenceIssue {
+                line: idx + 1,
+                kind: FenceIssueKind::Unclosed,
             });
         }
+        issues.sort_by_key(|issue| issue.line);
+        issues
+    }
 
-        sections
+    /// Parse an ATX heading (`#` through `######` followed by a space).
+    fn parse_atx_heading(trimmed: &str) -> Option<(usize, String)> {
+        let level = trimmed.chars().take_while(|&c| c == '#').count();
+        if level == 0 || level > 6 {
+            return None;
+        }
+        let text = trimmed[level..].strip_prefix(' ')?;
+        Some((level, text.trim().to_string()))
+    }
+
+    /// Return the setext heading level if `underline` underlines `text`.
+    ///
+    /// `=` underlines make level-1 headings and `-` underlines make level-2
+    /// headings. The text line must be a plain paragraph line, so list items,
+    /// blockquotes, tables, and HTML comments followed by `---` are left alone.
+    fn setext_level(text: &str, underline: &str) -> Option<usize> {
+        const NON_PARAGRAPH_PREFIXES: &[&str] =
+            &["#", ">", "|", "<", "- ", "* ", "+ ", "```", "---", "==="];
+        if text.is_empty() || NON_PARAGRAPH_PREFIXES.iter().any(|p| text.starts_with(p)) {
+            return None;
+        }
+
+        if !underline.is_empty() && underline.chars().all(|c| c == '=') {
+            Some(1)
+        } else if !underline.is_empty() && underline.chars().all(|c| c == '-') {
+            Some(2)
+        } else {
+            None
+        }
+    }
+
+    /// Index of the first line after a leading frontmatter block (0 if none).
+    pub(crate) fn frontmatter_end(lines: &[&str]) -> usize {
+        let Some(open) = lines.iter().position(|l| !l.trim().is_empty()) else {
+            return 0;
+        };
+        if lines[open].trim() != "---" {
+            return 0;
+        }
+        lines[open + 1..]
+            .iter()
+            .position(|l| l.trim() == "---")
+            .map(|close| open + close + 2)
+            .unwrap_or(0)
     }
 
     /// Detect if content contains code blocks (triple backticks).
@@ -256,15 +686,22 @@ impl ParsedDoc {
     /// - Language tag (if present after opening ```)
     /// - Content between the fences
     /// - Line number of the opening fence
-    /// - Whether the block is executable (shell language, prompts, or pave:run marker)
+    /// - Whether the block is executable (`pave` tag, shell language, prompts,
+    ///   or pave:run marker)
     /// - Expected output (inline or from explicit blocks)
     ///
     /// The `base_line` parameter is the 1-indexed line number of the first line in `lines`.
-    fn extract_code_blocks(lines: &[&str], base_line: usize) -> Vec<CodeBlock> {
+    fn extract_code_blocks(
+        lines: &[&str],
+        base_line: usize,
+        options: &ParseOptions,
+    ) -> Vec<CodeBlock> {
         let mut code_blocks: Vec<CodeBlock> = Vec::new();
         let mut in_code_block = false;
         let mut current_block_start: usize = 0;
         let mut current_language: Option<String> = None;
+        let mut current_skip = false;
+        let mut current_pave_tag = false;
         let mut current_content: Vec<&str> = Vec::new();
         let mut opening_fence_len: usize = 0;
         let mut has_run_marker = false;
@@ -298,6 +735,8 @@ impl ParsedDoc {
                     opening_fence_len = fence_content.0;
                     current_block_start = base_line + idx;
                     current_language = fence_content.1;
+                    current_skip = fence_content.2;
+                    current_pave_tag = fence_content.3;
                     current_content.clear();
                 }
             } else {
@@ -322,8 +761,13 @@ impl ParsedDoc {
                         pending_working_dir = None;
                         pending_env_vars.clear();
                     } else {
-                        let is_executable =
-                            Self::is_block_executable(&current_language, &content, has_run_marker);
+                        let is_executable = Self::is_block_executable(
+                            &current_language,
+                            &content,
+                            has_run_marker || current_pave_tag,
+                            current_pave_tag,
+                            options,
+                        );
 
                         // Extract inline expected output from shell-style blocks
                         let (command_content, inline_output) =
@@ -337,6 +781,8 @@ impl ParsedDoc {
                             expected_output: inline_output,
                             working_dir: pending_working_dir.take(),
                             env_vars: std::mem::take(&mut pending_env_vars),
+                            skip: current_skip,
+                            pave_tagged: current_pave_tag,
                         });
                     }
                     in_code_block = false;
@@ -351,8 +797,13 @@ impl ParsedDoc {
         // Handle unclosed code block at end of section (treat as if closed)
         if in_code_block && !current_content.is_empty() {
             let content = current_content.join("\n");
-            let is_executable =
-                Self::is_block_executable(&current_language, &content, has_run_marker);
+            let is_executable = Self::is_block_executable(
+                &current_language,
+                &content,
+                has_run_marker || current_pave_tag,
+                current_pave_tag,
+                options,
+            );
             let (command_content, inline_output) = Self::extract_inline_expected_output(&content);
             code_blocks.push(CodeBlock {
                 language: current_language,
@@ -362,15 +813,19 @@ impl ParsedDoc {
                 expected_output: inline_output,
                 working_dir: pending_working_dir,
                 env_vars: pending_env_vars,
+                skip: current_skip,
+                pave_tagged: current_pave_tag,
             });
         }
 
         code_blocks
     }
 
-    /// Parse an opening fence line, returning (fence_length, optional_language).
+    /// Parse an opening fence line, returning (fence_length, optional_language,
+    /// skip, pave_tagged), where `skip` is set by a `skip` word after the
+    /// language and `pave_tagged` by a `pave` language or word.
     /// Returns None if not an opening fence.
-    fn parse_opening_fence(trimmed: &str) -> Option<(usize, Option<String>)> {
+    fn parse_opening_fence(trimmed: &str) -> Option<(usize, Option<String>, bool, bool)> {
         if !trimmed.starts_with("```") {
             return None;
         }
@@ -382,12 +837,13 @@ impl ParsedDoc {
         }
 
         // Extract language tag (first word after the backticks, if any)
-        let language = trimmed[fence_len..]
-            .split_whitespace()
-            .next()
-            .map(|s| s.to_string());
+        let mut info = trimmed[fence_len..].split_whitespace();
+        let language = info.next().map(|s| s.to_string());
+        let words: Vec<&str> = info.collect();
+        let skip = words.contains(&"skip");
+        let pave_tagged = language.as_deref() == Some("pave") || words.contains(&"pave");
 
-        Some((fence_len, language))
+        Some((fence_len, language, skip, pave_tagged))
     }
 
     /// Check if a line is a closing fence (at least `min_len` backticks, nothing else).
@@ -404,21 +860,36 @@ impl ParsedDoc {
     /// Determine if a code block is executable based on language, content, and markers.
     ///
     /// A code block is considered executable if:
-    /// 1. Language tag is a shell language: `bash`, `sh`, `shell`, `zsh`
+    /// 1. Language tag is in `options.executable_languages`
     /// 2. Content contains lines starting with `$ ` or `> ` (shell prompts)
     /// 3. The block is preceded by a `<!-- pave:run -->` HTML comment marker
-    fn is_block_executable(language: &Option<String>, content: &str, has_run_marker: bool) -> bool {
+    ///    or its fence is tagged `pave`
+    ///
+    /// With `options.require_pave_tag`, only the `pave` tag counts.
+    fn is_block_executable(
+        language: &Option<String>,
+        content: &str,
+        has_run_marker: bool,
+        pave_tagged: bool,
+        options: &ParseOptions,
+    ) -> bool {
+        if options.require_pave_tag {
+            return pave_tagged;
+        }
+
         // Check explicit marker first
         if has_run_marker {
             return true;
         }
 
-        // Check shell language tags
-        if let Some(lang) = language {
-            let lang_lower = lang.to_lowercase();
-            if matches!(lang_lower.as_str(), "bash" | "sh" | "shell" | "zsh") {
-                return true;
-            }
+        // Check executable language tags
+        if let Some(lang) = language
+            && options
+                .executable_languages
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(lang))
+        {
+            return true;
         }
 
         // Check for shell prompt prefixes in content
@@ -470,6 +941,41 @@ impl ParsedDoc {
         None
     }
 
+    /// Collect the rule ids named by `ignore-rule` directives at the top of
+    /// the document.
+    ///
+    /// Directives are `# pave: ignore-rule max-lines` comments in the YAML
+    /// frontmatter or `<!-- pave: ignore-rule max-lines -->` lines before
+    /// the first H2. Ids are separated by commas or spaces.
+    fn scan_ignored_rules(lines: &[&str]) -> Vec<String> {
+        let mut rules: Vec<String> = Vec::new();
+        let mut in_frontmatter = lines.first().is_some_and(|line| line.trim() == "---");
+        for (index, line) in lines.iter().enumerate() {
+            let trimmed = line.trim();
+            let directive = if in_frontmatter {
+                if index > 0 && trimmed == "---" {
+                    in_frontmatter = false;
+                }
+                trimmed.strip_prefix('#')
+            } else if trimmed.starts_with("## ") {
+                break;
+            } else {
+                trimmed
+                    .strip_prefix("<!--")
+                    .and_then(|rest| rest.strip_suffix("-->"))
+            };
+            let ids = directive
+                .and_then(|text| text.trim().strip_prefix("pave:"))
+                .and_then(|text| text.trim().strip_prefix("ignore-rule "));
+            for id in ids.into_iter().flat_map(|ids| ids.split([',', ' '])) {
+                if !id.is_empty() && !rules.iter().any(|rule| rule == id) {
+                    rules.push(id.to_string());
+                }
+            }
+        }
+        rules
+    }
+
     /// Parse a pave:working_dir marker and return the directory path.
     ///
     /// Supports:
@@ -601,21 +1107,43 @@ impl ParsedDoc {
         (command_content, expected_output)
     }
 
-    /// Extract pave frontmatter from document content.
+    /// Extract frontmatter from document content.
     ///
     /// Looks for YAML frontmatter delimited by `---` at the start of the document.
-    /// Returns the pave-specific configuration if present.
-    fn extract_frontmatter(content: &str) -> Option<PaveFrontmatter> {
+    /// Returns the pave-specific configuration and top-level `status`, both
+    /// empty when there is no frontmatter.
+    ///
+    /// Returns where and why the YAML failed to parse if the frontmatter
+    /// block is malformed.
+    fn extract_frontmatter(
+        content: &str,
+    ) -> std::result::Result<FrontmatterWrapper, FrontmatterIssue> {
         let trimmed = content.trim_start();
-        let after_first = trimmed.strip_prefix("---")?;
+        let Some(after_first) = trimmed.strip_prefix("---") else {
+            return Ok(FrontmatterWrapper::default());
+        };
 
         // Find the closing ---
-        let close_pos = after_first.find("\n---")?;
+        let Some(close_pos) = after_first.find("\n---") else {
+            return Ok(FrontmatterWrapper::default());
+        };
         let yaml_content = &after_first[..close_pos];
 
         // Parse the YAML and extract pave section
-        let wrapper: FrontmatterWrapper = serde_yaml::from_str(yaml_content).ok()?;
-        wrapper.pave
+        match serde_yaml::from_str::<FrontmatterWrapper>(yaml_content) {
+            Ok(wrapper) => Ok(wrapper),
+            Err(e) => {
+                // YAML line 1 is the opening `---` line itself
+                let opening_line = content[..content.len() - trimmed.len()]
+                    .matches('\n')
+                    .count();
+                let yaml_line = e.location().map(|loc| loc.line()).unwrap_or(1);
+                Err(FrontmatterIssue {
+                    line: opening_line + yaml_line,
+                    message: e.to_string(),
+                })
+            }
+        }
     }
 }
 
@@ -702,6 +1230,7 @@ Basic usage example.
         let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
 
         assert_eq!(doc.title, Some("My Component".to_string()));
+        assert_eq!(doc.title_line, Some(1));
         assert_eq!(doc.sections.len(), 4);
         assert!(doc.has_section("Purpose"));
         assert!(doc.has_section("Interface"));
@@ -796,6 +1325,51 @@ cargo build --release
         assert!(section.has_commands);
     }
 
+    #[test]
+    fn paths_patterns_reads_bullets_outside_code_blocks() {
+        let content = r#"# Doc
+
+## Purpose
+
+```markdown
+## Paths
+- `should/not/match`
+```
+
+## Paths
+- `src/commands/*.rs`
+* src/cli.rs
+
+```text
+- not/an/entry
+```
+
+## Examples
+- `not/paths`
+"#;
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        assert_eq!(
+            doc.paths_patterns(),
+            vec!["src/commands/*.rs", "src/cli.rs"]
+        );
+
+        let doc =
+            ParsedDoc::parse_content(PathBuf::from("test.md"), "# Doc\n\n## Purpose\n").unwrap();
+        assert!(doc.paths_patterns().is_empty());
+    }
+
+    #[test]
+    fn path_entries_follow_setext_and_lowercase_headings() {
+        let content = "# Doc\n\npaths\n-----\n\n- `src/a.rs`\n- docs/\n";
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let section = doc.get_section("Paths").unwrap();
+        assert_eq!(
+            section.path_entries(),
+            vec![(6, "src/a.rs".to_string()), (7, "docs/".to_string())]
+        );
+        assert_eq!(doc.paths_patterns(), vec!["src/a.rs", "docs/"]);
+    }
+
     #[test]
     fn section_start_line_is_correct() {
         let content = r#"# Title
@@ -820,6 +1394,8 @@ Content line 2.
 
         assert_eq!(first.start_line, 3);
         assert_eq!(second.start_line, 6);
+        assert_eq!((first.end_line, first.line_count()), (5, 3));
+        assert_eq!((second.end_line, second.line_count()), (7, 2));
     }
 
     #[test]
@@ -968,6 +1544,30 @@ plain text here
         assert_eq!(block.content, "plain text here");
     }
 
+    #[test]
+    fn extract_code_block_with_skip_attribute() {
+        let content = r#"# Test
+
+## Verification
+```bash skip
+rm -rf /tmp/demo
+```
+
+```bash
+ls
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let section = doc.get_section("Verification").unwrap();
+
+        let skipped = &section.code_blocks[0];
+        assert_eq!(skipped.language, Some("bash".to_string()));
+        assert!(skipped.skip);
+        assert!(skipped.is_executable);
+        assert!(!section.code_blocks[1].skip);
+    }
+
     #[test]
     fn extract_multiline_code_block() {
         let content = r#"# Test
@@ -1107,13 +1707,37 @@ echo "zsh"
         let section = doc.get_section("Commands").unwrap();
 
         assert_eq!(section.code_blocks.len(), 3);
-        for block in &section.code_blocks {
+        for block in &section.code_blocks[..2] {
             assert!(
                 block.is_executable,
                 "Block with language {:?} should be executable",
                 block.language
             );
         }
+        // zsh isn't in the default list
+        assert!(!section.code_blocks[2].is_executable);
+    }
+
+    #[test]
+    fn configured_languages_replace_the_defaults() {
+        let content = "# Test\n\n## Commands\n```ZSH\necho zsh\n```\n```sh\necho sh\n```\n```bash\n$ echo prompt\n```\n";
+        let options = ParseOptions {
+            executable_languages: vec!["zsh".to_string()],
+            ..Default::default()
+        };
+
+        let doc =
+            ParsedDoc::parse_content_with(PathBuf::from("test.md"), content, &options).unwrap();
+        let executable: Vec<bool> = doc
+            .get_section("Commands")
+            .unwrap()
+            .code_blocks
+            .iter()
+            .map(|b| b.is_executable)
+            .collect();
+
+        // Prompts stay executable whatever the language list says
+        assert_eq!(executable, vec![true, false, true]);
     }
 
     #[test]
@@ -1355,6 +1979,160 @@ Authentication handling.
         assert_eq!(frontmatter.paths[1], "crates/auth/");
     }
 
+    #[test]
+    fn parse_setext_headings_mixed_with_atx() {
+        let content = r#"Auth Service
+============
+
+Purpose
+-------
+Handles login.
+
+## Interface
+Endpoints.
+
+Verification
+------------
+```bash
+cargo test auth
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        assert_eq!(doc.title, Some("Auth Service".to_string()));
+        let names: Vec<&str> = doc.sections.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Purpose", "Interface", "Verification"]);
+
+        let verification = doc.get_section("Verification").unwrap();
+        assert_eq!(verification.start_line, 11);
+        assert_eq!(verification.code_blocks.len(), 1);
+        assert_eq!(verification.code_blocks[0].content, "cargo test auth");
+        assert_eq!(verification.code_blocks[0].start_line, 13);
+    }
+
+    #[test]
+    fn setext_underline_ignores_frontmatter_and_thematic_breaks() {
+        let content = r#"---
+title: Not A Heading
+---
+# Doc
+
+- list item
+---
+
+## Purpose
+Text.
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        assert_eq!(doc.title, Some("Doc".to_string()));
+        assert_eq!(doc.title_line, Some(4));
+        let names: Vec<&str> = doc.sections.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Purpose"]);
+    }
+
+    #[test]
+    fn parse_subsections_under_verification() {
+        let content = r#"# Doc
+
+## Verification
+
+Run everything:
+
+### Unit
+
+```bash
+cargo test --lib
+```
+
+### Integration
+
+```bash
+cargo test --test api
+```
+
+## Examples
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+
+        // The section still includes commands from every subsection
+        let verification = doc.get_section("Verification").unwrap();
+        let commands: Vec<&str> = verification
+            .code_blocks
+            .iter()
+            .map(|b| b.content.as_str())
+            .collect();
+        assert_eq!(commands, vec!["cargo test --lib", "cargo test --test api"]);
+
+        let subsections = doc.get_subsections("verification");
+        let names: Vec<&str> = subsections.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Unit", "Integration"]);
+        assert_eq!(subsections[0].start_line, 7);
+        assert_eq!(subsections[0].code_blocks[0].content, "cargo test --lib");
+        assert_eq!(subsections[1].code_blocks.len(), 1);
+        assert_eq!(
+            subsections[1].code_blocks[0].content,
+            "cargo test --test api"
+        );
+        assert_eq!(subsections[1].code_blocks[0].start_line, 15);
+
+        assert!(doc.get_subsections("Examples").is_empty());
+        assert!(doc.get_subsections("Missing").is_empty());
+    }
+
+    #[test]
+    fn get_section_ignores_case_and_trailing_punctuation() {
+        let content = "# Doc\n\n## verification\n```bash\ncargo test\n```\n\n## EXAMPLES:\nText.\n";
+        let doc = ParsedDoc::parse_content(PathBuf::from("doc.md"), content).unwrap();
+
+        let section = doc.get_section("Verification").unwrap();
+        assert_eq!(section.name, "verification");
+        assert_eq!(section.code_blocks.len(), 1);
+        assert!(doc.has_section("Examples"));
+        assert!(!doc.has_section("Example"));
+        assert!(section_name_matches("Paths.", "paths"));
+    }
+
+    #[test]
+    fn ignore_rule_directives_are_read_from_the_top_of_the_doc() {
+        let content = "---\n# pave: ignore-rule max-lines\nstatus: active\n---\n# Doc\n<!-- pave: ignore-rule broken-link, max-lines -->\n\n## Purpose\n<!-- pave: ignore-rule section-casing -->\n";
+        let doc = ParsedDoc::parse_content(PathBuf::from("doc.md"), content).unwrap();
+        assert_eq!(doc.ignored_rules, vec!["max-lines", "broken-link"]);
+        assert_eq!(doc.status.as_deref(), Some("active"));
+    }
+
+    #[test]
+    fn parse_reports_unclosed_fences_and_headings_swallowed_by_a_block() {
+        let content =
+            "# Doc\n\n## Verification\n```bash\ncargo test\n\n## Examples\n```bash\necho hi\n";
+        let doc = ParsedDoc::parse_content(PathBuf::from("doc.md"), content).unwrap();
+        assert_eq!(
+            doc.fence_issues,
+            vec![
+                FenceIssue {
+                    line: 4,
+                    kind: FenceIssueKind::Unclosed,
+                },
+                FenceIssue {
+                    line: 7,
+                    kind: FenceIssueKind::HeadingInside {
+                        heading: "Examples".to_string()
+                    },
+                },
+            ]
+        );
+
+        // Example headings in untagged or Markdown blocks are fine
+        let content = "# Doc\n\n```markdown\n## Purpose\n```\n\n```\n## Paths\n```\n";
+        let doc = ParsedDoc::parse_content(PathBuf::from("doc.md"), content).unwrap();
+        assert!(doc.fence_issues.is_empty());
+
+        let doc =
+            ParsedDoc::parse_content(PathBuf::from("doc.md"), "# Doc\n```bash```\n```\n").unwrap();
+        assert_eq!(doc.fence_issues[0].kind, FenceIssueKind::BacktickInInfo);
+    }
+
     #[test]
     fn parse_document_without_frontmatter() {
         let content = r#"# Simple Doc
@@ -1383,6 +2161,46 @@ Has frontmatter but not pave config.
         assert!(doc.frontmatter.is_none());
     }
 
+    #[test]
+    fn parse_document_with_malformed_frontmatter_records_issue() {
+        let content = r#"---
+pave:
+  paths: [src/
+---
+# My Document
+
+## Purpose
+Still parsed.
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("docs/bad.md"), content).unwrap();
+        assert!(doc.frontmatter.is_none());
+        assert_eq!(doc.frontmatter_issue.as_ref().unwrap().line, 4);
+        assert_eq!(doc.title.as_deref(), Some("My Document"));
+        assert!(doc.has_section("Purpose"));
+    }
+
+    #[test]
+    fn parse_file_reports_invalid_utf8_as_parse_error() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("latin1.md");
+        std::fs::write(&path, b"# Doc\n\nCaf\xe9\n").unwrap();
+
+        match parse_file(&path).unwrap_err() {
+            Error::Parse { file, line, .. } => {
+                assert_eq!(file, path);
+                assert_eq!(line, 3);
+            }
+            other => panic!("expected parse error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn parse_file_reports_missing_file_as_io_error() {
+        let err = parse_file("does/not/exist.md").unwrap_err();
+        assert!(matches!(err, Error::Io { .. }), "{err:?}");
+    }
+
     #[test]
     fn parse_document_with_empty_pave_paths() {
         let content = r#"---
@@ -0,0 +1,537 @@
+//! Matching source files against a doc's `## Paths` entries.
+//!
+//! Every command that maps code to docs (`coverage`, `changed`, `check
+//! --orphans`, and the verify cache) reads the entries with
+//! [`ParsedDoc::paths_patterns`](crate::parser::ParsedDoc::paths_patterns)
+//! and matches them through [`PathsMatcher`], so they all agree on what a doc
+//! covers.
+//!
+//! Entries use the [`glob`] crate's dialect, extended with brace
+//! alternatives: `*` and `?` match within a path component, `**` matches
+//! any number of directories, `[abc]` and `[!abc]` match one character from
+//! a set, and `{foo,bar}` expands to one entry per alternative (braces may
+//! nest). A leading `./` is ignored, and an entry naming a directory covers
+//! everything beneath it. A `:L10-L80` (or `:L10`) suffix limits an entry
+//! to those lines of the file; matching still selects the whole file, and
+//! only coverage looks at the lines (see [`PathsMatcher::selection`]).
+//!
+//! ```
+//! use std::path::Path;
+//! use pave::paths::PathsMatcher;
+//!
+//! let matcher = PathsMatcher::new(&["src/**", "!src/generated/**"]);
+//! assert!(matcher.matches(Path::new("src/lib.rs")));
+//! assert!(!matcher.matches(Path::new("src/generated/api.rs")));
+//! ```
+
+use std::path::{Path, PathBuf};
+
+/// An entry from a document's `## Paths` section.
+///
+/// Entries starting with `!` exclude files matched by earlier entries, like
+/// negation in `.gitignore`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathPattern {
+    /// The glob pattern, without any leading `!`.
+    pub glob: String,
+    /// Whether this entry excludes matching files.
+    pub negated: bool,
+    /// Lines the entry covers, from a `:L10-L80` suffix (1-indexed,
+    /// inclusive). Ignored on `!` entries, which always exclude whole files.
+    pub lines: Option<(usize, usize)>,
+}
+
+impl PathPattern {
+    /// Parse a `## Paths` entry such as `src/**`, `!src/generated/**`, or
+    /// `src/big.rs:L10-L80`.
+    pub fn parse(entry: &str) -> Self {
+        let (entry, lines) = split_line_range(entry);
+        match entry.strip_prefix('!') {
+            Some(glob) => Self {
+                glob: glob.trim().to_string(),
+                negated: true,
+                lines,
+            },
+            None => Self {
+                glob: entry.to_string(),
+                negated: false,
+                lines,
+            },
+        }
+    }
+}
+
+/// Split a trailing `:L<start>-L<end>` or `:L<line>` range off an entry.
+///
+/// A malformed or backwards range is left in place as part of the glob.
+fn split_line_range(entry: &str) -> (&str, Option<(usize, usize)>) {
+    let Some((glob, range)) = entry.rsplit_once(":L") else {
+        return (entry, None);
+    };
+    let (start, end) = range.split_once("-L").unwrap_or((range, range));
+    match (start.parse::<usize>(), end.parse::<usize>()) {
+        (Ok(start), Ok(end)) if start >= 1 && start <= end => (glob, Some((start, end))),
+        _ => (entry, None),
+    }
+}
+
+/// The part of a file a [`PathsMatcher`] selects.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Selection {
+    /// The whole file.
+    Whole,
+    /// Only these line ranges (1-indexed, inclusive), possibly overlapping.
+    Lines(Vec<(usize, usize)>),
+}
+
+/// A `## Paths` entry with its glob compiled.
+#[derive(Debug, Clone)]
+struct CompiledPattern {
+    /// The glob with any leading `./` and trailing `/` removed.
+    glob: String,
+    compiled: Option<glob::Pattern>,
+    /// Trailing `*` or `/` entries also match by string prefix.
+    prefix: Option<String>,
+    negated: bool,
+    lines: Option<(usize, usize)>,
+}
+
+impl CompiledPattern {
+    fn new(pattern: PathPattern) -> Self {
+        let raw = pattern.glob.trim_start_matches("./");
+        let prefix = (raw.ends_with('/') || raw.ends_with('*'))
+            .then(|| raw.trim_end_matches('*').trim_end_matches('/').to_string());
+        let glob = raw.trim_end_matches('/').to_string();
+        Self {
+            compiled: glob::Pattern::new(&glob).ok(),
+            glob,
+            prefix,
+            negated: pattern.negated,
+            lines: pattern.lines,
+        }
+    }
+
+    /// Returns true if `path` or one of its parent directories matches.
+    fn matches(&self, path: &Path) -> bool {
+        if let Some(prefix) = &self.prefix
+            && path.to_string_lossy().starts_with(prefix.as_str())
+        {
+            return true;
+        }
+        let Some(compiled) = &self.compiled else {
+            return false;
+        };
+        path.ancestors()
+            .filter(|p| !p.as_os_str().is_empty())
+            .any(|p| compiled.matches_path(p))
+    }
+}
+
+/// A compiled, ordered list of `## Paths` entries.
+///
+/// Entries are evaluated top to bottom and the last matching entry wins: a
+/// plain entry selects a path and a `!` entry deselects it. An entry that
+/// matches a directory covers everything beneath it.
+#[derive(Debug, Clone, Default)]
+pub struct PathsMatcher {
+    patterns: Vec<CompiledPattern>,
+}
+
+impl PathsMatcher {
+    /// Compile the given `## Paths` entries.
+    ///
+    /// Entries with `{a,b}` alternatives are expanded in place.
+    pub fn new<S: AsRef<str>>(entries: &[S]) -> Self {
+        tracing::debug!(
+            globs = ?entries.iter().map(AsRef::as_ref).collect::<Vec<_>>(),
+            "compiling path globs"
+        );
+        Self {
+            patterns: entries
+                .iter()
+                .flat_map(|e| {
+                    let pattern = PathPattern::parse(e.as_ref());
+                    expand_braces(&pattern.glob).into_iter().map(move |glob| {
+                        CompiledPattern::new(PathPattern {
+                            glob,
+                            negated: pattern.negated,
+                            lines: pattern.lines,
+                        })
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns true if no entries were given.
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Returns true if `path` (relative to the project root) is selected.
+    pub fn matches(&self, path: &Path) -> bool {
+        self.patterns
+            .iter()
+            .rfind(|p| p.matches(path))
+            .is_some_and(|p| !p.negated)
+    }
+
+    /// Which lines of `path` these entries select, or `None` if `path`
+    /// isn't selected.
+    ///
+    /// The file is selected whole if any plain entry without a line range
+    /// matches it; otherwise the ranges of every matching entry are returned.
+    pub fn selection(&self, path: &Path) -> Option<Selection> {
+        if !self.matches(path) {
+            return None;
+        }
+        let mut ranges = Vec::new();
+        for pattern in self
+            .patterns
+            .iter()
+            .filter(|p| !p.negated && p.matches(path))
+        {
+            match pattern.lines {
+                Some(range) => ranges.push(range),
+                None => return Some(Selection::Whole),
+            }
+        }
+        Some(Selection::Lines(ranges))
+    }
+
+    /// List the files under `root` selected by these entries.
+    ///
+    /// Returned paths are relative to `root`, sorted, and deduplicated.
+    pub fn matched_files(&self, root: &Path) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        for pattern in self.patterns.iter().filter(|p| !p.negated) {
+            // Walk everything under the pattern's literal prefix, then let
+            // `matches` decide which files are selected
+            let base = root.join(literal_prefix(&pattern.glob));
+            if base.is_dir() {
+                collect_files(&base, &mut files);
+            } else if base.is_file() {
+                files.push(base);
+            }
+        }
+
+        let mut files: Vec<PathBuf> = files
+            .iter()
+            .filter_map(|f| f.strip_prefix(root).ok())
+            .filter(|f| self.matches(f))
+            .map(Path::to_path_buf)
+            .collect();
+        files.sort();
+        files.dedup();
+        files
+    }
+}
+
+/// Expand `{a,b}` alternatives in `glob` into one glob per combination.
+///
+/// `src/{api,cli}/*.rs` becomes `src/api/*.rs` and `src/cli/*.rs`. Braces
+/// nest, and a brace without a matching `}` or without a comma is kept as a
+/// literal.
+pub fn expand_braces(glob: &str) -> Vec<String> {
+    let mut depth = 0;
+    let mut open = None;
+    let mut commas = Vec::new();
+    for (i, c) in glob.char_indices() {
+        match c {
+            '{' => {
+                if depth == 0 {
+                    open = Some(i);
+                    commas.clear();
+                }
+                depth += 1;
+            }
+            ',' if depth == 1 => commas.push(i),
+            '}' if depth > 0 => {
+                depth -= 1;
+                if depth == 0
+                    && let Some(start) = open
+                    && !commas.is_empty()
+                {
+                    let (prefix, suffix) = (&glob[..start], &glob[i + 1..]);
+                    let bounds = std::iter::once(start)
+                        .chain(commas.iter().copied())
+                        .chain(std::iter::once(i));
+                    let bounds: Vec<usize> = bounds.collect();
+                    return bounds
+                        .windows(2)
+                        .flat_map(|w| {
+                            let alternative = &glob[w[0] + 1..w[1]];
+                            expand_braces(&format!("{}{}{}", prefix, alternative, suffix))
+                        })
+                        .collect();
+                }
+            }
+            _ => {}
+        }
+    }
+    vec![glob.to_string()]
+}
+
+/// The leading path components of `glob` that contain no wildcards.
+fn literal_prefix(glob: &str) -> PathBuf {
+    Path::new(glob)
+        .components()
+        .take_while(|c| {
+            !c.as_os_str()
+                .to_string_lossy()
+                .contains(['*', '?', '[', '{'])
+        })
+        .collect()
+}
+
+/// List the files under `dir`, sorted.
+///
+/// With `respect_ignore`, files excluded by `.gitignore`, `.ignore`, or git's
+/// exclude files are left out, whether or not the project is a git
+/// repository. Hidden files are listed either way, but `.git` never is.
+/// Entries (below `dir`) for which `skip` returns true are pruned, along with
+/// everything beneath them.
+pub fn walk_files(
+    dir: &Path,
+    respect_ignore: bool,
+    skip: impl Fn(&Path) -> bool + Send + Sync + 'static,
+) -> Vec<PathBuf> {
+    walk_dirs(&[dir.to_path_buf()], respect_ignore, skip)
+}
+
+/// List the files under each of `dirs`, like [`walk_files`].
+///
+/// A file reachable from more than one directory (such as `docs` and
+/// `docs/components`) is listed once.
+pub fn walk_dirs(
+    dirs: &[PathBuf],
+    respect_ignore: bool,
+    skip: impl Fn(&Path) -> bool + Send + Sync + 'static,
+) -> Vec<PathBuf> {
+    let Some((first, rest)) = dirs.split_first() else {
+        return Vec::new();
+    };
+    let mut builder = ignore::WalkBuilder::new(first);
+    for dir in rest {
+        builder.add(dir);
+    }
+    let mut files: Vec<PathBuf> = builder
+        .standard_filters(respect_ignore)
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(move |entry| {
+            entry.depth() == 0 || (entry.file_name() != ".git" && !skip(entry.path()))
+        })
+        .build()
+        .flatten()
+        .filter(|entry| entry.file_type().is_some_and(|t| t.is_file()))
+        .map(ignore::DirEntry::into_path)
+        .collect();
+    files.sort();
+    files.dedup();
+    tracing::debug!(dirs = ?dirs, files = files.len(), "scanned directories");
+    files
+}
+
+/// Recursively collect all files in a directory.
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn walk_files_honors_gitignore_unless_disabled() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join(".gitignore"), "build/\n").unwrap();
+        fs::create_dir_all(root.join("build")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("build/out.rs"), "").unwrap();
+        fs::write(root.join("src/lib.rs"), "").unwrap();
+
+        let relative = |files: Vec<PathBuf>| -> Vec<PathBuf> {
+            files
+                .iter()
+                .map(|f| f.strip_prefix(root).unwrap().to_path_buf())
+                .collect()
+        };
+
+        assert_eq!(
+            relative(walk_files(root, true, |_| false)),
+            vec![PathBuf::from(".gitignore"), PathBuf::from("src/lib.rs")]
+        );
+        assert_eq!(
+            relative(walk_files(root, false, |p| p.ends_with("src"))),
+            vec![PathBuf::from(".gitignore"), PathBuf::from("build/out.rs")]
+        );
+    }
+
+    #[test]
+    fn path_pattern_parses_negation() {
+        assert_eq!(
+            PathPattern::parse("!src/generated/**"),
+            PathPattern {
+                glob: "src/generated/**".to_string(),
+                negated: true,
+                lines: None,
+            }
+        );
+        assert!(!PathPattern::parse("src/**").negated);
+    }
+
+    #[test]
+    fn path_pattern_parses_line_ranges() {
+        let parsed = PathPattern::parse("src/big.rs:L10-L80");
+        assert_eq!(parsed.glob, "src/big.rs");
+        assert_eq!(parsed.lines, Some((10, 80)));
+        assert_eq!(PathPattern::parse("src/big.rs:L7").lines, Some((7, 7)));
+        // Backwards or malformed ranges stay part of the glob
+        for entry in ["src/big.rs:L80-L10", "src/big.rs:L0", "src/big.rs:Lx"] {
+            assert_eq!(PathPattern::parse(entry).glob, entry);
+        }
+
+        let matcher = PathsMatcher::new(&["src/big.rs:L1-L10", "src/big.rs:L20-L30"]);
+        assert_eq!(
+            matcher.selection(Path::new("src/big.rs")),
+            Some(Selection::Lines(vec![(1, 10), (20, 30)]))
+        );
+        let matcher = PathsMatcher::new(&["src/big.rs:L1-L10", "src/"]);
+        assert_eq!(
+            matcher.selection(Path::new("src/big.rs")),
+            Some(Selection::Whole)
+        );
+        assert_eq!(matcher.selection(Path::new("lib/other.rs")), None);
+    }
+
+    #[test]
+    fn matcher_supports_recursive_globs() {
+        let matcher = PathsMatcher::new(&["src/**/*.rs"]);
+        assert!(matcher.matches(Path::new("src/commands/check.rs")));
+        assert!(matcher.matches(Path::new("src/lib.rs")));
+        assert!(!matcher.matches(Path::new("tests/cli.rs")));
+    }
+
+    #[test]
+    fn matcher_supports_extension_globs() {
+        let matcher = PathsMatcher::new(&["*.rs"]);
+        assert!(matcher.matches(Path::new("build.rs")));
+        assert!(!matcher.matches(Path::new("Cargo.toml")));
+    }
+
+    #[test]
+    fn matcher_treats_directories_as_covering_their_contents() {
+        for entry in ["src/auth/", "src/auth", "./src/auth"] {
+            let matcher = PathsMatcher::new(&[entry]);
+            assert!(matcher.matches(Path::new("src/auth/login.rs")), "{entry}");
+            assert!(!matcher.matches(Path::new("src/other.rs")), "{entry}");
+        }
+    }
+
+    #[test]
+    fn matcher_applies_include_then_exclude() {
+        let matcher = PathsMatcher::new(&["src/**", "!src/generated/**"]);
+        assert!(matcher.matches(Path::new("src/lib.rs")));
+        assert!(!matcher.matches(Path::new("src/generated/api.rs")));
+        assert!(!matcher.matches(Path::new("tests/it.rs")));
+    }
+
+    #[test]
+    fn matcher_later_entries_win() {
+        let matcher = PathsMatcher::new(&["src/**", "!src/generated/", "src/generated/keep.rs"]);
+        assert!(matcher.matches(Path::new("src/generated/keep.rs")));
+        assert!(!matcher.matches(Path::new("src/generated/drop.rs")));
+
+        // An exclusion before any inclusion has nothing to remove
+        let matcher = PathsMatcher::new(&["!src/generated/**", "src/**"]);
+        assert!(matcher.matches(Path::new("src/generated/api.rs")));
+    }
+
+    #[test]
+    fn matched_files_lists_selected_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src/generated")).unwrap();
+        fs::write(root.join("src/lib.rs"), "").unwrap();
+        fs::write(root.join("src/generated/api.rs"), "").unwrap();
+        fs::write(root.join("README.md"), "").unwrap();
+
+        let matcher = PathsMatcher::new(&["src/", "!src/generated/**"]);
+        assert_eq!(
+            matcher.matched_files(root),
+            vec![PathBuf::from("src/lib.rs")]
+        );
+
+        assert!(
+            PathsMatcher::new(&["missing/"])
+                .matched_files(root)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn expand_braces_expands_each_alternative() {
+        assert_eq!(
+            expand_braces("src/{foo,bar}/**/*.rs"),
+            vec!["src/foo/**/*.rs", "src/bar/**/*.rs"]
+        );
+        assert_eq!(
+            expand_braces("{a,b{c,d}}/{x,y}"),
+            vec!["a/x", "a/y", "bc/x", "bc/y", "bd/x", "bd/y"]
+        );
+        assert_eq!(expand_braces("src/{lib}.rs"), vec!["src/{lib}.rs"]);
+        assert_eq!(expand_braces("src/{a,b"), vec!["src/{a,b"]);
+    }
+
+    #[test]
+    fn matcher_supports_braces_single_char_wildcards_and_dot_slash() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for dir in ["src/foo/deep", "src/bar", "src/baz"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        for file in [
+            "src/foo/deep/a.rs",
+            "src/bar/b.rs",
+            "src/baz/c.rs",
+            "src/v1.rs",
+            "src/v10.rs",
+        ] {
+            fs::write(root.join(file), "").unwrap();
+        }
+
+        let braces = PathsMatcher::new(&["src/{foo,bar}/**/*.rs"]);
+        assert_eq!(
+            braces.matched_files(root),
+            vec![
+                PathBuf::from("src/bar/b.rs"),
+                PathBuf::from("src/foo/deep/a.rs")
+            ]
+        );
+
+        let single = PathsMatcher::new(&["src/v?.rs"]);
+        assert_eq!(single.matched_files(root), vec![PathBuf::from("src/v1.rs")]);
+
+        let dot_slash = PathsMatcher::new(&["./src/{foo,baz}/**", "!./src/foo/deep/**"]);
+        assert!(dot_slash.matches(Path::new("src/baz/c.rs")));
+        assert!(!dot_slash.matches(Path::new("src/foo/deep/a.rs")));
+        assert_eq!(
+            dot_slash.matched_files(root),
+            vec![PathBuf::from("src/baz/c.rs")]
+        );
+    }
+}
@@ -9,6 +9,7 @@ use glob::Pattern;
 
 use crate::config::RulesSection;
 use crate::parser::ParsedDoc;
+use crate::paths::{PathPattern, PathsMatcher};
 
 /// Document type for type-specific validation.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -336,9 +337,9 @@ impl RulesEngine {
                 warn_empty,
             } => {
                 if let Some(section) = doc.get_section("Paths") {
-                    let patterns = Self::extract_paths_patterns(&section.content);
-                    for (line_offset, pattern) in patterns {
-                        let line = section.start_line + line_offset;
+                    for (line, entry) in section.path_entries() {
+                        // Validate the glob behind a `!` exclusion the same way
+                        let pattern = PathPattern::parse(&entry).glob;
                         // Check for absolute paths
                         if pattern.starts_with('/') {
                             result.errors.push(ValidationError {
@@ -387,50 +388,15 @@ impl RulesEngine {
         }
     }
 
-    /// Extract path patterns from the Paths section content.
-    /// Returns pairs of (line_offset, pattern).
-    fn extract_paths_patterns(content: &str) -> Vec<(usize, String)> {
-        let mut patterns = Vec::new();
-
-        for (idx, line) in content.lines().enumerate() {
-            let trimmed = line.trim();
-
-            // Collect patterns (lines starting with - or *)
-            if let Some(pattern) = trimmed
-                .strip_prefix("- ")
-                .or_else(|| trimmed.strip_prefix("* "))
-            {
-                let pattern = pattern.trim();
-                // Remove backticks if present
-                let pattern = pattern.trim_matches('`');
-                if !pattern.is_empty() {
-                    // Line offset is idx + 1 since content starts after the heading
-                    patterns.push((idx + 1, pattern.to_string()));
-                }
-            }
-        }
-
-        patterns
-    }
-
     /// Check if a glob pattern matches any files in the given directory.
-    fn pattern_matches_files(pattern: &str, root: &Path) -> bool {
-        // Build the full glob pattern from the root directory
-        let full_pattern = root.join(pattern);
-        let pattern_str = full_pattern.to_string_lossy();
-
-        // Try to match files using glob
-        if let Ok(paths) = glob::glob(&pattern_str) {
-            return paths.flatten().next().is_some();
-        }
-
-        // Also try simple prefix matching for directory patterns
-        if pattern.ends_with('/') || !pattern.contains('*') {
-            let target = root.join(pattern.trim_end_matches('/'));
-            return target.exists();
+    pub fn pattern_matches_files(pattern: &str, root: &Path) -> bool {
+        if !PathsMatcher::new(&[pattern]).matched_files(root).is_empty() {
+            return true;
         }
 
-        false
+        // An existing but empty directory still counts
+        let target = root.join(pattern.trim_start_matches("./").trim_end_matches('/'));
+        !pattern.contains(['*', '?', '[', '{']) && target.exists()
     }
 
     /// Returns the rules in this engine.
@@ -683,6 +649,7 @@ Missing verification.
             warn_empty_paths: false,
             gradual: false,
             gradual_until: None,
+            section_casing: false,
         };
         let engine = RulesEngine::from_config(&config);
 
@@ -723,6 +690,7 @@ Missing verification.
             warn_empty_paths: false,
             gradual: false,
             gradual_until: None,
+            section_casing: false,
         };
         let engine = RulesEngine::from_config(&config);
 
@@ -1434,6 +1402,7 @@ This is a component.
             warn_empty_paths: true,
             gradual: false,
             gradual_until: None,
+            section_casing: false,
         };
         let engine = RulesEngine::from_config_with_root(&config, "/project/root");
 
@@ -1462,6 +1431,7 @@ This is a component.
             warn_empty_paths: false,
             gradual: false,
             gradual_until: None,
+            section_casing: false,
         };
         let engine = RulesEngine::from_config(&config);
 
@@ -1474,19 +1444,4 @@ This is a component.
                 .any(|r| matches!(r, Rule::ValidatePaths { .. }))
         );
     }
-
-    #[test]
-    fn extract_paths_patterns_helper() {
-        let content = r#"Some intro text.
-- `src/commands/*.rs`
-- `src/cli.rs`
-* docs/
-"#;
-        let patterns = RulesEngine::extract_paths_patterns(content);
-
-        assert_eq!(patterns.len(), 3);
-        assert_eq!(patterns[0], (2, "src/commands/*.rs".to_string()));
-        assert_eq!(patterns[1], (3, "src/cli.rs".to_string()));
-        assert_eq!(patterns[2], (4, "docs/".to_string()));
-    }
 }
@@ -31,6 +31,15 @@ impl TemplateType {
             TemplateType::Adr => "adr.md",
         }
     }
+
+    /// Returns a one-line description of what the template documents.
+    pub fn description(&self) -> &'static str {
+        match self {
+            TemplateType::Component => "Services, libraries, and modules",
+            TemplateType::Runbook => "Operational procedures",
+            TemplateType::Adr => "Architecture decision records",
+        }
+    }
 }
 
 /// Returns the template content for the given template type.
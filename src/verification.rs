@@ -5,12 +5,15 @@
 //! - Execute verification commands with timeout and output capture
 //! - Report results including pass/fail status, timing, and error details
 
-use std::path::PathBuf;
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::sync::mpsc;
 use std::thread;
 use std::time::{Duration, Instant};
 
+use serde::{Serialize, Serializer};
+
 use crate::parser::{CodeBlock, ExpectMatchStrategy, ParsedDoc};
 
 /// Default timeout for command execution in seconds.
@@ -29,6 +32,67 @@ pub enum OutputMatcher {
     ExitCodeOnly,
 }
 
+/// Exit codes accepted by an `# expect-exit:` annotation: single codes and
+/// inclusive ranges, such as `0,1` or `0-2`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExitCodes(Vec<(i32, i32)>);
+
+impl ExitCodes {
+    /// Parse a comma-separated list of codes and `low-high` ranges.
+    ///
+    /// Returns `None` if any entry isn't a code or a range with `low <= high`.
+    pub fn parse(spec: &str) -> Option<Self> {
+        let ranges = spec
+            .split(',')
+            .map(|entry| {
+                let entry = entry.trim();
+                let (low, high) = entry.split_once('-').unwrap_or((entry, entry));
+                let low: i32 = low.trim().parse().ok()?;
+                let high: i32 = high.trim().parse().ok()?;
+                (low <= high).then_some((low, high))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self(ranges))
+    }
+
+    /// Returns true if `code` is one of the accepted codes.
+    pub fn contains(&self, code: i32) -> bool {
+        self.0
+            .iter()
+            .any(|&(low, high)| (low..=high).contains(&code))
+    }
+
+    /// The code, if exactly one is accepted.
+    fn single(&self) -> Option<i32> {
+        match self.0[..] {
+            [(low, high)] if low == high => Some(low),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ExitCodes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, &(low, high)) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            if low == high {
+                write!(f, "{}", low)?;
+            } else {
+                write!(f, "{}-{}", low, high)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for ExitCodes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
 /// A single verification item representing a command to execute.
 #[derive(Debug, Clone, PartialEq)]
 pub struct VerificationItem {
@@ -38,12 +102,34 @@ pub struct VerificationItem {
     pub working_dir: Option<PathBuf>,
     /// Expected exit code (default: 0).
     pub expected_exit_code: Option<i32>,
+    /// Several accepted exit codes, from an `# expect-exit:` set or range.
+    /// Takes precedence over `expected_exit_code`, which then holds the
+    /// lowest accepted code.
+    pub expected_exit_codes: Option<ExitCodes>,
     /// How to validate command output.
     pub expected_output: Option<OutputMatcher>,
-    /// Timeout in seconds (default: 30).
+    /// Timeout in seconds. `None` uses the runner's default (30 unless
+    /// configured) and `0` disables the timeout.
     pub timeout_secs: Option<u32>,
     /// Environment variables to set for this command.
     pub env_vars: Vec<(String, String)>,
+    /// Lines of the code blocks this item was extracted from.
+    pub source_lines: Vec<usize>,
+    /// Friendly name from a `# name: <label>` comment, shown in reports in
+    /// place of the command.
+    pub label: Option<String>,
+    /// Tags from a `# tags: <tag>, <tag>` comment, used by `--tag` and
+    /// `--exclude-tag` to select items.
+    pub tags: Vec<String>,
+    /// The block's text as written, with comments and prompts intact.
+    ///
+    /// Only for showing the block to people and agents (see
+    /// [`VerificationItem::prompt_block`]); `command` is what runs.
+    pub raw: String,
+    /// Interpreter from a leading `#!` line, such as `/usr/bin/env python3`.
+    /// When set, `command` is the whole block, run as a script file under
+    /// this interpreter instead of through the shell.
+    pub interpreter: Option<String>,
 }
 
 impl Default for VerificationItem {
@@ -52,13 +138,33 @@ impl Default for VerificationItem {
             command: String::new(),
             working_dir: None,
             expected_exit_code: Some(0),
+            expected_exit_codes: None,
             expected_output: None,
-            timeout_secs: Some(DEFAULT_TIMEOUT_SECS),
+            timeout_secs: None,
             env_vars: Vec::new(),
+            source_lines: Vec::new(),
+            label: None,
+            tags: Vec::new(),
+            raw: String::new(),
+            interpreter: None,
         }
     }
 }
 
+impl VerificationItem {
+    /// The item as a fenced `bash` block for prompts, keeping the comments
+    /// that explain it. Falls back to the cleaned command when the raw text
+    /// is unknown.
+    pub fn prompt_block(&self) -> String {
+        let body = if self.raw.trim().is_empty() {
+            &self.command
+        } else {
+            &self.raw
+        };
+        format!("```bash\n{}\n```\n", body.trim_end_matches('\n'))
+    }
+}
+
 /// A verification specification extracted from a document.
 #[derive(Debug, Clone, PartialEq)]
 pub struct VerificationSpec {
@@ -68,6 +174,108 @@ pub struct VerificationSpec {
     pub section_line: usize,
     /// List of verification items to execute.
     pub items: Vec<VerificationItem>,
+    /// Commands and blocks left out by `# pave: skip` or a `skip` fence.
+    pub skipped: usize,
+    /// Run every item one after another, even with parallel jobs. Set by a
+    /// `# pave: serial` line in any Verification block.
+    pub serial: bool,
+    /// Commands from a `## Setup` section, run once before the items.
+    pub setup: Vec<VerificationItem>,
+    /// Commands from a `## Teardown` section, run once after the items,
+    /// even when some of them fail.
+    pub teardown: Vec<VerificationItem>,
+}
+
+impl VerificationSpec {
+    /// Collapse items that would run identically into the first occurrence.
+    ///
+    /// Items match when their command, working directory, environment, and
+    /// expectations are all equal. The kept item records the source lines of
+    /// every occurrence.
+    pub fn dedupe(&mut self) {
+        let mut kept: Vec<VerificationItem> = Vec::with_capacity(self.items.len());
+        for item in self.items.drain(..) {
+            match kept.iter_mut().find(|k| k.runs_like(&item)) {
+                Some(existing) => {
+                    existing.source_lines.extend(item.source_lines);
+                    for tag in item.tags {
+                        if !existing.tags.contains(&tag) {
+                            existing.tags.push(tag);
+                        }
+                    }
+                }
+                None => kept.push(item),
+            }
+        }
+        self.items = kept;
+    }
+
+    /// Run items without a working directory of their own in `base_dir`.
+    ///
+    /// A relative `base_dir` resolves against the directory containing the
+    /// document, so `.` runs each command next to its doc. Items that set a
+    /// directory (via frontmatter or `<!-- pave:working_dir -->`) keep it.
+    pub fn apply_base_dir(&mut self, base_dir: &Path) {
+        let doc_dir = self.source_file.parent().unwrap_or(Path::new(""));
+        let dir = normalize(&doc_dir.join(base_dir));
+        let hooks = self.setup.iter_mut().chain(&mut self.teardown);
+        for item in self.items.iter_mut().chain(hooks) {
+            if item.working_dir.is_none() {
+                item.working_dir = Some(dir.clone());
+            }
+        }
+    }
+}
+
+/// Lexically drop `.` components and fold `..` into the preceding one.
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir
+                if matches!(out.components().next_back(), Some(Component::Normal(_))) =>
+            {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    if out.as_os_str().is_empty() {
+        out.push(".");
+    }
+    out
+}
+
+impl VerificationItem {
+    /// Returns true if both items would run the same way and pass or fail
+    /// together.
+    fn runs_like(&self, other: &VerificationItem) -> bool {
+        self.command == other.command
+            && self.working_dir == other.working_dir
+            && self.env_vars == other.env_vars
+            && self.expected_exit_code == other.expected_exit_code
+            && self.expected_exit_codes == other.expected_exit_codes
+            && self.expected_output == other.expected_output
+            && self.timeout_secs == other.timeout_secs
+            && self.interpreter == other.interpreter
+    }
+
+    /// Returns true if `code` is an exit code this item expects.
+    pub fn expects_exit_code(&self, code: i32) -> bool {
+        match &self.expected_exit_codes {
+            Some(codes) => codes.contains(code),
+            None => code == self.expected_exit_code.unwrap_or(0),
+        }
+    }
+
+    /// The expected exit codes as shown in failure messages, e.g. `0-2`.
+    pub fn expected_exit_description(&self) -> String {
+        match &self.expected_exit_codes {
+            Some(codes) => codes.to_string(),
+            None => self.expected_exit_code.unwrap_or(0).to_string(),
+        }
+    }
 }
 
 /// Result of executing a single verification item.
@@ -92,7 +300,8 @@ pub struct VerificationResult {
 /// Extract a verification specification from a parsed document.
 ///
 /// Looks for a "Verification" section and extracts executable code blocks
-/// as verification items.
+/// as verification items. Executable blocks in "Setup" and "Teardown"
+/// sections become the spec's setup and teardown commands.
 ///
 /// # Arguments
 /// * `doc` - The parsed markdown document
@@ -103,9 +312,7 @@ pub struct VerificationResult {
 pub fn extract_verification_spec(doc: &ParsedDoc) -> Option<VerificationSpec> {
     let section = doc.get_section("Verification")?;
 
-    let executable_blocks: Vec<&CodeBlock> = section.executable_commands();
-
-    if executable_blocks.is_empty() {
+    if section.executable_commands().is_empty() {
         return None;
     }
 
@@ -116,33 +323,122 @@ pub fn extract_verification_spec(doc: &ParsedDoc) -> Option<VerificationSpec> {
         .and_then(|fm| fm.working_dir.as_ref())
         .map(PathBuf::from);
 
-    let items: Vec<VerificationItem> = executable_blocks
-        .into_iter()
-        .map(|block| {
-            let command = extract_command_from_block(&block.content);
-            let expected_output = convert_expected_output(block);
+    let blocks = &section.code_blocks;
+    let serial = blocks
+        .iter()
+        .filter(|block| block.is_executable)
+        .any(|block| block.content.lines().any(is_serial_directive));
+    let (items, skipped) = section_items(blocks, default_working_dir.as_deref());
+    let hooks = |name: &str| {
+        doc.get_section(name)
+            .map(|hook| section_items(&hook.code_blocks, default_working_dir.as_deref()).0)
+            .unwrap_or_default()
+    };
+    let setup = hooks("Setup");
+    let teardown = hooks("Teardown");
+
+    tracing::debug!(
+        file = %doc.path.display(),
+        commands = items.len(),
+        skipped,
+        "extracted verification commands"
+    );
+    Some(VerificationSpec {
+        source_file: doc.path.clone(),
+        section_line: section.start_line,
+        items,
+        skipped,
+        serial,
+        setup,
+        teardown,
+    })
+}
+
+/// Turn a section's executable blocks into verification items.
+///
+/// Returns the items and the number of commands and blocks left out by
+/// `# pave: skip` or a `skip` fence.
+fn section_items(
+    blocks: &[CodeBlock],
+    default_working_dir: Option<&Path>,
+) -> (Vec<VerificationItem>, usize) {
+    // ```bash skip blocks are shown in the doc but never run
+    let mut skipped = blocks
+        .iter()
+        .filter(|block| block.is_executable && block.skip)
+        .count();
+    let items: Vec<VerificationItem> = blocks
+        .iter()
+        .enumerate()
+        .filter(|(_, block)| block.is_executable && !block.skip)
+        .filter_map(|(idx, block)| {
+            // A shebang block runs whole as a script; others run line by line
+            let interpreter = shebang(&block.content);
+            let (command, skipped_lines) = match interpreter {
+                Some(_) => (block.content.trim_end().to_string(), 0),
+                None => extract_commands(&block.content),
+            };
+            skipped += skipped_lines;
+            if command.is_empty() && skipped_lines > 0 {
+                return None;
+            }
+            // Inline annotations take precedence over a following output block
+            let expected_output = convert_expected_output(block)
+                .or_else(|| following_output_block(blocks.get(idx + 1)?));
             // Per-block working_dir overrides frontmatter default
             let working_dir = block
                 .working_dir
                 .as_ref()
                 .map(PathBuf::from)
-                .or_else(|| default_working_dir.clone());
-            VerificationItem {
+                .or_else(|| default_working_dir.map(Path::to_path_buf));
+            let (expected_exit_code, expected_exit_codes) = extract_expected_exit(&block.content);
+            Some(VerificationItem {
                 command,
                 working_dir,
-                expected_exit_code: Some(0),
+                expected_exit_code,
+                expected_exit_codes,
                 expected_output,
-                timeout_secs: Some(DEFAULT_TIMEOUT_SECS),
+                timeout_secs: None,
                 env_vars: block.env_vars.clone(),
-            }
+                source_lines: vec![block.start_line],
+                label: extract_label(&block.content),
+                tags: extract_tags(&block.content),
+                raw: block.content.clone(),
+                interpreter: interpreter.map(str::to_string),
+            })
         })
         .collect();
 
-    Some(VerificationSpec {
-        source_file: doc.path.clone(),
-        section_line: section.start_line,
-        items,
-    })
+    (items, skipped)
+}
+
+/// The interpreter named by a `#!` first line, e.g. `/usr/bin/env python3`.
+fn shebang(content: &str) -> Option<&str> {
+    let interpreter = content.lines().next()?.trim().strip_prefix("#!")?.trim();
+    (!interpreter.is_empty()).then_some(interpreter)
+}
+
+/// Language tag for a code block holding the expected output of the
+/// preceding command block.
+pub const OUTPUT_BLOCK_LANGUAGE: &str = "output";
+
+/// Build a matcher from an `output` block that follows a command block.
+///
+/// The block's content must appear somewhere in the command's stdout.
+fn following_output_block(block: &CodeBlock) -> Option<OutputMatcher> {
+    let is_output = block
+        .language
+        .as_deref()
+        .is_some_and(|lang| lang.eq_ignore_ascii_case(OUTPUT_BLOCK_LANGUAGE));
+    if !is_output || block.is_executable {
+        return None;
+    }
+
+    let expected = block.content.trim();
+    if expected.is_empty() {
+        return None;
+    }
+    Some(OutputMatcher::Contains(expected.to_string()))
 }
 
 /// Convert parsed expected output to an OutputMatcher.
@@ -162,13 +458,32 @@ fn convert_expected_output(block: &CodeBlock) -> Option<OutputMatcher> {
 ///
 /// Handles various formats:
 /// - Lines starting with `$ ` (shell prompt) - strips the prompt
-/// - Lines starting with `> ` (REPL prompt) - strips the prompt
+/// - Lines starting with `> ` (REPL prompt) - strips the prompt, except
+///   directly after a `$ ` command, where they continue it (see
+///   [`fold_continuations`])
 /// - Plain commands without prompts
-/// - Skips empty lines and comment lines (starting with #)
-fn extract_command_from_block(content: &str) -> String {
+/// - Skips empty lines and comment lines (starting with #), and drops
+///   trailing comments
+/// - Lines ending in `\` continue onto the next line
+/// - Heredocs (`<<EOF`, `<<-EOF`, `<<'EOF'`) - the body up to the closing
+///   delimiter is kept verbatim as part of the command that opened it
+///
+/// Each line is one shell command and keeps its own `;`, `&&`, and `||`.
+/// Lines are chained with `&&`, so a later line only runs if every earlier
+/// line succeeded; lines using `;` or `||` are grouped with `{ ...; }` so
+/// the chain can't change their meaning. Heredoc bodies follow the chain in
+/// order, which is where the shell reads them from.
+///
+/// Lines ending in a `# pave: skip` comment are left out; the second value
+/// counts them.
+fn extract_commands(content: &str) -> (String, usize) {
+    let mut skipped = 0;
     let mut commands = Vec::new();
+    let mut heredoc_bodies = Vec::new();
+    let folded = fold_continuations(content);
+    let mut lines = folded.iter().map(String::as_str);
 
-    for line in content.lines() {
+    while let Some(line) = lines.next() {
         let trimmed = line.trim();
 
         // Skip empty lines and comment-only lines
@@ -176,21 +491,334 @@ fn extract_command_from_block(content: &str) -> String {
             continue;
         }
 
-        // Strip shell prompt prefixes
-        let cmd = if let Some(rest) = trimmed.strip_prefix("$ ") {
-            rest.to_string()
-        } else if let Some(rest) = trimmed.strip_prefix("> ") {
-            rest.to_string()
-        } else {
-            trimmed.to_string()
-        };
+        let mut cmd = strip_prompt(trimmed).to_string();
+        while let Some(head) = cmd.strip_suffix('\\') {
+            let head = head.trim_end();
+            cmd = match lines.next() {
+                Some(next) => format!("{} {}", head, strip_prompt(next.trim())),
+                None => head.to_string(),
+            };
+        }
+        let code = strip_trailing_comment(&cmd);
+
+        if is_skip_comment(&cmd[code.len()..]) {
+            // Still consume any heredoc bodies so they aren't read as commands
+            for heredoc in heredocs_in(code) {
+                heredoc.read_body(&mut lines);
+            }
+            skipped += 1;
+        } else if !code.is_empty() {
+            for heredoc in heredocs_in(code) {
+                heredoc_bodies.push(heredoc.read_body(&mut lines));
+            }
+            commands.push(code.to_string());
+        }
+    }
+
+    let mut script = match commands.as_slice() {
+        [single] => single.clone(),
+        _ => commands
+            .iter()
+            .map(|cmd| group_list(cmd))
+            .collect::<Vec<_>>()
+            .join(" && "),
+    };
+    for body in heredoc_bodies {
+        script.push('\n');
+        script.push_str(&body);
+    }
+    (script, skipped)
+}
+
+/// Fold `> ` continuation prompts into the `$ ` command they continue.
+///
+/// In a transcript-style block (one whose first command has a `$ ` prompt),
+/// a `> ` line directly after a prompted line continues that command. It is
+/// joined on with a newline, so multi-line constructs like `for ... do` /
+/// `done` run as written, or kept as its own line when the command opens a
+/// heredoc, so the body is read as usual. Other blocks, and every other
+/// line, are returned as they are.
+fn fold_continuations(content: &str) -> Vec<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let transcript = lines
+        .iter()
+        .map(|line| line.trim())
+        .find(|line| !line.is_empty() && !line.starts_with('#'))
+        .is_some_and(|line| line.starts_with("$ "));
+    if !transcript {
+        return lines.iter().map(|line| line.to_string()).collect();
+    }
+
+    let mut folded: Vec<String> = Vec::new();
+    // The command a `> ` line would continue, and whether it opened a heredoc
+    let mut open: Option<(usize, bool)> = None;
+    for line in lines {
+        let trimmed = line.trim();
+        let continuation = line
+            .trim_start()
+            .strip_prefix("> ")
+            .map(str::trim_end)
+            .or((trimmed == ">").then_some(""));
+
+        match (continuation, open) {
+            (Some(body), Some((_, true))) => folded.push(body.to_string()),
+            (Some(text), Some((idx, false))) => {
+                let head = &folded[idx];
+                let head_code = strip_trailing_comment(head);
+                let code = strip_trailing_comment(text);
+                let skip = is_skip_comment(&head[head_code.len()..])
+                    || is_skip_comment(&text[code.len()..]);
+                let mut joined = format!("{}\n{}", head_code, code);
+                if skip {
+                    joined.push_str(" # pave: skip");
+                }
+                folded[idx] = joined;
+            }
+            _ if trimmed.starts_with("$ ") => {
+                let command = strip_trailing_comment(strip_prompt(trimmed));
+                let heredoc = !heredocs_in(command).is_empty();
+                folded.push(line.to_string());
+                open = Some((folded.len() - 1, heredoc));
+            }
+            _ => {
+                folded.push(line.to_string());
+                open = None;
+            }
+        }
+    }
+    folded
+}
+
+/// The label from a `# name: <label>` comment line in a command block.
+fn extract_label(content: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let label = annotation_value(line, "name:")?;
+        (!label.is_empty()).then(|| label.to_string())
+    })
+}
+
+/// The exit codes from an `# expect-exit: 1`, `# expect-exit: 0,1`, or
+/// `# expect-exit: 0-2` comment line in a command block.
+///
+/// A single code is returned as the plain expected code; a set or range also
+/// fills the richer [`ExitCodes`]. Without a valid annotation, 0 is expected.
+fn extract_expected_exit(content: &str) -> (Option<i32>, Option<ExitCodes>) {
+    let codes = content
+        .lines()
+        .find_map(|line| ExitCodes::parse(annotation_value(line, "expect-exit:")?));
+    match codes {
+        None => (Some(0), None),
+        Some(codes) => match codes.single() {
+            Some(code) => (Some(code), None),
+            None => {
+                let lowest = codes.0.iter().map(|&(low, _)| low).min();
+                (lowest, Some(codes))
+            }
+        },
+    }
+}
+
+/// The comma-separated tags from `# tags: <tag>, <tag>` comment lines in a
+/// command block, in order and without duplicates.
+fn extract_tags(content: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in content
+        .lines()
+        .filter_map(|line| annotation_value(line, "tags:"))
+        .flat_map(|value| value.split(','))
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+    {
+        if !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+/// The trimmed text after `# <key>` on a comment line.
+fn annotation_value<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    Some(
+        line.trim()
+            .strip_prefix('#')?
+            .trim_start()
+            .strip_prefix(key)?
+            .trim(),
+    )
+}
+
+/// Whether a trailing comment (as split off by `strip_trailing_comment`) is
+/// the `# pave: skip` annotation.
+fn is_skip_comment(comment: &str) -> bool {
+    comment
+        .trim()
+        .strip_prefix('#')
+        .and_then(|rest| rest.trim().strip_prefix("pave:"))
+        .is_some_and(|rest| rest.trim() == "skip")
+}
 
-        if !cmd.is_empty() {
-            commands.push(cmd);
+/// Whether a line is the doc-level `# pave: serial` directive.
+fn is_serial_directive(line: &str) -> bool {
+    annotation_value(line, "pave:") == Some("serial")
+}
+
+/// Strip a leading `$ ` or `> ` prompt from a trimmed line.
+fn strip_prompt(line: &str) -> &str {
+    line.strip_prefix("$ ")
+        .or_else(|| line.strip_prefix("> "))
+        .unwrap_or(line)
+}
+
+/// Characters of `line`, with their byte offsets, that are outside quotes
+/// and not escaped.
+fn unquoted_chars(line: &str) -> Vec<(usize, char)> {
+    let mut chars = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') | (None, '\\') => escaped = true,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, c) => chars.push((i, c)),
         }
     }
+    chars
+}
 
-    commands.join(" && ")
+/// Remove a trailing `# comment` from a command line.
+fn strip_trailing_comment(command: &str) -> &str {
+    unquoted_chars(command)
+        .into_iter()
+        .find(|&(i, c)| c == '#' && (i == 0 || command[..i].ends_with(char::is_whitespace)))
+        .map_or(command, |(i, _)| command[..i].trim_end())
+}
+
+/// Wrap a command using `;` or `||` in `{ ...; }` so it chains as one unit.
+fn group_list(command: &str) -> String {
+    let chars = unquoted_chars(command);
+    let is_list = chars
+        .iter()
+        .enumerate()
+        .any(|(n, &(i, c))| c == ';' || (c == '|' && chars.get(n + 1) == Some(&(i + 1, '|'))));
+    if is_list {
+        format!("{{ {}; }}", command.trim_end_matches(';').trim_end())
+    } else {
+        command.to_string()
+    }
+}
+
+/// A heredoc opened by a command line.
+#[derive(Debug, PartialEq)]
+struct Heredoc {
+    /// The closing delimiter, with any quotes removed.
+    delimiter: String,
+    /// Whether leading tabs are stripped (`<<-`), so the delimiter may be indented.
+    strip_tabs: bool,
+}
+
+impl Heredoc {
+    /// Consume lines up to and including the closing delimiter.
+    ///
+    /// Returns the consumed lines unchanged. An unterminated heredoc runs to
+    /// the end of the block.
+    fn read_body<'a>(&self, lines: &mut impl Iterator<Item = &'a str>) -> String {
+        let mut body = Vec::new();
+        for line in lines {
+            body.push(line);
+            let candidate = if self.strip_tabs {
+                line.trim_start_matches('\t')
+            } else {
+                line
+            };
+            if candidate.trim_end() == self.delimiter {
+                break;
+            }
+        }
+        body.join("\n")
+    }
+}
+
+/// Find the heredocs opened by a command line, in order.
+///
+/// Ignores `<<` inside quotes, in trailing comments, and in here-strings (`<<<`).
+fn heredocs_in(command: &str) -> Vec<Heredoc> {
+    let chars: Vec<char> = command.chars().collect();
+    let mut heredocs = Vec::new();
+    let mut quote: Option<char> = None;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if let Some(q) = quote {
+            if c == q {
+                quote = None;
+            } else if c == '\\' && q == '"' {
+                i += 1;
+            }
+            i += 1;
+            continue;
+        }
+
+        match c {
+            '\'' | '"' => quote = Some(c),
+            '\\' => i += 1,
+            '#' if i == 0 || chars[i - 1].is_whitespace() => break,
+            '<' if chars.get(i + 1) == Some(&'<') && chars.get(i + 2) != Some(&'<') => {
+                i += 2;
+                let strip_tabs = chars.get(i) == Some(&'-');
+                if strip_tabs {
+                    i += 1;
+                }
+                while chars.get(i).is_some_and(|c| c.is_whitespace()) {
+                    i += 1;
+                }
+
+                let mut delimiter = String::new();
+                while let Some(&c) = chars.get(i) {
+                    match c {
+                        '\'' | '"' => {
+                            i += 1;
+                            while let Some(&inner) = chars.get(i) {
+                                i += 1;
+                                if inner == c {
+                                    break;
+                                }
+                                delimiter.push(inner);
+                            }
+                            continue;
+                        }
+                        '\\' => {}
+                        c if c.is_whitespace() || ";|&<>()".contains(c) => break,
+                        c => delimiter.push(c),
+                    }
+                    i += 1;
+                }
+
+                if !delimiter.is_empty() {
+                    heredocs.push(Heredoc {
+                        delimiter,
+                        strip_tabs,
+                    });
+                }
+                continue;
+            }
+            '<' if chars.get(i + 1) == Some(&'<') => {
+                // Here-string: skip all three `<`
+                i += 3;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    heredocs
 }
 
 /// Execute all verification items in a specification.
@@ -212,7 +840,11 @@ pub fn run_verification(spec: &VerificationSpec) -> Vec<VerificationResult> {
 
 /// Execute a single verification item.
 fn run_single_verification(item: &VerificationItem) -> VerificationResult {
-    let timeout = Duration::from_secs(item.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS) as u64);
+    // A zero timeout means the command may run indefinitely
+    let timeout = match item.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS) {
+        0 => Duration::MAX,
+        secs => Duration::from_secs(secs as u64),
+    };
     let start = Instant::now();
 
     // Clone item for the result
@@ -265,8 +897,7 @@ fn run_single_verification(item: &VerificationItem) -> VerificationResult {
             let stdout = String::from_utf8_lossy(&output.stdout).to_string();
             let stderr = String::from_utf8_lossy(&output.stderr).to_string();
 
-            let expected_code = item.expected_exit_code.unwrap_or(0);
-            let code_matches = exit_code == Some(expected_code);
+            let code_matches = exit_code.is_some_and(|code| item.expects_exit_code(code));
 
             let output_matches = match &item.expected_output {
                 None => true,
@@ -332,15 +963,25 @@ mod tests {
     use super::*;
     use std::path::PathBuf;
 
+    fn extract_command_from_block(content: &str) -> String {
+        extract_commands(content).0
+    }
+
     #[test]
     fn test_successful_command_returns_passed_true() {
         let item = VerificationItem {
             command: "echo hello".to_string(),
             working_dir: None,
             expected_exit_code: Some(0),
+            expected_exit_codes: None,
             expected_output: None,
             timeout_secs: Some(5),
             env_vars: Vec::new(),
+            source_lines: Vec::new(),
+            label: None,
+            tags: Vec::new(),
+            raw: String::new(),
+            interpreter: None,
         };
 
         let result = run_single_verification(&item);
@@ -357,9 +998,15 @@ mod tests {
             command: "exit 1".to_string(),
             working_dir: None,
             expected_exit_code: Some(0),
+            expected_exit_codes: None,
             expected_output: None,
             timeout_secs: Some(5),
             env_vars: Vec::new(),
+            source_lines: Vec::new(),
+            label: None,
+            tags: Vec::new(),
+            raw: String::new(),
+            interpreter: None,
         };
 
         let result = run_single_verification(&item);
@@ -374,9 +1021,15 @@ mod tests {
             command: "sleep 10".to_string(),
             working_dir: None,
             expected_exit_code: Some(0),
+            expected_exit_codes: None,
             expected_output: None,
             timeout_secs: Some(1),
             env_vars: Vec::new(),
+            source_lines: Vec::new(),
+            label: None,
+            tags: Vec::new(),
+            raw: String::new(),
+            interpreter: None,
         };
 
         let result = run_single_verification(&item);
@@ -391,9 +1044,15 @@ mod tests {
             command: "echo 'test output'".to_string(),
             working_dir: None,
             expected_exit_code: Some(0),
+            expected_exit_codes: None,
             expected_output: None,
             timeout_secs: Some(5),
             env_vars: Vec::new(),
+            source_lines: Vec::new(),
+            label: None,
+            tags: Vec::new(),
+            raw: String::new(),
+            interpreter: None,
         };
 
         let result = run_single_verification(&item);
@@ -408,9 +1067,15 @@ mod tests {
             command: "echo 'error message' >&2".to_string(),
             working_dir: None,
             expected_exit_code: Some(0),
+            expected_exit_codes: None,
             expected_output: None,
             timeout_secs: Some(5),
             env_vars: Vec::new(),
+            source_lines: Vec::new(),
+            label: None,
+            tags: Vec::new(),
+            raw: String::new(),
+            interpreter: None,
         };
 
         let result = run_single_verification(&item);
@@ -425,9 +1090,15 @@ mod tests {
             command: "nonexistent_command_12345".to_string(),
             working_dir: None,
             expected_exit_code: Some(0),
+            expected_exit_codes: None,
             expected_output: None,
             timeout_secs: Some(5),
             env_vars: Vec::new(),
+            source_lines: Vec::new(),
+            label: None,
+            tags: Vec::new(),
+            raw: String::new(),
+            interpreter: None,
         };
 
         let result = run_single_verification(&item);
@@ -443,9 +1114,15 @@ mod tests {
             command: "exit 42".to_string(),
             working_dir: None,
             expected_exit_code: Some(42),
+            expected_exit_codes: None,
             expected_output: None,
             timeout_secs: Some(5),
             env_vars: Vec::new(),
+            source_lines: Vec::new(),
+            label: None,
+            tags: Vec::new(),
+            raw: String::new(),
+            interpreter: None,
         };
 
         let result = run_single_verification(&item);
@@ -460,9 +1137,15 @@ mod tests {
             command: "echo 'hello world'".to_string(),
             working_dir: None,
             expected_exit_code: Some(0),
+            expected_exit_codes: None,
             expected_output: Some(OutputMatcher::Contains("world".to_string())),
             timeout_secs: Some(5),
             env_vars: Vec::new(),
+            source_lines: Vec::new(),
+            label: None,
+            tags: Vec::new(),
+            raw: String::new(),
+            interpreter: None,
         };
 
         let result = run_single_verification(&item);
@@ -476,9 +1159,15 @@ mod tests {
             command: "echo 'hello world'".to_string(),
             working_dir: None,
             expected_exit_code: Some(0),
+            expected_exit_codes: None,
             expected_output: Some(OutputMatcher::Contains("foo".to_string())),
             timeout_secs: Some(5),
             env_vars: Vec::new(),
+            source_lines: Vec::new(),
+            label: None,
+            tags: Vec::new(),
+            raw: String::new(),
+            interpreter: None,
         };
 
         let result = run_single_verification(&item);
@@ -492,9 +1181,15 @@ mod tests {
             command: "sleep 0.1".to_string(),
             working_dir: None,
             expected_exit_code: Some(0),
+            expected_exit_codes: None,
             expected_output: None,
             timeout_secs: Some(5),
             env_vars: Vec::new(),
+            source_lines: Vec::new(),
+            label: None,
+            tags: Vec::new(),
+            raw: String::new(),
+            interpreter: None,
         };
 
         let result = run_single_verification(&item);
@@ -537,6 +1232,190 @@ mod tests {
         assert_eq!(cmd, "echo hello && echo world");
     }
 
+    #[test]
+    fn test_extract_command_from_block_keeps_heredoc_with_its_command() {
+        let content = "$ cat <<EOF > greeting.txt\nhello\n\nworld\nEOF\n$ grep hello greeting.txt";
+        let cmd = extract_command_from_block(content);
+        assert_eq!(
+            cmd,
+            "cat <<EOF > greeting.txt && grep hello greeting.txt\nhello\n\nworld\nEOF"
+        );
+    }
+
+    #[test]
+    fn test_extract_command_from_block_joins_continuation_prompts() {
+        let content = "$ for i in 1 2; do\n>   echo $i\n> done\n$ echo end";
+        let cmd = extract_command_from_block(content);
+        assert_eq!(cmd, "{ for i in 1 2; do\n  echo $i\ndone; } && echo end");
+
+        let result = run_single_verification(&VerificationItem {
+            command: cmd,
+            ..Default::default()
+        });
+        assert!(result.passed);
+        assert_eq!(result.stdout, "1\n2\nend\n");
+    }
+
+    #[test]
+    fn test_extract_command_from_block_continuation_prompts_in_heredoc() {
+        let content = "$ cat <<EOF\n> hi\n> EOF";
+        let cmd = extract_command_from_block(content);
+        assert_eq!(cmd, "cat <<EOF\nhi\nEOF");
+    }
+
+    #[test]
+    fn test_extract_command_from_block_heredoc_lines_are_not_commands() {
+        let content =
+            "cat <<-'END'\n\t$ rm -rf build\n\t# not a comment\n\techo inside\n\tEND\necho after";
+        let cmd = extract_command_from_block(content);
+        assert_eq!(
+            cmd,
+            "cat <<-'END' && echo after\n\t$ rm -rf build\n\t# not a comment\n\techo inside\n\tEND"
+        );
+
+        let item = VerificationItem {
+            command: cmd,
+            ..Default::default()
+        };
+        let result = run_single_verification(&item);
+        assert!(result.passed);
+        assert_eq!(
+            result.stdout,
+            "$ rm -rf build\n# not a comment\necho inside\nafter\n"
+        );
+    }
+
+    #[test]
+    fn test_heredocs_in_parses_delimiters() {
+        let heredoc = |delimiter: &str, strip_tabs| Heredoc {
+            delimiter: delimiter.to_string(),
+            strip_tabs,
+        };
+        assert_eq!(heredocs_in("cat <<EOF"), vec![heredoc("EOF", false)]);
+        assert_eq!(
+            heredocs_in("cat <<- \"END\" | wc"),
+            vec![heredoc("END", true)]
+        );
+        assert_eq!(
+            heredocs_in("diff <<A /dev/stdin <<'B'"),
+            vec![heredoc("A", false), heredoc("B", false)]
+        );
+        assert!(heredocs_in("grep x <<< \"$input\"").is_empty());
+        assert!(heredocs_in("echo '<<EOF'").is_empty());
+        assert!(heredocs_in("echo hi # <<EOF").is_empty());
+    }
+
+    #[test]
+    fn test_chained_line_is_a_single_item() {
+        for line in ["cd src && cargo test", "make a; make b", "make a || make b"] {
+            let content = format!("# Doc\n\n## Verification\n```bash\n$ {line}\n```\n");
+            let doc = ParsedDoc::parse_content(PathBuf::from("doc.md"), &content).unwrap();
+            let spec = extract_verification_spec(&doc).unwrap();
+            assert_eq!(spec.items.len(), 1, "{line}");
+            assert_eq!(spec.items[0].command, line);
+        }
+    }
+
+    #[test]
+    fn test_extract_command_from_block_groups_lists_across_lines() {
+        let content = "false\necho a; echo b\necho c || true";
+        let cmd = extract_command_from_block(content);
+        assert_eq!(cmd, "false && { echo a; echo b; } && { echo c || true; }");
+
+        // `echo b` must not run once the first line has failed
+        let item = VerificationItem {
+            command: cmd,
+            ..Default::default()
+        };
+        let result = run_single_verification(&item);
+        assert!(!result.passed);
+        assert_eq!(result.stdout, "");
+    }
+
+    #[test]
+    fn test_extract_command_from_block_joins_continuations_and_drops_comments() {
+        let content = "$ cargo test \\\n    --all-features # slow\n$ echo '# kept'";
+        let cmd = extract_command_from_block(content);
+        assert_eq!(cmd, "cargo test --all-features && echo '# kept'");
+    }
+
+    #[test]
+    fn test_extract_commands_drops_skip_annotated_lines() {
+        let content = "$ make build\n$ rm -rf /  # pave: skip\n$ make test #pave:skip\n$ echo done";
+        assert_eq!(
+            extract_commands(content),
+            ("make build && echo done".to_string(), 2)
+        );
+        // Only the exact annotation skips; other comments are just dropped
+        assert_eq!(
+            extract_commands("ls # pave: skipping is fine"),
+            ("ls".to_string(), 0)
+        );
+    }
+
+    #[test]
+    fn test_extract_verification_spec_omits_skipped_commands_and_blocks() {
+        let content = r#"# Test Doc
+
+## Verification
+
+```bash skip
+rm -rf build
+```
+
+```bash
+cargo build
+```
+
+```bash
+cargo clean # pave: skip
+```
+
+```bash
+cargo clean # pave: skip
+cargo test
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+
+        let commands: Vec<_> = spec.items.iter().map(|i| i.command.as_str()).collect();
+        assert_eq!(commands, vec!["cargo build", "cargo test"]);
+        assert_eq!(spec.items[1].source_lines, vec![17]);
+        assert_eq!(spec.skipped, 3);
+    }
+
+    #[test]
+    fn test_setup_and_teardown_sections_become_hooks() {
+        let content = "---\npave:\n  working_dir: app\n---\n# Doc\n\n## Setup\n```bash\ndocker compose up -d\n```\n\n## Verification\n```bash\ncurl localhost\n```\n\n## Teardown\n```bash\ndocker compose down\n```\n";
+        let doc = ParsedDoc::parse_content(PathBuf::from("doc.md"), content).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+
+        assert_eq!(spec.items.len(), 1);
+        assert_eq!(spec.setup.len(), 1);
+        assert_eq!(spec.setup[0].command, "docker compose up -d");
+        assert_eq!(spec.setup[0].working_dir, Some(PathBuf::from("app")));
+        assert_eq!(spec.teardown[0].command, "docker compose down");
+    }
+
+    #[test]
+    fn test_shebang_block_is_one_interpreter_item() {
+        let content = "# Doc\n\n## Verification\n```bash\n#!/usr/bin/env python\nimport json\nprint(json.dumps({\"ok\": True}))\n```\n\n```bash\necho one\necho two\n```\n";
+        let doc = ParsedDoc::parse_content(PathBuf::from("doc.md"), content).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+
+        assert_eq!(spec.items.len(), 2);
+        let script = &spec.items[0];
+        assert_eq!(script.interpreter.as_deref(), Some("/usr/bin/env python"));
+        assert_eq!(
+            script.command,
+            "#!/usr/bin/env python\nimport json\nprint(json.dumps({\"ok\": True}))"
+        );
+        assert_eq!(spec.items[1].interpreter, None);
+        assert_eq!(spec.items[1].command, "echo one && echo two");
+    }
+
     #[test]
     fn test_extract_verification_spec_from_doc() {
         let content = r#"# Test Doc
@@ -558,6 +1437,152 @@ echo "test"
         assert_eq!(spec.items[0].command, "echo \"test\"");
     }
 
+    #[test]
+    fn test_comments_survive_in_prompt_block_but_not_in_command() {
+        let content = r#"# Test
+
+## Verification
+```bash
+# Build first so the test binary links
+cargo build  # debug profile
+cargo test
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+        let item = &spec.items[0];
+        assert_eq!(item.command, "cargo build && cargo test");
+        assert!(!item.command.contains('#'));
+
+        let block = item.prompt_block();
+        assert!(block.starts_with("```bash\n# Build first so the test binary links\n"));
+        assert!(block.contains("cargo build  # debug profile\n"));
+        assert!(block.ends_with("cargo test\n```\n"));
+
+        // Items built without source text still render their command
+        let bare = VerificationItem {
+            command: "make check".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(bare.prompt_block(), "```bash\nmake check\n```\n");
+    }
+
+    #[test]
+    fn test_extract_tags_splits_and_dedupes() {
+        assert_eq!(
+            extract_tags("# tags: smoke, slow,\n#tags:smoke,ci\ncargo test"),
+            vec!["smoke", "slow", "ci"]
+        );
+        assert!(extract_tags("# name: tests\ncargo test").is_empty());
+    }
+
+    #[test]
+    fn test_extract_verification_spec_reads_name_labels() {
+        let content = r#"# Test Doc
+
+## Verification
+```bash
+# name: Full test suite
+cargo test --workspace --all-features
+```
+
+```bash
+# name:
+echo unlabeled
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+        assert_eq!(spec.items[0].label.as_deref(), Some("Full test suite"));
+        assert_eq!(
+            spec.items[0].command,
+            "cargo test --workspace --all-features"
+        );
+        assert_eq!(spec.items[1].label, None);
+    }
+
+    #[test]
+    fn test_extract_expect_exit_sets_ranges_and_single_codes() {
+        let content = "# Test\n\n## Verification\n\n```bash\n# expect-exit: 0,1\ngit diff --quiet\n```\n\n```bash\n# expect-exit: 0-2\n./lint\n```\n\n```bash\n# expect-exit: 3\nexit 3\n```\n\n```bash\n# expect-exit: often\ntrue\n```\n";
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let items = extract_verification_spec(&doc).unwrap().items;
+        assert_eq!(items[0].command, "git diff --quiet");
+
+        let set = &items[0];
+        assert_eq!(set.expected_exit_description(), "0,1");
+        assert!(set.expects_exit_code(0) && set.expects_exit_code(1));
+        assert!(!set.expects_exit_code(2));
+
+        let range = &items[1];
+        assert_eq!(range.expected_exit_description(), "0-2");
+        assert!((0..=2).all(|code| range.expects_exit_code(code)));
+        assert!(!range.expects_exit_code(3));
+
+        // A single code stays the simple case
+        assert_eq!(items[2].expected_exit_code, Some(3));
+        assert_eq!(items[2].expected_exit_codes, None);
+        assert!(items[2].expects_exit_code(3) && !items[2].expects_exit_code(0));
+
+        // An unparseable annotation leaves the default of 0
+        assert_eq!(items[3].expected_exit_code, Some(0));
+        assert_eq!(items[3].expected_exit_codes, None);
+    }
+
+    #[test]
+    fn test_exit_codes_parse_rejects_malformed_specs() {
+        assert_eq!(ExitCodes::parse("0, 2-4").unwrap().to_string(), "0,2-4");
+        assert!(ExitCodes::parse("").is_none());
+        assert!(ExitCodes::parse("2-1").is_none());
+        assert!(ExitCodes::parse("0,,1").is_none());
+        assert!(ExitCodes::parse("one").is_none());
+    }
+
+    #[test]
+    fn test_extract_verification_spec_uses_configured_languages() {
+        let content = "# Test\n\n## Verification\n\n```zsh\nsetopt extendedglob\n```\n";
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        assert!(extract_verification_spec(&doc).is_none());
+
+        let options = crate::parser::ParseOptions {
+            executable_languages: vec!["bash".to_string(), "zsh".to_string()],
+            ..Default::default()
+        };
+        let doc =
+            ParsedDoc::parse_content_with(PathBuf::from("test.md"), content, &options).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+        assert_eq!(spec.items.len(), 1);
+        assert_eq!(spec.items[0].command, "setopt extendedglob");
+    }
+
+    #[test]
+    fn test_extract_verification_spec_runs_pave_tagged_fences() {
+        let content = "# Doc\n\n## Verification\n```pave\nmake check\n```\n\n```text pave\nmake lint\n```\n\n```text\nmake illustrative\n```\n";
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+        let commands: Vec<&str> = spec.items.iter().map(|i| i.command.as_str()).collect();
+        assert_eq!(commands, vec!["make check", "make lint"]);
+    }
+
+    #[test]
+    fn test_extract_verification_spec_require_pave_tag_ignores_untagged_bash() {
+        let content = "# Doc\n\n## Verification\n```bash\nrm -rf /tmp/example\n```\n\n<!-- pave:run -->\n```\n$ make deploy\n```\n\n```bash pave\nmake test\n```\n";
+        let options = crate::parser::ParseOptions {
+            require_pave_tag: true,
+            ..Default::default()
+        };
+
+        let doc =
+            ParsedDoc::parse_content_with(PathBuf::from("test.md"), content, &options).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+        assert_eq!(spec.items.len(), 1);
+        assert_eq!(spec.items[0].command, "make test");
+    }
+
     #[test]
     fn test_extract_verification_spec_no_verification_section() {
         let content = r#"# Test Doc
@@ -607,27 +1632,99 @@ echo "second"
         assert_eq!(spec.items.len(), 2);
     }
 
+    #[test]
+    fn test_dedupe_collapses_identical_commands() {
+        let content = r#"# Test
+
+## Verification
+
+```bash
+cargo test
+```
+
+```bash
+cargo build
+```
+
+```bash
+cargo test
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let mut spec = extract_verification_spec(&doc).unwrap();
+        assert_eq!(spec.items.len(), 3);
+
+        spec.dedupe();
+        assert_eq!(spec.items.len(), 2);
+        assert_eq!(spec.items[0].command, "cargo test");
+        assert_eq!(spec.items[0].source_lines, vec![5, 13]);
+        assert_eq!(spec.items[1].command, "cargo build");
+    }
+
+    #[test]
+    fn test_dedupe_keeps_commands_with_different_expectations() {
+        let mut spec = VerificationSpec {
+            source_file: PathBuf::from("test.md"),
+            section_line: 1,
+            skipped: 0,
+            serial: false,
+            setup: vec![],
+            teardown: vec![],
+            items: vec![
+                VerificationItem {
+                    command: "make check".to_string(),
+                    ..Default::default()
+                },
+                VerificationItem {
+                    command: "make check".to_string(),
+                    env_vars: vec![("CI".to_string(), "1".to_string())],
+                    ..Default::default()
+                },
+            ],
+        };
+
+        spec.dedupe();
+        assert_eq!(spec.items.len(), 2);
+    }
+
     #[test]
     fn test_run_verification_executes_all_items() {
         let spec = VerificationSpec {
             source_file: PathBuf::from("test.md"),
             section_line: 1,
+            skipped: 0,
+            serial: false,
+            setup: vec![],
+            teardown: vec![],
             items: vec![
                 VerificationItem {
                     command: "echo 'first'".to_string(),
                     working_dir: None,
                     expected_exit_code: Some(0),
+                    expected_exit_codes: None,
                     expected_output: None,
                     timeout_secs: Some(5),
                     env_vars: Vec::new(),
+                    source_lines: Vec::new(),
+                    label: None,
+                    tags: Vec::new(),
+                    raw: String::new(),
+                    interpreter: None,
                 },
                 VerificationItem {
                     command: "echo 'second'".to_string(),
                     working_dir: None,
                     expected_exit_code: Some(0),
+                    expected_exit_codes: None,
                     expected_output: None,
                     timeout_secs: Some(5),
                     env_vars: Vec::new(),
+                    source_lines: Vec::new(),
+                    label: None,
+                    tags: Vec::new(),
+                    raw: String::new(),
+                    interpreter: None,
                 },
             ],
         };
@@ -647,9 +1744,15 @@ echo "second"
             command: "echo 'Hello, World!'".to_string(),
             working_dir: None,
             expected_exit_code: Some(0),
+            expected_exit_codes: None,
             expected_output: Some(OutputMatcher::Contains("Hello, World!".to_string())),
             timeout_secs: Some(5),
             env_vars: Vec::new(),
+            source_lines: Vec::new(),
+            label: None,
+            tags: Vec::new(),
+            raw: String::new(),
+            interpreter: None,
         };
 
         let result = run_single_verification(&item);
@@ -667,9 +1770,15 @@ echo "second"
             command: "echo 'test 123 passed'".to_string(),
             working_dir: None,
             expected_exit_code: Some(0),
+            expected_exit_codes: None,
             expected_output: Some(OutputMatcher::Regex(r"test \d+ passed".to_string())),
             timeout_secs: Some(5),
             env_vars: Vec::new(),
+            source_lines: Vec::new(),
+            label: None,
+            tags: Vec::new(),
+            raw: String::new(),
+            interpreter: None,
         };
 
         let result = run_single_verification(&item);
@@ -683,9 +1792,15 @@ echo "second"
             command: "echo 'test abc passed'".to_string(),
             working_dir: None,
             expected_exit_code: Some(0),
+            expected_exit_codes: None,
             expected_output: Some(OutputMatcher::Regex(r"test \d+ passed".to_string())),
             timeout_secs: Some(5),
             env_vars: Vec::new(),
+            source_lines: Vec::new(),
+            label: None,
+            tags: Vec::new(),
+            raw: String::new(),
+            interpreter: None,
         };
 
         let result = run_single_verification(&item);
@@ -699,9 +1814,15 @@ echo "second"
             command: "echo 'hello'".to_string(),
             working_dir: None,
             expected_exit_code: Some(0),
+            expected_exit_codes: None,
             expected_output: Some(OutputMatcher::Exact("hello".to_string())),
             timeout_secs: Some(5),
             env_vars: Vec::new(),
+            source_lines: Vec::new(),
+            label: None,
+            tags: Vec::new(),
+            raw: String::new(),
+            interpreter: None,
         };
 
         let result = run_single_verification(&item);
@@ -715,9 +1836,15 @@ echo "second"
             command: "echo 'hello world'".to_string(),
             working_dir: None,
             expected_exit_code: Some(0),
+            expected_exit_codes: None,
             expected_output: Some(OutputMatcher::Exact("hello".to_string())),
             timeout_secs: Some(5),
             env_vars: Vec::new(),
+            source_lines: Vec::new(),
+            label: None,
+            tags: Vec::new(),
+            raw: String::new(),
+            interpreter: None,
         };
 
         let result = run_single_verification(&item);
@@ -752,6 +1879,46 @@ hello
         }
     }
 
+    #[test]
+    fn test_extract_verification_spec_ignores_output_between_commands() {
+        let content = r#"# Test Doc
+
+## Verification
+```bash
+$ echo one
+one
+$ echo two \
+>   three
+two three
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+
+        assert_eq!(spec.items.len(), 1);
+        assert_eq!(spec.items[0].command, "echo one && echo two \\\n  three");
+        let result = run_single_verification(&spec.items[0]);
+        assert_eq!(result.stdout, "one\ntwo three\n");
+    }
+
+    #[test]
+    fn test_extract_verification_spec_serial_directive() {
+        let content = "# Test Doc\n\n## Verification\n```bash\n# pave: serial\nmake db-reset\n```\n\n```bash\nmake db-test\n```\n";
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+        assert!(spec.serial);
+        assert_eq!(spec.items[0].command, "make db-reset");
+
+        let doc = ParsedDoc::parse_content(
+            PathBuf::from("test.md"),
+            &content.replace("# pave: serial\n", ""),
+        )
+        .unwrap();
+        assert!(!extract_verification_spec(&doc).unwrap().serial);
+    }
+
     #[test]
     fn test_extract_verification_spec_with_explicit_output_block() {
         let content = r#"# Test Doc
@@ -892,9 +2059,15 @@ cargo test
             command: "echo $MY_VAR".to_string(),
             working_dir: None,
             expected_exit_code: Some(0),
+            expected_exit_codes: None,
             expected_output: Some(OutputMatcher::Contains("hello_from_env".to_string())),
             timeout_secs: Some(5),
             env_vars: vec![("MY_VAR".to_string(), "hello_from_env".to_string())],
+            source_lines: Vec::new(),
+            label: None,
+            tags: Vec::new(),
+            raw: String::new(),
+            interpreter: None,
         };
 
         let result = run_single_verification(&item);
@@ -903,6 +2076,70 @@ cargo test
         assert!(result.stdout.contains("hello_from_env"));
     }
 
+    #[test]
+    fn test_apply_base_dir_resolves_against_doc_directory() {
+        let content = r#"# Test Doc
+
+## Verification
+
+```bash
+ls
+```
+
+```bash
+make check
+```
+"#;
+        let doc =
+            ParsedDoc::parse_content(PathBuf::from("docs/runbooks/deploy.md"), content).unwrap();
+        let mut spec = extract_verification_spec(&doc).unwrap();
+        spec.apply_base_dir(Path::new("."));
+        assert_eq!(
+            spec.items[0].working_dir,
+            Some(PathBuf::from("docs/runbooks"))
+        );
+        assert_eq!(
+            spec.items[1].working_dir,
+            Some(PathBuf::from("docs/runbooks"))
+        );
+
+        let mut spec = extract_verification_spec(&doc).unwrap();
+        spec.apply_base_dir(Path::new("../../services/api"));
+        assert_eq!(
+            spec.items[0].working_dir,
+            Some(PathBuf::from("services/api"))
+        );
+
+        let mut spec = extract_verification_spec(&doc).unwrap();
+        spec.apply_base_dir(Path::new("../.."));
+        assert_eq!(spec.items[0].working_dir, Some(PathBuf::from(".")));
+    }
+
+    #[test]
+    fn test_apply_base_dir_keeps_per_item_working_dir() {
+        let content = r#"# Test Doc
+
+## Verification
+
+```bash
+ls
+```
+
+<!-- pave:working_dir src/tests -->
+```bash
+cargo test
+```
+"#;
+        let doc = ParsedDoc::parse_content(PathBuf::from("docs/guide.md"), content).unwrap();
+        let mut spec = extract_verification_spec(&doc).unwrap();
+        spec.apply_base_dir(Path::new("fixtures"));
+        assert_eq!(
+            spec.items[0].working_dir,
+            Some(PathBuf::from("docs/fixtures"))
+        );
+        assert_eq!(spec.items[1].working_dir, Some(PathBuf::from("src/tests")));
+    }
+
     #[test]
     fn test_frontmatter_working_dir_applies_to_all_blocks() {
         let content = r#"---
@@ -937,4 +2174,75 @@ echo second
             Some(PathBuf::from("packages/shared"))
         );
     }
+
+    #[test]
+    fn test_output_block_sets_expected_output_of_preceding_command() {
+        let content = r#"# Test
+
+## Verification
+```bash
+echo "hello world"
+```
+
+```output
+hello world
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+
+        assert_eq!(spec.items.len(), 1);
+        assert_eq!(
+            spec.items[0].expected_output,
+            Some(OutputMatcher::Contains("hello world".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_inline_expectation_takes_precedence_over_output_block() {
+        let content = r#"# Test
+
+## Verification
+```bash
+echo done
+```
+<!-- pave:expect:exact -->
+```
+done
+```
+```output
+something else
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+
+        assert_eq!(
+            spec.items[0].expected_output,
+            Some(OutputMatcher::Exact("done".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_dangling_output_block_is_ignored() {
+        let content = r#"# Test
+
+## Verification
+```output
+orphaned output
+```
+```bash
+echo first
+```
+"#;
+
+        let doc = ParsedDoc::parse_content(PathBuf::from("test.md"), content).unwrap();
+        let spec = extract_verification_spec(&doc).unwrap();
+
+        assert_eq!(spec.items.len(), 1);
+        assert_eq!(spec.items[0].command, "echo first");
+        assert_eq!(spec.items[0].expected_output, None);
+    }
 }
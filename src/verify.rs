@@ -0,0 +1,1038 @@
+//! Run verification specs programmatically.
+//!
+//! This is the engine behind `pave verify`, exposed so other tools (a Rust
+//! test harness, for example) can run documentation checks without shelling
+//! out to the CLI.
+//!
+//! ```
+//! use pave::verification::{OutputMatcher, VerificationItem, VerificationSpec};
+//! use pave::verify::{RunOptions, run_spec};
+//!
+//! let spec = VerificationSpec {
+//!     source_file: "docs/example.md".into(),
+//!     section_line: 1,
+//!     skipped: 0,
+//!     serial: false,
+//!     setup: vec![],
+//!     teardown: vec![],
+//!     items: vec![VerificationItem {
+//!         command: "echo hello".to_string(),
+//!         expected_output: Some(OutputMatcher::Contains("hello".to_string())),
+//!         ..Default::default()
+//!     }],
+//! };
+//!
+//! let results = run_spec(&spec, &RunOptions::default());
+//! assert!(results[0].passed());
+//! assert_eq!(results[0].exit_code, Some(0));
+//! ```
+
+use regex::Regex;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::error::{Error, Result};
+use crate::verification::{
+    DEFAULT_TIMEOUT_SECS, OutputMatcher, VerificationItem, VerificationSpec,
+};
+
+/// How often to poll a running command for completion.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Options controlling how a [`VerificationSpec`] is run.
+#[derive(Debug, Clone)]
+pub struct RunOptions {
+    /// Maximum number of items to run at once. `1` (the default) runs items
+    /// one after another; items in the same [`serial_groups`] group, such as
+    /// those sharing a directory, never overlap. Results are always returned
+    /// in item order.
+    pub jobs: usize,
+    /// Timeout applied to every item, overriding each item's `timeout_secs`.
+    /// A zero duration disables the timeout.
+    pub timeout: Option<Duration>,
+    /// Timeout for items without their own `timeout_secs` (default: 30
+    /// seconds). A zero duration disables the timeout.
+    pub default_timeout: Option<Duration>,
+    /// Environment variables set for every command. Variables declared on an
+    /// item take precedence.
+    pub env: Vec<(String, String)>,
+    /// Which output streams to capture into the result (default: both).
+    /// Uncaptured streams go straight to the parent's stdout/stderr. Items
+    /// with expected output always capture stdout (see [`Capture::for_item`]).
+    pub capture: Capture,
+    /// Directory to run commands in when an item has no working directory.
+    pub working_dir: Option<PathBuf>,
+    /// Interpreter each command is passed to (default: [`Shell::default`]).
+    pub shell: Shell,
+    /// Match expected output strictly (see [`check_output`]).
+    pub strict_output: bool,
+    /// Run each item without its own working directory in a fresh, empty
+    /// temporary directory, removed once the item finishes.
+    pub isolated: bool,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            jobs: 1,
+            timeout: None,
+            default_timeout: None,
+            env: Vec::new(),
+            capture: Capture::All,
+            working_dir: None,
+            shell: Shell::default(),
+            strict_output: false,
+            isolated: false,
+        }
+    }
+}
+
+/// Which of a command's output streams are captured rather than inherited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Capture {
+    /// Inherit stdout and stderr, streaming output live without buffering it.
+    None,
+    /// Capture stdout; stderr is inherited.
+    Stdout,
+    /// Capture both stdout and stderr.
+    #[default]
+    All,
+}
+
+impl Capture {
+    /// The capture `item` actually runs with: an item with expected output
+    /// needs its stdout to match against, so `None` becomes `Stdout` for it.
+    pub fn for_item(self, item: &VerificationItem) -> Self {
+        let has_matcher = item
+            .expected_output
+            .as_ref()
+            .is_some_and(|m| !matches!(m, OutputMatcher::ExitCodeOnly));
+        match self {
+            Capture::None if has_matcher => Capture::Stdout,
+            capture => capture,
+        }
+    }
+}
+
+/// The interpreter verification commands run under, such as `sh -c` or
+/// `pwsh -Command`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shell {
+    /// Program name (looked up on `PATH`) or path to the interpreter.
+    pub program: String,
+    /// Arguments placed before the command string.
+    pub args: Vec<String>,
+}
+
+impl Shell {
+    /// Build a shell for `program`, inferring how it takes a command string:
+    /// `-Command` for PowerShell, `/C` for `cmd`, and `-c` for everything else.
+    pub fn new(program: impl Into<String>) -> Self {
+        let program = program.into();
+        let name = Path::new(&program)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_ascii_lowercase())
+            .unwrap_or_default();
+        let flag = match name.as_str() {
+            "pwsh" | "powershell" => "-Command",
+            "cmd" => "/C",
+            _ => "-c",
+        };
+        Self {
+            program,
+            args: vec![flag.to_string()],
+        }
+    }
+
+    /// Find the interpreter on disk, failing if it isn't installed.
+    ///
+    /// A program containing a path separator is checked as a path; a bare
+    /// name is searched for on `PATH`.
+    pub fn resolve(&self) -> Result<PathBuf> {
+        let program = Path::new(&self.program);
+        let found = if program.components().count() > 1 {
+            program.is_file().then(|| program.to_path_buf())
+        } else {
+            std::env::var_os("PATH").and_then(|paths| {
+                std::env::split_paths(&paths)
+                    .map(|dir| dir.join(program))
+                    .find(|candidate| {
+                        candidate.is_file()
+                            || (cfg!(windows) && candidate.with_extension("exe").is_file())
+                    })
+            })
+        };
+        found.ok_or_else(|| {
+            let place = if program.components().count() > 1 {
+                ""
+            } else {
+                " on PATH"
+            };
+            Error::config(format!(
+                "Shell `{}` not found{}; pass --shell or set verification.shell to an installed interpreter",
+                self.program, place
+            ))
+        })
+    }
+
+    fn command(&self, script: &str) -> Command {
+        let mut cmd = Command::new(&self.program);
+        cmd.args(&self.args).arg(script);
+        cmd
+    }
+}
+
+impl Default for Shell {
+    /// `sh` on Unix, `cmd` on Windows.
+    fn default() -> Self {
+        Self::new(if cfg!(windows) { "cmd" } else { "sh" })
+    }
+}
+
+/// Outcome of comparing a command's stdout with its expected output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MatchOutcome {
+    /// No expected output was declared, or output was not captured.
+    NotChecked,
+    /// Output matched the expectation.
+    Matched,
+    /// Output did not match the expectation.
+    Mismatched {
+        /// Match strategy used (`contains`, `regex`, or `exact`).
+        strategy: &'static str,
+        /// The expected output pattern.
+        expected: String,
+    },
+}
+
+/// Result of running a single verification item.
+#[derive(Debug, Clone)]
+pub struct ItemResult {
+    /// The item that was run.
+    pub item: VerificationItem,
+    /// Exit code, if the command ran to completion.
+    pub exit_code: Option<i32>,
+    /// Captured stdout (empty when not captured).
+    pub stdout: String,
+    /// Captured stderr (empty when not captured).
+    pub stderr: String,
+    /// Wall-clock time the command took.
+    pub duration: Duration,
+    /// Whether the command was killed for exceeding its timeout.
+    pub timed_out: bool,
+    /// Result of output matching.
+    pub output_match: MatchOutcome,
+    /// Error that prevented the command from running (e.g. spawn failure).
+    pub error: Option<String>,
+    /// Temporary directory the item ran in under [`RunOptions::isolated`].
+    /// It has already been removed.
+    pub isolated_dir: Option<PathBuf>,
+}
+
+impl ItemResult {
+    /// A result for `item` before it has run.
+    fn new(item: &VerificationItem) -> Self {
+        Self {
+            item: item.clone(),
+            exit_code: None,
+            stdout: String::new(),
+            stderr: String::new(),
+            duration: Duration::ZERO,
+            timed_out: false,
+            output_match: MatchOutcome::NotChecked,
+            error: None,
+            isolated_dir: None,
+        }
+    }
+
+    /// The exit code the item expects (default: 0).
+    pub fn expected_exit_code(&self) -> i32 {
+        self.item.expected_exit_code.unwrap_or(0)
+    }
+
+    /// Returns true if the command completed with the expected exit code.
+    pub fn exit_code_matches(&self) -> bool {
+        !self.timed_out
+            && self.error.is_none()
+            && self
+                .exit_code
+                .is_some_and(|code| self.item.expects_exit_code(code))
+    }
+
+    /// Returns true if the exit code matched and the output did not mismatch.
+    pub fn passed(&self) -> bool {
+        self.exit_code_matches() && !matches!(self.output_match, MatchOutcome::Mismatched { .. })
+    }
+
+    /// Convert the result into an [`Error::Verification`] if it did not pass.
+    pub fn check(&self) -> Result<()> {
+        let message = if let Some(error) = &self.error {
+            error.clone()
+        } else if self.timed_out {
+            format!("timed out after {:.1}s", self.duration.as_secs_f64())
+        } else if !self.exit_code_matches() {
+            match self.exit_code {
+                Some(code) => format!(
+                    "exit code {} (expected {})",
+                    code,
+                    self.item.expected_exit_description()
+                ),
+                None => "terminated by signal".to_string(),
+            }
+        } else if let MatchOutcome::Mismatched { strategy, expected } = &self.output_match {
+            format!("output did not match ({}): {}", strategy, expected)
+        } else {
+            return Ok(());
+        };
+
+        Err(Error::Verification {
+            command: self.item.command.clone(),
+            message,
+        })
+    }
+}
+
+/// Run every item in a verification spec.
+///
+/// Items are independent: a failing item does not stop later ones. With
+/// `jobs` above 1, items run in parallel except within a group (see
+/// [`serial_groups`]), whose items run one after another in order.
+pub fn run_spec(spec: &VerificationSpec, options: &RunOptions) -> Vec<ItemResult> {
+    let count = spec.items.len();
+    let groups = serial_groups(spec, options);
+    let jobs = options.jobs.clamp(1, groups.len().max(1));
+
+    if jobs == 1 {
+        return spec
+            .items
+            .iter()
+            .map(|item| run_item(item, options))
+            .collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<ItemResult>>> = Mutex::new((0..count).map(|_| None).collect());
+
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| {
+                loop {
+                    let group = next.fetch_add(1, Ordering::SeqCst);
+                    let Some(indices) = groups.get(group) else {
+                        break;
+                    };
+                    for &idx in indices {
+                        let result = run_item(&spec.items[idx], options);
+                        results.lock().unwrap_or_else(|e| e.into_inner())[idx] = Some(result);
+                    }
+                }
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap_or_else(|e| e.into_inner())
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Split a spec's items (as indices, in order) into groups that must not
+/// run concurrently when run with `options`.
+///
+/// A `serial` spec is a single group. Otherwise items that run in the same
+/// directory share a group, since they usually touch the same state. That is
+/// the item's `working_dir`, else `options.working_dir` (the project root
+/// for `pave verify`), else the current directory, compared as absolute
+/// paths so `./db` and `db` match. Items that get a fresh directory under
+/// [`RunOptions::isolated`] share nothing and each get their own group.
+pub fn serial_groups(spec: &VerificationSpec, options: &RunOptions) -> Vec<Vec<usize>> {
+    if spec.serial {
+        return vec![(0..spec.items.len()).collect()];
+    }
+
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut by_dir: Vec<(PathBuf, usize)> = Vec::new();
+    for (idx, item) in spec.items.iter().enumerate() {
+        let dir = match &item.working_dir {
+            Some(dir) => dir.as_path(),
+            None if options.isolated => {
+                groups.push(vec![idx]);
+                continue;
+            }
+            None => options.working_dir.as_deref().unwrap_or(Path::new(".")),
+        };
+        let dir = std::path::absolute(dir).unwrap_or_else(|_| dir.to_path_buf());
+        match by_dir.iter().find(|(seen, _)| *seen == dir) {
+            Some(&(_, group)) => groups[group].push(idx),
+            None => {
+                by_dir.push((dir, groups.len()));
+                groups.push(vec![idx]);
+            }
+        }
+    }
+    groups
+}
+
+/// Resolve the timeout for `item`, or `None` if it may run indefinitely.
+///
+/// `options.timeout` wins over the item's own `timeout_secs`, which wins over
+/// `options.default_timeout`.
+pub fn effective_timeout(item: &VerificationItem, options: &RunOptions) -> Option<Duration> {
+    let timeout = options
+        .timeout
+        .or_else(|| {
+            item.timeout_secs
+                .map(|secs| Duration::from_secs(secs as u64))
+        })
+        .or(options.default_timeout)
+        .unwrap_or(Duration::from_secs(DEFAULT_TIMEOUT_SECS as u64));
+    (!timeout.is_zero()).then_some(timeout)
+}
+
+/// Run a single verification item.
+pub fn run_item(item: &VerificationItem, options: &RunOptions) -> ItemResult {
+    let timeout = effective_timeout(item, options);
+    let start = Instant::now();
+
+    // Kept alive until the command exits
+    let script = match &item.interpreter {
+        Some(_) => match ScriptFile::write(&item.command) {
+            Ok(script) => Some(script),
+            Err(e) => {
+                return ItemResult {
+                    error: Some(format!("failed to write script: {}", e)),
+                    duration: start.elapsed(),
+                    ..ItemResult::new(item)
+                };
+            }
+        },
+        None => None,
+    };
+    // A fresh, randomly named directory, also kept alive until the command
+    // exits and then removed with everything the item left in it
+    let isolated = if options.isolated && item.working_dir.is_none() {
+        match tempfile::Builder::new().prefix("pave-isolated-").tempdir() {
+            Ok(dir) => Some(dir),
+            Err(e) => {
+                return ItemResult {
+                    error: Some(format!("failed to create isolated directory: {}", e)),
+                    duration: start.elapsed(),
+                    ..ItemResult::new(item)
+                };
+            }
+        }
+    } else {
+        None
+    };
+    let mut cmd = match (&item.interpreter, &script) {
+        (Some(interpreter), Some(script)) => script_command(interpreter, &script.path),
+        _ => options.shell.command(&item.command),
+    };
+    cmd.stdin(Stdio::null());
+
+    let isolated_path = isolated.as_ref().map(|dir| dir.path().to_path_buf());
+    if let Some(dir) = item
+        .working_dir
+        .as_ref()
+        .or(isolated_path.as_ref())
+        .or(options.working_dir.as_ref())
+    {
+        cmd.current_dir(dir);
+    }
+    for (key, value) in options.env.iter().chain(item.env_vars.iter()) {
+        cmd.env(key, value);
+    }
+    let capture = options.capture.for_item(item);
+    if capture != Capture::None {
+        cmd.stdout(Stdio::piped());
+    }
+    if capture == Capture::All {
+        cmd.stderr(Stdio::piped());
+    }
+
+    let mut result = ItemResult::new(item);
+    result.isolated_dir = isolated_path;
+
+    let mut child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            result.error = Some(format!("failed to spawn command: {}", e));
+            result.duration = start.elapsed();
+            return result;
+        }
+    };
+
+    // Drain pipes on background threads so a chatty command can't block on a
+    // full pipe while we wait for it
+    let stdout_reader = child.stdout.take().map(spawn_reader);
+    let stderr_reader = child.stderr.take().map(spawn_reader);
+
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                result.exit_code = status.code();
+                break;
+            }
+            Ok(None) if timeout.is_some_and(|t| start.elapsed() >= t) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                result.timed_out = true;
+                break;
+            }
+            Ok(None) => thread::sleep(POLL_INTERVAL),
+            Err(e) => {
+                result.error = Some(format!("command execution failed: {}", e));
+                break;
+            }
+        }
+    }
+    result.duration = start.elapsed();
+
+    // After a timeout, grandchildren may still hold the pipes open; don't wait
+    // for them
+    if !result.timed_out {
+        result.stdout = stdout_reader.map(join_reader).unwrap_or_default();
+        result.stderr = stderr_reader.map(join_reader).unwrap_or_default();
+    }
+
+    if capture != Capture::None
+        && result.exit_code_matches()
+        && let Some(matcher) = &item.expected_output
+    {
+        result.output_match = check_output(matcher, &result.stdout, options.strict_output);
+    }
+
+    result
+}
+
+/// A shebang block written to a private temporary file, removed on drop.
+struct ScriptFile {
+    path: tempfile::TempPath,
+}
+
+impl ScriptFile {
+    fn write(content: &str) -> std::io::Result<Self> {
+        // Created exclusively with owner-only permissions, so nothing planted
+        // at a guessable path in the temp dir can redirect the write
+        let mut file = tempfile::Builder::new().prefix("pave-script-").tempfile()?;
+        writeln!(file, "{}", content)?;
+        Ok(Self {
+            path: file.into_temp_path(),
+        })
+    }
+}
+
+/// Run `script` under a shebang `interpreter` line, which may carry
+/// arguments (`/usr/bin/env python3 -u`).
+fn script_command(interpreter: &str, script: &Path) -> Command {
+    let mut words = interpreter.split_whitespace();
+    let mut cmd = Command::new(words.next().unwrap_or_default());
+    cmd.args(words).arg(script);
+    cmd
+}
+
+/// Compare stdout against an output matcher.
+///
+/// With `strict`, a `Contains` matcher must equal the whole output and a
+/// `Regex` must match all of it (as if anchored with `^...$`), both after
+/// trimming surrounding whitespace.
+pub fn check_output(matcher: &OutputMatcher, stdout: &str, strict: bool) -> MatchOutcome {
+    let (matches, strategy, expected) = match matcher {
+        OutputMatcher::Contains(expected) if strict => {
+            (stdout.trim() == expected.trim(), "exact", expected)
+        }
+        OutputMatcher::Contains(substring) => (stdout.contains(substring), "contains", substring),
+        OutputMatcher::Regex(pattern) => {
+            let (pattern_source, haystack) = if strict {
+                (format!("^(?:{})$", pattern), stdout.trim())
+            } else {
+                (pattern.clone(), stdout)
+            };
+            let matches = Regex::new(&pattern_source)
+                .map(|re| re.is_match(haystack))
+                .unwrap_or(false);
+            (matches, "regex", pattern)
+        }
+        OutputMatcher::Exact(expected) => (stdout.trim() == expected.trim(), "exact", expected),
+        OutputMatcher::ExitCodeOnly => return MatchOutcome::NotChecked,
+    };
+
+    if matches {
+        MatchOutcome::Matched
+    } else {
+        MatchOutcome::Mismatched {
+            strategy,
+            expected: expected.clone(),
+        }
+    }
+}
+
+fn spawn_reader<R: Read + Send + 'static>(mut pipe: R) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        buf
+    })
+}
+
+fn join_reader(handle: thread::JoinHandle<Vec<u8>>) -> String {
+    handle
+        .join()
+        .map(|buf| String::from_utf8_lossy(&buf).to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verification::ExitCodes;
+
+    fn spec(commands: &[&str]) -> VerificationSpec {
+        VerificationSpec {
+            source_file: PathBuf::from("test.md"),
+            section_line: 1,
+            skipped: 0,
+            serial: false,
+            setup: vec![],
+            teardown: vec![],
+            items: commands
+                .iter()
+                .map(|command| VerificationItem {
+                    command: command.to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn run_spec_reports_exit_codes_in_order() {
+        let results = run_spec(
+            &spec(&["true", "exit 3", "echo hi"]),
+            &RunOptions::default(),
+        );
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].passed());
+        assert_eq!(results[1].exit_code, Some(3));
+        assert!(!results[1].passed());
+        assert!(matches!(
+            results[1].check(),
+            Err(Error::Verification { ref command, .. }) if command == "exit 3"
+        ));
+        assert!(results[2].check().is_ok());
+        assert_eq!(results[2].stdout.trim(), "hi");
+    }
+
+    #[test]
+    fn run_item_accepts_any_code_in_an_expect_exit_set() {
+        let item = |command: &str, codes: &str| VerificationItem {
+            command: command.to_string(),
+            expected_exit_codes: ExitCodes::parse(codes),
+            ..Default::default()
+        };
+        let options = RunOptions::default();
+
+        assert!(run_item(&item("exit 1", "0,1"), &options).passed());
+        assert!(run_item(&item("exit 2", "0-2"), &options).passed());
+        let result = run_item(&item("exit 3", "0-2"), &options);
+        assert!(!result.passed());
+        assert!(matches!(
+            result.check(),
+            Err(Error::Verification { ref message, .. }) if message == "exit code 3 (expected 0-2)"
+        ));
+    }
+
+    #[test]
+    fn isolated_items_each_get_a_fresh_directory_that_is_removed() {
+        let options = RunOptions {
+            isolated: true,
+            ..Default::default()
+        };
+        let results = run_spec(&spec(&["touch leaked", "test ! -e leaked"]), &options);
+
+        assert!(results.iter().all(ItemResult::passed));
+        let first = results[0].isolated_dir.as_ref().unwrap();
+        assert_ne!(Some(first), results[1].isolated_dir.as_ref());
+        assert!(!first.exists());
+
+        // An item's own working_dir wins
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut pinned = spec(&["touch kept"]);
+        pinned.items[0].working_dir = Some(temp_dir.path().to_path_buf());
+        let results = run_spec(&pinned, &options);
+        assert_eq!(results[0].isolated_dir, None);
+        assert!(temp_dir.path().join("kept").exists());
+    }
+
+    #[test]
+    fn run_spec_with_jobs_preserves_order() {
+        let options = RunOptions {
+            jobs: 3,
+            ..Default::default()
+        };
+        let results = run_spec(&spec(&["sleep 0.2; echo a", "echo b", "echo c"]), &options);
+
+        let outputs: Vec<&str> = results.iter().map(|r| r.stdout.trim()).collect();
+        assert_eq!(outputs, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn run_spec_with_jobs_runs_groups_in_parallel_but_never_overlaps_a_group() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let shared = temp_dir.path().join("shared");
+        std::fs::create_dir(&shared).unwrap();
+        let options = RunOptions {
+            jobs: 4,
+            ..Default::default()
+        };
+
+        // Both items claim the same lock; they fail if they ever overlap
+        let claim = "mkdir lock && sleep 0.2 && rmdir lock";
+        let mut same_dir = spec(&[claim, claim]);
+        for item in &mut same_dir.items {
+            item.working_dir = Some(shared.clone());
+        }
+        assert_eq!(serial_groups(&same_dir, &options), vec![vec![0, 1]]);
+        let results = run_spec(&same_dir, &options);
+        assert!(results.iter().all(ItemResult::passed));
+
+        // Each item waits for the other's marker, so they pass only together
+        let root = temp_dir.path().display();
+        let wait_for = |mine: &str, theirs: &str| {
+            format!(
+                "touch {root}/{mine}; for i in $(seq 100); do [ -e {root}/{theirs} ] && exit 0; sleep 0.02; done; exit 1"
+            )
+        };
+        let mut independent = spec(&[&wait_for("a", "b"), &wait_for("b", "a")]);
+        for (item, dir) in independent.items.iter_mut().zip(["a-dir", "b-dir"]) {
+            std::fs::create_dir(temp_dir.path().join(dir)).unwrap();
+            item.working_dir = Some(temp_dir.path().join(dir));
+        }
+        assert_eq!(
+            serial_groups(&independent, &options),
+            vec![vec![0], vec![1]]
+        );
+        let results = run_spec(&independent, &options);
+        assert!(results.iter().all(ItemResult::passed));
+
+        let serial = VerificationSpec {
+            serial: true,
+            ..independent
+        };
+        assert_eq!(serial_groups(&serial, &options), vec![vec![0, 1]]);
+    }
+
+    #[test]
+    fn serial_groups_compare_the_directory_items_run_in() {
+        let mut items = spec(&["a", "b", "c", "d"]);
+        items.items[0].working_dir = Some(PathBuf::from("./db"));
+        items.items[1].working_dir = Some(PathBuf::from("db"));
+        items.items[3].working_dir = Some(PathBuf::from("other"));
+
+        // Items without a working_dir run in the project root, together
+        let options = RunOptions {
+            working_dir: Some(PathBuf::from("db")),
+            ..Default::default()
+        };
+        assert_eq!(
+            serial_groups(&items, &options),
+            vec![vec![0, 1, 2], vec![3]]
+        );
+        assert_eq!(
+            serial_groups(&items, &RunOptions::default()),
+            vec![vec![0, 1], vec![2], vec![3]]
+        );
+
+        // ...unless each gets a fresh --isolated directory
+        let isolated = RunOptions {
+            isolated: true,
+            ..options
+        };
+        assert_eq!(
+            serial_groups(&items, &isolated),
+            vec![vec![0, 1], vec![2], vec![3]]
+        );
+    }
+
+    #[test]
+    fn run_item_captures_only_the_requested_streams() {
+        let item = VerificationItem {
+            command: "echo out; echo err >&2".to_string(),
+            ..Default::default()
+        };
+        let run = |capture| {
+            run_item(
+                &item,
+                &RunOptions {
+                    capture,
+                    ..Default::default()
+                },
+            )
+        };
+
+        let all = run(Capture::All);
+        assert_eq!((all.stdout.trim(), all.stderr.trim()), ("out", "err"));
+        let stdout_only = run(Capture::Stdout);
+        assert_eq!(stdout_only.stdout.trim(), "out");
+        assert!(stdout_only.stderr.is_empty());
+        let none = run(Capture::None);
+        assert!(none.passed());
+        assert!(none.stdout.is_empty() && none.stderr.is_empty());
+    }
+
+    #[test]
+    fn run_item_captures_stdout_for_matchers_even_with_capture_none() {
+        let item = |expected: &str| VerificationItem {
+            command: "echo hello world".to_string(),
+            expected_output: Some(OutputMatcher::Contains(expected.to_string())),
+            ..Default::default()
+        };
+        assert_eq!(Capture::None.for_item(&item("hello")), Capture::Stdout);
+        assert_eq!(
+            Capture::None.for_item(&VerificationItem::default()),
+            Capture::None
+        );
+
+        for capture in [Capture::None, Capture::All] {
+            let options = RunOptions {
+                capture,
+                ..Default::default()
+            };
+            assert_eq!(
+                run_item(&item("hello"), &options).output_match,
+                MatchOutcome::Matched
+            );
+            assert!(matches!(
+                run_item(&item("goodbye"), &options).output_match,
+                MatchOutcome::Mismatched { .. }
+            ));
+        }
+    }
+
+    #[test]
+    fn run_item_applies_option_env_and_item_override() {
+        let item = VerificationItem {
+            command: "echo $SHARED-$ITEM".to_string(),
+            env_vars: vec![("ITEM".to_string(), "item".to_string())],
+            ..Default::default()
+        };
+        let options = RunOptions {
+            env: vec![
+                ("SHARED".to_string(), "shared".to_string()),
+                ("ITEM".to_string(), "ignored".to_string()),
+            ],
+            ..Default::default()
+        };
+
+        let result = run_item(&item, &options);
+        assert_eq!(result.stdout.trim(), "shared-item");
+    }
+
+    #[test]
+    fn run_item_kills_command_after_timeout() {
+        let item = VerificationItem {
+            command: "sleep 5".to_string(),
+            ..Default::default()
+        };
+        let options = RunOptions {
+            timeout: Some(Duration::from_millis(200)),
+            ..Default::default()
+        };
+
+        let result = run_item(&item, &options);
+        assert!(result.timed_out);
+        assert!(!result.passed());
+        assert!(result.duration < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn effective_timeout_prefers_override_then_item_then_default() {
+        let item = VerificationItem {
+            command: "true".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            effective_timeout(&item, &RunOptions::default()),
+            Some(Duration::from_secs(30))
+        );
+
+        let options = RunOptions {
+            default_timeout: Some(Duration::from_secs(5)),
+            ..Default::default()
+        };
+        assert_eq!(
+            effective_timeout(&item, &options),
+            Some(Duration::from_secs(5))
+        );
+
+        let explicit = VerificationItem {
+            timeout_secs: Some(10),
+            ..item.clone()
+        };
+        assert_eq!(
+            effective_timeout(&explicit, &options),
+            Some(Duration::from_secs(10))
+        );
+
+        let overridden = RunOptions {
+            timeout: Some(Duration::from_secs(2)),
+            ..options
+        };
+        assert_eq!(
+            effective_timeout(&explicit, &overridden),
+            Some(Duration::from_secs(2))
+        );
+    }
+
+    #[test]
+    fn zero_timeout_disables_the_limit() {
+        let item = VerificationItem {
+            command: "sleep 0.2 && echo done".to_string(),
+            timeout_secs: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(effective_timeout(&item, &RunOptions::default()), None);
+
+        let options = RunOptions {
+            default_timeout: Some(Duration::ZERO),
+            ..Default::default()
+        };
+        let item = VerificationItem {
+            timeout_secs: None,
+            ..item
+        };
+        let result = run_item(&item, &options);
+        assert!(!result.timed_out);
+        assert!(result.passed());
+        assert_eq!(result.stdout.trim(), "done");
+    }
+
+    #[test]
+    fn check_output_strict_requires_whole_output() {
+        let contains = OutputMatcher::Contains("hello".to_string());
+        let regex = OutputMatcher::Regex("h.llo".to_string());
+        for matcher in [&contains, &regex] {
+            assert_eq!(
+                check_output(matcher, "hello world\n", false),
+                MatchOutcome::Matched
+            );
+            assert!(matches!(
+                check_output(matcher, "hello world\n", true),
+                MatchOutcome::Mismatched { .. }
+            ));
+            assert_eq!(
+                check_output(matcher, "hello\n", true),
+                MatchOutcome::Matched
+            );
+        }
+        // Alternation stays inside the anchors
+        let alternation = OutputMatcher::Regex("a|hello".to_string());
+        assert!(matches!(
+            check_output(&alternation, "abc", true),
+            MatchOutcome::Mismatched { .. }
+        ));
+    }
+
+    #[test]
+    fn run_item_reports_output_mismatch() {
+        let item = VerificationItem {
+            command: "echo actual".to_string(),
+            expected_output: Some(OutputMatcher::Exact("expected".to_string())),
+            ..Default::default()
+        };
+
+        let result = run_item(&item, &RunOptions::default());
+        assert!(result.exit_code_matches());
+        assert_eq!(
+            result.output_match,
+            MatchOutcome::Mismatched {
+                strategy: "exact",
+                expected: "expected".to_string()
+            }
+        );
+        assert!(!result.passed());
+    }
+
+    #[test]
+    fn shell_infers_command_flag() {
+        assert_eq!(Shell::new("bash").args, vec!["-c"]);
+        assert_eq!(Shell::new("/usr/bin/pwsh").args, vec!["-Command"]);
+        assert_eq!(Shell::new("powershell.exe").args, vec!["-Command"]);
+        assert_eq!(Shell::new("cmd").args, vec!["/C"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_item_uses_configured_shell() {
+        let item = VerificationItem {
+            command: "words=(a b c); [[ ${#words[@]} == 3 ]] && echo ${words[1]}".to_string(),
+            ..Default::default()
+        };
+        let options = RunOptions {
+            shell: Shell::new("bash"),
+            ..Default::default()
+        };
+
+        let result = run_item(&item, &options);
+        assert!(result.passed(), "stderr: {}", result.stderr);
+        assert_eq!(result.stdout.trim(), "b");
+    }
+
+    #[test]
+    fn resolve_rejects_missing_shell() {
+        let err = Shell::new("/nonexistent/bin/shell").resolve().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Shell `/nonexistent/bin/shell` not found; pass --shell or set verification.shell to an installed interpreter"
+        );
+        let err = Shell::new("no-such-shell-for-pave").resolve().unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Shell `no-such-shell-for-pave` not found on PATH;")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn shebang_item_runs_as_a_script_under_its_interpreter() {
+        if Shell::new("python3").resolve().is_err() {
+            return;
+        }
+        let item = VerificationItem {
+            command: "#!/usr/bin/env python3\nimport sys\nfor n in range(3):\n    print(n * 2)\nsys.exit(0)".to_string(),
+            interpreter: Some("/usr/bin/env python3".to_string()),
+            expected_output: Some(OutputMatcher::Contains("0\n2\n4".to_string())),
+            ..Default::default()
+        };
+
+        let result = run_item(&item, &RunOptions::default());
+        assert!(result.passed(), "{:?}", result);
+        assert_eq!(result.output_match, MatchOutcome::Matched);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn script_file_is_private_and_removed_on_drop() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let script = ScriptFile::write("#!/bin/sh\necho hi").unwrap();
+        let path = script.path.to_path_buf();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "#!/bin/sh\necho hi\n"
+        );
+
+        drop(script);
+        assert!(!path.exists());
+    }
+}